@@ -57,7 +57,7 @@
 //! ```
 use crate::{
     error::BBSPlusError,
-    prelude::PreparedPublicKeyG2,
+    prelude::{PreparedPublicKeyG2, PublicKeyG2},
     setup::{MultiMessageSignatureParams, PreparedSignatureParamsG1, SignatureParamsG1},
     signature::SignatureG1,
 };
@@ -273,7 +273,9 @@ impl<E: Pairing> PoKOfSignatureG1Protocol<E> {
         })
     }
 
-    /// Get the contribution of this protocol towards the challenge, i.e. bytecode of items that will be hashed
+    /// Get the contribution of this protocol towards the challenge, i.e. bytecode of items that will be hashed.
+    /// As `revealed_msgs` is a `BTreeMap`, the revealed messages are always written in ascending order of their
+    /// index, irrespective of the order they were inserted in, so the prover and verifier get the same bytes.
     pub fn challenge_contribution<W: Write>(
         &self,
         revealed_msgs: &BTreeMap<usize, E::ScalarField>,
@@ -315,6 +317,26 @@ impl<E: Pairing> PoKOfSignatureG1Protocol<E> {
         })
     }
 
+    /// Get the blinding used in the Schnorr protocol for the given message index `msg_idx`. Another protocol
+    /// using the same blinding for the same message has the same response as this protocol for the message.
+    pub fn get_blinding_for_message(
+        &self,
+        msg_idx: usize,
+        revealed_msg_ids: &BTreeSet<usize>,
+    ) -> Result<&E::ScalarField, BBSPlusError> {
+        // Revealed messages are not part of Schnorr protocol
+        if revealed_msg_ids.contains(&msg_idx) {
+            return Err(BBSPlusError::InvalidMsgIdxForResponse(msg_idx));
+        }
+        // Adjust message index as the revealed messages are not part of the Schnorr protocol
+        let adjusted_idx = msg_idx - revealed_msg_ids.range(..msg_idx).count();
+        // 2 added to the index, since 0th and 1st index are reserved for `-r3` and `s'`
+        self.sc_comm_2
+            .blindings
+            .get(2 + adjusted_idx)
+            .ok_or(BBSPlusError::InvalidMsgIdxForResponse(msg_idx))
+    }
+
     /// Helper that serializes state to get challenge contribution. Serialized the randomized signature,
     /// and commitments and instances for both Schnorr protocols
     pub fn compute_challenge_contribution<W: Write>(
@@ -350,6 +372,8 @@ impl<E: Pairing> PoKOfSignatureG1Protocol<E> {
         let r = E::ScalarField::one();
         r.serialize_compressed(&mut writer)?;
         exponents.push(r);
+        // Iterating over the map gives the revealed messages in ascending order of their index so the prover and
+        // verifier write them in the same order
         for (i, msg) in revealed_msgs {
             assert!(*i < params.h.len());
             params.h[*i].serialize_compressed(&mut writer)?;
@@ -397,6 +421,27 @@ where
         Ok(())
     }
 
+    /// Verify the proof against each of the given public keys, like the current and the previous versions of a
+    /// rotated key, and return the index of the first one the signature is valid under. The checks not involving
+    /// the public key are done only once. Assumes that the public keys and parameters have been validated already.
+    pub fn verify_with_key_set(
+        &self,
+        revealed_msgs: &BTreeMap<usize, E::ScalarField>,
+        challenge: &E::ScalarField,
+        public_keys: &[PublicKeyG2<E>],
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+    ) -> Result<usize, BBSPlusError> {
+        let params = params.into();
+        self.verify_except_pairings(revealed_msgs, challenge, params.g1, params.h_0, params.h)?;
+
+        // The randomized signature is valid under public key `W` if `e(A', W) = e(A_bar, g2)`
+        let a_bar_pairing = E::pairing(self.A_bar, params.g2);
+        public_keys
+            .iter()
+            .position(|pk| E::pairing(self.A_prime, pk.0) == a_bar_pairing)
+            .ok_or(BBSPlusError::PairingCheckFailed)
+    }
+
     pub fn verify_with_randomized_pairing_checker(
         &self,
         revealed_msgs: &BTreeMap<usize, E::ScalarField>,
@@ -1124,6 +1169,161 @@ mod tests {
         }
     }
 
+    #[test]
+    fn challenge_contribution_independent_of_revealed_message_order() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 6;
+        let (messages, params, _keypair, sig) = sig_setup(&mut rng, message_count);
+        let revealed_indices = BTreeSet::from([4, 1, 3]);
+
+        // Protocols created with the same randomness are the same
+        let init = |seed: u64| {
+            PoKOfSignatureG1Protocol::init(
+                &mut StdRng::seed_from_u64(seed),
+                &sig,
+                &params,
+                messages.iter().enumerate().map(|(idx, msg)| {
+                    if revealed_indices.contains(&idx) {
+                        MessageOrBlinding::RevealMessage(msg)
+                    } else {
+                        MessageOrBlinding::BlindMessageRandomly(msg)
+                    }
+                }),
+            )
+            .unwrap()
+        };
+        let pok_1 = init(1);
+        let pok_2 = init(1);
+
+        let mut revealed_msgs_1 = BTreeMap::new();
+        for i in [1, 3, 4] {
+            revealed_msgs_1.insert(i, messages[i]);
+        }
+        let mut revealed_msgs_2 = BTreeMap::new();
+        for i in [4, 1, 3] {
+            revealed_msgs_2.insert(i, messages[i]);
+        }
+
+        let mut bytes_1 = vec![];
+        pok_1
+            .challenge_contribution(&revealed_msgs_1, &params, &mut bytes_1)
+            .unwrap();
+        let mut bytes_2 = vec![];
+        pok_2
+            .challenge_contribution(&revealed_msgs_2, &params, &mut bytes_2)
+            .unwrap();
+        assert_eq!(bytes_1, bytes_2);
+
+        // Reference computed by writing each item in order with the revealed messages in ascending order of index
+        let mut expected = vec![];
+        pok_1.A_bar.serialize_compressed(&mut expected).unwrap();
+        pok_1.A_prime.serialize_compressed(&mut expected).unwrap();
+        params.h_0.serialize_compressed(&mut expected).unwrap();
+        (pok_1.A_bar.into_group() - pok_1.d.into_group())
+            .into_affine()
+            .serialize_compressed(&mut expected)
+            .unwrap();
+        pok_1
+            .sc_comm_1
+            .t
+            .serialize_compressed(&mut expected)
+            .unwrap();
+        params.g1.serialize_compressed(&mut expected).unwrap();
+        Fr::one().serialize_compressed(&mut expected).unwrap();
+        let mut revealed_sum = params.g1.into_group();
+        for i in [1, 3, 4] {
+            params.h[i].serialize_compressed(&mut expected).unwrap();
+            messages[i].serialize_compressed(&mut expected).unwrap();
+            revealed_sum += params.h[i] * messages[i];
+        }
+        revealed_sum.serialize_compressed(&mut expected).unwrap();
+        pok_1
+            .sc_comm_2
+            .t
+            .serialize_compressed(&mut expected)
+            .unwrap();
+        assert_eq!(bytes_1, expected);
+
+        // Different randomness gives different bytes
+        let mut bytes_3 = vec![];
+        init(2)
+            .challenge_contribution(&revealed_msgs_1, &params, &mut bytes_3)
+            .unwrap();
+        assert_ne!(bytes_1, bytes_3);
+    }
+
+    #[test]
+    fn verify_with_rotated_keys() {
+        // Signature issued under the previous version of a rotated key verifies against the set of key versions
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 5;
+        let (messages, params, keypair_1, sig) = sig_setup(&mut rng, message_count);
+        let keypair_2 = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let sig_2 =
+            SignatureG1::<Bls12_381>::new(&mut rng, &messages, &keypair_2.secret_key, &params)
+                .unwrap();
+
+        let mut revealed_msgs = BTreeMap::new();
+        revealed_msgs.insert(1, messages[1]);
+        let create_proof = |rng: &mut StdRng, sig: &SignatureG1<Bls12_381>| {
+            let pok = PoKOfSignatureG1Protocol::init(
+                rng,
+                sig,
+                &params,
+                messages.iter().enumerate().map(|(idx, msg)| {
+                    if revealed_msgs.contains_key(&idx) {
+                        MessageOrBlinding::RevealMessage(msg)
+                    } else {
+                        MessageOrBlinding::BlindMessageRandomly(msg)
+                    }
+                }),
+            )
+            .unwrap();
+            let mut chal_bytes = vec![];
+            pok.challenge_contribution(&revealed_msgs, &params, &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            (pok.gen_proof(&challenge).unwrap(), challenge)
+        };
+
+        let key_versions = [keypair_1.public_key.clone(), keypair_2.public_key.clone()];
+        let (proof, challenge) = create_proof(&mut rng, &sig);
+        assert_eq!(
+            proof
+                .verify_with_key_set(&revealed_msgs, &challenge, &key_versions, params.clone())
+                .unwrap(),
+            0
+        );
+        let (proof_2, challenge_2) = create_proof(&mut rng, &sig_2);
+        assert_eq!(
+            proof_2
+                .verify_with_key_set(&revealed_msgs, &challenge_2, &key_versions, params.clone())
+                .unwrap(),
+            1
+        );
+
+        // Fails when the signer's key isn't in the set
+        assert!(matches!(
+            proof.verify_with_key_set(
+                &revealed_msgs,
+                &challenge,
+                &key_versions[1..],
+                params.clone()
+            ),
+            Err(BBSPlusError::PairingCheckFailed)
+        ));
+        assert!(proof
+            .verify_with_key_set(&revealed_msgs, &challenge, &[], params.clone())
+            .is_err());
+
+        // Other checks of the proof are still done
+        let mut wrong_revealed_msgs = revealed_msgs.clone();
+        wrong_revealed_msgs.insert(1, Fr::rand(&mut rng));
+        assert!(proof
+            .verify_with_key_set(&wrong_revealed_msgs, &challenge, &key_versions, params)
+            .is_err());
+    }
+
     #[test]
     fn test_PoK_multiple_sigs_with_randomized_pairing_check() {
         gen_test_PoK_multiple_sigs_with_randomized_pairing_check!(
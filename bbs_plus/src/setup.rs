@@ -78,6 +78,9 @@ use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
+/// Domain separation tag used when hashing messages to field elements with `hash_messages`
+pub const MESSAGE_HASH_DST: &[u8] = b"BBS-SIG-MESSAGE-HASH";
+
 // TODO: Move to utils to that coconut can use it
 /// Trait implemented by a signature scheme params that can sign multiple messages
 pub trait MultiMessageSignatureParams {
@@ -308,6 +311,23 @@ macro_rules! impl_sig_params {
                     || cfg_iter!(self.h).any(|v| v.is_zero()))
             }
 
+            /// Hash each message to a field element so that messages like strings, integers or dates can be
+            /// signed. Uses arkworks' `DefaultFieldHasher` with the domain separation tag [`MESSAGE_HASH_DST`] so
+            /// the same bytes always give the same field element, irrespective of the params or the message index.
+            /// This is not the hash-to-field of the IETF hash-to-curve spec as the `expand_message_xmd` of arkworks
+            /// 0.4 uses a zero pad of the number of bytes drawn per field element instead of the digest's input
+            /// block size, so the output won't match other implementations of the spec.
+            pub fn hash_messages<D: Default + DynDigest + Clone>(
+                messages: &[impl AsRef<[u8]>],
+            ) -> Vec<E::ScalarField> {
+                let hasher =
+                    <DefaultFieldHasher<D> as HashToField<E::ScalarField>>::new(MESSAGE_HASH_DST);
+                messages
+                    .iter()
+                    .map(|m| hasher.hash_to_field(m.as_ref(), 1).pop().unwrap())
+                    .collect()
+            }
+
             impl_sig_params_prepared!($group_affine, $group_projective);
         }
     };
@@ -790,4 +810,34 @@ mod tests {
             SignatureParamsG2
         );
     }
+
+    #[test]
+    fn hashing_messages() {
+        use core::str::FromStr;
+
+        // Expected values are `OS2IP(expand_message_xmd(msg, DST || I2OSP(len(DST), 1), 48)) mod r` with Blake2b512
+        // where the zero pad is of 48 bytes, i.e. the byte length of a hashed field element.
+        let messages: [&[u8]; 3] = [b"John Doe", b"1990-01-01", b""];
+        let expected = [
+            "42049728035352997780234916164387791493991666358714367393149139113745174208651",
+            "27294249422573791235582267757036368772970266971572149042182231842592172731236",
+            "35771576844857306429413606245249279366036491595804590421061625111306859560191",
+        ]
+        .map(|e| Fr::from_str(e).unwrap());
+
+        let hashed = SignatureParamsG1::<Bls12_381>::hash_messages::<Blake2b512>(&messages);
+        assert_eq!(hashed, expected);
+
+        // Hashing doesn't depend on the group of the params
+        assert_eq!(
+            SignatureParamsG2::<Bls12_381>::hash_messages::<Blake2b512>(&messages),
+            hashed
+        );
+        assert_eq!(
+            SignatureParamsG1::<Bls12_381>::hash_messages::<Blake2b512>(&[String::from(
+                "John Doe"
+            )]),
+            vec![hashed[0]]
+        );
+    }
 }
@@ -0,0 +1,94 @@
+use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use test_utils::accumulators::setup_positive_accum;
+use vb_accumulator::prelude::{
+    Accumulator, MembershipProofProtocol, MembershipProvingKey, PreparedPublicKey,
+    PreparedSetupParams,
+};
+
+type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+/// Compare verifying many membership proofs when the accumulator public key is prepared for each
+/// verification vs when it is prepared once and reused.
+fn verification(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let elem = Fr::rand(&mut rng);
+    accumulator = accumulator
+        .add(elem, &keypair.secret_key, &mut state)
+        .unwrap();
+    let wit = accumulator
+        .get_membership_witness(&elem, &keypair.secret_key, &state)
+        .unwrap();
+
+    let protocol = MembershipProofProtocol::init(
+        &mut rng,
+        &elem,
+        None,
+        &wit,
+        &keypair.public_key,
+        &params,
+        &prk,
+    );
+    let challenge = Fr::rand(&mut rng);
+    let proof = protocol.gen_proof(&challenge);
+
+    let count = 1000;
+    let prepared_params = PreparedSetupParams::from(params.clone());
+    let prepared_pk = PreparedPublicKey::from(keypair.public_key.clone());
+
+    let mut group = c.benchmark_group("Accumulator membership proof verification");
+    group.sample_size(10);
+    group.bench_function(
+        format!("{} verifications without prepared public key", count),
+        |b| {
+            b.iter(|| {
+                for _ in 0..count {
+                    black_box(
+                        proof
+                            .verify(
+                                accumulator.value(),
+                                &challenge,
+                                keypair.public_key.clone(),
+                                prepared_params.clone(),
+                                &prk,
+                            )
+                            .unwrap(),
+                    );
+                }
+            })
+        },
+    );
+    group.bench_function(
+        format!("{} verifications with prepared public key", count),
+        |b| {
+            b.iter(|| {
+                for _ in 0..count {
+                    black_box(
+                        proof
+                            .verify(
+                                accumulator.value(),
+                                &challenge,
+                                prepared_pk.clone(),
+                                prepared_params.clone(),
+                                &prk,
+                            )
+                            .unwrap(),
+                    );
+                }
+            })
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, verification);
+criterion_main!(benches);
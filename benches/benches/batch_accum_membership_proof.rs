@@ -0,0 +1,155 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use proof_system::{
+    prelude::{MetaStatements, ProofSpec, Witnesses},
+    statement::{
+        accumulator::{
+            AccumulatorMembership as AccumulatorMembershipStmt,
+            BatchAccumulatorMembership as BatchAccumulatorMembershipStmt,
+        },
+        Statements,
+    },
+    witness::Membership as MembershipWit,
+};
+use test_utils::{accumulators::setup_positive_accum, Fr, ProofG1};
+use vb_accumulator::prelude::{Accumulator, MembershipProvingKey};
+
+/// Creates specs proving membership of `count` elements in the same accumulator, one with a statement for each
+/// element and the other with a single batch statement for all elements.
+fn setup(
+    rng: &mut StdRng,
+    count: usize,
+) -> [(ProofSpec<Bls12_381, G1Affine>, Witnesses<Bls12_381>); 2] {
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(rng);
+
+    let members = (0..count).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    for m in &members {
+        accumulator = accumulator
+            .add(*m, &accum_keypair.secret_key, &mut state)
+            .unwrap();
+    }
+    let wits = members
+        .iter()
+        .map(|m| {
+            accumulator
+                .get_membership_witness(m, &accum_keypair.secret_key, &state)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut statements = Statements::new();
+    let mut witnesses = Witnesses::new();
+    for (m, w) in members.iter().zip(wits.iter()) {
+        statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+            accum_params.clone(),
+            accum_keypair.public_key.clone(),
+            mem_prk.clone(),
+            *accumulator.value(),
+        ));
+        witnesses.add(MembershipWit::new_as_witness(*m, w.clone()));
+    }
+    let individual = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    individual.validate().unwrap();
+
+    let mut batch_statements = Statements::new();
+    batch_statements.add(BatchAccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key,
+        mem_prk,
+        *accumulator.value(),
+        count,
+    ));
+    let mut batch_witnesses = Witnesses::new();
+    batch_witnesses.add(MembershipWit::new_batch_as_witness(
+        members.into_iter().zip(wits),
+    ));
+    let batch = ProofSpec::new(batch_statements, MetaStatements::new(), vec![], None);
+    batch.validate().unwrap();
+
+    [(individual, witnesses), (batch, batch_witnesses)]
+}
+
+fn batch_membership(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let nonce = Some(b"test-nonce".to_vec());
+
+    let counts = [10, 100];
+    // For each count, the spec with individual statements and then the one with the batch statement
+    let specs = counts
+        .iter()
+        .flat_map(|n| setup(&mut rng, *n))
+        .collect::<Vec<_>>();
+    let ids = counts
+        .iter()
+        .flat_map(|n| ["individual", "batch"].map(|name| BenchmarkId::new(name, n)))
+        .collect::<Vec<_>>();
+
+    let mut prove_group = c.benchmark_group("Batch accumulator membership proof generation");
+    prove_group.sample_size(10);
+    for ((proof_spec, witnesses), id) in specs.iter().zip(ids.iter()) {
+        prove_group.bench_with_input(
+            id.clone(),
+            &(proof_spec, witnesses),
+            |b, &(proof_spec, witnesses)| {
+                b.iter(|| {
+                    ProofG1::new::<StdRng, Blake2b512>(
+                        &mut rng,
+                        black_box(proof_spec),
+                        witnesses.clone(),
+                        nonce.clone(),
+                        Default::default(),
+                    )
+                    .unwrap()
+                });
+            },
+        );
+    }
+    prove_group.finish();
+
+    let proofs = specs
+        .iter()
+        .map(|(proof_spec, witnesses)| {
+            ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec,
+                witnesses.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap()
+            .0
+        })
+        .collect::<Vec<_>>();
+
+    let mut verify_group = c.benchmark_group("Batch accumulator membership proof verification");
+    verify_group.sample_size(10);
+    for (((proof_spec, _), proof), id) in specs.iter().zip(proofs.iter()).zip(ids.iter()) {
+        verify_group.bench_with_input(
+            id.clone(),
+            &(proof_spec, proof),
+            |b, &(proof_spec, proof)| {
+                b.iter(|| {
+                    proof
+                        .clone()
+                        .verify::<StdRng, Blake2b512>(
+                            &mut rng,
+                            black_box(proof_spec),
+                            nonce.clone(),
+                            Default::default(),
+                        )
+                        .unwrap()
+                });
+            },
+        );
+    }
+    verify_group.finish();
+}
+
+criterion_group!(benches, batch_membership);
+criterion_main!(benches);
@@ -0,0 +1,103 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{rngs::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use proof_system::{
+    prelude::{MetaStatements, ProofSpec, Witnesses},
+    statement::{bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, Statements},
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+use test_utils::{bbs::bbs_plus_sig_setup, ProofG1};
+
+const MESSAGE_COUNT: u32 = 10;
+
+/// A proof with the proof spec and nonce to verify it with
+type Item = (ProofG1, ProofSpec<Bls12_381, G1Affine>, Option<Vec<u8>>);
+/// Same as `Item` but borrowing the proof spec, as taken by batch verification
+type BatchItem<'a> = (ProofG1, &'a ProofSpec<Bls12_381, G1Affine>, Option<Vec<u8>>);
+
+/// Creates `count` proofs of knowledge of BBS+ signatures from different signers, each with its own
+/// proof spec and nonce
+fn setup(rng: &mut StdRng, count: usize) -> Vec<Item> {
+    (0..count)
+        .map(|i| {
+            let (msgs, params, keypair, sig) = bbs_plus_sig_setup(rng, MESSAGE_COUNT);
+            let mut statements = Statements::new();
+            statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+                params,
+                keypair.public_key.clone(),
+                BTreeMap::new(),
+            ));
+            let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+            let mut witnesses = Witnesses::new();
+            witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+                sig,
+                msgs.into_iter().enumerate().collect(),
+            ));
+            let nonce = Some((i as u64).to_le_bytes().to_vec());
+            let proof = ProofG1::new::<StdRng, Blake2b512>(
+                rng,
+                &proof_spec,
+                witnesses,
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap()
+            .0;
+            (proof, proof_spec, nonce)
+        })
+        .collect()
+}
+
+fn batch(items: &[Item]) -> Vec<BatchItem<'_>> {
+    items
+        .iter()
+        .map(|(p, s, n)| (p.clone(), s, n.clone()))
+        .collect()
+}
+
+fn batch_verification_benchmark(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let proof_counts = [10, 100];
+    let items = proof_counts
+        .iter()
+        .map(|n| setup(&mut rng, *n))
+        .collect::<Vec<_>>();
+
+    // A group for each number of proofs so that serial and parallel verification are compared side by side
+    for (i, count) in proof_counts.iter().enumerate() {
+        let mut group = c.benchmark_group(format!("Batch verification of {} proofs", count));
+        group.sample_size(10);
+        group.bench_with_input(
+            BenchmarkId::from_parameter("serial"),
+            &items[i],
+            |b, items| {
+                b.iter(|| {
+                    ProofG1::batch_verify::<StdRng, Blake2b512>(&mut rng, batch(items), true)
+                        .unwrap()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::from_parameter("parallel"),
+            &items[i],
+            |b, items| {
+                b.iter(|| {
+                    ProofG1::batch_verify_parallel::<StdRng, Blake2b512>(
+                        &mut rng,
+                        batch(items),
+                        true,
+                    )
+                    .unwrap()
+                })
+            },
+        );
+        group.finish();
+    }
+}
+
+criterion_group!(benches, batch_verification_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,133 @@
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
+use ark_std::{
+    collections::BTreeMap,
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use bbs_plus::{
+    proof::{MessageOrBlinding, PoKOfSignatureG1Proof, PoKOfSignatureG1Protocol},
+    setup::{KeypairG2, SignatureParamsG1},
+    signature::SignatureG1,
+};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+struct Setup<E: Pairing> {
+    messages: Vec<E::ScalarField>,
+    params: SignatureParamsG1<E>,
+    keypair: KeypairG2<E>,
+    sig: SignatureG1<E>,
+    /// Half the messages are revealed
+    revealed_msgs: BTreeMap<usize, E::ScalarField>,
+}
+
+impl<E: Pairing> Setup<E> {
+    fn new(rng: &mut StdRng, message_count: u32) -> Self {
+        let messages = (0..message_count)
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let params = SignatureParamsG1::<E>::generate_using_rng(rng, message_count);
+        let keypair = KeypairG2::<E>::generate_using_rng(rng, &params);
+        let sig = SignatureG1::<E>::new(rng, &messages, &keypair.secret_key, &params).unwrap();
+        let revealed_msgs = messages
+            .iter()
+            .take(messages.len() / 2)
+            .cloned()
+            .enumerate()
+            .collect();
+        Self {
+            messages,
+            params,
+            keypair,
+            sig,
+            revealed_msgs,
+        }
+    }
+
+    fn prove(&self, rng: &mut StdRng) -> (PoKOfSignatureG1Proof<E>, E::ScalarField) {
+        let pok = PoKOfSignatureG1Protocol::init(
+            rng,
+            black_box(&self.sig),
+            black_box(&self.params),
+            self.messages.iter().enumerate().map(|(idx, msg)| {
+                if self.revealed_msgs.contains_key(&idx) {
+                    MessageOrBlinding::RevealMessage(msg)
+                } else {
+                    MessageOrBlinding::BlindMessageRandomly(msg)
+                }
+            }),
+        )
+        .unwrap();
+        // Not benchmarking challenge contribution as that is just serialization
+        let challenge = E::ScalarField::rand(rng);
+        (pok.gen_proof(&challenge).unwrap(), challenge)
+    }
+
+    fn verify(&self, proof: &PoKOfSignatureG1Proof<E>, challenge: &E::ScalarField) {
+        proof
+            .verify(
+                black_box(&self.revealed_msgs),
+                black_box(challenge),
+                black_box(self.keypair.public_key.clone()),
+                black_box(self.params.clone()),
+            )
+            .unwrap();
+    }
+}
+
+fn bbs_plus_curves_benchmark(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let message_counts = [4, 15, 30, 60];
+    let setups_381 = message_counts
+        .iter()
+        .map(|c| Setup::<Bls12_381>::new(&mut rng, *c))
+        .collect::<Vec<_>>();
+    let setups_377 = message_counts
+        .iter()
+        .map(|c| Setup::<Bls12_377>::new(&mut rng, *c))
+        .collect::<Vec<_>>();
+
+    let mut prove_group = c.benchmark_group("Creating proof of knowledge of BBS+ signature");
+    for (i, count) in message_counts.iter().enumerate() {
+        prove_group.bench_with_input(
+            BenchmarkId::new("Bls12_381", count),
+            &setups_381[i],
+            |b, setup| b.iter(|| setup.prove(&mut rng)),
+        );
+        prove_group.bench_with_input(
+            BenchmarkId::new("Bls12_377", count),
+            &setups_377[i],
+            |b, setup| b.iter(|| setup.prove(&mut rng)),
+        );
+    }
+    prove_group.finish();
+
+    let proofs_381 = setups_381
+        .iter()
+        .map(|s| s.prove(&mut rng))
+        .collect::<Vec<_>>();
+    let proofs_377 = setups_377
+        .iter()
+        .map(|s| s.prove(&mut rng))
+        .collect::<Vec<_>>();
+
+    let mut verify_group = c.benchmark_group("Verifying proof of knowledge of BBS+ signature");
+    for (i, count) in message_counts.iter().enumerate() {
+        verify_group.bench_with_input(
+            BenchmarkId::new("Bls12_381", count),
+            &(&setups_381[i], &proofs_381[i]),
+            |b, (setup, (proof, challenge))| b.iter(|| setup.verify(proof, challenge)),
+        );
+        verify_group.bench_with_input(
+            BenchmarkId::new("Bls12_377", count),
+            &(&setups_377[i], &proofs_377[i]),
+            |b, (setup, (proof, challenge))| b.iter(|| setup.verify(proof, challenge)),
+        );
+    }
+    verify_group.finish();
+}
+
+criterion_group!(benches, bbs_plus_curves_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,135 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{rngs::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use proof_system::{
+    prelude::{MetaStatements, ProofSpec, ProverConfig, VerifierConfig, Witnesses},
+    statement::{bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, Statements},
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+use test_utils::{bbs::bbs_plus_sig_setup, ProofG1};
+
+/// Creates specs for a signature over `count` messages, one revealing all messages, for which the signature is sent
+/// as it is, and the other hiding the last message, which needs a proof of knowledge of the signature.
+fn setup(
+    rng: &mut StdRng,
+    count: u32,
+) -> [(ProofSpec<Bls12_381, G1Affine>, Witnesses<Bls12_381>); 2] {
+    let (msgs, sig_params, keypair, sig) = bbs_plus_sig_setup(rng, count);
+    [count as usize, count as usize - 1].map(|revealed_count| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            msgs[..revealed_count]
+                .iter()
+                .enumerate()
+                .map(|(i, m)| (i, *m))
+                .collect(),
+        ));
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.iter()
+                .enumerate()
+                .skip(revealed_count)
+                .map(|(i, m)| (i, *m))
+                .collect::<BTreeMap<_, _>>(),
+        ));
+        (proof_spec, witnesses)
+    })
+}
+
+/// Sending the signature as it is needs both the prover and verifier to opt in
+fn prover_config() -> ProverConfig<Bls12_381> {
+    ProverConfig {
+        send_fully_revealed_bbs_plus_signatures: true,
+        ..Default::default()
+    }
+}
+
+fn full_reveal(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let nonce = Some(b"test-nonce".to_vec());
+
+    let counts = [10, 30];
+    // For each count, the spec revealing all messages and then the one hiding a message
+    let specs = counts
+        .iter()
+        .flat_map(|n| setup(&mut rng, *n))
+        .collect::<Vec<_>>();
+    let ids = counts
+        .iter()
+        .flat_map(|n| ["all_revealed", "one_hidden"].map(|name| BenchmarkId::new(name, n)))
+        .collect::<Vec<_>>();
+
+    let mut prove_group = c.benchmark_group("BBS+ proof generation with revealed messages");
+    for ((proof_spec, witnesses), id) in specs.iter().zip(ids.iter()) {
+        prove_group.bench_with_input(
+            id.clone(),
+            &(proof_spec, witnesses),
+            |b, &(proof_spec, witnesses)| {
+                b.iter(|| {
+                    ProofG1::new::<StdRng, Blake2b512>(
+                        &mut rng,
+                        black_box(proof_spec),
+                        witnesses.clone(),
+                        nonce.clone(),
+                        prover_config(),
+                    )
+                    .unwrap()
+                });
+            },
+        );
+    }
+    prove_group.finish();
+
+    let proofs = specs
+        .iter()
+        .map(|(proof_spec, witnesses)| {
+            ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec,
+                witnesses.clone(),
+                nonce.clone(),
+                prover_config(),
+            )
+            .unwrap()
+            .0
+        })
+        .collect::<Vec<_>>();
+
+    let mut verify_group = c.benchmark_group("BBS+ proof verification with revealed messages");
+    for (((proof_spec, _), proof), id) in specs.iter().zip(proofs.iter()).zip(ids.iter()) {
+        verify_group.bench_with_input(
+            id.clone(),
+            &(proof_spec, proof),
+            |b, &(proof_spec, proof)| {
+                b.iter(|| {
+                    proof
+                        .clone()
+                        .verify::<StdRng, Blake2b512>(
+                            &mut rng,
+                            black_box(proof_spec),
+                            nonce.clone(),
+                            VerifierConfig {
+                                accept_fully_revealed_bbs_plus_signatures: true,
+                                ..Default::default()
+                            },
+                        )
+                        .unwrap()
+                });
+            },
+        );
+    }
+    verify_group.finish();
+}
+
+criterion_group!(benches, full_reveal);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{
+    collections::BTreeMap,
+    rand::{rngs::StdRng, SeedableRng},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use proof_system::{
+    prelude::{MetaStatements, ProofSpec},
+    statement::{bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, Statements},
+    versioning::{CachedSize, VersionedSerialization},
+};
+use test_utils::bbs::bbs_plus_sig_setup;
+
+/// Compare serializing a large proof spec many times when its size is computed for each serialization vs when
+/// the size is cached.
+fn serialization(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    for _ in 0..50 {
+        let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 20);
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params,
+            keypair.public_key,
+            BTreeMap::new(),
+        ));
+    }
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    let cached = CachedSize::new(proof_spec.clone());
+
+    let mut group = c.benchmark_group("Proof spec with 50 statements");
+    group.bench_function("serialized size", |b| {
+        b.iter(|| black_box(&proof_spec).compressed_size())
+    });
+    group.bench_function("cached serialized size", |b| {
+        b.iter(|| black_box(&cached).compressed_size())
+    });
+    group.bench_function("versioned serialization", |b| {
+        b.iter(|| black_box(&proof_spec).to_versioned_bytes().unwrap())
+    });
+    group.bench_function("versioned serialization with cached size", |b| {
+        b.iter(|| black_box(&cached).to_versioned_bytes().unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, serialization);
+criterion_main!(benches);
@@ -0,0 +1,183 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use proof_system::{
+    prelude::{EqualWitnesses, MetaStatements, ProofSpec, Witnesses},
+    statement::{
+        accumulator::AccumulatorMembership as AccumulatorMembershipStmt,
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, Statements,
+    },
+    witness::{Membership as MembershipWit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit},
+};
+use test_utils::{
+    accumulators::setup_positive_accum, bbs::bbs_plus_sig_setup_given_messages, Fr, ProofG1,
+};
+use vb_accumulator::prelude::{Accumulator, MembershipProvingKey};
+
+const MESSAGE_COUNT: usize = 10;
+
+/// Creates a spec with `sig_count` proofs of knowledge of BBS+ signatures, and when `with_accumulator` is set,
+/// as many accumulator membership proofs. The 1st message of all signatures is same and the 2nd message
+/// of each signature is the accumulator member so witness equalities are exercised as well.
+fn setup(
+    rng: &mut StdRng,
+    sig_count: usize,
+    with_accumulator: bool,
+) -> (ProofSpec<Bls12_381, G1Affine>, Witnesses<Bls12_381>) {
+    let common_msg = Fr::rand(rng);
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(rng);
+
+    let mut statements = Statements::new();
+    let mut meta_statements = MetaStatements::new();
+    let mut witnesses = Witnesses::new();
+    let mut members = vec![];
+
+    for _ in 0..sig_count {
+        let mut msgs = (0..MESSAGE_COUNT)
+            .map(|_| Fr::rand(rng))
+            .collect::<Vec<_>>();
+        msgs[0] = common_msg;
+        let (params, keypair, sig) = bbs_plus_sig_setup_given_messages(rng, &msgs);
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params,
+            keypair.public_key,
+            BTreeMap::new(),
+        ));
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig,
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        members.push(msgs[1]);
+    }
+
+    if sig_count > 1 {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            (0..sig_count).map(|i| (i, 0)).collect::<BTreeSet<_>>(),
+        ));
+    }
+
+    if with_accumulator {
+        for m in &members {
+            accumulator = accumulator
+                .add(*m, &accum_keypair.secret_key, &mut state)
+                .unwrap();
+        }
+        for (i, m) in members.into_iter().enumerate() {
+            let wit = accumulator
+                .get_membership_witness(&m, &accum_keypair.secret_key, &state)
+                .unwrap();
+            let s_idx = statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+                accum_params.clone(),
+                accum_keypair.public_key.clone(),
+                mem_prk.clone(),
+                *accumulator.value(),
+            ));
+            witnesses.add(MembershipWit::new_as_witness(m, wit));
+            meta_statements.add_witness_equality(EqualWitnesses(
+                vec![(i, 1), (s_idx, 0)]
+                    .into_iter()
+                    .collect::<BTreeSet<_>>(),
+            ));
+        }
+    }
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+    (proof_spec, witnesses)
+}
+
+fn composite_benchmark(
+    c: &mut Criterion,
+    name: &str,
+    sig_counts: &[usize],
+    with_accumulator: bool,
+) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let nonce = Some(b"test-nonce".to_vec());
+
+    let specs = sig_counts
+        .iter()
+        .map(|n| setup(&mut rng, *n, with_accumulator))
+        .collect::<Vec<_>>();
+
+    let mut prove_group = c.benchmark_group(format!("{} proof generation", name));
+    prove_group.sample_size(10);
+    for (i, (proof_spec, witnesses)) in specs.iter().enumerate() {
+        prove_group.bench_with_input(
+            BenchmarkId::from_parameter(sig_counts[i]),
+            &(proof_spec, witnesses),
+            |b, &(proof_spec, witnesses)| {
+                b.iter(|| {
+                    ProofG1::new::<StdRng, Blake2b512>(
+                        &mut rng,
+                        black_box(proof_spec),
+                        witnesses.clone(),
+                        nonce.clone(),
+                        Default::default(),
+                    )
+                    .unwrap()
+                });
+            },
+        );
+    }
+    prove_group.finish();
+
+    let proofs = specs
+        .iter()
+        .map(|(proof_spec, witnesses)| {
+            ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                proof_spec,
+                witnesses.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap()
+            .0
+        })
+        .collect::<Vec<_>>();
+
+    let mut verify_group = c.benchmark_group(format!("{} proof verification", name));
+    verify_group.sample_size(10);
+    for (i, ((proof_spec, _), proof)) in specs.iter().zip(proofs.iter()).enumerate() {
+        verify_group.bench_with_input(
+            BenchmarkId::from_parameter(sig_counts[i]),
+            &(proof_spec, proof),
+            |b, &(proof_spec, proof)| {
+                b.iter(|| {
+                    proof
+                        .clone()
+                        .verify::<StdRng, Blake2b512>(
+                            &mut rng,
+                            black_box(proof_spec),
+                            nonce.clone(),
+                            Default::default(),
+                        )
+                        .unwrap()
+                });
+            },
+        );
+    }
+    verify_group.finish();
+}
+
+fn bbs_plus_composite(c: &mut Criterion) {
+    composite_benchmark(c, "BBS+ composite", &[1, 3, 5, 10], false);
+}
+
+fn bbs_plus_and_accumulator_composite(c: &mut Criterion) {
+    composite_benchmark(c, "BBS+ and accumulator composite", &[1, 3, 5], true);
+}
+
+criterion_group!(
+    benches,
+    bbs_plus_composite,
+    bbs_plus_and_accumulator_composite
+);
+criterion_main!(benches);
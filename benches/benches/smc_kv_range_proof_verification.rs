@@ -0,0 +1,92 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use proof_system::{
+    statement::{
+        bound_check_smc::SmcParamsAndCommitmentKey,
+        bound_check_smc_with_kv::SmcParamsAndCommitmentKeyAndSecretKey,
+    },
+    statement_proof::StatementProof,
+    sub_protocols::bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
+};
+use test_utils::Fr;
+
+/// Compare verifying many keyed-verification range proofs when the verifier's params are prepared for each
+/// verification vs when they are prepared once and reused.
+fn verification(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (params, sk) =
+        SmcParamsAndCommitmentKey::<Bls12_381>::new::<_, Blake2b512>(&mut rng, b"test", 16);
+    let comm_key = [params.comm_key.g, params.comm_key.h];
+    let params_with_sk = SmcParamsAndCommitmentKeyAndSecretKey {
+        params_and_comm_key: params.clone(),
+        sk,
+    };
+
+    let min = 100;
+    let max = min + 2_u64.pow(32);
+    let value = Fr::from(min + 1000);
+
+    let mut protocol = BoundCheckSmcWithKVProtocol::new_for_prover(0, min, max, &params);
+    protocol.init(&mut rng, &comm_key, value, None).unwrap();
+    let challenge = Fr::rand(&mut rng);
+    let proof = match protocol
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap()
+    {
+        StatementProof::BoundCheckSmcWithKV(p) => p,
+        _ => panic!("Expected keyed-verification range proof"),
+    };
+
+    let count = 1000;
+    let prepared_params = params_with_sk.precompute();
+
+    let mut group = c.benchmark_group("Keyed-verification range proof verification");
+    group.sample_size(10);
+    group.bench_function(
+        format!("{} verifications without prepared params", count),
+        |b| {
+            b.iter(|| {
+                for _ in 0..count {
+                    let verifier =
+                        BoundCheckSmcWithKVProtocol::new_for_verifier(0, min, max, &params_with_sk);
+                    black_box(
+                        verifier
+                            .verify_proof_contribution(&challenge, &proof, &comm_key)
+                            .unwrap(),
+                    );
+                }
+            })
+        },
+    );
+    group.bench_function(
+        format!("{} verifications with prepared params", count),
+        |b| {
+            b.iter(|| {
+                for _ in 0..count {
+                    let verifier =
+                        BoundCheckSmcWithKVProtocol::new_for_verifier_with_prepared_params(
+                            0,
+                            min,
+                            max,
+                            &prepared_params,
+                        );
+                    black_box(
+                        verifier
+                            .verify_proof_contribution(&challenge, &proof, &comm_key)
+                            .unwrap(),
+                    );
+                }
+            })
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, verification);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! Manager of a universal accumulator for the party holding its secret key, to create the witnesses used in
+//! `Witness::AccumulatorMembership` and `Witness::AccumulatorNonMembership`
+
+use ark_ec::pairing::Pairing;
+use ark_std::fmt::{self, Debug};
+use vb_accumulator::{
+    persistence::UniversalAccumulatorState,
+    positive::Accumulator,
+    setup::{SecretKey as AccumSecretKey, SetupParams as AccumParams},
+    universal::UniversalAccumulator,
+    witness::{MembershipWitness, NonMembershipWitness},
+};
+
+use crate::error::ProofSystemError;
+
+/// Holds a universal accumulator with its secret key and state to create the membership and non-membership
+/// witnesses used in `Witness::AccumulatorMembership` and `Witness::AccumulatorNonMembership`. The state must
+/// have the accumulated elements other than the ones added while initializing the accumulator.
+#[derive(Clone)]
+pub struct AccumulatorManager<E: Pairing, S> {
+    pub accumulator: UniversalAccumulator<E>,
+    pub params: AccumParams<E>,
+    pub state: S,
+    secret_key: AccumSecretKey<E::ScalarField>,
+}
+
+impl<E: Pairing, S> AccumulatorManager<E, S>
+where
+    S: for<'a> UniversalAccumulatorState<'a, E::ScalarField>,
+{
+    pub fn new(
+        accumulator: UniversalAccumulator<E>,
+        params: AccumParams<E>,
+        secret_key: AccumSecretKey<E::ScalarField>,
+        state: S,
+    ) -> Self {
+        Self {
+            accumulator,
+            params,
+            state,
+            secret_key,
+        }
+    }
+
+    /// Create the witness for proving that `element` is not in the accumulator. Errors if it is.
+    pub fn generate_non_membership_witness(
+        &self,
+        element: &E::ScalarField,
+    ) -> Result<NonMembershipWitness<E::G1Affine>, ProofSystemError> {
+        Ok(self.accumulator.get_non_membership_witness(
+            element,
+            &self.secret_key,
+            &self.state,
+            &self.params,
+        )?)
+    }
+
+    /// Create the witness for proving that `element` is in the accumulator. Errors if it isn't.
+    pub fn generate_membership_witness(
+        &self,
+        element: &E::ScalarField,
+    ) -> Result<MembershipWitness<E::G1Affine>, ProofSystemError> {
+        Ok(self
+            .accumulator
+            .get_membership_witness(element, &self.secret_key, &self.state)?)
+    }
+}
+
+/// The secret key is left out so that the manager can be logged without leaking it
+impl<E: Pairing, S: Debug> Debug for AccumulatorManager<E, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccumulatorManager")
+            .field("accumulator", &self.accumulator)
+            .field("params", &self.params)
+            .field("state", &self.state)
+            .field("secret_key", &"<redacted>")
+            .finish()
+    }
+}
@@ -0,0 +1,334 @@
+//! Static estimate of the work a prover has to do to create a proof for a `ProofSpec`. This lets a client
+//! (like a thin wallet) decide whether it can afford to create a requested proof before trying to create it.
+//!
+//! The estimate is derived only from the statements and their parameters (number of messages, range widths,
+//! proving key sizes, etc.) and is not a measurement. Scalar multiplications are counted as the number of
+//! terms in the (multi-)scalar multiplications and exponentiations in the target group, pairings are counted as
+//! Miller loops and hash inputs are the number of group or field elements written to the challenge hash.
+//! Only the dominant operations are counted so the numbers are approximate except for the number of pairings.
+//! Statements used only by the verifier, like `SaverVerifier`, don't add any cost.
+
+use crate::{
+    error::ProofSystemError,
+    proof_spec::ProofSpec,
+    statement::Statement,
+    sub_protocols::{should_use_cls, SubProtocolType},
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::{
+    collections::BTreeMap,
+    ops::{Add, AddAssign},
+};
+use bbs_plus::setup::MultiMessageSignatureParams;
+use legogroth16::ProvingKeyCommon;
+use smc_range_proof::ccs_range_proof::util::find_l_greater_than;
+
+/// Number of expensive operations done by the prover
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OperationCounts {
+    pub scalar_multiplications: usize,
+    pub pairings: usize,
+    pub hash_inputs: usize,
+}
+
+/// Estimated cost of creating a proof, grouped by the type of sub-protocol that the prover runs for a statement
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProverCostEstimate {
+    pub per_statement_type: BTreeMap<SubProtocolType, OperationCounts>,
+}
+
+impl OperationCounts {
+    pub fn new(scalar_multiplications: usize, pairings: usize, hash_inputs: usize) -> Self {
+        Self {
+            scalar_multiplications,
+            pairings,
+            hash_inputs,
+        }
+    }
+}
+
+impl Add for OperationCounts {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            scalar_multiplications: self.scalar_multiplications + rhs.scalar_multiplications,
+            pairings: self.pairings + rhs.pairings,
+            hash_inputs: self.hash_inputs + rhs.hash_inputs,
+        }
+    }
+}
+
+impl AddAssign for OperationCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl ProverCostEstimate {
+    /// Counts summed over all statement types
+    pub fn total(&self) -> OperationCounts {
+        self.per_statement_type
+            .values()
+            .fold(OperationCounts::default(), |acc, c| acc + *c)
+    }
+
+    /// Counts for the given statement type. Zero if the spec has no statement of that type.
+    pub fn for_statement_type(&self, typ: SubProtocolType) -> OperationCounts {
+        self.per_statement_type
+            .get(&typ)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn add(&mut self, typ: SubProtocolType, counts: OperationCounts) {
+        *self.per_statement_type.entry(typ).or_default() += counts;
+    }
+}
+
+impl<E, G> ProofSpec<E, G>
+where
+    E: Pairing,
+    G: AffineRepr,
+{
+    /// Estimate the number of scalar multiplications, pairings and hash inputs needed by the prover to create
+    /// a proof for this spec. See the module docs for what is counted.
+    pub fn estimate_prover_cost(&self) -> Result<ProverCostEstimate, ProofSystemError> {
+        let mut estimate = ProverCostEstimate::default();
+        for (s_idx, statement) in self.statements.0.iter().enumerate() {
+            match statement {
+                Statement::PoKBBSSignatureG1(s) => {
+                    let n = s
+                        .get_sig_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let revealed = s.revealed_messages.len();
+                    // Computing `b` needs all message bases, the randomized signature 5 and the Schnorr
+                    // commitments are over the hidden messages and 4 more bases. This is the cost with the default
+                    // `ProverConfig` which creates a proof of knowledge even when all messages are revealed.
+                    estimate.add(
+                        SubProtocolType::PoKBBSSignatureG1,
+                        OperationCounts::new(
+                            n + 2 + 5 + n.saturating_sub(revealed) + 4,
+                            0,
+                            10 + 2 * revealed,
+                        ),
+                    );
+                }
+                Statement::PoKBBSSignature23G1(s) => {
+                    let n = s
+                        .get_sig_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let revealed = s.revealed_messages.len();
+                    estimate.add(
+                        SubProtocolType::PoKBBSSignature23G1,
+                        OperationCounts::new(
+                            n + 1 + 3 + n.saturating_sub(revealed) + 2,
+                            0,
+                            6 + 2 * revealed,
+                        ),
+                    );
+                }
+                Statement::PoKPSSignature(s) => {
+                    let n = s
+                        .get_sig_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let revealed = s.revealed_messages.len();
+                    estimate.add(
+                        SubProtocolType::PSSignaturePoK,
+                        OperationCounts::new(
+                            4 + n.saturating_sub(revealed) + 2,
+                            0,
+                            6 + 2 * revealed,
+                        ),
+                    );
+                }
+                Statement::AccumulatorMembership(_) => {
+                    // The pairing in the Schnorr commitment is a multi-pairing of 2 pairs
+                    estimate.add(
+                        SubProtocolType::AccumulatorMembership,
+                        OperationCounts::new(10, 2, 14),
+                    );
+                }
+                Statement::AccumulatorNonMembership(_) => {
+                    estimate.add(
+                        SubProtocolType::AccumulatorNonMembership,
+                        OperationCounts::new(17, 2, 20),
+                    );
+                }
+                Statement::BatchAccumulatorMembership(s) => {
+                    let n = s.num_elements;
+                    estimate.add(
+                        SubProtocolType::BatchAccumulatorMembership,
+                        OperationCounts::new(10 * n, 2 * n, 14 * n),
+                    );
+                }
+                Statement::CommittedAccumulatorMembership(_) => {
+                    // Same as membership with a scalar multiplication and pairing for the commitment's randomness
+                    estimate.add(
+                        SubProtocolType::CommittedAccumulatorMembership,
+                        OperationCounts::new(11, 3, 15),
+                    );
+                }
+                Statement::BatchAccumulatorNonMembership(s) => {
+                    let n = s.num_elements;
+                    estimate.add(
+                        SubProtocolType::BatchAccumulatorNonMembership,
+                        OperationCounts::new(17 * n, 2 * n, 20 * n),
+                    );
+                }
+                Statement::PedersenCommitment(s) => {
+                    let k = s.get_commitment_key::<E>(&self.setup_params, s_idx)?.len();
+                    estimate.add(
+                        SubProtocolType::PoKDiscreteLogs,
+                        OperationCounts::new(k, 0, k + 2),
+                    );
+                }
+                Statement::SaverProver(s) => {
+                    let chunks =
+                        saver::utils::chunks_count::<E::ScalarField>(s.chunk_bit_size) as usize;
+                    let pk = &s.get_snark_proving_key(&self.setup_params, s_idx)?.pk;
+                    let snark = pk.a_query.len()
+                        + pk.b_g1_query.len()
+                        + pk.b_g2_query.len()
+                        + pk.h_query.len()
+                        + pk.l_query.len();
+                    // Ciphertext has `chunks + 2` elements and the commitment to the chunks has `chunks + 1` terms.
+                    // The 3 Schnorr protocols are over the ciphertext, chunked commitment and the commitment.
+                    estimate.add(
+                        SubProtocolType::Saver,
+                        OperationCounts::new(
+                            snark + 2 * (chunks + 2) + (chunks + 1) + 2 * chunks + 6,
+                            0,
+                            4 * chunks + 12,
+                        ),
+                    );
+                }
+                Statement::BoundCheckLegoGroth16Prover(s) => {
+                    let pk = &s.get_proving_key(&self.setup_params, s_idx)?.common;
+                    estimate.add(
+                        SubProtocolType::BoundCheckLegoGroth16,
+                        OperationCounts::new(legogroth16_proving_cost(pk) + 2, 0, 8),
+                    );
+                }
+                Statement::R1CSCircomProver(s) => {
+                    let pk = &s.get_proving_key(&self.setup_params, s_idx)?.common;
+                    estimate.add(
+                        SubProtocolType::R1CSLegogroth16,
+                        OperationCounts::new(legogroth16_proving_cost(pk) + 2, 0, 8),
+                    );
+                }
+                Statement::BoundCheckBpp(s) => {
+                    let params = s.get_setup_params::<E>(&self.setup_params, s_idx)?;
+                    let bases = params.G_vec.len() + params.H_vec.len();
+                    // Each round of the norm argument folds the bases and commits to 2 elements
+                    estimate.add(
+                        SubProtocolType::BoundCheckBpp,
+                        OperationCounts::new(2 * bases + 4, 0, bases + 8),
+                    );
+                }
+                Statement::BoundCheckSmc(s) => {
+                    let base = s
+                        .get_params_and_comm_key(&self.setup_params, s_idx)?
+                        .params
+                        .get_supported_base_for_range_proof();
+                    estimate.add(
+                        SubProtocolType::BoundCheckSmc,
+                        smc_range_proof_cost(s.min, s.max, base, true),
+                    );
+                }
+                Statement::BoundCheckSmcWithKVProver(s) => {
+                    let base = s
+                        .get_params_and_comm_key(&self.setup_params, s_idx)?
+                        .params
+                        .get_supported_base_for_range_proof();
+                    estimate.add(
+                        SubProtocolType::BoundCheckSmcWithKV,
+                        smc_range_proof_cost(s.min, s.max, base, false),
+                    );
+                }
+                Statement::PublicInequality(_) => {
+                    estimate.add(SubProtocolType::Inequality, OperationCounts::new(8, 0, 8));
+                }
+                Statement::PoKBBSSignatureG1HiddenPK(s) => {
+                    let n = s
+                        .get_sig_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let revealed = s.revealed_messages.len();
+                    let keys = s.public_keys.len();
+                    // Same as for `PoKBBSSignatureG1` with 2 more for randomizing the signature and the public
+                    // key and the commitments of the proof that the randomized key is from one of the keys.
+                    estimate.add(
+                        SubProtocolType::PoKBBSSignatureG1HiddenPK,
+                        OperationCounts::new(
+                            n + 2 + 5 + n.saturating_sub(revealed) + 4 + 2 + 2 * keys,
+                            0,
+                            10 + 2 * revealed + 1 + 2 * keys,
+                        ),
+                    );
+                }
+                Statement::SchnorrLinearRelation(s) => {
+                    // No group operations. The coefficients, targets and commitments of all equations are
+                    // hashed.
+                    let m = s.coefficients.len();
+                    estimate.add(
+                        SubProtocolType::SchnorrLinearRelation,
+                        OperationCounts::new(0, 0, m * (s.witness_count() + 2)),
+                    );
+                }
+                Statement::MerkleMembershipProver(s) => {
+                    let pk = &s.get_proving_key(&self.setup_params, s_idx)?.common;
+                    estimate.add(
+                        SubProtocolType::MerkleMembership,
+                        OperationCounts::new(legogroth16_proving_cost(pk) + 2, 0, 8),
+                    );
+                }
+                Statement::SaverVerifier(_)
+                | Statement::BoundCheckLegoGroth16Verifier(_)
+                | Statement::R1CSCircomVerifier(_)
+                | Statement::BoundCheckSmcWithKVVerifier(_)
+                | Statement::MerkleMembershipVerifier(_)
+                | Statement::Noop(_) => (),
+            }
+        }
+        Ok(estimate)
+    }
+}
+
+/// Size of the multi-scalar multiplications done when creating a LegoGroth16 proof
+fn legogroth16_proving_cost<E: Pairing>(pk: &ProvingKeyCommon<E>) -> usize {
+    pk.a_query.len()
+        + pk.b_g1_query.len()
+        + pk.b_g2_query.len()
+        + pk.h_query.len()
+        + pk.l_query.len()
+}
+
+/// Cost of the set-membership check based range proof for `min <= value < max`. Each digit proof needs a
+/// pairing unless the proof is for keyed verification. When the range is small enough, the protocol from CLS
+/// is used which proves each digit once rather than once each for the upper and lower bound
+fn smc_range_proof_cost(min: u64, max: u64, base: u16, with_pairing: bool) -> OperationCounts {
+    let (digit_proofs, l) = if should_use_cls(min, max) {
+        let l = find_number_of_digits(max - min, base);
+        (l, l)
+    } else {
+        let l = find_l_greater_than(max, base) as usize;
+        (2 * l, l)
+    };
+    let pairings = if with_pairing { digit_proofs } else { 0 };
+    // Each digit proof randomizes a signature, commits to it and to the digit. The commitment to the digits
+    // has `l + 1` terms and the Schnorr protocol for the commitment to the value has 2.
+    OperationCounts::new(3 * digit_proofs + l + 1 + 2, pairings, 2 * digit_proofs + 4)
+}
+
+/// Number of base `base` digits needed to represent the numbers in `[0, range)` as done by the CLS protocol
+fn find_number_of_digits(range: u64, base: u16) -> usize {
+    let b_1 = (base - 1) as u64;
+    let range = if range % b_1 != 0 { range * b_1 } else { range };
+    let mut power = 1_u64;
+    let mut l = 0;
+    while power < range {
+        power = power.saturating_mul(base as u64);
+        l += 1;
+    }
+    l
+}
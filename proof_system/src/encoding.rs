@@ -0,0 +1,210 @@
+//! Encoding attribute values of common types as field elements so that the signer, prover and verifier agree on
+//! the field element signed and proven for a value.
+//!
+//! Integers and dates are encoded as a `u64` which is then converted to a field element. The `u64` preserves the
+//! order of the values so bound checks over the `u64`s, as given by `U64Encodable`, are bound checks over the
+//! values. Signed integers are offset by `2^63` so `i64::MIN` is encoded as 0 and `i64::MAX` as `u64::MAX`. Dates
+//! are encoded as the signed integer number of days since 0001-01-01 (day 1 of the common era is 0) so each
+//! day in the past is smaller. These encodings are lossless and can be decoded. Encoding dates needs the
+//! `date-encoding` feature.
+//!
+//! Strings are hashed to a field element, along with a domain separator, and can't be decoded nor bound checked.
+
+use crate::{error::ProofSystemError, sub_protocols::enforce_and_get_u64};
+use ark_ff::PrimeField;
+#[cfg(feature = "date-encoding")]
+use ark_std::format;
+use ark_std::vec::Vec;
+#[cfg(feature = "date-encoding")]
+use chrono::{Datelike, NaiveDate};
+use digest::Digest;
+use dock_crypto_utils::hashing_utils::field_elem_from_try_and_incr;
+
+/// Domain separator prefixed to strings before hashing them to a field element
+pub const STRING_ENCODING_DOMAIN: &[u8] = b"proof-system-string-encoding";
+
+/// Offset added to signed integers to map them to unsigned integers while preserving their order
+const I64_OFFSET: u64 = 1 << 63;
+
+/// Values which are encoded as a `u64` before being converted to a field element. Bounds for bound checks over
+/// these values should be given as the `u64` returned by `Self::encode_as_u64`.
+pub trait U64Encodable {
+    fn encode_as_u64(&self) -> u64;
+}
+
+impl U64Encodable for u64 {
+    fn encode_as_u64(&self) -> u64 {
+        *self
+    }
+}
+
+impl U64Encodable for i64 {
+    fn encode_as_u64(&self) -> u64 {
+        (*self as u64) ^ I64_OFFSET
+    }
+}
+
+#[cfg(feature = "date-encoding")]
+impl U64Encodable for NaiveDate {
+    fn encode_as_u64(&self) -> u64 {
+        (self.num_days_from_ce() as i64 - 1).encode_as_u64()
+    }
+}
+
+pub fn encode_u64<F: PrimeField>(value: u64) -> F {
+    F::from(value)
+}
+
+/// Inverse of `encode_u64`. Errors if the field element is larger than a `u64`.
+pub fn decode_u64<F: PrimeField>(elem: &F) -> Result<u64, ProofSystemError> {
+    enforce_and_get_u64(elem)
+}
+
+pub fn encode_i64<F: PrimeField>(value: i64) -> F {
+    encode_u64(value.encode_as_u64())
+}
+
+/// Inverse of `encode_i64`. Errors if the field element is larger than a `u64`.
+pub fn decode_i64<F: PrimeField>(elem: &F) -> Result<i64, ProofSystemError> {
+    Ok((decode_u64(elem)? ^ I64_OFFSET) as i64)
+}
+
+#[cfg(feature = "date-encoding")]
+pub fn encode_date<F: PrimeField>(date: &NaiveDate) -> F {
+    encode_u64(date.encode_as_u64())
+}
+
+/// Inverse of `encode_date`. Errors if the field element is larger than a `u64` or isn't a date supported by
+/// `NaiveDate`.
+#[cfg(feature = "date-encoding")]
+pub fn decode_date<F: PrimeField>(elem: &F) -> Result<NaiveDate, ProofSystemError> {
+    let days = decode_i64(elem)?;
+    days.checked_add(1)
+        .and_then(|d| i32::try_from(d).ok())
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or_else(|| {
+            ProofSystemError::UnsupportedValue(format!("{} days is out of range of dates", days))
+        })
+}
+
+/// Hash the string to a field element using the hash function `D`. Can't be decoded.
+pub fn encode_string<F: PrimeField, D: Digest>(value: &str) -> F {
+    let mut bytes = Vec::with_capacity(STRING_ENCODING_DOMAIN.len() + value.len());
+    bytes.extend_from_slice(STRING_ENCODING_DOMAIN);
+    bytes.extend_from_slice(value.as_bytes());
+    field_elem_from_try_and_incr::<F, D>(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicates::predicate_spec::Predicate;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use blake2::Blake2b512;
+
+    #[test]
+    fn encoded_values() {
+        assert_eq!(encode_u64::<Fr>(0), Fr::from(0u64));
+        assert_eq!(encode_u64::<Fr>(u64::MAX), Fr::from(u64::MAX));
+
+        assert_eq!(encode_i64::<Fr>(i64::MIN), Fr::from(0u64));
+        assert_eq!(encode_i64::<Fr>(-1), Fr::from((1u64 << 63) - 1));
+        assert_eq!(encode_i64::<Fr>(0), Fr::from(1u64 << 63));
+        assert_eq!(encode_i64::<Fr>(i64::MAX), Fr::from(u64::MAX));
+
+        let mut bytes = b"proof-system-string-encoding".to_vec();
+        bytes.extend_from_slice(b"Germany");
+        assert_eq!(
+            encode_string::<Fr, Blake2b512>("Germany"),
+            field_elem_from_try_and_incr::<Fr, Blake2b512>(&bytes)
+        );
+        assert_ne!(
+            encode_string::<Fr, Blake2b512>("Germany"),
+            encode_string::<Fr, Blake2b512>("France")
+        );
+    }
+
+    #[test]
+    fn encoding_preserves_order() {
+        let values = [i64::MIN, -100, -1, 0, 1, 100, i64::MAX];
+        for w in values.windows(2) {
+            assert!(w[0].encode_as_u64() < w[1].encode_as_u64());
+        }
+    }
+
+    #[test]
+    fn integer_round_trip() {
+        for v in [0, 1, 1000, u64::MAX - 1, u64::MAX] {
+            assert_eq!(decode_u64(&encode_u64::<Fr>(v)).unwrap(), v);
+        }
+        for v in [i64::MIN, -1000, -1, 0, 1, 1000, i64::MAX] {
+            assert_eq!(decode_i64(&encode_i64::<Fr>(v)).unwrap(), v);
+        }
+
+        let too_large = Fr::from(u64::MAX) + Fr::from(1u64);
+        assert!(decode_u64(&too_large).is_err());
+        assert!(decode_i64(&too_large).is_err());
+    }
+
+    #[cfg(feature = "date-encoding")]
+    #[test]
+    fn encoded_dates() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(encode_date::<Fr>(&date(1, 1, 1)), encode_i64::<Fr>(0));
+        assert_eq!(
+            encode_date::<Fr>(&date(1970, 1, 1)),
+            encode_i64::<Fr>(719162)
+        );
+        assert_eq!(
+            encode_date::<Fr>(&date(2000, 2, 29)),
+            encode_i64::<Fr>(730178)
+        );
+        assert_eq!(encode_date::<Fr>(&date(0, 12, 31)), encode_i64::<Fr>(-1));
+
+        // Order is preserved
+        assert_eq!(
+            date(1999, 12, 31).encode_as_u64() + 1,
+            date(2000, 1, 1).encode_as_u64()
+        );
+
+        for d in [
+            NaiveDate::MIN,
+            NaiveDate::from_ymd_opt(1, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            NaiveDate::MAX,
+        ] {
+            assert_eq!(decode_date(&encode_date::<Fr>(&d)).unwrap(), d);
+        }
+
+        let too_large = Fr::from(u64::MAX) + Fr::from(1u64);
+        assert!(decode_date(&too_large).is_err());
+        assert!(decode_date(&encode_i64::<Fr>(i64::MAX)).is_err());
+    }
+
+    #[test]
+    fn range_predicate_over_encoded_values() {
+        let (min, max) = (-40i64, 50i64);
+        assert_eq!(
+            Predicate::<Bls12_381>::in_range("temperature", min, max),
+            Predicate::InRange {
+                attribute: "temperature".into(),
+                min: min.encode_as_u64(),
+                max: max.encode_as_u64(),
+            }
+        );
+
+        #[cfg(feature = "date-encoding")]
+        {
+            let min = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+            let max = NaiveDate::from_ymd_opt(2006, 1, 1).unwrap();
+            assert_eq!(
+                Predicate::<Bls12_381>::in_range("date_of_birth", min, max),
+                Predicate::InRange {
+                    attribute: "date_of_birth".into(),
+                    min: min.encode_as_u64(),
+                    max: max.encode_as_u64(),
+                }
+            );
+        }
+    }
+}
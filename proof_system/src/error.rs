@@ -5,8 +5,11 @@ use dock_crypto_utils::try_iter::InvalidPair;
 use legogroth16::{circom::CircomError, error::Error as LegoGroth16Error};
 use saver::error::SaverError;
 use schnorr_pok::error::SchnorrError;
+use serde::{Deserialize, Serialize};
 use vb_accumulator::error::VBAccumulatorError;
 
+use crate::sub_protocols::SubProtocolType;
+
 #[derive(Debug)]
 pub enum ProofSystemError {
     UnequalWitnessAndStatementCount(usize, usize),
@@ -87,8 +90,313 @@ pub enum ProofSystemError {
     /// For an arbitrary range proof, the response of both Schnorr protocols should be same
     DifferentResponsesForSchnorrProtocolInBpp(usize),
     BulletproofsPlusPlus(bulletproofs_plus_plus::prelude::BulletproofsPlusPlusError),
+    /// An invalid proof results in `SmcRangeProofError::InvalidRangeProofAtDigit` with the index of the digit, in
+    /// the base decomposition of the value, whose check failed
     SetMembershipBasedRangeProof(smc_range_proof::prelude::SmcRangeProofError),
     SmcParamsNotProvided,
+    /// The accumulator membership witness obtained after applying the updates is not valid for the new
+    /// accumulator value
+    InvalidUpdatedAccumulatorWitness,
+    /// The pairing checks of the non-membership proofs of a batch non-membership statement failed
+    InvalidNonMembershipProof,
+    /// The proof for the BBS+ signature statement at this index is the signature itself, which the verifier didn't
+    /// opt in to accept
+    FullRevealSignatureNotAccepted(usize),
+    /// The statement at this index can't be converted to a statement for the verifier since the verifier's
+    /// statement needs data which the prover's statement doesn't have
+    NoVerifierStatement(usize),
+    /// A predicate refers to an attribute which is not in the credential schema
+    UnknownAttribute(String),
+    /// A range predicate needs to be proved but params for none of the range proof protocols were given
+    BoundCheckParamsNotProvided,
+    /// The proof spec has no statements so there is nothing to prove
+    EmptyProofSpec,
+    /// No witnesses were given but the proof spec has statements which need them
+    EmptyWitnesses,
+    /// The bitmask has no bits or more than 64, or the constraint has no bits or a bit outside the mask
+    InvalidBitmaskPredicate,
+    /// A predicate over several witnesses, like a sum or a count of them, was given no witnesses
+    NoWitnessesToAggregate,
+    /// Field arithmetic failed, like when inverting 0. The message says which value and statement it was for
+    ArithmeticError(String),
+    /// The message and blinding given to open a commitment don't open it
+    InvalidCommitmentOpening,
+    /// The statement proof at this index is not of the expected variant
+    UnexpectedStatementProof {
+        index: usize,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The statement proving knowledge of a BBS+ signature with a hidden public key has no public keys
+    NoPublicKeysForHiddenPK(usize),
+    /// The index of the signer's public key in the witness for the statement at the given index is not the
+    /// index of any of its public keys
+    InvalidHiddenPKIndex(usize, usize),
+    /// The proof that the randomized public key is created from one of the public keys of the statement at this
+    /// index is invalid
+    InvalidRandomizedPublicKeyProof(usize),
+    /// The issuer consistency meta statement at this index has fewer than 2 statements, a statement that doesn't
+    /// prove knowledge of a BBS+ signature with a hidden public key, statements with different public keys or a
+    /// statement that is also in an earlier issuer consistency
+    InvalidIssuerConsistency(usize),
+    /// The witnesses of the statements at these indices are required to have the same issuer but their signatures
+    /// are by different public keys
+    UnsatisfiedIssuerConsistency(usize, usize),
+    /// The proofs of the statements at these indices are required to have the same issuer but their randomized
+    /// public keys differ
+    InvalidIssuerConsistencyProof(usize, usize),
+    /// A witness equality has witnesses of the statements at these indices but their witnesses are elements of
+    /// different scalar fields
+    IncompatibleWitnessFields(usize, usize),
+    /// The public inputs given for a LegoGroth16 bound check proof are not the ones of its statement
+    InvalidBoundCheckPublicInputs,
+    /// The linear relation has no equations, the equations have different numbers of coefficients or the number
+    /// of targets is not the number of equations
+    InvalidLinearRelation,
+    /// The witnesses don't satisfy the linear relation of the statement at this index
+    UnsatisfiedLinearRelation(usize),
+    /// The witness at the given index of the linear relation of the statement at the given index is not proven
+    /// equal to a witness of a statement other than a linear relation so the prover can choose it freely
+    UnboundLinearRelationWitness(usize, usize),
+    /// The proof of the linear relation of the statement at this index is invalid
+    InvalidLinearRelationProof(usize),
+    /// More than one sub-protocol was created for the statement at this index
+    DuplicateStatementId(usize),
+    /// The statement indices of the sub-protocols are not `0, 1, ..., n-1` for `n` sub-protocols
+    NonContiguousStatementIds,
+    /// The proof being read still has this many statement proofs which haven't been read
+    UnreadStatementProofs(usize),
+    /// The unrevealed messages don't have the revocation handle, which is at this index of the signed messages
+    MissingRevocationHandle(usize),
+    /// The message at this index is already revealed
+    MessageAlreadyRevealed(usize),
+    /// The message at this index is not revealed
+    MessageNotRevealed(usize),
+    /// The revealed message index is not less than the number of messages supported by the signature params
+    RevealedMessageIndexOutOfBounds(usize, usize),
+    /// The proof coalescing the Pedersen commitment statement at this index with other statements doesn't match the
+    /// statements, like when a coalesced statement has a different commitment key or is part of a witness equality
+    InvalidCoalescedProof(usize),
+    /// The statement at this index is of a type which is not in the allowed statement types
+    DisallowedStatementType(usize, SubProtocolType),
+    IncompatibleMerkleMembershipSetupParamAtIndex(usize),
+    /// Merkle tree should have a power of 2 number of leaves, and at least 2
+    InvalidMerkleTreeSize(usize),
+    InvalidMerkleTreeLeafIndex(usize),
+    /// The path in the witness of the Merkle membership statement at this index doesn't lead to the root
+    InvalidMerkleMembershipWitness(usize),
+    /// The statement at this index of the proof spec is not a `Noop` so it can't be replaced
+    InvalidNoopReplacement(usize),
+    /// The number of elements in the witness or proof of the batch non-membership statement at this index (first
+    /// member) is not the number of elements in the statement. The second member is the expected count and the
+    /// third is the count found.
+    BatchNonMembershipCountMismatch(usize, usize, usize),
+    /// Same as `BatchNonMembershipCountMismatch` but for the batch membership statement
+    BatchMembershipCountMismatch(usize, usize, usize),
+    /// No value satisfies the bounds of the bound check as `min` is not less than `max`
+    EmptyRange {
+        min: u64,
+        max: u64,
+    },
+    /// The set-membership check params have fewer members, i.e. the base of the range proof, than `required` to
+    /// decompose the range of the bound check into digits
+    InsufficientSmcBases {
+        required: u16,
+        available: u16,
+    },
+    /// The basis of the commitment to hidden messages should have a base for each of the committed messages and one
+    /// for the randomness. Has the number of committed messages and the number of bases
+    InvalidMessageCommitmentBasis(usize, usize),
+    /// No commitment to hidden messages was given for the sub-protocol with this id
+    MessageCommitmentNotGiven(usize),
+    /// The revealed message at `index` of the signature statement at index `statement` is not the same in the
+    /// verifier's proof spec and the one the proof was created for, or is revealed in only one of them
+    RevealedMessageMismatch {
+        statement: usize,
+        index: usize,
+    },
+    /// The accumulator value of the accumulator statement with this id is the identity element
+    InvalidAccumulatorValue(usize),
+    /// Witnesses encrypted with `Witnesses::encrypt_at_rest` couldn't be decrypted as the key is wrong or the
+    /// ciphertext was modified
+    WitnessDecryptionFailed,
+    /// Witnesses couldn't be encrypted with `Witnesses::encrypt_at_rest`
+    WitnessEncryptionFailed,
+    /// The serialized object has a format version which is not supported by this version of the crate
+    UnsupportedProofVersion {
+        found: u16,
+        supported: u16,
+    },
+    /// The proof of the statement at index `statement` doesn't have the structure expected for the statement, like
+    /// the number of Schnorr responses. Returned by `StatementProof::validate_structure`
+    MalformedStatementProof {
+        statement: usize,
+        reason: String,
+    },
+    /// The setup param with this content hash was left out of a serialized proof spec but isn't in the cache it's
+    /// being deserialized with
+    MissingExternalSetup(Vec<u8>),
+    /// The statement disjunction meta statement at this index has fewer than 2 statements, a statement that doesn't
+    /// prove knowledge of a BBS+ signature, a statement that is also in an earlier disjunction or a statement with
+    /// a witness in a witness equality
+    InvalidStatementDisjunction(usize),
+    /// The witnesses of the statements of the statement disjunction meta statement at this index should be a
+    /// witness for exactly one of them and `Witness::Noop` for the others
+    UnsatisfiedStatementDisjunction(usize),
+    /// The challenges of the proofs of the statements of the statement disjunction meta statement at this index
+    /// don't add up to the challenge of the composite proof
+    InvalidStatementDisjunctionProof(usize),
+}
+
+/// Coarse reason for a failure that can be returned to a client without revealing which statement or part of
+/// the proof caused it. Created using `ProofSystemError::to_public`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PublicError {
+    /// The proof doesn't verify
+    InvalidProof,
+    /// The proof, proof spec, witnesses or params are malformed or inconsistent with each other
+    MalformedInput,
+    /// The input needs something this version of the crate doesn't support
+    UnsupportedFeature,
+    /// The input is larger than supported
+    TooLarge,
+}
+
+impl ProofSystemError {
+    /// Map to the coarse `PublicError` which doesn't contain any details like statement indices. The error itself
+    /// should only be logged.
+    pub fn to_public(&self) -> PublicError {
+        match self {
+            Self::ProofIncompatibleWithStatement(..)
+            | Self::ProofIncompatibleWithBBSPlusProtocol
+            | Self::ProofIncompatibleWithSchnorrProtocol
+            | Self::ProofIncompatibleWithAccumulatorMembershipProtocol
+            | Self::ProofIncompatibleWithAccumulatorNonMembershipProtocol
+            | Self::ProofIncompatibleWithSaverProtocol
+            | Self::ProofIncompatibleWithBoundCheckProtocol
+            | Self::WitnessResponseNotEqual(..)
+            | Self::SchnorrError(_)
+            | Self::BBSPlusError(_)
+            | Self::VBAccumError(_)
+            | Self::UnsatisfiedWitnessEqualities(_)
+            | Self::UnsatisfiedStatements(..)
+            | Self::SaverError(_)
+            | Self::SaverInequalChunkedCommitment
+            | Self::SaverInsufficientChunkedCommitmentResponses
+            | Self::SaverInequalChunkedCommitmentResponse
+            | Self::LegoGroth16Error(_)
+            | Self::LegoGroth16InequalResponse
+            | Self::RandomizedPairingCheckFailed
+            | Self::NoAggregateGroth16ProofFound
+            | Self::InvalidNumberOfAggregateGroth16Proofs(..)
+            | Self::NotFoundAggregateGroth16ProofForRequiredStatements(..)
+            | Self::NoAggregateLegoGroth16ProofFound
+            | Self::InvalidNumberOfAggregateLegoGroth16Proofs(..)
+            | Self::NotFoundAggregateLegoGroth16ProofForRequiredStatements(..)
+            | Self::PSSignaturePoKError(_)
+            | Self::DifferentResponsesForSchnorrProtocolInBpp(_)
+            | Self::BulletproofsPlusPlus(_)
+            | Self::SetMembershipBasedRangeProof(_)
+            | Self::InvalidNonMembershipProof
+            | Self::FullRevealSignatureNotAccepted(_)
+            | Self::InvalidCommitmentOpening
+            | Self::InvalidRandomizedPublicKeyProof(_)
+            | Self::InvalidIssuerConsistencyProof(..)
+            | Self::InvalidStatementDisjunctionProof(_)
+            | Self::InvalidBoundCheckPublicInputs
+            | Self::InvalidLinearRelationProof(_)
+            | Self::InvalidCoalescedProof(_)
+            | Self::RevealedMessageMismatch { .. }
+            | Self::MalformedStatementProof { .. } => PublicError::InvalidProof,
+            Self::UnequalWitnessAndStatementCount(..)
+            | Self::WitnessIncompatibleWithStatement(..)
+            | Self::BBSPlusProtocolInvalidMessageCount(..)
+            | Self::BBSProtocolInvalidBlindingIndex(_)
+            | Self::BBSProtocolNonSequentialMessageIndices(_)
+            | Self::BBSProtocolMessageIndicesMustStartFromZero(_)
+            | Self::PSProtocolInvalidMessageCount(..)
+            | Self::PSProtocolNonSequentialMessageIndices(_)
+            | Self::PSProtocolInvalidBlindingIndex(_)
+            | Self::PSProtocolInvalidMessageIndex(..)
+            | Self::PSProtocolMessageIndicesMustStartFromZero(_)
+            | Self::SubProtocolNotReadyToGenerateChallenge(_)
+            | Self::SubProtocolAlreadyInitialized(_)
+            | Self::SubProtocolNotReadyToGenerateProof(_)
+            | Self::InvalidSetupParamsIndex(_)
+            | Self::NeitherParamsNorRefGiven(_)
+            | Self::IncompatibleBBSPlusSetupParamAtIndex(_)
+            | Self::IncompatiblePSSetupParamAtIndex(_)
+            | Self::IncompatiblePedCommSetupParamAtIndex(_)
+            | Self::IncompatibleAccumulatorSetupParamAtIndex(_)
+            | Self::IncompatibleSaverSetupParamAtIndex(_)
+            | Self::IncompatibleBoundCheckSetupParamAtIndex(_)
+            | Self::Serialization(_)
+            | Self::InvalidProofSpec
+            | Self::InvalidStatement
+            | Self::InvalidWitnessEqualities(_)
+            | Self::InvalidStatementProofIndex(_)
+            | Self::SaverSnarkProvingKeyNotProvided
+            | Self::SaverSnarkVerifyingKeyNotProvided
+            | Self::LegoGroth16ProvingKeyNotProvided
+            | Self::LegoGroth16VerifyingKeyNotProvided
+            | Self::BoundCheckMaxNotGreaterThanMin
+            | Self::IncompatibleR1CSSetupParamAtIndex(_)
+            | Self::CircomError(_)
+            | Self::R1CSInsufficientPrivateInputs(..)
+            | Self::InvalidWitnessEquality
+            | Self::WitnessAlreadyBeingRevealed(..)
+            | Self::SnarckpackSrsNotProvided
+            | Self::NotASaverStatementProof
+            | Self::SameStatementIdsFoundInMultipleAggregations(_)
+            | Self::SmcParamsNotProvided
+            | Self::InvalidUpdatedAccumulatorWitness
+            | Self::NoVerifierStatement(_)
+            | Self::UnknownAttribute(_)
+            | Self::BoundCheckParamsNotProvided
+            | Self::EmptyProofSpec
+            | Self::EmptyWitnesses
+            | Self::InvalidBitmaskPredicate
+            | Self::NoWitnessesToAggregate
+            | Self::ArithmeticError(_)
+            | Self::UnexpectedStatementProof { .. }
+            | Self::NoPublicKeysForHiddenPK(_)
+            | Self::InvalidHiddenPKIndex(..)
+            | Self::InvalidIssuerConsistency(_)
+            | Self::UnsatisfiedIssuerConsistency(..)
+            | Self::InvalidStatementDisjunction(_)
+            | Self::UnsatisfiedStatementDisjunction(_)
+            | Self::IncompatibleWitnessFields(..)
+            | Self::InvalidLinearRelation
+            | Self::UnsatisfiedLinearRelation(_)
+            | Self::UnboundLinearRelationWitness(..)
+            | Self::DuplicateStatementId(_)
+            | Self::NonContiguousStatementIds
+            | Self::UnreadStatementProofs(_)
+            | Self::MissingRevocationHandle(_)
+            | Self::MessageAlreadyRevealed(_)
+            | Self::MessageNotRevealed(_)
+            | Self::RevealedMessageIndexOutOfBounds(..)
+            | Self::IncompatibleMerkleMembershipSetupParamAtIndex(_)
+            | Self::InvalidMerkleTreeSize(_)
+            | Self::InvalidMerkleTreeLeafIndex(_)
+            | Self::InvalidMerkleMembershipWitness(_)
+            | Self::InvalidNoopReplacement(_)
+            | Self::BatchNonMembershipCountMismatch(..)
+            | Self::BatchMembershipCountMismatch(..)
+            | Self::EmptyRange { .. }
+            | Self::InsufficientSmcBases { .. }
+            | Self::InvalidMessageCommitmentBasis(..)
+            | Self::MessageCommitmentNotGiven(_)
+            | Self::InvalidAccumulatorValue(_)
+            | Self::WitnessDecryptionFailed
+            | Self::WitnessEncryptionFailed
+            | Self::MissingExternalSetup(_) => PublicError::MalformedInput,
+            Self::UnsupportedValue(_)
+            | Self::UnsupportedProofVersion { .. }
+            | Self::DisallowedStatementType(..) => PublicError::UnsupportedFeature,
+            Self::TooManyCifertexts(_) => PublicError::TooLarge,
+        }
+    }
 }
 
 impl From<SchnorrError> for ProofSystemError {
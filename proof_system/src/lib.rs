@@ -158,25 +158,34 @@ extern crate core;
 
 #[macro_use]
 pub mod setup_params;
+pub mod accumulator_manager;
 #[macro_use]
 mod derived_params;
+pub mod cost_estimate;
+pub mod encoding;
 pub mod error;
 mod macros;
 pub mod meta_statement;
+pub mod predicates;
+pub mod presentation;
 pub mod proof;
+pub mod proof_context;
 pub mod proof_spec;
 pub mod prover;
 pub mod statement;
 pub mod statement_proof;
 pub mod sub_protocols;
 pub mod verifier;
+pub mod versioning;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod witness;
 
 pub mod prelude {
     pub use crate::{
-        error::ProofSystemError, meta_statement::*, proof::*, proof_spec::*, prover::*,
-        setup_params::*, statement::*, statement_proof::*,
-        sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check, verifier::*,
-        witness::*,
+        accumulator_manager::*, cost_estimate::*, error::ProofSystemError, meta_statement::*, presentation::*, proof::*,
+        proof_context::*, proof_spec::*, prover::*, setup_params::*, statement::*,
+        statement_proof::*, sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check,
+        verifier::*, versioning::*, witness::*,
     };
 }
@@ -2,7 +2,7 @@
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     io::{Read, Write},
     vec,
     vec::Vec,
@@ -18,6 +18,8 @@ pub type WitnessRef = (usize, usize);
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MetaStatement {
     WitnessEquality(EqualWitnesses),
+    IssuerConsistency(SameIssuer),
+    StatementDisjunction(StatementDisjunction),
 }
 
 #[derive(
@@ -68,6 +70,62 @@ impl EqualWitnesses {
     }
 }
 
+/// Describes that the signatures of the given statements, each proving knowledge of a BBS+ signature with a hidden
+/// public key, are by the same issuer without revealing which one. Eg. if the signatures of statements 0 and 2 are to
+/// be proven to be by the same issuer, then it's written as
+/// ```
+/// use ark_std::collections::BTreeSet;
+/// use proof_system::meta_statement::{MetaStatements, SameIssuer};
+/// let mut same = BTreeSet::new();
+/// same.insert(0);
+/// same.insert(2);
+///
+/// let mut meta_statements = MetaStatements::new();
+/// meta_statements.add_issuer_consistency(SameIssuer(same));
+/// ```
+///
+/// The statements should have the same public keys and a statement can be in only one `SameIssuer`. The public keys are
+/// the registry of issuers, see `PoKBBSSignatureG1HiddenPK` for why it's not an accumulator.
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct SameIssuer(pub BTreeSet<usize>);
+
+impl SameIssuer {
+    /// An issuer consistency should have at least 2 statements.
+    pub fn is_valid(&self) -> bool {
+        self.0.len() > 1
+    }
+}
+
+/// Describes that at least one of the given statements, each proving knowledge of a BBS+ signature, is satisfied
+/// without revealing which one. Eg. if either the signature of statement 0 or the one of statement 1 is to be proven,
+/// then it's written as
+/// ```
+/// use ark_std::collections::BTreeSet;
+/// use proof_system::meta_statement::{MetaStatements, StatementDisjunction};
+/// let mut either = BTreeSet::new();
+/// either.insert(0);
+/// either.insert(1);
+///
+/// let mut meta_statements = MetaStatements::new();
+/// meta_statements.add_statement_disjunction(StatementDisjunction(either));
+/// ```
+///
+/// The prover gives the witness for one of the statements and `Witness::Noop` for the others, whose proofs are
+/// simulated. A statement can be in only one `StatementDisjunction` and its witnesses can't be in a witness equality.
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct StatementDisjunction(pub BTreeSet<usize>);
+
+impl StatementDisjunction {
+    /// A statement disjunction should have at least 2 statements.
+    pub fn is_valid(&self) -> bool {
+        self.0.len() > 1
+    }
+}
+
 impl MetaStatements {
     pub fn new() -> Self {
         Self(Vec::new())
@@ -82,6 +140,30 @@ impl MetaStatements {
         self.add(MetaStatement::WitnessEquality(item))
     }
 
+    pub fn add_issuer_consistency(&mut self, item: SameIssuer) -> usize {
+        self.add(MetaStatement::IssuerConsistency(item))
+    }
+
+    pub fn add_statement_disjunction(&mut self, item: StatementDisjunction) -> usize {
+        self.add(MetaStatement::StatementDisjunction(item))
+    }
+
+    /// All issuer consistencies in the order they were added
+    pub fn issuer_consistencies(&self) -> impl Iterator<Item = &SameIssuer> {
+        self.0.iter().filter_map(|m| match m {
+            MetaStatement::IssuerConsistency(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// All statement disjunctions with their indices in the meta statements
+    pub fn statement_disjunctions(&self) -> impl Iterator<Item = (usize, &StatementDisjunction)> {
+        self.0.iter().enumerate().filter_map(|(i, m)| match m {
+            MetaStatement::StatementDisjunction(s) => Some((i, s)),
+            _ => None,
+        })
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
@@ -95,44 +177,103 @@ impl MetaStatements {
     /// when given a collection of sets, return a new collection of sets such that all sets in the new
     /// collection are pairwise distinct.
     pub fn disjoint_witness_equalities(&self) -> Vec<EqualWitnesses> {
-        let mut equalities = vec![];
-        let mut disjoints = vec![];
+        let mut union_find = WitnessRefUnionFind::default();
         for stmt in &self.0 {
             match stmt {
                 MetaStatement::WitnessEquality(eq_wits) => {
-                    equalities.push(eq_wits);
+                    union_find.union(eq_wits);
                 }
+                MetaStatement::IssuerConsistency(_) | MetaStatement::StatementDisjunction(_) => (),
             }
         }
-        while !equalities.is_empty() {
-            // Traverse `equalities` in reverse as that doesn't change index on removal
-            let mut current_set = equalities.pop().unwrap().0.clone();
-            if !equalities.is_empty() {
-                let mut i = equalities.len() - 1;
-                loop {
-                    if !current_set.is_disjoint(&equalities[i].0) {
-                        current_set = current_set
-                            .union(&equalities.remove(i).0)
-                            .cloned()
-                            .collect();
-                        // Found new members for the current set so traverse previously traversed sets
-                        // as well to find any sets that overlap with the newly found set
-                        if !equalities.is_empty() {
-                            i = equalities.len() - 1;
-                        } else {
-                            break;
-                        }
-                        continue;
-                    }
-                    if i == 0 {
-                        break;
+        union_find.sets().into_iter().map(EqualWitnesses).collect()
+    }
+
+    /// All witness equalities whose witness references are all already equal because of the witness equalities
+    /// before them, in the order they were added, i.e. witness equalities that were declared separately but add no
+    /// new constraint. Witness equalities sharing some but not all references with earlier ones aren't redundant
+    /// as they extend an existing equality. Redundant equalities don't make the proof invalid but add unnecessary
+    /// work, so this helps find the ones that can be removed. Empty when there are no redundant equalities.
+    pub fn find_redundant_equalities(&self) -> Vec<EqualWitnesses> {
+        self.redundant_witness_equality_indices()
+            .into_iter()
+            .filter_map(|i| match &self.0[i] {
+                MetaStatement::WitnessEquality(eq_wits) => Some(eq_wits.clone()),
+                MetaStatement::IssuerConsistency(_) | MetaStatement::StatementDisjunction(_) => {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn redundant_witness_equality_indices(&self) -> Vec<usize> {
+        let mut redundant = vec![];
+        let mut union_find = WitnessRefUnionFind::default();
+        for (i, stmt) in self.0.iter().enumerate() {
+            match stmt {
+                MetaStatement::WitnessEquality(eq_wits) => {
+                    if !union_find.union(eq_wits) {
+                        redundant.push(i);
                     }
-                    i -= 1;
                 }
+                MetaStatement::IssuerConsistency(_) | MetaStatement::StatementDisjunction(_) => (),
             }
-            disjoints.push(EqualWitnesses(current_set));
         }
-        disjoints
+        redundant
+    }
+}
+
+/// Union-find over witness references. A reference not in `parents` hasn't been seen yet.
+#[derive(Default)]
+struct WitnessRefUnionFind {
+    parents: BTreeMap<WitnessRef, WitnessRef>,
+}
+
+impl WitnessRefUnionFind {
+    fn find(&mut self, r: WitnessRef) -> WitnessRef {
+        let mut root = *self.parents.entry(r).or_insert(r);
+        while self.parents[&root] != root {
+            root = self.parents[&root];
+        }
+        // Compress the path so later lookups are faster
+        let mut current = r;
+        while current != root {
+            let next = self.parents[&current];
+            self.parents.insert(current, root);
+            current = next;
+        }
+        root
+    }
+
+    /// Merge the sets of all references of the given equality. Returns false if they were already in the same set.
+    fn union(&mut self, eq_wits: &EqualWitnesses) -> bool {
+        let all_seen = eq_wits.0.iter().all(|r| self.parents.contains_key(r));
+        let roots = eq_wits
+            .0
+            .iter()
+            .map(|r| self.find(*r))
+            .collect::<BTreeSet<_>>();
+        if all_seen && roots.len() == 1 {
+            return false;
+        }
+        let mut roots = roots.into_iter();
+        if let Some(root) = roots.next() {
+            for r in roots {
+                self.parents.insert(r, root);
+            }
+        }
+        true
+    }
+
+    /// The disjoint sets of all references seen
+    fn sets(mut self) -> Vec<BTreeSet<WitnessRef>> {
+        let refs = self.parents.keys().copied().collect::<Vec<_>>();
+        let mut sets = BTreeMap::<WitnessRef, BTreeSet<WitnessRef>>::new();
+        for r in refs {
+            let root = self.find(r);
+            sets.entry(root).or_default().insert(r);
+        }
+        sets.into_values().collect()
     }
 }
 
@@ -157,6 +298,14 @@ mod serialization {
                     CanonicalSerialize::serialize_with_mode(&0u8, &mut writer, compress)?;
                     CanonicalSerialize::serialize_with_mode(s, &mut writer, compress)
                 }
+                Self::IssuerConsistency(s) => {
+                    CanonicalSerialize::serialize_with_mode(&1u8, &mut writer, compress)?;
+                    CanonicalSerialize::serialize_with_mode(s, &mut writer, compress)
+                }
+                Self::StatementDisjunction(s) => {
+                    CanonicalSerialize::serialize_with_mode(&2u8, &mut writer, compress)?;
+                    CanonicalSerialize::serialize_with_mode(s, &mut writer, compress)
+                }
             }
         }
 
@@ -165,6 +314,12 @@ mod serialization {
                 Self::WitnessEquality(s) => {
                     0u8.serialized_size(compress) + s.serialized_size(compress)
                 }
+                Self::IssuerConsistency(s) => {
+                    1u8.serialized_size(compress) + s.serialized_size(compress)
+                }
+                Self::StatementDisjunction(s) => {
+                    2u8.serialized_size(compress) + s.serialized_size(compress)
+                }
             }
         }
     }
@@ -181,6 +336,12 @@ mod serialization {
                 0u8 => Ok(Self::WitnessEquality(
                     CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
                 )),
+                1u8 => Ok(Self::IssuerConsistency(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
+                2u8 => Ok(Self::StatementDisjunction(
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                )),
                 _ => Err(SerializationError::InvalidData),
             }
         }
@@ -301,4 +462,123 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn redundant_witness_equality() {
+        fn meta_statements(eqs: Vec<Vec<WitnessRef>>) -> MetaStatements {
+            let mut meta_statements = MetaStatements::new();
+            for eq in eqs {
+                meta_statements.add_witness_equality(EqualWitnesses(eq.into_iter().collect()));
+            }
+            meta_statements
+        }
+
+        // Disjoint and overlapping equalities aren't redundant
+        assert_eq!(
+            meta_statements(vec![vec![(0, 1), (1, 1)], vec![(0, 3), (1, 4)]])
+                .redundant_witness_equality_indices()
+                .first()
+                .copied(),
+            None
+        );
+        assert_eq!(
+            meta_statements(vec![
+                vec![(0, 1), (1, 1)],
+                vec![(1, 1), (2, 0)],
+                vec![(2, 0), (3, 0), (0, 1)]
+            ])
+            .redundant_witness_equality_indices()
+            .first()
+            .copied(),
+            None
+        );
+
+        // Same equality declared twice
+        assert_eq!(
+            meta_statements(vec![
+                vec![(0, 1), (1, 1)],
+                vec![(0, 3), (1, 4)],
+                vec![(1, 1), (0, 1)]
+            ])
+            .redundant_witness_equality_indices()
+            .first()
+            .copied(),
+            Some(2)
+        );
+        // Subset of an earlier equality
+        assert_eq!(
+            meta_statements(vec![
+                vec![(0, 3), (1, 4), (2, 0), (5, 0)],
+                vec![(2, 0), (5, 0)]
+            ])
+            .redundant_witness_equality_indices()
+            .first()
+            .copied(),
+            Some(1)
+        );
+        // Follows by transitivity from the earlier equalities
+        assert_eq!(
+            meta_statements(vec![
+                vec![(0, 0), (1, 0)],
+                vec![(1, 0), (2, 0)],
+                vec![(0, 0), (2, 0)]
+            ])
+            .redundant_witness_equality_indices()
+            .first()
+            .copied(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn all_redundant_witness_equalities() {
+        fn meta_statements(eqs: Vec<Vec<WitnessRef>>) -> MetaStatements {
+            let mut meta_statements = MetaStatements::new();
+            for eq in eqs {
+                meta_statements.add_witness_equality(EqualWitnesses(eq.into_iter().collect()));
+            }
+            meta_statements
+        }
+        fn equalities(eqs: Vec<Vec<WitnessRef>>) -> Vec<EqualWitnesses> {
+            eqs.into_iter()
+                .map(|eq| EqualWitnesses(eq.into_iter().collect()))
+                .collect()
+        }
+
+        assert_eq!(MetaStatements::new().find_redundant_equalities(), vec![]);
+        assert_eq!(
+            meta_statements(vec![
+                vec![(0, 1), (1, 1)],
+                vec![(1, 1), (2, 0)],
+                vec![(0, 3), (1, 4)]
+            ])
+            .find_redundant_equalities(),
+            vec![]
+        );
+
+        // Redundant by transitivity, repeated and a subset of an earlier equality
+        let meta_statements = meta_statements(vec![
+            vec![(0, 0), (1, 0)],
+            vec![(1, 0), (2, 0)],
+            vec![(0, 0), (2, 0)],
+            vec![(0, 3), (1, 4), (2, 5)],
+            vec![(1, 0), (0, 0)],
+            vec![(2, 5), (0, 3)],
+        ]);
+        assert_eq!(
+            meta_statements.find_redundant_equalities(),
+            equalities(vec![
+                vec![(0, 0), (2, 0)],
+                vec![(0, 0), (1, 0)],
+                vec![(0, 3), (2, 5)]
+            ])
+        );
+        assert_eq!(
+            meta_statements
+                .redundant_witness_equality_indices()
+                .first()
+                .copied(),
+            Some(2)
+        );
+    }
 }
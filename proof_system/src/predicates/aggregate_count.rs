@@ -0,0 +1,185 @@
+//! Proving how many of several witnesses exceed a threshold, eg. that exactly 3 of 5 signed scores are above a
+//! passing mark, without revealing which ones or the witnesses themselves. Only the count is revealed.
+//!
+//! All witnesses must be less than a public `max` and the threshold `t` must be less than `max`. For each witness
+//! `m_i`, the prover proves knowledge of an indicator `b_i`, which is 1 if `m_i > t` and 0 otherwise, and of
+//! `z_i = m_i - (t + 1) + (1 - b_i) * max` such that
+//! 1. `b_i` is in `[0, 2)` with a bound check, so it's either 0 or 1,
+//! 2. `z_i` is in `[0, max)` with a bound check. When `b_i` is 1, this means `m_i >= t + 1` and when `b_i` is 0,
+//!    this means `m_i <= t`, so the indicator can't be set to the wrong value,
+//! 3. `m_i` is in `[0, max)` with a bound check. Without this, a value like `p - 1` (for the field modulus `p`)
+//!    satisfies the relation below with `b_i = 0` and a small `z_i`, so a huge value would be counted as not
+//!    exceeding the threshold. Similarly a value in `[max, t + max]` would be counted as exceeding it,
+//! 4. `m_i - max * b_i - z_i = t + 1 - max` and `b_0 + b_1 + ... + b_{n-1} = k` for the revealed count `k`, with
+//!    a single linear relation statement whose witnesses `m_i`, `b_i` and `z_i` are proven equal to the linked
+//!    witnesses and to the witnesses of the bound checks.
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    predicates::check_bound_check,
+    proof_spec::ProofSpec,
+    statement::{linear_relation::SchnorrLinearRelation, Statement},
+    sub_protocols::{enforce_and_get_u64, validate_bounds},
+    witness::{Witness, Witnesses},
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::{One, Zero};
+use ark_std::{format, vec, vec::Vec};
+
+/// Links witnesses (from the same or different statements) and the number of them which exceed the threshold
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregateCountPredicate {
+    /// References to the witnesses being counted
+    pub witness_refs: Vec<WitnessRef>,
+    /// A witness is counted if it's greater than this
+    pub threshold: u64,
+    /// Exclusive upper bound on all witnesses
+    pub max: u64,
+    /// Number of witnesses greater than the threshold. This is revealed to the verifier.
+    pub count: u64,
+}
+
+/// Indices of the statements added to the `ProofSpec` by `AggregateCountPredicate`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregateCountStatements {
+    /// Statements for the bound checks of the indicators, in the order of the linked witnesses
+    pub indicator_bound_checks: Vec<usize>,
+    /// Statements for the bound checks of the shifted witnesses `z_i`, in the order of the linked witnesses
+    pub shifted_bound_checks: Vec<usize>,
+    /// Statements for the bound checks of the linked witnesses `m_i`, in the order of the linked witnesses
+    pub value_bound_checks: Vec<usize>,
+    /// Statement for the linear relation between the linked witnesses, the indicators and the shifted witnesses
+    pub linear_relation: usize,
+}
+
+impl AggregateCountPredicate {
+    pub fn new(
+        witness_refs: Vec<WitnessRef>,
+        threshold: u64,
+        max: u64,
+        count: u64,
+    ) -> Result<Self, ProofSystemError> {
+        if witness_refs.is_empty() {
+            return Err(ProofSystemError::NoWitnessesToAggregate);
+        }
+        validate_bounds(threshold, max)?;
+        Ok(Self {
+            witness_refs,
+            threshold,
+            max,
+            count,
+        })
+    }
+
+    /// Add the required statements and meta-statements to the `ProofSpec`. `bound_check` creates a bound check
+    /// statement for the range `[min, max)` given as its arguments. Both prover and verifier call this.
+    pub fn add_to_proof_spec<E: Pairing, G: AffineRepr>(
+        &self,
+        proof_spec: &mut ProofSpec<E, G>,
+        bound_check: impl Fn(u64, u64) -> Result<Statement<E, G>, ProofSystemError>,
+    ) -> Result<AggregateCountStatements, ProofSystemError> {
+        let n = self.witness_refs.len();
+        let indicator_bound_checks = (0..n)
+            .map(|_| Ok(proof_spec.add_statement(check_bound_check(bound_check(0, 2)?)?)))
+            .collect::<Result<Vec<_>, ProofSystemError>>()?;
+        let shifted_bound_checks = (0..n)
+            .map(|_| Ok(proof_spec.add_statement(check_bound_check(bound_check(0, self.max)?)?)))
+            .collect::<Result<Vec<_>, ProofSystemError>>()?;
+        let value_bound_checks = (0..n)
+            .map(|_| Ok(proof_spec.add_statement(check_bound_check(bound_check(0, self.max)?)?)))
+            .collect::<Result<Vec<_>, ProofSystemError>>()?;
+
+        // Witnesses of the relation are `m_0, ..., m_{n-1}, b_0, ..., b_{n-1}, z_0, ..., z_{n-1}`
+        let max = E::ScalarField::from(self.max);
+        let mut coefficients = Vec::with_capacity(n + 1);
+        let mut targets = Vec::with_capacity(n + 1);
+        for i in 0..n {
+            let mut row = vec![E::ScalarField::zero(); 3 * n];
+            row[i] = E::ScalarField::one();
+            row[n + i] = -max;
+            row[2 * n + i] = -E::ScalarField::one();
+            coefficients.push(row);
+            targets.push(E::ScalarField::from(self.threshold) + E::ScalarField::one() - max);
+        }
+        let mut row = vec![E::ScalarField::zero(); 3 * n];
+        for c in &mut row[n..2 * n] {
+            *c = E::ScalarField::one();
+        }
+        coefficients.push(row);
+        targets.push(E::ScalarField::from(self.count));
+        let linear_relation = proof_spec.add_statement(
+            SchnorrLinearRelation::<E>::new_statement_from_params(coefficients, targets)?,
+        );
+
+        for (i, w_ref) in self.witness_refs.iter().enumerate() {
+            for w in [
+                vec![*w_ref, (value_bound_checks[i], 0), (linear_relation, i)],
+                vec![(indicator_bound_checks[i], 0), (linear_relation, n + i)],
+                vec![(shifted_bound_checks[i], 0), (linear_relation, 2 * n + i)],
+            ] {
+                proof_spec
+                    .meta_statements
+                    .add_witness_equality(EqualWitnesses(w.into_iter().collect()));
+            }
+        }
+        Ok(AggregateCountStatements {
+            indicator_bound_checks,
+            shifted_bound_checks,
+            value_bound_checks,
+            linear_relation,
+        })
+    }
+
+    /// Called by the prover to add the witnesses for the statements added by `Self::add_to_proof_spec`. The
+    /// witnesses must be added in the same order as the statements so this should be called at the same point
+    /// while building `Witnesses` as `Self::add_to_proof_spec` was called while building the `ProofSpec`.
+    /// `values` are the linked witnesses in the same order as `self.witness_refs` and `bound_check_witness`
+    /// creates the bound check witness from the bound checked value.
+    pub fn add_witnesses<E: Pairing>(
+        &self,
+        witnesses: &mut Witnesses<E>,
+        values: Vec<E::ScalarField>,
+        bound_check_witness: impl Fn(E::ScalarField) -> Witness<E>,
+    ) -> Result<(), ProofSystemError> {
+        if values.len() != self.witness_refs.len() {
+            return Err(ProofSystemError::UnequalWitnessAndStatementCount(
+                self.witness_refs.len(),
+                values.len(),
+            ));
+        }
+        let mut indicators = Vec::<u64>::with_capacity(values.len());
+        let mut shifted = Vec::<u64>::with_capacity(values.len());
+        for v in &values {
+            let v = enforce_and_get_u64(v)?;
+            // A value not less than `max` can't be proven to be on either side of the threshold
+            if v >= self.max {
+                return Err(ProofSystemError::UnsupportedValue(format!(
+                    "value {} is not less than {}",
+                    v, self.max
+                )));
+            }
+            if v > self.threshold {
+                indicators.push(1);
+                shifted.push(v - self.threshold - 1);
+            } else {
+                indicators.push(0);
+                shifted.push(v + (self.max - self.threshold - 1));
+            }
+        }
+        for b in &indicators {
+            witnesses.add(bound_check_witness(E::ScalarField::from(*b)));
+        }
+        for z in &shifted {
+            witnesses.add(bound_check_witness(E::ScalarField::from(*z)));
+        }
+        for v in &values {
+            witnesses.add(bound_check_witness(*v));
+        }
+        let mut relation_witnesses = values;
+        relation_witnesses.extend(indicators.into_iter().map(E::ScalarField::from));
+        relation_witnesses.extend(shifted.into_iter().map(E::ScalarField::from));
+        witnesses.add(Witness::SchnorrLinearRelation(relation_witnesses));
+        Ok(())
+    }
+}
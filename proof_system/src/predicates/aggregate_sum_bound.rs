@@ -0,0 +1,159 @@
+//! Proving that the sum of several witnesses satisfies given bounds, eg. that the sum of amounts signed in
+//! different credentials is below a spending limit, without revealing the individual witnesses or their sum.
+//!
+//! The prover creates a Pedersen commitment `C = g * s + h * r` to the sum `s = m_1 + m_2 + ... + m_n` and
+//! proves knowledge of the opening of `C` in 2 ways,
+//! 1. as `C = g * m_1 + g * m_2 + ... + g * m_n + h * r` where each `m_i` is proven equal to a linked witness,
+//! 2. as `C = g * s + h * r` where `s` is proven equal to the witness of a bound check statement.
+//!
+//! As the randomness `r` is proven equal in both, it follows that `s = m_1 + m_2 + ... + m_n`.
+//!
+//! The sum is over the scalar field so it wraps around the field modulus `p`. This predicate only proves the
+//! bounds over the sum and not over the individual witnesses, so it relies on each of them being small, eg. being
+//! less than 2^64 as signed by the issuer or as proven by a separate bound check. Otherwise a witness like `p - 1`
+//! would act as `-1` and reduce the sum.
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    predicates::check_bound_check,
+    proof_spec::ProofSpec,
+    statement::{ped_comm::PedersenCommitment, Statement},
+    witness::{Witness, Witnesses},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_std::{vec, vec::Vec};
+use digest::Digest;
+use dock_crypto_utils::{concat_slices, hashing_utils::affine_group_elem_from_try_and_incr};
+
+/// Links witnesses (from the same or different statements) whose sum is bound checked. Each witness must be known
+/// to be small, like less than 2^64, as the bounds are not checked for the individual witnesses. See the module
+/// docs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregateSumBound<G: AffineRepr> {
+    /// References to the witnesses being summed
+    pub witness_refs: Vec<WitnessRef>,
+    /// Commitment key `(g, h)` for the Pedersen commitment to the sum
+    pub comm_key: (G, G),
+}
+
+/// Indices of the statements added to the `ProofSpec` by `AggregateSumBound`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregateSumBoundStatements {
+    /// Statement for the commitment opened as the linked witnesses
+    pub linked_commitment: usize,
+    /// Statement for the commitment opened as the sum
+    pub sum_commitment: usize,
+    /// Statement for the bound check over the sum
+    pub bound_check: usize,
+}
+
+impl<G: AffineRepr> AggregateSumBound<G> {
+    pub fn new(witness_refs: Vec<WitnessRef>, comm_key: (G, G)) -> Result<Self, ProofSystemError> {
+        if witness_refs.is_empty() {
+            return Err(ProofSystemError::NoWitnessesToAggregate);
+        }
+        Ok(Self {
+            witness_refs,
+            comm_key,
+        })
+    }
+
+    /// Same as `Self::new` but generates the commitment key by hashing `label`
+    pub fn new_with_label<D: Digest>(
+        witness_refs: Vec<WitnessRef>,
+        label: &[u8],
+    ) -> Result<Self, ProofSystemError> {
+        let g = affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : g"]);
+        let h = affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : h"]);
+        Self::new(witness_refs, (g, h))
+    }
+
+    /// Called by the prover to create the commitment `g * (values[0] + values[1] + ...) + h * randomness`. This
+    /// commitment needs to be shared with the verifier.
+    pub fn commit(&self, values: &[G::ScalarField], randomness: &G::ScalarField) -> G {
+        let sum = values.iter().fold(G::ScalarField::from(0u64), |a, b| a + b);
+        G::Group::msm_unchecked(&[self.comm_key.0, self.comm_key.1], &[sum, *randomness])
+            .into_affine()
+    }
+
+    /// Add the required statements and meta-statements to the `ProofSpec`. `bound_check` must be a bound check
+    /// statement over the sum. Both prover and verifier call this.
+    pub fn add_to_proof_spec<E: Pairing>(
+        &self,
+        proof_spec: &mut ProofSpec<E, G>,
+        commitment: G,
+        bound_check: Statement<E, G>,
+    ) -> Result<AggregateSumBoundStatements, ProofSystemError> {
+        let bound_check = check_bound_check(bound_check)?;
+        let n = self.witness_refs.len();
+        let mut linked_key = vec![self.comm_key.0; n];
+        linked_key.push(self.comm_key.1);
+
+        let linked_commitment = proof_spec.add_statement(
+            PedersenCommitment::new_statement_from_params(linked_key, commitment),
+        );
+        let sum_commitment =
+            proof_spec.add_statement(PedersenCommitment::new_statement_from_params(
+                vec![self.comm_key.0, self.comm_key.1],
+                commitment,
+            ));
+        let bound_check = proof_spec.add_statement(bound_check);
+
+        for (i, w_ref) in self.witness_refs.iter().enumerate() {
+            proof_spec
+                .meta_statements
+                .add_witness_equality(EqualWitnesses(
+                    vec![*w_ref, (linked_commitment, i)].into_iter().collect(),
+                ));
+        }
+        // Same randomness in both commitments
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![(linked_commitment, n), (sum_commitment, 1)]
+                    .into_iter()
+                    .collect(),
+            ));
+        // The bound checked value is the sum
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![(sum_commitment, 0), (bound_check, 0)]
+                    .into_iter()
+                    .collect(),
+            ));
+        Ok(AggregateSumBoundStatements {
+            linked_commitment,
+            sum_commitment,
+            bound_check,
+        })
+    }
+
+    /// Called by the prover to add the witnesses for the statements added by `Self::add_to_proof_spec`. The
+    /// witnesses must be added in the same order as the statements so this should be called at the same point
+    /// while building `Witnesses` as `Self::add_to_proof_spec` was called while building the `ProofSpec`.
+    /// `values` are the linked witnesses in the same order as `self.witness_refs` and `bound_check_witness`
+    /// creates the bound check witness from the sum.
+    pub fn add_witnesses<E: Pairing<ScalarField = G::ScalarField>>(
+        &self,
+        witnesses: &mut Witnesses<E>,
+        values: Vec<E::ScalarField>,
+        randomness: E::ScalarField,
+        bound_check_witness: impl FnOnce(E::ScalarField) -> Witness<E>,
+    ) -> Result<(), ProofSystemError> {
+        if values.len() != self.witness_refs.len() {
+            return Err(ProofSystemError::UnequalWitnessAndStatementCount(
+                self.witness_refs.len(),
+                values.len(),
+            ));
+        }
+        let sum = values.iter().fold(E::ScalarField::from(0u64), |a, b| a + b);
+        let mut linked = values;
+        linked.push(randomness);
+        witnesses.add(Witness::PedersenCommitment(linked));
+        witnesses.add(Witness::PedersenCommitment(vec![sum, randomness]));
+        witnesses.add(bound_check_witness(sum));
+        Ok(())
+    }
+}
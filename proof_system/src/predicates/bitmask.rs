@@ -0,0 +1,218 @@
+//! Proving constraints on the bits of a witness treated as a bitmask, eg. that the permissions signed in a
+//! credential have bit 3 set, without revealing the other bits.
+//!
+//! The mask `m` of `n` bits is decomposed into bits `b_0, b_1, ..., b_{n-1}`, each proven to be 0 or 1 by a
+//! bound check over `[0, 2)`. The decomposition is proven with a Pedersen commitment statement whose bases are
+//! all multiples of the same generator `g` so knowing an opening proves a linear relation between the
+//! committed values,
+//! - when all bits of a set `S` must be set, only the other bits are decomposed and the statement is
+//!   `g * m - g * 2^i * b_i - ... = g * (sum of 2^j for j in S)` where `i` ranges over the bits not in `S`,
+//! - when any bit of a set `S` must be set, all bits are decomposed with `g * m - g * 2^i * b_i - ... = 0` and
+//!   another statement `g * s - g * b_j - ... = 0` for the bits `j` in `S` proves that `s` is their sum. A bound
+//!   check over `[1, |S| + 1)` proves that `s` is not 0.
+//!
+//! Each committed value is proven equal to the corresponding bound checked bit (or sum) and `m` is proven
+//! equal to the linked witness.
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    predicates::check_bound_check,
+    proof_spec::ProofSpec,
+    statement::{ped_comm::PedersenCommitment, Statement},
+    witness::{Witness, Witnesses},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_std::{collections::BTreeSet, vec, vec::Vec, Zero};
+use digest::Digest;
+use dock_crypto_utils::{concat_slices, hashing_utils::affine_group_elem_from_try_and_incr};
+
+/// Constraint on the bits of the mask. Bits are indexed from the least significant bit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BitConstraint {
+    /// All the bits must be set
+    AllSet(BTreeSet<u8>),
+    /// At least one of the bits must be set
+    AnySet(BTreeSet<u8>),
+}
+
+/// Links a witness to the constraint on its bits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitmaskPredicate<G: AffineRepr> {
+    /// Reference to the mask
+    pub mask: WitnessRef,
+    /// Number of bits in the mask, at most 64
+    pub bit_count: u8,
+    pub constraint: BitConstraint,
+    /// Generator whose multiples are the bases of the Pedersen commitment statements
+    pub generator: G,
+}
+
+/// Indices of the statements added to the `ProofSpec` by `BitmaskPredicate`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitmaskStatements {
+    /// Statement for the decomposition of the mask into bits
+    pub decomposition: usize,
+    /// Bound check statements for each decomposed bit, in increasing order of the bit index
+    pub bit_checks: Vec<usize>,
+    /// For `BitConstraint::AnySet`, statements for the sum of the bits and the bound check over the sum
+    pub sum: Option<(usize, usize)>,
+}
+
+impl<G: AffineRepr> BitmaskPredicate<G> {
+    pub fn new(
+        mask: WitnessRef,
+        bit_count: u8,
+        constraint: BitConstraint,
+        generator: G,
+    ) -> Result<Self, ProofSystemError> {
+        if bit_count == 0 || bit_count > 64 {
+            return Err(ProofSystemError::InvalidBitmaskPredicate);
+        }
+        let bits = match &constraint {
+            BitConstraint::AllSet(b) | BitConstraint::AnySet(b) => b,
+        };
+        if bits.is_empty() || bits.iter().any(|b| *b >= bit_count) {
+            return Err(ProofSystemError::InvalidBitmaskPredicate);
+        }
+        Ok(Self {
+            mask,
+            bit_count,
+            constraint,
+            generator,
+        })
+    }
+
+    /// Same as `Self::new` but generates the generator by hashing `label`
+    pub fn new_with_label<D: Digest>(
+        mask: WitnessRef,
+        bit_count: u8,
+        constraint: BitConstraint,
+        label: &[u8],
+    ) -> Result<Self, ProofSystemError> {
+        let g = affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : g"]);
+        Self::new(mask, bit_count, constraint, g)
+    }
+
+    /// Bits of the mask that are decomposed and bound checked
+    pub fn decomposed_bits(&self) -> Vec<u8> {
+        match &self.constraint {
+            BitConstraint::AllSet(bits) => {
+                (0..self.bit_count).filter(|i| !bits.contains(i)).collect()
+            }
+            BitConstraint::AnySet(_) => (0..self.bit_count).collect(),
+        }
+    }
+
+    /// Add the required statements and meta-statements to the `ProofSpec`. `bound_check` creates a bound check
+    /// statement for the given `(min, max)`. Both prover and verifier call this.
+    pub fn add_to_proof_spec<E: Pairing>(
+        &self,
+        proof_spec: &mut ProofSpec<E, G>,
+        bound_check: impl Fn(u64, u64) -> Result<Statement<E, G>, ProofSystemError>,
+    ) -> Result<BitmaskStatements, ProofSystemError> {
+        let decomposed = self.decomposed_bits();
+        let g = self.generator.into_group();
+
+        // Key is `[g, -g * 2^i, ...]` for each decomposed bit `i`
+        let mut key = vec![g];
+        key.extend(
+            decomposed
+                .iter()
+                .map(|i| -(g * G::ScalarField::from(1u64 << i))),
+        );
+        let commitment = match &self.constraint {
+            BitConstraint::AllSet(bits) => {
+                g * bits.iter().fold(G::ScalarField::zero(), |a, i| {
+                    a + G::ScalarField::from(1u64 << i)
+                })
+            }
+            BitConstraint::AnySet(_) => G::Group::zero(),
+        };
+        let decomposition =
+            proof_spec.add_statement(PedersenCommitment::new_statement_from_params(
+                G::Group::normalize_batch(&key),
+                commitment.into_affine(),
+            ));
+
+        let mut bit_checks = Vec::with_capacity(decomposed.len());
+        for _ in 0..decomposed.len() {
+            let s_idx = proof_spec.add_statement(check_bound_check(bound_check(0, 2)?)?);
+            bit_checks.push(s_idx);
+        }
+
+        let sum = match &self.constraint {
+            BitConstraint::AnySet(bits) => {
+                let mut key = vec![g];
+                key.extend(bits.iter().map(|_| -g));
+                let sum_commitment =
+                    proof_spec.add_statement(PedersenCommitment::new_statement_from_params(
+                        G::Group::normalize_batch(&key),
+                        G::zero(),
+                    ));
+                let sum_check = proof_spec
+                    .add_statement(check_bound_check(bound_check(1, bits.len() as u64 + 1)?)?);
+                proof_spec
+                    .meta_statements
+                    .add_witness_equality(EqualWitnesses(
+                        vec![(sum_commitment, 0), (sum_check, 0)]
+                            .into_iter()
+                            .collect(),
+                    ));
+                Some((sum_commitment, sum_check))
+            }
+            BitConstraint::AllSet(_) => None,
+        };
+
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![self.mask, (decomposition, 0)].into_iter().collect(),
+            ));
+        for (j, (i, s_idx)) in decomposed.iter().zip(bit_checks.iter()).enumerate() {
+            let mut eq = vec![(decomposition, j + 1), (*s_idx, 0)];
+            if let (BitConstraint::AnySet(bits), Some((sum_commitment, _))) =
+                (&self.constraint, sum)
+            {
+                if let Some(k) = bits.iter().position(|b| b == i) {
+                    eq.push((sum_commitment, k + 1));
+                }
+            }
+            proof_spec
+                .meta_statements
+                .add_witness_equality(EqualWitnesses(eq.into_iter().collect()));
+        }
+        Ok(BitmaskStatements {
+            decomposition,
+            bit_checks,
+            sum,
+        })
+    }
+
+    /// Called by the prover to add the witnesses for the statements added by `Self::add_to_proof_spec`. The
+    /// witnesses must be added in the same order as the statements so this should be called at the same point
+    /// while building `Witnesses` as `Self::add_to_proof_spec` was called while building the `ProofSpec`.
+    /// `bound_check_witness` creates the bound check witness from a bit (or the sum of bits).
+    pub fn add_witnesses<E: Pairing<ScalarField = G::ScalarField>>(
+        &self,
+        witnesses: &mut Witnesses<E>,
+        mask: u64,
+        bound_check_witness: impl Fn(E::ScalarField) -> Witness<E>,
+    ) {
+        let bit = |i: &u8| (mask >> i) & 1;
+        let decomposed = self.decomposed_bits();
+        let mut decomposition = vec![E::ScalarField::from(mask)];
+        decomposition.extend(decomposed.iter().map(|i| E::ScalarField::from(bit(i))));
+        witnesses.add(Witness::PedersenCommitment(decomposition));
+        for i in &decomposed {
+            witnesses.add(bound_check_witness(E::ScalarField::from(bit(i))));
+        }
+        if let BitConstraint::AnySet(bits) = &self.constraint {
+            let sum = bits.iter().map(bit).sum::<u64>();
+            let mut sum_opening = vec![E::ScalarField::from(sum)];
+            sum_opening.extend(bits.iter().map(|i| E::ScalarField::from(bit(i))));
+            witnesses.add(Witness::PedersenCommitment(sum_opening));
+            witnesses.add(bound_check_witness(E::ScalarField::from(sum)));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Revealing a Pedersen commitment to a signed message rather than the message itself, i.e. selective blinding.
+//! The verifier learns `C = g * m + h * r` for the signed message `m` and a random blinding `r` chosen by the
+//! prover. The prover keeps the `CommitmentOpening` and can later open the commitment by giving it to the verifier
+//! who then learns `m` and can check that it's the message the proof was about.
+//!
+//! The commitment is proven using a Pedersen commitment statement with bases `[g, h]` whose first witness is
+//! proven equal to the signed message.
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    proof_spec::ProofSpec,
+    statement::ped_comm::PedersenCommitment,
+    witness::{Witness, Witnesses},
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::PrimeField;
+use ark_std::{rand::RngCore, vec, UniformRand};
+use schnorr_pok::inequality::CommitmentKey;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A commitment to a signed message which is revealed in place of the message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommittedReveal<G: AffineRepr> {
+    /// Reference to the committed message
+    pub message: WitnessRef,
+    pub comm_key: CommitmentKey<G>,
+    pub commitment: G,
+}
+
+/// The committed message and the blinding used in the commitment. Held by the prover until it wants to open the
+/// commitment.
+#[derive(Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct CommitmentOpening<F: PrimeField> {
+    pub message: F,
+    pub blinding: F,
+}
+
+impl<G: AffineRepr> CommittedReveal<G> {
+    /// Used by the verifier to create the predicate from the commitment received from the prover
+    pub fn new(message: WitnessRef, comm_key: CommitmentKey<G>, commitment: G) -> Self {
+        Self {
+            message,
+            comm_key,
+            commitment,
+        }
+    }
+
+    /// Used by the prover to commit to the message referred by `message_ref` using a random blinding. Returns the
+    /// predicate, whose commitment is sent to the verifier, and the opening which the prover keeps.
+    pub fn commit<R: RngCore>(
+        rng: &mut R,
+        message_ref: WitnessRef,
+        comm_key: CommitmentKey<G>,
+        message: G::ScalarField,
+    ) -> (Self, CommitmentOpening<G::ScalarField>) {
+        let blinding = G::ScalarField::rand(rng);
+        let commitment = comm_key.commit(&message, &blinding);
+        (
+            Self::new(message_ref, comm_key, commitment),
+            CommitmentOpening { message, blinding },
+        )
+    }
+
+    /// Add the Pedersen commitment statement and the meta-statement linking it to the message to the `ProofSpec`.
+    /// Both prover and verifier call this. Returns the index of the statement. The prover adds the witness
+    /// created by `Self::add_witness` at the same index in the `Witnesses`.
+    pub fn add_to_proof_spec<E: Pairing>(&self, proof_spec: &mut ProofSpec<E, G>) -> usize {
+        let s_idx = proof_spec.add_statement(PedersenCommitment::new_statement_from_params(
+            vec![self.comm_key.g, self.comm_key.h],
+            self.commitment,
+        ));
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![self.message, (s_idx, 0)].into_iter().collect(),
+            ));
+        s_idx
+    }
+
+    /// Called by the prover to add the witness for the statement added by `Self::add_to_proof_spec`
+    pub fn add_witness<E: Pairing<ScalarField = G::ScalarField>>(
+        &self,
+        witnesses: &mut Witnesses<E>,
+        opening: &CommitmentOpening<G::ScalarField>,
+    ) {
+        witnesses.add(Witness::PedersenCommitment(vec![
+            opening.message,
+            opening.blinding,
+        ]));
+    }
+
+    /// Called by the verifier with the opening received from the prover. Returns the committed message if the
+    /// opening is for this commitment.
+    pub fn open(
+        &self,
+        opening: &CommitmentOpening<G::ScalarField>,
+    ) -> Result<G::ScalarField, ProofSystemError> {
+        if self.comm_key.commit(&opening.message, &opening.blinding) != self.commitment {
+            return Err(ProofSystemError::InvalidCommitmentOpening);
+        }
+        Ok(opening.message)
+    }
+}
@@ -0,0 +1,66 @@
+//! Proving that a signed timestamp, like the issuance time of a credential, is within a window ending at the
+//! current time, i.e. `now - window <= issued_at <= now`, without revealing the timestamp. `now` and `window`
+//! are chosen by the verifier so a prover can't reuse a stale credential or one dated in the future.
+//!
+//! Both bounds are proved by a single bound check statement over `[now - window, now + 1)` whose witness is
+//! proven equal to the signed timestamp.
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, WitnessRef},
+    predicates::check_bound_check,
+    proof_spec::ProofSpec,
+    statement::Statement,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::vec;
+
+/// Links a signed timestamp to the window it must lie in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FreshnessCheck {
+    /// Reference to the signed timestamp
+    pub issued_at: WitnessRef,
+    /// Current time as seen by the verifier
+    pub now: u64,
+    /// Maximum age of the timestamp. A timestamp exactly `window` old is accepted.
+    pub window: u64,
+}
+
+impl FreshnessCheck {
+    pub fn new(issued_at: WitnessRef, now: u64, window: u64) -> Result<Self, ProofSystemError> {
+        if now == u64::MAX {
+            return Err(ProofSystemError::BoundCheckMaxNotGreaterThanMin);
+        }
+        Ok(Self {
+            issued_at,
+            now,
+            window,
+        })
+    }
+
+    /// The bounds `(min, max)` for the bound check such that `min <= issued_at < max`. The window is cut off at
+    /// 0 if it's larger than `now`.
+    pub fn bounds(&self) -> (u64, u64) {
+        (self.now.saturating_sub(self.window), self.now + 1)
+    }
+
+    /// Add the bound check statement and the meta-statement linking it to the timestamp to the `ProofSpec`.
+    /// `bound_check` creates a bound check statement for the given `(min, max)`. Both prover and verifier call
+    /// this. Returns the index of the bound check statement. The prover adds the bound check witness with the
+    /// signed timestamp as its value at the same index in the `Witnesses`.
+    pub fn add_to_proof_spec<E: Pairing, G: AffineRepr>(
+        &self,
+        proof_spec: &mut ProofSpec<E, G>,
+        bound_check: impl FnOnce(u64, u64) -> Result<Statement<E, G>, ProofSystemError>,
+    ) -> Result<usize, ProofSystemError> {
+        let (min, max) = self.bounds();
+        let bound_check = check_bound_check(bound_check(min, max)?)?;
+        let s_idx = proof_spec.add_statement(bound_check);
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![self.issued_at, (s_idx, 0)].into_iter().collect(),
+            ));
+        Ok(s_idx)
+    }
+}
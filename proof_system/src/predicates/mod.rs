@@ -0,0 +1,30 @@
+//! Predicates that are not proved by a single `Statement` but by combining several `Statement`s and
+//! `MetaStatement`s. Each predicate knows which statements, meta-statements and witnesses it needs and
+//! adds them to the given `ProofSpec` and `Witnesses`.
+
+pub mod aggregate_count;
+pub mod aggregate_sum_bound;
+pub mod bitmask;
+pub mod committed_reveal;
+pub mod freshness_check;
+pub mod non_revoked_credential;
+pub mod predicate_spec;
+pub mod scoped_pseudonym;
+
+use crate::{error::ProofSystemError, statement::Statement};
+use ark_ec::{pairing::Pairing, AffineRepr};
+
+/// Returns the given statement if it's a bound check statement, of any protocol, and an error otherwise
+pub(crate) fn check_bound_check<E: Pairing, G: AffineRepr>(
+    statement: Statement<E, G>,
+) -> Result<Statement<E, G>, ProofSystemError> {
+    match statement {
+        Statement::BoundCheckLegoGroth16Prover(_)
+        | Statement::BoundCheckLegoGroth16Verifier(_)
+        | Statement::BoundCheckBpp(_)
+        | Statement::BoundCheckSmc(_)
+        | Statement::BoundCheckSmcWithKVProver(_)
+        | Statement::BoundCheckSmcWithKVVerifier(_) => Ok(statement),
+        _ => Err(ProofSystemError::InvalidStatement),
+    }
+}
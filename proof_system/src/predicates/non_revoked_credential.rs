@@ -0,0 +1,135 @@
+//! Proving knowledge of a BBS+ signature on a credential along with the credential not being revoked. A credential
+//! has a revocation handle as one of its signed messages and is revoked by adding the handle to a universal
+//! accumulator. So the credential is not revoked if the handle is not a member of the accumulator.
+//!
+//! This adds a statement for the signature, a statement for the non-membership of the handle in the accumulator
+//! and a meta-statement proving the handle equal to the non-member. The handle must not be revealed as that would
+//! make all presentations of the credential linkable.
+
+use crate::{
+    error::ProofSystemError,
+    meta_statement::EqualWitnesses,
+    proof_spec::ProofSpec,
+    statement::{
+        accumulator::AccumulatorNonMembership,
+        bbs_plus::{PoKBBSSignatureG1 as PoKBBSSignatureG1Stmt, RevealedMessages},
+    },
+    witness::{NonMembership, PoKBBSSignatureG1 as PoKBBSSignatureG1Wit, Witnesses},
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_std::{collections::BTreeMap, vec};
+use bbs_plus::prelude::{PublicKeyG2, SignatureG1, SignatureParamsG1};
+use vb_accumulator::prelude::{
+    NonMembershipProvingKey, NonMembershipWitness, PublicKey as AccumPublicKey,
+    SetupParams as AccumParams,
+};
+
+/// Public values for proving knowledge of a BBS+ signature whose revocation handle is not in the accumulator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonRevokedCredential<E: Pairing> {
+    pub signature_params: SignatureParamsG1<E>,
+    pub public_key: PublicKeyG2<E>,
+    /// Index of the revocation handle in the signed messages
+    pub revocation_handle_idx: usize,
+    pub accumulator_params: AccumParams<E>,
+    pub accumulator_public_key: AccumPublicKey<E>,
+    pub proving_key: NonMembershipProvingKey<E::G1Affine>,
+    /// Accumulator of the revoked handles
+    pub accumulator_value: E::G1Affine,
+}
+
+/// Indices of the statements added to the `ProofSpec` by `NonRevokedCredential`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonRevokedCredentialStatements {
+    /// Statement for the knowledge of the signature
+    pub signature: usize,
+    /// Statement for the non-membership of the revocation handle
+    pub non_membership: usize,
+}
+
+impl<E: Pairing> NonRevokedCredential<E> {
+    pub fn new(
+        signature_params: SignatureParamsG1<E>,
+        public_key: PublicKeyG2<E>,
+        revocation_handle_idx: usize,
+        accumulator_params: AccumParams<E>,
+        accumulator_public_key: AccumPublicKey<E>,
+        proving_key: NonMembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+    ) -> Self {
+        Self {
+            signature_params,
+            public_key,
+            revocation_handle_idx,
+            accumulator_params,
+            accumulator_public_key,
+            proving_key,
+            accumulator_value,
+        }
+    }
+
+    /// Add the signature and non-membership statements and the meta-statement linking the revocation handle to the
+    /// non-member to the `ProofSpec`. Both prover and verifier call this. Errors if the revocation handle is one of
+    /// the revealed messages. The prover adds the witnesses using `Self::add_witnesses` in the same order.
+    pub fn add_to_proof_spec<G: AffineRepr>(
+        &self,
+        proof_spec: &mut ProofSpec<E, G>,
+        revealed_messages: impl RevealedMessages<E>,
+    ) -> Result<NonRevokedCredentialStatements, ProofSystemError> {
+        let revealed_messages = revealed_messages.into_field_elements();
+        let signature = proof_spec.statements.len();
+        if revealed_messages.contains_key(&self.revocation_handle_idx) {
+            return Err(ProofSystemError::WitnessAlreadyBeingRevealed(
+                signature,
+                self.revocation_handle_idx,
+            ));
+        }
+        proof_spec.add_statement(PoKBBSSignatureG1Stmt::new_statement_from_params(
+            self.signature_params.clone(),
+            self.public_key.clone(),
+            revealed_messages,
+        ));
+        let non_membership =
+            proof_spec.add_statement(AccumulatorNonMembership::new_statement_from_params(
+                self.accumulator_params.clone(),
+                self.accumulator_public_key.clone(),
+                self.proving_key.clone(),
+                self.accumulator_value,
+            ));
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![(signature, self.revocation_handle_idx), (non_membership, 0)]
+                    .into_iter()
+                    .collect(),
+            ));
+        Ok(NonRevokedCredentialStatements {
+            signature,
+            non_membership,
+        })
+    }
+
+    /// Called by the prover to add the witnesses for the statements added by `Self::add_to_proof_spec`. The
+    /// revocation handle is taken from the unrevealed messages. `non_membership_witness` must be for the handle
+    /// and the accumulator value of `self`.
+    pub fn add_witnesses(
+        &self,
+        witnesses: &mut Witnesses<E>,
+        signature: SignatureG1<E>,
+        unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+        non_membership_witness: NonMembershipWitness<E::G1Affine>,
+    ) -> Result<(), ProofSystemError> {
+        let handle = *unrevealed_messages.get(&self.revocation_handle_idx).ok_or(
+            ProofSystemError::MissingRevocationHandle(self.revocation_handle_idx),
+        )?;
+        witnesses.add(PoKBBSSignatureG1Wit::new_as_witness(
+            signature,
+            unrevealed_messages,
+        ));
+        witnesses.add(NonMembership::new_as_witness(
+            handle,
+            non_membership_witness,
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+//! Declarative description of what a verifier wants proven about a BBS+ credential. The verifier lists
+//! high-level predicates over named attributes, like "age >= 18", "country in {...}" or "credential not revoked",
+//! and `PredicateSpec::compile` turns them into a `ProofSpec` along with a `WitnessTemplate` which the prover
+//! uses to create the `Witnesses` for that `ProofSpec`.
+//!
+//! Range predicates are proved using set-membership check based range proofs when the range is small enough
+//! for them to be efficient and their params are given, otherwise using Bulletproofs++. Set membership
+//! predicates are proved using accumulator (non-)membership. Both prover and verifier compile the same
+//! `PredicateSpec` so they get the same `ProofSpec`.
+
+use crate::{
+    encoding::U64Encodable,
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, MetaStatements},
+    proof_spec::ProofSpec,
+    statement::{
+        accumulator::{AccumulatorMembership, AccumulatorNonMembership},
+        bbs_plus::PoKBBSSignatureG1 as PoKBBSSignatureG1Stmt,
+        bound_check_bpp::BoundCheckBpp,
+        bound_check_smc::{BoundCheckSmc, SmcParamsAndCommitmentKey},
+        Statements,
+    },
+    sub_protocols::{should_use_cls, validate_bounds},
+    witness::{
+        Membership, NonMembership, PoKBBSSignatureG1 as PoKBBSSignatureG1Wit, Witness, Witnesses,
+    },
+};
+use ark_ec::pairing::Pairing;
+use ark_std::{collections::BTreeMap, string::String, vec, vec::Vec};
+use bbs_plus::{
+    prelude::{PublicKeyG2, SignatureG1, SignatureParamsG1},
+    setup::MultiMessageSignatureParams,
+};
+use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
+use vb_accumulator::prelude::{
+    MembershipProvingKey, MembershipWitness, NonMembershipProvingKey, NonMembershipWitness,
+    PublicKey as AccumPublicKey, SetupParams as AccumParams,
+};
+
+/// Names of the attributes of a credential in the order in which they are signed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CredentialSchema {
+    pub attributes: Vec<String>,
+}
+
+/// Public values of an accumulator used for set membership predicates
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorInfo<E: Pairing> {
+    pub params: AccumParams<E>,
+    pub public_key: AccumPublicKey<E>,
+    pub accumulator_value: E::G1Affine,
+}
+
+/// A high-level predicate over a named attribute of the credential
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Predicate<E: Pairing> {
+    /// `min <= attribute < max`
+    InRange {
+        attribute: String,
+        min: u64,
+        max: u64,
+    },
+    /// Attribute is a member of the accumulator, like when the accumulator represents a set of allowed values
+    /// or the ids of the non-revoked credentials
+    InAccumulator {
+        attribute: String,
+        accumulator: AccumulatorInfo<E>,
+        proving_key: MembershipProvingKey<E::G1Affine>,
+    },
+    /// Attribute is not a member of the (universal) accumulator, like when the accumulator represents the ids
+    /// of the revoked credentials
+    NotInAccumulator {
+        attribute: String,
+        accumulator: AccumulatorInfo<E>,
+        proving_key: NonMembershipProvingKey<E::G1Affine>,
+    },
+}
+
+/// Params of the range proof protocols that range predicates can be proved with. At least one of them must be
+/// given if there are any range predicates.
+#[derive(Clone, Debug)]
+pub struct BoundCheckParams<E: Pairing> {
+    pub bpp: Option<BppSetupParams<E::G1Affine>>,
+    pub smc: Option<SmcParamsAndCommitmentKey<E>>,
+}
+
+impl<E: Pairing> Default for BoundCheckParams<E> {
+    fn default() -> Self {
+        Self {
+            bpp: None,
+            smc: None,
+        }
+    }
+}
+
+/// Predicates over the attributes of a BBS+ credential
+#[derive(Clone, Debug)]
+pub struct PredicateSpec<E: Pairing> {
+    pub schema: CredentialSchema,
+    pub sig_params: SignatureParamsG1<E>,
+    pub public_key: PublicKeyG2<E>,
+    pub predicates: Vec<Predicate<E>>,
+    pub bound_check_params: BoundCheckParams<E>,
+    pub context: Option<Vec<u8>>,
+}
+
+/// Witness needed for a statement of the compiled `ProofSpec`, in the same order as the statements
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WitnessSlot {
+    /// Signature and all attributes
+    Signature,
+    /// Attribute with the given index being bound checked using Bulletproofs++
+    BoundCheckBpp(usize),
+    /// Attribute with the given index being bound checked using set-membership check
+    BoundCheckSmc(usize),
+    /// Attribute with the given index as an accumulator member
+    AccumulatorMembership(usize),
+    /// Attribute with the given index as an accumulator non-member
+    AccumulatorNonMembership(usize),
+}
+
+/// Tells the prover which witnesses are needed for the compiled `ProofSpec`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WitnessTemplate {
+    pub slots: Vec<WitnessSlot>,
+    /// Number of attributes in the credential
+    pub attribute_count: usize,
+}
+
+impl<E: Pairing> Predicate<E> {
+    /// `min <= attribute < max` where the attribute and bounds are values encoded using `U64Encodable`, like
+    /// signed integers or dates. The attribute must be signed with the same encoding.
+    pub fn in_range<T: U64Encodable>(attribute: impl Into<String>, min: T, max: T) -> Self {
+        Self::InRange {
+            attribute: attribute.into(),
+            min: min.encode_as_u64(),
+            max: max.encode_as_u64(),
+        }
+    }
+}
+
+impl CredentialSchema {
+    pub fn new(attributes: Vec<String>) -> Self {
+        Self { attributes }
+    }
+
+    /// Index of the attribute in the signed messages
+    pub fn attribute_index(&self, attribute: &str) -> Result<usize, ProofSystemError> {
+        self.attributes
+            .iter()
+            .position(|a| a == attribute)
+            .ok_or_else(|| ProofSystemError::UnknownAttribute(attribute.into()))
+    }
+}
+
+impl<E: Pairing> PredicateSpec<E> {
+    /// Compile the predicates into a `ProofSpec` with a statement for the proof of knowledge of the signature
+    /// followed by a statement for each predicate, in order, and the `WitnessTemplate` for it. Errors if a
+    /// predicate refers to an attribute not in the schema or no params are given for a range predicate.
+    pub fn compile(
+        &self,
+    ) -> Result<(ProofSpec<E, E::G1Affine>, WitnessTemplate), ProofSystemError> {
+        if self.schema.attributes.len() != self.sig_params.supported_message_count() {
+            return Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                self.schema.attributes.len(),
+                self.sig_params.supported_message_count(),
+            ));
+        }
+        let mut statements = Statements::new();
+        let mut meta_statements = MetaStatements::new();
+        let mut slots = vec![];
+
+        let sig_idx = statements.add(PoKBBSSignatureG1Stmt::new_statement_from_params(
+            self.sig_params.clone(),
+            self.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        slots.push(WitnessSlot::Signature);
+
+        for predicate in &self.predicates {
+            let (attr_idx, statement, slot) = match predicate {
+                Predicate::InRange {
+                    attribute,
+                    min,
+                    max,
+                } => {
+                    let attr_idx = self.schema.attribute_index(attribute)?;
+                    validate_bounds(*min, *max)?;
+                    let (statement, slot) =
+                        match (&self.bound_check_params.smc, &self.bound_check_params.bpp) {
+                            (Some(smc), bpp) if bpp.is_none() || should_use_cls(*min, *max) => (
+                                BoundCheckSmc::new_statement_from_params(*min, *max, smc.clone())?,
+                                WitnessSlot::BoundCheckSmc(attr_idx),
+                            ),
+                            (_, Some(bpp)) => (
+                                BoundCheckBpp::new_statement_from_params(*min, *max, bpp.clone())?,
+                                WitnessSlot::BoundCheckBpp(attr_idx),
+                            ),
+                            // Only when neither is given as otherwise one of the above arms matches
+                            _ => return Err(ProofSystemError::BoundCheckParamsNotProvided),
+                        };
+                    (attr_idx, statement, slot)
+                }
+                Predicate::InAccumulator {
+                    attribute,
+                    accumulator,
+                    proving_key,
+                } => {
+                    let attr_idx = self.schema.attribute_index(attribute)?;
+                    let statement = AccumulatorMembership::new_statement_from_params(
+                        accumulator.params.clone(),
+                        accumulator.public_key.clone(),
+                        proving_key.clone(),
+                        accumulator.accumulator_value,
+                    );
+                    (
+                        attr_idx,
+                        statement,
+                        WitnessSlot::AccumulatorMembership(attr_idx),
+                    )
+                }
+                Predicate::NotInAccumulator {
+                    attribute,
+                    accumulator,
+                    proving_key,
+                } => {
+                    let attr_idx = self.schema.attribute_index(attribute)?;
+                    let statement = AccumulatorNonMembership::new_statement_from_params(
+                        accumulator.params.clone(),
+                        accumulator.public_key.clone(),
+                        proving_key.clone(),
+                        accumulator.accumulator_value,
+                    );
+                    (
+                        attr_idx,
+                        statement,
+                        WitnessSlot::AccumulatorNonMembership(attr_idx),
+                    )
+                }
+            };
+            let s_idx = statements.add(statement);
+            meta_statements.add_witness_equality(EqualWitnesses(
+                vec![(sig_idx, attr_idx), (s_idx, 0)].into_iter().collect(),
+            ));
+            slots.push(slot);
+        }
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], self.context.clone());
+        Ok((
+            proof_spec,
+            WitnessTemplate {
+                slots,
+                attribute_count: self.schema.attributes.len(),
+            },
+        ))
+    }
+}
+
+impl WitnessTemplate {
+    /// Create the witnesses for the compiled `ProofSpec` from the signature, all the signed attributes and
+    /// the accumulator (non-)membership witnesses in the same order as the corresponding predicates.
+    pub fn witnesses<E: Pairing>(
+        &self,
+        signature: SignatureG1<E>,
+        attributes: Vec<E::ScalarField>,
+        membership_witnesses: Vec<MembershipWitness<E::G1Affine>>,
+        non_membership_witnesses: Vec<NonMembershipWitness<E::G1Affine>>,
+    ) -> Result<Witnesses<E>, ProofSystemError> {
+        if attributes.len() != self.attribute_count {
+            return Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                attributes.len(),
+                self.attribute_count,
+            ));
+        }
+        let mut membership_witnesses = membership_witnesses.into_iter();
+        let mut non_membership_witnesses = non_membership_witnesses.into_iter();
+        let mut witnesses = Witnesses::new();
+        for slot in &self.slots {
+            let witness = match slot {
+                WitnessSlot::Signature => PoKBBSSignatureG1Wit::new_as_witness(
+                    signature.clone(),
+                    attributes.iter().copied().enumerate().collect(),
+                ),
+                WitnessSlot::BoundCheckBpp(i) => Witness::BoundCheckBpp(attributes[*i]),
+                WitnessSlot::BoundCheckSmc(i) => Witness::BoundCheckSmc(attributes[*i]),
+                WitnessSlot::AccumulatorMembership(i) => match membership_witnesses.next() {
+                    Some(w) => Membership::new_as_witness(attributes[*i], w),
+                    None => return Err(self.missing_witness_error(&witnesses)),
+                },
+                WitnessSlot::AccumulatorNonMembership(i) => match non_membership_witnesses.next() {
+                    Some(w) => NonMembership::new_as_witness(attributes[*i], w),
+                    None => return Err(self.missing_witness_error(&witnesses)),
+                },
+            };
+            witnesses.add(witness);
+        }
+        Ok(witnesses)
+    }
+
+    fn missing_witness_error<E: Pairing>(&self, witnesses: &Witnesses<E>) -> ProofSystemError {
+        ProofSystemError::UnequalWitnessAndStatementCount(self.slots.len(), witnesses.len())
+    }
+}
@@ -0,0 +1,92 @@
+//! Scoped pseudonyms which let a verifier recognize the same prover across presentations made for the same scope,
+//! like a website's domain, without being able to link presentations made for different scopes.
+//!
+//! For a scope `s`, the base `B_s` is created by hashing `s` to the group and the pseudonym is `B_s * m` for a
+//! signed message `m`. So the pseudonym is the same whenever the same message is used for the same scope while
+//! pseudonyms for different scopes are unlinkable under the DDH assumption. The pseudonym is proven using a
+//! Pedersen commitment statement with the single base `B_s` whose witness is proven equal to the signed message.
+//!
+//! The message should be a high entropy secret, like a link secret, as anyone who can guess the message can check
+//! the guess against the pseudonym.
+
+use crate::{
+    meta_statement::{EqualWitnesses, WitnessRef},
+    proof_spec::ProofSpec,
+    statement::ped_comm::PedersenCommitment,
+    witness::{Witness, Witnesses},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_std::{vec, vec::Vec};
+use digest::Digest;
+use dock_crypto_utils::{concat_slices, hashing_utils::affine_group_elem_from_try_and_incr};
+
+/// Pseudonym of a signed message for a scope
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopedPseudonym<G: AffineRepr> {
+    /// Reference to the message the pseudonym is created from
+    pub message: WitnessRef,
+    pub scope: Vec<u8>,
+    /// Base created from the scope
+    pub base: G,
+    pub pseudonym: G,
+}
+
+impl<G: AffineRepr> ScopedPseudonym<G> {
+    /// Base for the pseudonyms of the given scope
+    pub fn base_for_scope<D: Digest>(scope: &[u8]) -> G {
+        affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![b"scoped pseudonym : ", scope])
+    }
+
+    /// Used by the verifier to create the predicate from the pseudonym received from the prover
+    pub fn new<D: Digest>(message: WitnessRef, scope: Vec<u8>, pseudonym: G) -> Self {
+        let base = Self::base_for_scope::<D>(&scope);
+        Self {
+            message,
+            scope,
+            base,
+            pseudonym,
+        }
+    }
+
+    /// Used by the prover to create the pseudonym of the message referred by `message_ref` for the scope. The
+    /// pseudonym is sent to the verifier.
+    pub fn generate<D: Digest>(
+        message_ref: WitnessRef,
+        scope: Vec<u8>,
+        message: G::ScalarField,
+    ) -> Self {
+        let base = Self::base_for_scope::<D>(&scope);
+        let pseudonym = (base * message).into_affine();
+        Self {
+            message: message_ref,
+            scope,
+            base,
+            pseudonym,
+        }
+    }
+
+    /// Add the Pedersen commitment statement for the pseudonym and the meta-statement linking it to the message to
+    /// the `ProofSpec`. Both prover and verifier call this. Returns the index of the statement. The prover adds
+    /// the witness using `Self::add_witness` at the same index in the `Witnesses`.
+    pub fn add_to_proof_spec<E: Pairing>(&self, proof_spec: &mut ProofSpec<E, G>) -> usize {
+        let s_idx = proof_spec.add_statement(PedersenCommitment::new_statement_from_params(
+            vec![self.base],
+            self.pseudonym,
+        ));
+        proof_spec
+            .meta_statements
+            .add_witness_equality(EqualWitnesses(
+                vec![self.message, (s_idx, 0)].into_iter().collect(),
+            ));
+        s_idx
+    }
+
+    /// Called by the prover to add the witness for the statement added by `Self::add_to_proof_spec`
+    pub fn add_witness<E: Pairing<ScalarField = G::ScalarField>>(
+        &self,
+        witnesses: &mut Witnesses<E>,
+        message: G::ScalarField,
+    ) {
+        witnesses.add(Witness::PedersenCommitment(vec![message]));
+    }
+}
@@ -0,0 +1,129 @@
+//! A self-contained presentation that a verifier can store and re-verify later without contacting the prover or
+//! fetching any public params. The token holds the proof and the spec the proof was created for, projected to what
+//! the verifier needs (see `ProofSpec::for_verifier`). The nonce is part of the proof and the revealed messages are
+//! part of the spec's statements so both are covered by the token's serialization as well.
+
+use crate::{
+    error::ProofSystemError, proof::Proof, proof_spec::ProofSpec, statement::Statement,
+    verifier::VerifierConfig,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::RngCore,
+    vec::Vec,
+};
+use digest::Digest;
+use serde::{Deserialize, Serialize};
+
+/// A proof bundled with everything needed to verify it
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PresentationToken<E: Pairing, G: AffineRepr> {
+    pub proof: Proof<E, G>,
+    pub proof_spec: ProofSpec<E, G>,
+}
+
+impl<E, G> PresentationToken<E, G>
+where
+    E: Pairing,
+    G: AffineRepr<ScalarField = E::ScalarField>,
+{
+    /// Create a token from a proof and the spec it was created with. The spec can be the prover's spec as it is
+    /// projected for the verifier.
+    pub fn new(proof: Proof<E, G>, proof_spec: &ProofSpec<E, G>) -> Result<Self, ProofSystemError> {
+        Ok(Self {
+            proof,
+            proof_spec: proof_spec.for_verifier()?,
+        })
+    }
+
+    /// Verify the proof against the bundled spec and nonce
+    pub fn verify<R: RngCore, D: Digest + Clone>(
+        &self,
+        rng: &mut R,
+        config: VerifierConfig,
+    ) -> Result<(), ProofSystemError> {
+        self.proof
+            .clone()
+            .verify::<R, D>(rng, &self.proof_spec, self.proof.nonce().clone(), config)
+    }
+
+    /// Verify the proof against the verifier's own `proof_spec` instead of the bundled one. The revealed messages
+    /// are compared first so a verifier expecting different revealed messages than the ones the proof was created
+    /// with gets `RevealedMessageMismatch` naming the statement and message index instead of a failed verification.
+    pub fn verify_with_spec<R: RngCore, D: Digest + Clone>(
+        &self,
+        rng: &mut R,
+        proof_spec: &ProofSpec<E, G>,
+        config: VerifierConfig,
+    ) -> Result<(), ProofSystemError> {
+        self.check_revealed_messages(proof_spec)?;
+        self.proof
+            .clone()
+            .verify::<R, D>(rng, proof_spec, self.proof.nonce().clone(), config)
+    }
+
+    /// Check that each signature statement of `proof_spec` reveals the same messages as the bundled spec
+    pub fn check_revealed_messages(
+        &self,
+        proof_spec: &ProofSpec<E, G>,
+    ) -> Result<(), ProofSystemError> {
+        let expected = revealed_messages(proof_spec);
+        let found = self.revealed_messages();
+        let statements = expected.keys().chain(found.keys()).collect::<BTreeSet<_>>();
+        for statement in statements {
+            let expected = expected.get(statement);
+            let found = found.get(statement);
+            let indices = expected
+                .into_iter()
+                .chain(found)
+                .flat_map(|m| m.keys())
+                .collect::<BTreeSet<_>>();
+            for index in indices {
+                if expected.and_then(|m| m.get(index)) != found.and_then(|m| m.get(index)) {
+                    return Err(ProofSystemError::RevealedMessageMismatch {
+                        statement: *statement,
+                        index: *index,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Nonce the proof was created with
+    pub fn nonce(&self) -> &Option<Vec<u8>> {
+        self.proof.nonce()
+    }
+
+    /// Revealed messages of each signature statement, keyed by the statement index. Statements not revealing any
+    /// message are skipped.
+    pub fn revealed_messages(&self) -> BTreeMap<usize, &BTreeMap<usize, E::ScalarField>> {
+        revealed_messages(&self.proof_spec)
+    }
+}
+
+fn revealed_messages<E: Pairing, G: AffineRepr>(
+    proof_spec: &ProofSpec<E, G>,
+) -> BTreeMap<usize, &BTreeMap<usize, E::ScalarField>> {
+    proof_spec
+        .statements
+        .0
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| {
+            let revealed = match s {
+                Statement::PoKBBSSignatureG1(s) => &s.revealed_messages,
+                Statement::PoKBBSSignatureG1HiddenPK(s) => &s.revealed_messages,
+                Statement::PoKBBSSignature23G1(s) => &s.revealed_messages,
+                Statement::PoKPSSignature(s) => &s.revealed_messages,
+                _ => return None,
+            };
+            (!revealed.is_empty()).then_some((i, revealed))
+        })
+        .collect()
+}
@@ -1,9 +1,19 @@
-use crate::prelude::StatementProof;
+use crate::{
+    error::ProofSystemError, prelude::StatementProof, proof_spec::ProofSpec,
+    setup_params::ExternalSetupCache,
+};
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeSet, vec::Vec};
+use ark_std::{
+    collections::BTreeSet,
+    io::{Read, Write},
+    marker::PhantomData,
+    vec::Vec,
+};
+use digest::Digest;
 use legogroth16::aggregation;
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct AggregatedGroth16<E: Pairing> {
@@ -31,3 +41,125 @@ impl<E: Pairing, G: AffineRepr> PartialEq for Proof<E, G> {
         // TODO: Add remaining
     }
 }
+
+impl<E: Pairing, G: AffineRepr> Proof<E, G> {
+    /// Compare with another proof in constant time by comparing their serializations. Unlike `PartialEq`, this
+    /// doesn't return early at the first difference so should be used when a proof is checked against a secret
+    /// one like when proofs are used as authentication tokens.
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        let mut self_bytes = Vec::with_capacity(self.compressed_size());
+        let mut other_bytes = Vec::with_capacity(other.compressed_size());
+        if self.serialize_compressed(&mut self_bytes).is_err()
+            || other.serialize_compressed(&mut other_bytes).is_err()
+        {
+            return Choice::from(0);
+        }
+        self_bytes.as_slice().ct_eq(other_bytes.as_slice())
+    }
+
+    /// Check the structure of each statement proof against its statement in the proof spec using
+    /// `StatementProof::validate_structure`. Cheaper than verification so useful to reject malformed proofs early.
+    pub fn validate_structure(&self, proof_spec: &ProofSpec<E, G>) -> Result<(), ProofSystemError> {
+        for (s_idx, (statement, proof)) in proof_spec
+            .statements
+            .0
+            .iter()
+            .zip(self.statement_proofs.iter())
+            .enumerate()
+        {
+            proof.validate_structure(s_idx, statement, &proof_spec.setup_params)?;
+        }
+        Ok(())
+    }
+
+    /// Serialize this proof along with the proof spec it was created for, leaving out the setup params of the spec
+    /// whose content hash (see `SetupParams::content_hash`) is in `known_hashes` and writing only their hash. This
+    /// makes the serialization much smaller when the receiver already has large setup params like SNARK keys or
+    /// signature params. The serialized bytes start with the format version. Deserialize using
+    /// `Self::deserialize_with_external_setup`.
+    pub fn serialize_with_external_setup<D: Digest, W: Write>(
+        &self,
+        proof_spec: &ProofSpec<E, G>,
+        known_hashes: &BTreeSet<Vec<u8>>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        proof_spec.serialize_with_external_setup::<D, _>(known_hashes, &mut writer)?;
+        self.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// Deserialize a proof and its proof spec serialized using `Self::serialize_with_external_setup`, taking the
+    /// setup params that were left out from `cache`. Errors if a left out setup param isn't in `cache` or if the
+    /// bytes were serialized with a different format version.
+    pub fn deserialize_with_external_setup<R: Read>(
+        mut reader: R,
+        cache: &ExternalSetupCache<E, G>,
+    ) -> Result<(Self, ProofSpec<E, G>), ProofSystemError> {
+        let proof_spec = ProofSpec::deserialize_with_external_setup(&mut reader, cache)?;
+        let proof = Self::deserialize_compressed(&mut reader)?;
+        Ok((proof, proof_spec))
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> ConstantTimeEq for Proof<E, G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Proof::ct_eq(self, other)
+    }
+}
+
+/// Reads a compressed serialized `Proof` one `StatementProof` at a time so that all statement proofs don't have
+/// to be deserialized at once. The proof must not have a version header, i.e. the header of
+/// `VersionedSerialization` should already have been read. The challenge depends on all statement proofs so they
+/// are all needed to verify the proof but each can be processed, like checked against its statement, before the
+/// next is read.
+pub struct ProofStreamReader<E: Pairing, G: AffineRepr, R: Read> {
+    reader: R,
+    remaining: usize,
+    _phantom: PhantomData<(E, G)>,
+}
+
+impl<E: Pairing, G: AffineRepr, R: Read> ProofStreamReader<E, G, R> {
+    /// Reads the number of statement proofs
+    pub fn new(mut reader: R) -> Result<Self, ProofSystemError> {
+        let remaining = u64::deserialize_compressed(&mut reader)? as usize;
+        Ok(Self {
+            reader,
+            remaining,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Number of statement proofs not read yet
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Read the next statement proof. Returns `None` once all statement proofs have been read.
+    pub fn next_statement_proof(
+        &mut self,
+    ) -> Result<Option<StatementProof<E, G>>, ProofSystemError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let proof = StatementProof::deserialize_compressed(&mut self.reader)?;
+        self.remaining -= 1;
+        Ok(Some(proof))
+    }
+
+    /// Read the rest of the proof after the statement proofs and create the proof with the given statement proofs,
+    /// like the ones returned by `Self::next_statement_proof`. Errors if all statement proofs haven't been read.
+    pub fn finish(
+        mut self,
+        statement_proofs: Vec<StatementProof<E, G>>,
+    ) -> Result<Proof<E, G>, ProofSystemError> {
+        if self.remaining != 0 {
+            return Err(ProofSystemError::UnreadStatementProofs(self.remaining));
+        }
+        Ok(Proof {
+            statement_proofs,
+            nonce: CanonicalDeserialize::deserialize_compressed(&mut self.reader)?,
+            aggregated_groth16: CanonicalDeserialize::deserialize_compressed(&mut self.reader)?,
+            aggregated_legogroth16: CanonicalDeserialize::deserialize_compressed(&mut self.reader)?,
+        })
+    }
+}
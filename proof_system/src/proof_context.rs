@@ -0,0 +1,122 @@
+//! Transcript from which the challenge of a proof is derived. Both the prover and the verifier write the nonce, the
+//! context, the proof spec and then the challenge contribution of each statement to it and derive the challenge
+//! from it so that they get the same challenge only if they wrote the same bytes.
+
+use crate::{error::ProofSystemError, proof_spec::ProofSpec};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::PrimeField;
+use ark_std::io::{self, Write};
+use digest::Digest;
+use dock_crypto_utils::hashing_utils::{field_elem_from_try_and_incr_given_digest, DigestWriter};
+
+/// Accumulates the challenge contributions of a proof and derives the challenge from them. The contributions are
+/// hashed as they are written rather than being collected first. It implements `Write` so challenge contributions
+/// can be written to it directly.
+pub struct ProofContext<D: Digest>(DigestWriter<D>);
+
+impl<D: Digest> ProofContext<D> {
+    pub fn new() -> Self {
+        Self(DigestWriter::new())
+    }
+
+    /// Create a context with the nonce's, the spec's context's and the spec's challenge contribution written to it
+    pub fn new_for_proof_spec<E: Pairing, G: AffineRepr>(
+        proof_spec: &ProofSpec<E, G>,
+        nonce: Option<&[u8]>,
+    ) -> Result<Self, ProofSystemError> {
+        let mut ctx = Self::new();
+        if let Some(n) = nonce {
+            ctx.write_contribution(n);
+        }
+        if let Some(c) = &proof_spec.context {
+            ctx.write_contribution(c);
+        }
+        proof_spec.challenge_contribution(&mut ctx)?;
+        Ok(ctx)
+    }
+
+    /// Write the bytes as they are
+    pub fn write_contribution(&mut self, bytes: impl AsRef<[u8]>) {
+        self.0.update(bytes)
+    }
+
+    /// Write the bytes after the label. Both the label and the bytes are prefixed with their length (as 8 bytes in
+    /// little endian) so that different label and bytes pairs never write the same byte sequence.
+    pub fn write_labeled(&mut self, label: &[u8], bytes: impl AsRef<[u8]>) {
+        let bytes = bytes.as_ref();
+        self.0.update((label.len() as u64).to_le_bytes());
+        self.0.update(label);
+        self.0.update((bytes.len() as u64).to_le_bytes());
+        self.0.update(bytes);
+    }
+
+    /// Derive the challenge from everything written so far
+    pub fn finalize<F: PrimeField>(self) -> F
+    where
+        D: Clone,
+    {
+        field_elem_from_try_and_incr_given_digest::<F, D>(self.0.into_inner())
+    }
+}
+
+impl<D: Digest> Default for ProofContext<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Digest> Write for ProofContext<D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+    use blake2::Blake2b512;
+    use dock_crypto_utils::hashing_utils::field_elem_from_try_and_incr;
+
+    #[test]
+    fn challenge_from_contributions() {
+        // The challenge is the same as hashing all contributions together irrespective of how they are written
+        let mut ctx = ProofContext::<Blake2b512>::new();
+        ctx.write_contribution(b"nonce");
+        ctx.write_all(&[1, 2, 3]).unwrap();
+        ctx.write_contribution([4]);
+        let challenge = ctx.finalize::<Fr>();
+        assert_eq!(
+            challenge,
+            field_elem_from_try_and_incr::<Fr, Blake2b512>(b"nonce\x01\x02\x03\x04")
+        );
+
+        // Moving bytes between the label and the contribution changes the challenge
+        let labeled = |label: &[u8], bytes: &[u8]| {
+            let mut ctx = ProofContext::<Blake2b512>::new();
+            ctx.write_labeled(label, bytes);
+            ctx.finalize::<Fr>()
+        };
+        assert_eq!(labeled(b"ab", b"c"), labeled(b"ab", b"c"));
+        assert_ne!(labeled(b"ab", b"c"), labeled(b"a", b"bc"));
+        assert_ne!(labeled(b"ab", b"c"), challenge);
+
+        // The nonce and the spec's context are written before the spec
+        let mut proof_spec = ProofSpec::<Bls12_381, G1Affine>::default();
+        proof_spec.context = Some(b"context".to_vec());
+        let mut expected = ProofContext::<Blake2b512>::new();
+        expected.write_contribution(b"nonce");
+        expected.write_contribution(b"context");
+        proof_spec.challenge_contribution(&mut expected).unwrap();
+        assert_eq!(
+            ProofContext::<Blake2b512>::new_for_proof_spec(&proof_spec, Some(b"nonce".as_slice()))
+                .unwrap()
+                .finalize::<Fr>(),
+            expected.finalize::<Fr>()
+        );
+    }
+}
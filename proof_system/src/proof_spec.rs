@@ -1,15 +1,18 @@
 use crate::{
     derived_params::{DerivedParamsTracker, StatementDerivedParams},
     error::ProofSystemError,
-    meta_statement::{MetaStatement, MetaStatements},
-    setup_params::SetupParams,
+    meta_statement::{MetaStatement, MetaStatements, WitnessRef},
+    setup_params::{ExternalSetupCache, SetupParams},
     statement::{Statement, Statements},
+    sub_protocols::SubProtocolType,
+    versioning::FORMAT_VERSION,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
     io::{Read, Write},
+    vec,
     vec::Vec,
 };
 use bbs_plus::setup::{
@@ -22,6 +25,8 @@ use coconut_crypto::setup::{
     PreparedPublicKey as PreparedPSPk, PreparedSignatureParams as PreparedPSSigParams,
     PublicKey as PSPk, SignatureParams as PSSigParams,
 };
+use core::any::TypeId;
+use digest::Digest;
 use legogroth16::{
     aggregation::srs::{ProverSRS, VerifierSRS},
     PreparedVerifyingKey as LegoPreparedVerifyingKey, VerifyingKey as LegoVerifyingKey,
@@ -38,9 +43,12 @@ use smc_range_proof::prelude::MemberCommitmentKey;
 use crate::prelude::bound_check_smc::{
     SmcParamsAndCommitmentKey, SmcParamsWithPairingAndCommitmentKey,
 };
-use vb_accumulator::setup::{
-    PreparedPublicKey as PreparedAccumPk, PreparedSetupParams as PreparedAccumParams,
-    PublicKey as AccumPk, SetupParams as AccumParams,
+use vb_accumulator::{
+    proofs::MembershipProvingKey,
+    setup::{
+        PreparedPublicKey as PreparedAccumPk, PreparedSetupParams as PreparedAccumParams,
+        PublicKey as AccumPk, SetupParams as AccumParams,
+    },
 };
 
 // TODO: Serialize snarkpack params
@@ -132,9 +140,137 @@ where
         self.meta_statements.add(meta_statement)
     }
 
+    /// Replace the `Noop` statement at index `s_idx`, which holds the slot of a statement not known when the spec
+    /// was created, with the given statement. Errors if the statement at that index isn't a `Noop`.
+    pub fn replace_noop(
+        &mut self,
+        s_idx: usize,
+        statement: Statement<E, G>,
+    ) -> Result<(), ProofSystemError> {
+        match self.statements.0.get_mut(s_idx) {
+            Some(s @ Statement::Noop(_)) => {
+                *s = statement;
+                Ok(())
+            }
+            _ => Err(ProofSystemError::InvalidNoopReplacement(s_idx)),
+        }
+    }
+
+    /// Writes the parts of this spec which both the prover and the verifier have, i.e. the type and public
+    /// values of each statement and the meta statements. This is hashed into the challenge before the
+    /// sub-protocols' contributions so that the proof is bound to the exact spec it was created for.
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        self.statements.len().serialize_compressed(&mut writer)?;
+        for s in &self.statements.0 {
+            s.protocol_type().challenge_contribution(&mut writer)?;
+            s.challenge_contribution(&mut writer)?;
+        }
+        self.meta_statements.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// Create the spec the verifier needs to verify a proof created using this spec. Prover-only statements
+    /// are replaced by their verifier counterparts (see `Statement::for_verifier`) and SNARK proving keys in
+    /// the setup params are replaced by their verifying keys so the resulting spec is much smaller to hold
+    /// and deserialize. Errors if the spec has a statement whose verifier counterpart can't be created from it.
+    pub fn for_verifier(&self) -> Result<Self, ProofSystemError> {
+        let statements = self
+            .statements
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s.for_verifier(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            statements: Statements(statements),
+            meta_statements: self.meta_statements.clone(),
+            setup_params: self.setup_params.iter().map(|p| p.for_verifier()).collect(),
+            context: self.context.clone(),
+            aggregate_groth16: self.aggregate_groth16.clone(),
+            aggregate_legogroth16: self.aggregate_legogroth16.clone(),
+            snark_aggregation_srs: self.snark_aggregation_srs.clone(),
+        })
+    }
+
+    /// Serialize this spec leaving out the setup params whose content hash is in `known_hashes`, writing only their
+    /// hash. Like `VersionedSerialization`, the serialized bytes start with the format version. Used by
+    /// `Proof::serialize_with_external_setup`.
+    pub(crate) fn serialize_with_external_setup<D: Digest, W: Write>(
+        &self,
+        known_hashes: &BTreeSet<Vec<u8>>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        FORMAT_VERSION.serialize_compressed(&mut writer)?;
+        self.statements.serialize_compressed(&mut writer)?;
+        self.meta_statements.serialize_compressed(&mut writer)?;
+        self.setup_params.len().serialize_compressed(&mut writer)?;
+        for p in &self.setup_params {
+            let hash = p.content_hash::<D>()?;
+            if known_hashes.contains(&hash) {
+                1u8.serialize_compressed(&mut writer)?;
+                hash.serialize_compressed(&mut writer)?;
+            } else {
+                0u8.serialize_compressed(&mut writer)?;
+                p.serialize_compressed(&mut writer)?;
+            }
+        }
+        self.context.serialize_compressed(&mut writer)?;
+        self.aggregate_groth16.serialize_compressed(&mut writer)?;
+        self.aggregate_legogroth16
+            .serialize_compressed(&mut writer)?;
+        self.snark_aggregation_srs
+            .serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// Deserialize a spec serialized using `Self::serialize_with_external_setup`, taking the setup params that
+    /// were left out from `cache`. Used by `Proof::deserialize_with_external_setup`.
+    pub(crate) fn deserialize_with_external_setup<R: Read>(
+        mut reader: R,
+        cache: &ExternalSetupCache<E, G>,
+    ) -> Result<Self, ProofSystemError> {
+        let found = u16::deserialize_compressed(&mut reader)?;
+        if found != FORMAT_VERSION {
+            return Err(ProofSystemError::UnsupportedProofVersion {
+                found,
+                supported: FORMAT_VERSION,
+            });
+        }
+        let statements = Statements::deserialize_compressed(&mut reader)?;
+        let meta_statements = MetaStatements::deserialize_compressed(&mut reader)?;
+        let count = usize::deserialize_compressed(&mut reader)?;
+        let mut setup_params = Vec::new();
+        for _ in 0..count {
+            match u8::deserialize_compressed(&mut reader)? {
+                0 => setup_params.push(SetupParams::deserialize_compressed(&mut reader)?),
+                1 => {
+                    let hash = Vec::<u8>::deserialize_compressed(&mut reader)?;
+                    match cache.get(&hash) {
+                        Some(p) => setup_params.push(p.clone()),
+                        None => return Err(ProofSystemError::MissingExternalSetup(hash)),
+                    }
+                }
+                _ => return Err(SerializationError::InvalidData.into()),
+            }
+        }
+        Ok(Self {
+            statements,
+            meta_statements,
+            setup_params,
+            context: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            aggregate_groth16: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            aggregate_legogroth16: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            snark_aggregation_srs: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+        })
+    }
+
     /// Sanity check to ensure the proof spec is valid. This should never error as these are used
     /// by same entity creating them.
     pub fn validate(&self) -> Result<(), ProofSystemError> {
+        if self.statements.is_empty() {
+            return Err(ProofSystemError::EmptyProofSpec);
+        }
+
         // Ensure that messages(s) being revealed are not used in a witness equality.
         let mut revealed_wit_refs = BTreeSet::new();
 
@@ -195,6 +331,11 @@ where
                         revealed_wit_refs.insert((i, *k));
                     }
                 }
+                Statement::PoKBBSSignatureG1HiddenPK(s) => {
+                    for k in s.revealed_messages.keys() {
+                        revealed_wit_refs.insert((i, *k));
+                    }
+                }
                 Statement::PoKPSSignature(s) => {
                     for k in s.revealed_messages.keys() {
                         revealed_wit_refs.insert((i, *k));
@@ -203,7 +344,11 @@ where
                 _ => continue,
             }
         }
-        for mt in &self.meta_statements.0 {
+        // Statements already in an issuer consistency
+        let mut same_issuer_stmts = BTreeSet::new();
+        // Statements already in a disjunction and the index of the disjunction
+        let mut disjunct_stmts = BTreeMap::new();
+        for (i, mt) in self.meta_statements.0.iter().enumerate() {
             match mt {
                 // All witness equalities should be valid
                 MetaStatement::WitnessEquality(w) => {
@@ -216,11 +361,219 @@ where
                         }
                     }
                 }
+                // Statements of an issuer consistency should be hidden public key statements with the same public
+                // keys as otherwise the randomized public keys of their proofs can't be compared
+                MetaStatement::IssuerConsistency(same) => {
+                    if !same.is_valid() {
+                        return Err(ProofSystemError::InvalidIssuerConsistency(i));
+                    }
+                    let mut public_keys = None;
+                    for s_idx in same.0.iter() {
+                        let pks = match self.statements.0.get(*s_idx) {
+                            Some(Statement::PoKBBSSignatureG1HiddenPK(s)) => &s.public_keys,
+                            _ => return Err(ProofSystemError::InvalidIssuerConsistency(i)),
+                        };
+                        if *public_keys.get_or_insert(pks) != pks
+                            || !same_issuer_stmts.insert(*s_idx)
+                        {
+                            return Err(ProofSystemError::InvalidIssuerConsistency(i));
+                        }
+                    }
+                }
+                // Statements of a disjunction should prove knowledge of a BBS+ signature as that's the only statement
+                // whose proof can be simulated
+                MetaStatement::StatementDisjunction(either) => {
+                    if !either.is_valid() {
+                        return Err(ProofSystemError::InvalidStatementDisjunction(i));
+                    }
+                    for s_idx in either.0.iter() {
+                        if !matches!(
+                            self.statements.0.get(*s_idx),
+                            Some(Statement::PoKBBSSignatureG1(_))
+                        ) || disjunct_stmts.insert(*s_idx, i).is_some()
+                        {
+                            return Err(ProofSystemError::InvalidStatementDisjunction(i));
+                        }
+                    }
+                }
+            }
+        }
+        // The proof of a statement of a disjunction might be simulated so its witnesses can't be proven equal to
+        // other witnesses
+        for mt in &self.meta_statements.0 {
+            if let MetaStatement::WitnessEquality(w) = mt {
+                if let Some(i) = w.0.iter().find_map(|(s_idx, _)| disjunct_stmts.get(s_idx)) {
+                    return Err(ProofSystemError::InvalidStatementDisjunction(*i));
+                }
+            }
+        }
+        // Witnesses can only be equal if they are elements of the same field. This is only a concern when the
+        // scalar fields of the pairing and the group are different types.
+        if TypeId::of::<E::ScalarField>() != TypeId::of::<G::ScalarField>() {
+            for mt in &self.meta_statements.0 {
+                match mt {
+                    MetaStatement::WitnessEquality(w) => {
+                        let mut with_fields = w.0.iter().filter_map(|(s_idx, _)| {
+                            self.statements
+                                .0
+                                .get(*s_idx)
+                                .map(|s| (*s_idx, s.has_pairing_scalar_field_witnesses()))
+                        });
+                        if let Some((first_idx, first_field)) = with_fields.next() {
+                            for (s_idx, field) in with_fields {
+                                if field != first_field {
+                                    return Err(ProofSystemError::IncompatibleWitnessFields(
+                                        first_idx, s_idx,
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    MetaStatement::IssuerConsistency(_)
+                    | MetaStatement::StatementDisjunction(_) => {}
+                }
+            }
+        }
+        // A linear relation doesn't commit to its witnesses so each should be equal to a witness of a statement
+        // which isn't a linear relation. Otherwise the prover can solve the relation for witnesses of its choice.
+        let equalities = self.meta_statements.disjoint_witness_equalities();
+        for (s_idx, st) in self.statements.0.iter().enumerate() {
+            if let Statement::SchnorrLinearRelation(s) = st {
+                for w_idx in 0..s.witness_count() {
+                    let is_bound = equalities.iter().any(|eq| {
+                        eq.0.contains(&(s_idx, w_idx))
+                            && eq.0.iter().any(|(i, _)| {
+                                self.statements.0.get(*i).map_or(false, |st| {
+                                    !matches!(st, Statement::SchnorrLinearRelation(_))
+                                })
+                            })
+                    });
+                    if !is_bound {
+                        return Err(ProofSystemError::UnboundLinearRelationWitness(s_idx, w_idx));
+                    }
+                }
             }
         }
         Ok(())
     }
 
+    /// Errors with the index and type of the first statement whose type is not one of `allowed`. A verifier calls
+    /// this before verifying a proof to reject statements outside its policy, like verifiable encryption, even
+    /// when the proof for them is valid. The prover's and the verifier's variants of a statement have the same type.
+    pub fn enforce_allowed_types(
+        &self,
+        allowed: &[SubProtocolType],
+    ) -> Result<(), ProofSystemError> {
+        for (i, st) in self.statements.0.iter().enumerate() {
+            let typ = st.protocol_type();
+            if !allowed.contains(&typ) {
+                return Err(ProofSystemError::DisallowedStatementType(i, typ));
+            }
+        }
+        Ok(())
+    }
+
+    /// All witness references that this spec reveals or constrains, i.e. the revealed messages of signature
+    /// statements and the witnesses in witness equalities. References are sorted and without duplicates.
+    pub fn witness_refs(&self) -> impl Iterator<Item = WitnessRef> {
+        let mut refs = BTreeSet::new();
+        for (i, st) in self.statements.0.iter().enumerate() {
+            let revealed = match st {
+                Statement::PoKBBSSignatureG1(s) => &s.revealed_messages,
+                Statement::PoKBBSSignatureG1HiddenPK(s) => &s.revealed_messages,
+                Statement::PoKBBSSignature23G1(s) => &s.revealed_messages,
+                Statement::PoKPSSignature(s) => &s.revealed_messages,
+                _ => continue,
+            };
+            refs.extend(revealed.keys().map(|k| (i, *k)));
+        }
+        for mt in &self.meta_statements.0 {
+            match mt {
+                MetaStatement::WitnessEquality(w) => refs.extend(w.0.iter().copied()),
+                MetaStatement::IssuerConsistency(_) | MetaStatement::StatementDisjunction(_) => (),
+            }
+        }
+        refs.into_iter()
+    }
+
+    /// The changes in going from this spec to `other`, like when reviewing a change of verifier policy. Statements
+    /// and setup params are compared by their index, so a statement whose type changed is reported as removed and
+    /// added. Meta statements are compared irrespective of their order. Context and aggregation are not compared.
+    pub fn diff(&self, other: &Self) -> SpecDiff {
+        let mut diff = SpecDiff::default();
+        let (old, new) = (&self.statements.0, &other.statements.0);
+        for i in 0..old.len().max(new.len()) {
+            match (old.get(i), new.get(i)) {
+                (Some(o), Some(n)) if o.protocol_type() == n.protocol_type() => {
+                    if o == n {
+                        continue;
+                    }
+                    diff.changed_statements.push(i);
+                    let (o_rev, n_rev) = (revealed_indices(o), revealed_indices(n));
+                    if o_rev != n_rev {
+                        diff.revealed_indices.push(RevealedIndicesDiff {
+                            statement: i,
+                            added: n_rev.difference(&o_rev).copied().collect(),
+                            removed: o_rev.difference(&n_rev).copied().collect(),
+                        });
+                    }
+                }
+                (o, n) => {
+                    if let Some(o) = o {
+                        diff.removed_statements.push((i, o.protocol_type()));
+                    }
+                    if let Some(n) = n {
+                        diff.added_statements.push((i, n.protocol_type()));
+                    }
+                }
+            }
+        }
+
+        let (old, new) = (&self.meta_statements.0, &other.meta_statements.0);
+        diff.added_meta_statements = new.iter().filter(|m| !old.contains(m)).cloned().collect();
+        diff.removed_meta_statements = old.iter().filter(|m| !new.contains(m)).cloned().collect();
+
+        let (old, new) = (&self.setup_params, &other.setup_params);
+        for i in 0..old.len().max(new.len()) {
+            match (old.get(i), new.get(i)) {
+                (Some(o), Some(n)) if o != n => diff.changed_setup_params.push(i),
+                (Some(_), None) => diff.removed_setup_params.push(i),
+                (None, Some(_)) => diff.added_setup_params.push(i),
+                _ => (),
+            }
+        }
+        diff
+    }
+
+    /// Groups of Pedersen commitment statements which have the same commitment key and whose witnesses are not in
+    /// any witness equality. Statements of a group can be proven using a single commitment to randomness, see
+    /// `ProverConfig::coalesce_pedersen_commitments`. Each group has at least 2 statements in increasing order and
+    /// the groups are ordered by their first statement.
+    pub fn coalescable_pedersen_commitments(&self) -> Result<Vec<Vec<usize>>, ProofSystemError> {
+        let constrained = self
+            .witness_refs()
+            .map(|(s_idx, _)| s_idx)
+            .collect::<BTreeSet<_>>();
+        let mut groups = Vec::<(&Vec<G>, Vec<usize>)>::new();
+        for (i, st) in self.statements.0.iter().enumerate() {
+            if let Statement::PedersenCommitment(s) = st {
+                if constrained.contains(&i) {
+                    continue;
+                }
+                let key = s.get_commitment_key(&self.setup_params, i)?;
+                match groups.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, g)) => g.push(i),
+                    None => groups.push((key, vec![i])),
+                }
+            }
+        }
+        Ok(groups
+            .into_iter()
+            .filter(|(_, g)| g.len() > 1)
+            .map(|(_, g)| g)
+            .collect())
+    }
+
     /// Derive commitment keys for Schnorr protocol from public params. This is done to avoid
     /// creating them if the same public params are used in multiple statements and is effectively a
     /// pre-processing step done for optimization.
@@ -315,7 +668,12 @@ where
                     derived_bound_check_lego_comm.on_new_statement_idx(verifying_key, s_idx);
                 }
 
-                Statement::R1CSCircomProver(_) | Statement::R1CSCircomVerifier(_) => {
+                // The leaf of a Merkle membership statement is committed in the LegoGroth16 proof the same way as
+                // the witnesses of an R1CS statement
+                Statement::R1CSCircomProver(_)
+                | Statement::R1CSCircomVerifier(_)
+                | Statement::MerkleMembershipProver(_)
+                | Statement::MerkleMembershipVerifier(_) => {
                     let verifying_key = match statement {
                         Statement::R1CSCircomProver(s) => {
                             &s.get_proving_key(&self.setup_params, s_idx)?.vk
@@ -323,6 +681,12 @@ where
                         Statement::R1CSCircomVerifier(s) => {
                             s.get_verifying_key(&self.setup_params, s_idx)?
                         }
+                        Statement::MerkleMembershipProver(s) => {
+                            &s.get_proving_key(&self.setup_params, s_idx)?.vk
+                        }
+                        Statement::MerkleMembershipVerifier(s) => {
+                            s.get_verifying_key(&self.setup_params, s_idx)?
+                        }
                         _ => panic!("This should never happen"),
                     };
                     derived_r1cs_comm.on_new_statement_idx(verifying_key, s_idx);
@@ -364,6 +728,80 @@ where
         ))
     }
 
+    /// Params and public key of each accumulator statement with the statement's index, in the order of statements,
+    /// so that specs built later can reuse them without fetching them again
+    pub fn accumulator_params(
+        &self,
+    ) -> Result<Vec<(usize, AccumulatorParamsRef<'_, E>)>, ProofSystemError> {
+        macro_rules! params_ref {
+            ($s: ident, $s_idx: ident) => {
+                AccumulatorParamsRef {
+                    params: $s.get_params(&self.setup_params, $s_idx)?,
+                    public_key: $s.get_public_key(&self.setup_params, $s_idx)?,
+                    params_ref: $s.params_ref,
+                    public_key_ref: $s.public_key_ref,
+                }
+            };
+        }
+
+        let mut accum_params = Vec::new();
+        for (s_idx, statement) in self.statements.0.iter().enumerate() {
+            let params = match statement {
+                Statement::AccumulatorMembership(s) => params_ref!(s, s_idx),
+                Statement::AccumulatorNonMembership(s) => params_ref!(s, s_idx),
+                Statement::BatchAccumulatorMembership(s) => params_ref!(s, s_idx),
+                Statement::BatchAccumulatorNonMembership(s) => params_ref!(s, s_idx),
+                Statement::CommittedAccumulatorMembership(s) => params_ref!(s, s_idx),
+                _ => continue,
+            };
+            accum_params.push((s_idx, params));
+        }
+        Ok(accum_params)
+    }
+
+    /// Derive the default proving keys, using `D` to hash the params, of accumulator membership statements that
+    /// don't embed or refer to a proving key and weren't created with the default proving key. The proving key is
+    /// created only once for each distinct accumulator params.
+    pub fn derive_default_accumulator_proving_keys<D: Digest>(
+        &self,
+    ) -> Result<BTreeMap<usize, MembershipProvingKey<E::G1Affine>>, ProofSystemError> {
+        let mut derived = Vec::<(&AccumParams<E>, MembershipProvingKey<E::G1Affine>)>::new();
+        let mut default_prk = BTreeMap::new();
+        for (s_idx, statement) in self.statements.0.iter().enumerate() {
+            if let Statement::AccumulatorMembership(s) = statement {
+                if s.default_proving_key && s.cached_default_proving_key.is_none() {
+                    let params = s.get_params(&self.setup_params, s_idx)?;
+                    let prk = match derived.iter().find(|(p, _)| *p == params) {
+                        Some((_, prk)) => prk.clone(),
+                        None => {
+                            let prk = MembershipProvingKey::default_for::<D, E>(params)?;
+                            derived.push((params, prk.clone()));
+                            prk
+                        }
+                    };
+                    default_prk.insert(s_idx, prk);
+                }
+            }
+        }
+        Ok(default_prk)
+    }
+
+    /// Create the default proving keys of accumulator membership statements which are missing them, like after
+    /// deserialization, and keep them with the statements so that they are not created again for each proof.
+    pub fn precompute_default_accumulator_proving_keys<D: Digest>(
+        &mut self,
+    ) -> Result<(), ProofSystemError> {
+        let mut default_prk = self.derive_default_accumulator_proving_keys::<D>()?;
+        for (s_idx, statement) in self.statements.0.iter_mut().enumerate() {
+            if let Statement::AccumulatorMembership(s) = statement {
+                if let Some(prk) = default_prk.remove(&s_idx) {
+                    s.cached_default_proving_key = Some(prk);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Derive prepared keys for performing pairings. This is done to avoid preparing the same
     /// parameters again and is effectively a pre-processing step done for optimization.
     pub fn derive_prepared_parameters(
@@ -420,6 +858,11 @@ where
                     let pk = s.get_public_key(&self.setup_params, s_idx)?;
                     derived_bbs_pk.on_new_statement_idx(pk, s_idx);
                 }
+                Statement::PoKBBSSignatureG1HiddenPK(s) => {
+                    // The public key is randomized by the prover so only the params are prepared
+                    let params = s.get_sig_params(&self.setup_params, s_idx)?;
+                    derived_bbs_p.on_new_statement_idx(params, s_idx);
+                }
                 Statement::PoKBBSSignature23G1(s) => {
                     let params = s.get_sig_params(&self.setup_params, s_idx)?;
                     derived_bbs.on_new_statement_idx(params, s_idx);
@@ -431,10 +874,34 @@ where
                     let params = s.get_params(&self.setup_params, s_idx)?;
                     derived_accum_p.on_new_statement_idx(params, s_idx);
 
+                    // No need to prepare the public key if the statement already has it prepared
+                    if s.prepared_public_key.is_none() {
+                        let pk = s.get_public_key(&self.setup_params, s_idx)?;
+                        derived_accum_pk.on_new_statement_idx(pk, s_idx);
+                    }
+                }
+                Statement::AccumulatorNonMembership(s) => {
+                    let params = s.get_params(&self.setup_params, s_idx)?;
+                    derived_accum_p.on_new_statement_idx(params, s_idx);
+
                     let pk = s.get_public_key(&self.setup_params, s_idx)?;
                     derived_accum_pk.on_new_statement_idx(pk, s_idx);
                 }
-                Statement::AccumulatorNonMembership(s) => {
+                Statement::BatchAccumulatorMembership(s) => {
+                    let params = s.get_params(&self.setup_params, s_idx)?;
+                    derived_accum_p.on_new_statement_idx(params, s_idx);
+
+                    let pk = s.get_public_key(&self.setup_params, s_idx)?;
+                    derived_accum_pk.on_new_statement_idx(pk, s_idx);
+                }
+                Statement::CommittedAccumulatorMembership(s) => {
+                    let params = s.get_params(&self.setup_params, s_idx)?;
+                    derived_accum_p.on_new_statement_idx(params, s_idx);
+
+                    let pk = s.get_public_key(&self.setup_params, s_idx)?;
+                    derived_accum_pk.on_new_statement_idx(pk, s_idx);
+                }
+                Statement::BatchAccumulatorNonMembership(s) => {
                     let params = s.get_params(&self.setup_params, s_idx)?;
                     derived_accum_p.on_new_statement_idx(params, s_idx);
 
@@ -459,6 +926,10 @@ where
                     let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
                     derived_lego_vk.on_new_statement_idx(verifying_key, s_idx);
                 }
+                Statement::MerkleMembershipVerifier(s) => {
+                    let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
+                    derived_lego_vk.on_new_statement_idx(verifying_key, s_idx);
+                }
                 Statement::PoKPSSignature(s) => {
                     let params = s.get_sig_params(&self.setup_params, s_idx)?;
                     derived_ps_p.on_new_statement_idx(params, s_idx);
@@ -508,6 +979,61 @@ where
     }
 }
 
+/// Difference between 2 `ProofSpec`s as returned by `ProofSpec::diff`. Statement indices of added statements are
+/// in the new spec and of removed statements in the old spec.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpecDiff {
+    pub added_statements: Vec<(usize, SubProtocolType)>,
+    pub removed_statements: Vec<(usize, SubProtocolType)>,
+    /// Indices of statements with the same type in both specs but different public values or parameters. This
+    /// includes the statements whose revealed messages changed.
+    pub changed_statements: Vec<usize>,
+    pub revealed_indices: Vec<RevealedIndicesDiff>,
+    pub added_meta_statements: Vec<MetaStatement>,
+    pub removed_meta_statements: Vec<MetaStatement>,
+    pub added_setup_params: Vec<usize>,
+    pub removed_setup_params: Vec<usize>,
+    pub changed_setup_params: Vec<usize>,
+}
+
+/// Change in the indices of the revealed messages of a signature statement
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevealedIndicesDiff {
+    pub statement: usize,
+    pub added: BTreeSet<usize>,
+    pub removed: BTreeSet<usize>,
+}
+
+impl SpecDiff {
+    /// Whether both specs have the same statements, meta statements and setup params
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Params and public key of an accumulator statement as returned by `ProofSpec::accumulator_params`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorParamsRef<'a, E: Pairing> {
+    pub params: &'a AccumParams<E>,
+    pub public_key: &'a AccumPk<E>,
+    /// Index of the params in the spec's setup params if the statement refers to them rather than having them
+    pub params_ref: Option<usize>,
+    /// Index of the public key in the spec's setup params if the statement refers to it rather than having it
+    pub public_key_ref: Option<usize>,
+}
+
+/// Indices of the revealed messages of a signature statement, empty for other statements
+fn revealed_indices<E: Pairing, G: AffineRepr>(statement: &Statement<E, G>) -> BTreeSet<usize> {
+    let revealed = match statement {
+        Statement::PoKBBSSignatureG1(s) => &s.revealed_messages,
+        Statement::PoKBBSSignatureG1HiddenPK(s) => &s.revealed_messages,
+        Statement::PoKBBSSignature23G1(s) => &s.revealed_messages,
+        Statement::PoKPSSignature(s) => &s.revealed_messages,
+        _ => return BTreeSet::new(),
+    };
+    revealed.keys().copied().collect()
+}
+
 mod serialization {
     use super::*;
     use ark_serialize::{Compress, Valid, Validate};
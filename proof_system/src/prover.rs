@@ -1,8 +1,24 @@
 //! Code for the prover to generate a `Proof`
 
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeMap, format, rand::RngCore, vec, vec::Vec, UniformRand};
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    io::{Read, Write},
+    rand::RngCore,
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+#[cfg(feature = "parallel")]
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    sync::Arc,
+};
 
 use crate::{
     error::ProofSystemError,
@@ -10,34 +26,54 @@ use crate::{
     sub_protocols::{ps_signature::PSSignaturePoK, SubProtocol},
     witness::{Witness, Witnesses},
 };
+use bbs_plus::prelude::{PoKOfSignature23G1Proof, PoKOfSignatureG1Proof, SignatureG1};
 use digest::Digest;
 use legogroth16::aggregation::srs::PreparedProverSRS;
 
 use crate::{
-    meta_statement::WitnessRef,
+    meta_statement::{MetaStatements, WitnessRef},
     prelude::SnarkpackSRS,
     proof::{AggregatedGroth16, Proof},
+    proof_context::ProofContext,
     proof_spec::ProofSpec,
-    statement_proof::StatementProof,
+    statement_proof::{
+        BatchAccumulatorMembershipProof, BatchAccumulatorNonMembershipProof, BoundCheckBppProof,
+        BoundCheckSmcProof, BoundCheckSmcWithKVProof, CoalescedPedersenCommitmentProof,
+        CommittedAccumulatorMembershipProof, InequalityProof, MerkleMembershipProof,
+        PedersenCommitmentProof, PoKBBSSignatureG1HiddenPKProof, SchnorrLinearRelationProof,
+        StatementProof,
+    },
     sub_protocols::{
-        accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol},
+        accumulator::{
+            AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol,
+            BatchAccumulatorMembershipSubProtocol, BatchAccumulatorNonMembershipSubProtocol,
+            CommittedAccumulatorMembershipSubProtocol,
+        },
         bbs_23::PoKBBSSigG1SubProtocol,
         bbs_plus::PoKBBSSigG1SubProtocol as PoKBBSPlusSigG1SubProtocol,
+        bbs_plus_hidden_pk::PoKBBSSigG1HiddenPKSubProtocol,
         bound_check_bpp::BoundCheckBppProtocol,
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
+        coalesced_schnorr::{CoalescedSchnorrProtocol, CoalescedStatementProtocol},
+        enforce_and_get_u32,
         inequality::InequalityProtocol,
+        linear_relation::SchnorrLinearRelationProtocol,
+        merkle_membership::MerkleMembershipProtocol,
+        noop::NoopProtocol,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         saver::SaverProtocol,
         schnorr::SchnorrProtocol,
+        statement_disjunction::PoKBBSSigG1DisjunctSubProtocol,
     },
 };
 use dock_crypto_utils::{
-    hashing_utils::field_elem_from_try_and_incr,
+    hashing_utils::{field_elem_from_try_and_incr, field_elem_from_try_and_incr_given_digest},
     transcript::{new_merlin_transcript, Transcript},
 };
 use saver::encryption::Ciphertext;
+use vb_accumulator::prelude::{MembershipProof, NonMembershipProof};
 
 /// The SAVER randomness, ciphertext and proof to reuse when creating the composite proof. This is more
 /// efficient than generating a new ciphertext and proof.
@@ -57,6 +93,18 @@ pub struct OldLegoGroth16Proof<E: Pairing>(pub E::ScalarField, pub legogroth16::
 pub struct ProverConfig<E: Pairing> {
     pub reuse_saver_proofs: Option<BTreeMap<usize, OldSaverProof<E>>>,
     pub reuse_legogroth16_proofs: Option<BTreeMap<usize, OldLegoGroth16Proof<E>>>,
+    /// Prove the Pedersen commitment statements of each group returned by
+    /// `ProofSpec::coalescable_pedersen_commitments` using a single commitment to randomness. This makes the proof
+    /// smaller and faster to create and verify.
+    pub coalesce_pedersen_commitments: bool,
+    /// For `PoKBBSSignatureG1` statements revealing all messages, send the signature itself rather than a proof
+    /// of knowledge of it. This makes the proof faster to create and verify but gives up what the proof of
+    /// knowledge provides: the signature is not bound to the nonce so anyone who sees the proof can replay the
+    /// signature, and all proofs sending the same signature are linkable. The verifier rejects such proofs unless
+    /// it sets `VerifierConfig::accept_fully_revealed_bbs_plus_signatures`.
+    pub send_fully_revealed_bbs_plus_signatures: bool,
+    /// Thread pool to create the proof in, set using `Self::with_thread_pool`
+    pub thread_pool: ProverThreadPool,
 }
 
 impl<E: Pairing> Default for ProverConfig<E> {
@@ -64,11 +112,115 @@ impl<E: Pairing> Default for ProverConfig<E> {
         Self {
             reuse_saver_proofs: None,
             reuse_legogroth16_proofs: None,
+            coalesce_pedersen_commitments: false,
+            send_fully_revealed_bbs_plus_signatures: false,
+            thread_pool: ProverThreadPool::default(),
+        }
+    }
+}
+
+/// Rayon thread pool in which the proof is created. When not set, the proof is created in the global pool. Only
+/// holds a pool with the `parallel` feature. A set pool can't be serialized and a deserialized config never has
+/// a pool.
+#[derive(Clone, Debug, Default)]
+pub struct ProverThreadPool(#[cfg(feature = "parallel")] pub Option<Arc<rayon::ThreadPool>>);
+
+impl Valid for ProverThreadPool {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalSerialize for ProverThreadPool {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        _writer: W,
+        _compress: Compress,
+    ) -> Result<(), SerializationError> {
+        #[cfg(feature = "parallel")]
+        if self.0.is_some() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: Compress) -> usize {
+        0
+    }
+}
+
+impl CanonicalDeserialize for ProverThreadPool {
+    fn deserialize_with_mode<R: Read>(
+        _reader: R,
+        _compress: Compress,
+        _validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self::default())
+    }
+}
+
+/// Blindings chosen by the prover for the witnesses that are part of witness equalities, keyed by the blinding.
+/// Used when testing that witnesses which aren't proven equal never get the same blinding as that would make them
+/// linkable. Blindings of other witnesses are chosen by the sub-protocols and aren't recorded. Only recorded in
+/// debug builds, empty otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProofBuildAudit<F: PrimeField> {
+    pub blindings: BTreeMap<F, BTreeSet<WitnessRef>>,
+}
+
+impl<F: PrimeField> ProofBuildAudit<F> {
+    #[cfg(debug_assertions)]
+    fn record(&mut self, blindings: &BTreeMap<WitnessRef, F>) {
+        for (wr, b) in blindings {
+            self.blindings.entry(*b).or_default().insert(*wr);
         }
     }
+
+    #[cfg(not(debug_assertions))]
+    fn record(&mut self, _blindings: &BTreeMap<WitnessRef, F>) {}
+
+    /// Witness references sharing a blinding even though they aren't proven equal by the given meta-statements.
+    /// Empty when no blinding is reused for unrelated witnesses.
+    pub fn unrelated_witnesses_with_same_blinding(
+        &self,
+        meta_statements: &MetaStatements,
+    ) -> Vec<BTreeSet<WitnessRef>> {
+        let equalities = meta_statements.disjoint_witness_equalities();
+        self.blindings
+            .values()
+            .filter(|refs| !equalities.iter().any(|eq| refs.is_subset(&eq.0)))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Phase of proof creation a statement has finished
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofPhase {
+    /// The sub-protocol of the statement is initialized, i.e. the commitments to randomness are created
+    Commit,
+    /// The response to the challenge is created for the statement
+    Response,
+}
+
+/// Reported during proof creation after each statement finishes a phase, see `Proof::new_with_progress`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProofProgress {
+    pub statement_index: usize,
+    /// Number of statements in the proof spec
+    pub total: usize,
+    pub phase: ProofPhase,
 }
 
 impl<E: Pairing> ProverConfig<E> {
+    /// Create the proof in the given thread pool rather than the global one. Useful for limiting the threads used
+    /// by each proof when many proofs are created concurrently.
+    #[cfg(feature = "parallel")]
+    pub fn with_thread_pool(mut self, pool: Arc<rayon::ThreadPool>) -> Self {
+        self.thread_pool = ProverThreadPool(Some(pool));
+        self
+    }
+
     /// Get SAVER randomness, ciphertext and proof to reuse for the given statement id
     fn get_saver_proof(&mut self, statement_id: &usize) -> Option<OldSaverProof<E>> {
         self.reuse_saver_proofs
@@ -94,6 +246,22 @@ macro_rules! err_incompat_witness {
     };
 }
 
+/// Creates a method returning the statement proof at the given index if it's of the given variant
+macro_rules! impl_statement_proof_accessor {
+    ($fn_name: ident, $variant: ident, $proof: ty) => {
+        pub fn $fn_name(&self, index: usize) -> Result<&$proof, ProofSystemError> {
+            match self.statement_proof(index)? {
+                StatementProof::$variant(p) => Ok(p),
+                p => Err(ProofSystemError::UnexpectedStatementProof {
+                    index,
+                    expected: stringify!($variant),
+                    found: p.variant_name(),
+                }),
+            }
+        }
+    };
+}
+
 impl<E, G> Proof<E, G>
 where
     E: Pairing,
@@ -106,15 +274,87 @@ where
     /// Also returns the randomness used by statements using SAVER and LegoGroth16 proofs which can
     /// then be used as helpers in subsequent proof creations where these proofs are reused than
     /// creating fresh proofs.
-    pub fn new<R: RngCore, D: Digest>(
+    pub fn new<R: RngCore, D: Digest + Clone>(
         rng: &mut R,
-        proof_spec: ProofSpec<E, G>,
+        proof_spec: &ProofSpec<E, G>,
         witnesses: Witnesses<E>,
         nonce: Option<Vec<u8>>,
-        mut config: ProverConfig<E>,
+        config: ProverConfig<E>,
+    ) -> Result<(Self, BTreeMap<usize, E::ScalarField>), ProofSystemError> {
+        Self::new_with_audit::<R, D>(rng, proof_spec, witnesses, nonce, config)
+            .map(|(proof, randomness, _)| (proof, randomness))
+    }
+
+    /// Same as `Self::new` but calls `progress` after each statement's sub-protocol is initialized and then after
+    /// each statement's response is created. Meant for showing the progress of creating proofs for large specs and
+    /// doesn't change the created proof.
+    /// As `progress` might not be callable from another thread, the proof is created in the current thread pool
+    /// even when `ProverConfig::thread_pool` is set.
+    pub fn new_with_progress<R: RngCore, D: Digest + Clone>(
+        rng: &mut R,
+        proof_spec: &ProofSpec<E, G>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        config: ProverConfig<E>,
+        progress: &mut dyn FnMut(ProofProgress),
     ) -> Result<(Self, BTreeMap<usize, E::ScalarField>), ProofSystemError> {
+        Self::create::<R, D>(rng, proof_spec, witnesses, nonce, config, Some(progress))
+            .map(|(proof, randomness, _)| (proof, randomness))
+    }
+
+    /// Same as `Self::new` but also returns the blindings given to the witnesses in witness equalities. Meant for
+    /// security testing, see `ProofBuildAudit`.
+    pub fn new_with_audit<R: RngCore, D: Digest + Clone>(
+        rng: &mut R,
+        proof_spec: &ProofSpec<E, G>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        config: ProverConfig<E>,
+    ) -> Result<
+        (
+            Self,
+            BTreeMap<usize, E::ScalarField>,
+            ProofBuildAudit<E::ScalarField>,
+        ),
+        ProofSystemError,
+    > {
+        #[cfg(feature = "parallel")]
+        let mut config = config;
+        #[cfg(feature = "parallel")]
+        if let Some(pool) = config.thread_pool.0.take() {
+            // The proof is created on a thread of the pool but `rng` might not be sendable to it so use an RNG
+            // seeded from `rng`
+            let mut seed = <StdRng as SeedableRng>::Seed::default();
+            rng.fill_bytes(&mut seed);
+            let mut pool_rng = StdRng::from_seed(seed);
+            return pool.install(|| {
+                Self::create::<StdRng, D>(&mut pool_rng, proof_spec, witnesses, nonce, config, None)
+            });
+        }
+        Self::create::<R, D>(rng, proof_spec, witnesses, nonce, config, None)
+    }
+
+    fn create<R: RngCore, D: Digest + Clone>(
+        rng: &mut R,
+        proof_spec: &ProofSpec<E, G>,
+        mut witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        mut config: ProverConfig<E>,
+        mut progress: Option<&mut dyn FnMut(ProofProgress)>,
+    ) -> Result<
+        (
+            Self,
+            BTreeMap<usize, E::ScalarField>,
+            ProofBuildAudit<E::ScalarField>,
+        ),
+        ProofSystemError,
+    > {
         proof_spec.validate()?;
 
+        if witnesses.is_empty() {
+            return Err(ProofSystemError::EmptyWitnesses);
+        }
+
         // There should be a witness for each statement
         if proof_spec.statements.len() != witnesses.len() {
             return Err(ProofSystemError::UnequalWitnessAndStatementCount(
@@ -139,6 +379,8 @@ where
                 }
             }
         }
+        let mut audit = ProofBuildAudit::default();
+        audit.record(&blindings);
 
         // Prepare commitment keys for running Schnorr protocols of all statements.
         let (
@@ -150,10 +392,87 @@ where
             bound_check_smc_comm,
             ineq_comm,
         ) = proof_spec.derive_commitment_keys()?;
+        let default_accum_prk = proof_spec.derive_default_accumulator_proving_keys::<D>()?;
 
         let mut sub_protocols =
             Vec::<SubProtocol<E, G>>::with_capacity(proof_spec.statements.0.len());
 
+        // Pedersen commitment statements proven along with the statement with the smallest index in their group. For
+        // the statement with the smallest index, the other statements of the group and their witnesses are kept.
+        let mut coalesced_into = BTreeMap::<usize, usize>::new();
+        let mut coalesced_groups = BTreeMap::<usize, (Vec<usize>, Vec<Vec<E::ScalarField>>)>::new();
+        if config.coalesce_pedersen_commitments {
+            for group in proof_spec.coalescable_pedersen_commitments()? {
+                let mut group_witnesses = Vec::with_capacity(group.len() - 1);
+                for i in &group[1..] {
+                    coalesced_into.insert(*i, group[0]);
+                    // The witness is used when initializing the sub-protocol of the first statement
+                    match core::mem::replace(
+                        &mut witnesses.0[*i],
+                        Witness::PedersenCommitment(Vec::new()),
+                    ) {
+                        Witness::PedersenCommitment(w) => group_witnesses.push(w),
+                        w => {
+                            return Err(ProofSystemError::WitnessIncompatibleWithStatement(
+                                *i,
+                                format!("{:?}", w),
+                                format!("{:?}", proof_spec.statements.0[*i]),
+                            ))
+                        }
+                    }
+                }
+                coalesced_groups.insert(group[0], (group[1..].to_vec(), group_witnesses));
+            }
+        }
+
+        // Statements whose signatures should be by the same issuer are proven with the same randomizer for the hidden
+        // public key so that their randomized public keys are equal
+        let mut pk_randomizers = BTreeMap::<usize, E::ScalarField>::new();
+        for same in proof_spec.meta_statements.issuer_consistencies() {
+            let mut pk_indices = same.0.iter().filter_map(|i| match &witnesses.0[*i] {
+                Witness::PoKBBSSignatureG1HiddenPK(w) => Some((*i, w.public_key_index)),
+                _ => None,
+            });
+            if let Some((first_idx, first_pk_idx)) = pk_indices.next() {
+                for (s_idx, pk_idx) in pk_indices {
+                    if pk_idx != first_pk_idx {
+                        return Err(ProofSystemError::UnsatisfiedIssuerConsistency(
+                            first_idx, s_idx,
+                        ));
+                    }
+                }
+            }
+            let randomizer = E::ScalarField::rand(rng);
+            for s_idx in same.0.iter() {
+                pk_randomizers.insert(*s_idx, randomizer);
+            }
+        }
+
+        // The challenges of the statements of a disjunction which aren't satisfied are chosen before the challenge of
+        // the proof and their proofs are simulated. The satisfied statement is given the sum of these challenges.
+        let mut simulated_challenges = BTreeMap::<usize, E::ScalarField>::new();
+        let mut satisfied_disjuncts = BTreeMap::<usize, E::ScalarField>::new();
+        for (i, either) in proof_spec.meta_statements.statement_disjunctions() {
+            let mut satisfied = None;
+            for s_idx in either.0.iter() {
+                match &witnesses.0[*s_idx] {
+                    Witness::Noop(_) => {
+                        simulated_challenges.insert(*s_idx, E::ScalarField::rand(rng));
+                    }
+                    _ if satisfied.is_none() => satisfied = Some(*s_idx),
+                    _ => return Err(ProofSystemError::UnsatisfiedStatementDisjunction(i)),
+                }
+            }
+            let satisfied =
+                satisfied.ok_or(ProofSystemError::UnsatisfiedStatementDisjunction(i))?;
+            let other_challenges = either
+                .0
+                .iter()
+                .filter_map(|s_idx| simulated_challenges.get(s_idx))
+                .sum::<E::ScalarField>();
+            satisfied_disjuncts.insert(satisfied, other_challenges);
+        }
+
         // Randomness used by SAVER and LegoGroth16 proofs. This is tracked and returned so subsequent proofs for
         // the same public params and witness can reuse this randomness
         let mut commitment_randomness = BTreeMap::<usize, E::ScalarField>::new();
@@ -170,6 +489,27 @@ where
             .enumerate()
         {
             match statement {
+                Statement::PoKBBSSignatureG1(s)
+                    if satisfied_disjuncts.contains_key(&s_idx)
+                        || simulated_challenges.contains_key(&s_idx) =>
+                {
+                    let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                    let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                    let mut sp = PoKBBSSigG1DisjunctSubProtocol::new(
+                        s_idx,
+                        &s.revealed_messages,
+                        sig_params,
+                        pk,
+                    );
+                    match (witness, simulated_challenges.remove(&s_idx)) {
+                        (Witness::Noop(_), Some(c)) => sp.init_simulated(rng, c)?,
+                        (Witness::PoKBBSSignatureG1(w), None) => {
+                            sp.init_satisfied(rng, w, satisfied_disjuncts[&s_idx])?
+                        }
+                        (witness, _) => err_incompat_witness!(s_idx, s, witness),
+                    }
+                    sub_protocols.push(SubProtocol::PoKBBSSignatureG1Disjunct(sp));
+                }
                 Statement::PoKBBSSignatureG1(s) => match witness {
                     Witness::PoKBBSSignatureG1(w) => {
                         // Prepare blindings for this BBS+ signature proof
@@ -188,11 +528,42 @@ where
                             sig_params,
                             pk,
                         );
-                        sp.init(rng, blindings_map, w)?;
+                        if config.send_fully_revealed_bbs_plus_signatures
+                            && w.unrevealed_messages.is_empty()
+                        {
+                            sp.init_full_reveal(w)?;
+                        } else {
+                            sp.init(rng, blindings_map, w)?;
+                        }
                         sub_protocols.push(SubProtocol::PoKBBSSignatureG1(sp));
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::PoKBBSSignatureG1HiddenPK(s) => match witness {
+                    Witness::PoKBBSSignatureG1HiddenPK(w) => {
+                        // Prepare blindings for this BBS+ signature proof
+                        let mut blindings_map = BTreeMap::new();
+                        for k in w.unrevealed_messages.keys() {
+                            match blindings.remove(&(s_idx, *k)) {
+                                Some(b) => blindings_map.insert(*k, b),
+                                None => None,
+                            };
+                        }
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = PoKBBSSigG1HiddenPKSubProtocol::new(
+                            s_idx,
+                            &s.revealed_messages,
+                            sig_params,
+                            &s.public_keys,
+                        );
+                        match pk_randomizers.remove(&s_idx) {
+                            Some(r) => sp.init_with_randomizer(rng, blindings_map, w, r)?,
+                            None => sp.init(rng, blindings_map, w)?,
+                        }
+                        sub_protocols.push(SubProtocol::PoKBBSSignatureG1HiddenPK(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::PoKBBSSignature23G1(s) => match witness {
                     Witness::PoKBBSSignature23G1(w) => {
                         // Prepare blindings for this BBS+ signature proof
@@ -216,13 +587,38 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
-                Statement::AccumulatorMembership(s) => match witness {
-                    Witness::AccumulatorMembership(w) => {
+                Statement::AccumulatorMembership(s) => {
+                    let w = match witness {
+                        Witness::AccumulatorMembership(w) => w,
+                        // Witness is fetched only now, when it is needed
+                        Witness::AccumulatorMembershipLazy(w) => w.fetch()?,
+                        _ => err_incompat_witness!(s_idx, s, witness),
+                    };
+                    let blinding = blindings.remove(&(s_idx, 0));
+                    let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                    let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                    let prk = s.get_proving_key_or_default(
+                        &proof_spec.setup_params,
+                        &default_accum_prk,
+                        s_idx,
+                    )?;
+                    let mut sp = AccumulatorMembershipSubProtocol::new(
+                        s_idx,
+                        params,
+                        pk,
+                        prk,
+                        s.accumulator_value,
+                    );
+                    sp.init(rng, blinding, w)?;
+                    sub_protocols.push(SubProtocol::AccumulatorMembership(sp));
+                }
+                Statement::AccumulatorNonMembership(s) => match witness {
+                    Witness::AccumulatorNonMembership(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let mut sp = AccumulatorMembershipSubProtocol::new(
+                        let mut sp = AccumulatorNonMembershipSubProtocol::new(
                             s_idx,
                             params,
                             pk,
@@ -230,29 +626,100 @@ where
                             s.accumulator_value,
                         );
                         sp.init(rng, blinding, w)?;
-                        sub_protocols.push(SubProtocol::AccumulatorMembership(sp));
+                        sub_protocols.push(SubProtocol::AccumulatorNonMembership(sp));
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
-                Statement::AccumulatorNonMembership(s) => match witness {
-                    Witness::AccumulatorNonMembership(w) => {
-                        let blinding = blindings.remove(&(s_idx, 0));
+                Statement::BatchAccumulatorNonMembership(s) => match witness {
+                    Witness::BatchAccumulatorNonMembership(w) => {
+                        let blindings = (0..s.num_elements)
+                            .map(|i| blindings.remove(&(s_idx, i)))
+                            .collect();
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let mut sp = AccumulatorNonMembershipSubProtocol::new(
+                        let mut sp = BatchAccumulatorNonMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.accumulator_value,
+                            s.num_elements,
+                        );
+                        sp.init(rng, blindings, w)?;
+                        sub_protocols.push(SubProtocol::BatchAccumulatorNonMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::BatchAccumulatorMembership(s) => match witness {
+                    Witness::BatchAccumulatorMembership(w) => {
+                        let blindings = (0..s.num_elements)
+                            .map(|i| blindings.remove(&(s_idx, i)))
+                            .collect();
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = BatchAccumulatorMembershipSubProtocol::new(
                             s_idx,
                             params,
                             pk,
                             prk,
                             s.accumulator_value,
+                            s.num_elements,
+                        );
+                        sp.init(rng, blindings, w)?;
+                        sub_protocols.push(SubProtocol::BatchAccumulatorMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::CommittedAccumulatorMembership(s) => match witness {
+                    Witness::CommittedAccumulatorMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = CommittedAccumulatorMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.commitment,
+                            s.commitment_key,
                         );
                         sp.init(rng, blinding, w)?;
-                        sub_protocols.push(SubProtocol::AccumulatorNonMembership(sp));
+                        sub_protocols.push(SubProtocol::CommittedAccumulatorMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::Noop(s) => match witness {
+                    Witness::Noop(_) => {
+                        sub_protocols.push(SubProtocol::Noop(NoopProtocol::new(s_idx)))
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 Statement::PedersenCommitment(s) => match witness {
+                    Witness::PedersenCommitment(_) if coalesced_into.contains_key(&s_idx) => {
+                        sub_protocols.push(SubProtocol::Coalesced(
+                            CoalescedStatementProtocol::new(s_idx, coalesced_into[&s_idx]),
+                        ));
+                    }
+                    Witness::PedersenCommitment(w) if coalesced_groups.contains_key(&s_idx) => {
+                        let (coalesced, mut group_witnesses) =
+                            coalesced_groups.remove(&s_idx).unwrap();
+                        let mut commitments = vec![s.commitment];
+                        for i in &coalesced {
+                            match &proof_spec.statements.0[*i] {
+                                Statement::PedersenCommitment(c) => commitments.push(c.commitment),
+                                _ => return Err(ProofSystemError::InvalidStatement),
+                            }
+                        }
+                        group_witnesses.insert(0, w);
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp =
+                            CoalescedSchnorrProtocol::new(s_idx, coalesced, comm_key, commitments);
+                        sp.init(rng, group_witnesses)?;
+                        sub_protocols.push(SubProtocol::CoalescedPoKDiscreteLogs(sp));
+                    }
                     Witness::PedersenCommitment(w) => {
                         let mut blindings_map = BTreeMap::new();
                         for i in 0..w.len() {
@@ -446,7 +913,10 @@ where
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 Statement::BoundCheckSmcWithKVProver(s) => match witness {
-                    Witness::BoundCheckSmcWithKV(w) => {
+                    Witness::BoundCheckSmcWithKV(w) | Witness::BoundCheckSmcWithKVU32(w) => {
+                        if matches!(witness, Witness::BoundCheckSmcWithKVU32(_)) {
+                            enforce_and_get_u32(&w)?;
+                        }
                         let blinding = blindings.remove(&(s_idx, 0));
                         let params_comm_key =
                             s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
@@ -473,10 +943,67 @@ where
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::SchnorrLinearRelation(s) => match witness {
+                    Witness::SchnorrLinearRelation(w) => {
+                        let mut blindings_map = BTreeMap::new();
+                        for i in 0..w.len() {
+                            match blindings.remove(&(s_idx, i)) {
+                                Some(b) => blindings_map.insert(i, b),
+                                None => None,
+                            };
+                        }
+                        let mut sp = SchnorrLinearRelationProtocol::new(s_idx, s);
+                        sp.init(rng, blindings_map, w)?;
+                        sub_protocols.push(SubProtocol::SchnorrLinearRelation(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::MerkleMembershipProver(s) => match witness {
+                    Witness::MerkleMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let proving_key = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let comm_key = r1cs_comm_keys.get(s_idx).unwrap();
+
+                        let mut sp =
+                            MerkleMembershipProtocol::new_for_prover(s_idx, s.root, proving_key);
+                        sp.init(
+                            rng,
+                            comm_key,
+                            &s.hash_params,
+                            s.depth as usize,
+                            &w,
+                            blinding,
+                        )?;
+
+                        commitment_randomness.insert(
+                            s_idx,
+                            *sp.sp
+                                .as_ref()
+                                .unwrap()
+                                .witnesses
+                                .as_ref()
+                                .unwrap()
+                                .last()
+                                .unwrap(),
+                        );
+
+                        sub_protocols.push(SubProtocol::MerkleMembership(sp));
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
+            if let Some(f) = progress.as_mut() {
+                f(ProofProgress {
+                    statement_index: s_idx,
+                    total: proof_spec.statements.len(),
+                    phase: ProofPhase::Commit,
+                });
+            }
         }
 
+        SubProtocol::check_ids(&sub_protocols)?;
+
         // If all blindings are not consumed, it means that there was some witness equality which was
         // incorrect like either statement index was wrong or witness index for certain statement was wrong.
         if !blindings.is_empty() {
@@ -485,27 +1012,29 @@ where
             ));
         }
 
-        // Get nonce's and context's challenge contribution
-        let mut challenge_bytes = vec![];
-        if let Some(n) = nonce.as_ref() {
-            challenge_bytes.extend_from_slice(n)
-        }
-        if let Some(ctx) = &proof_spec.context {
-            challenge_bytes.extend_from_slice(ctx);
-        }
+        // Get nonce's, context's and the proof spec's challenge contribution
+        let mut challenge_hasher =
+            ProofContext::<D>::new_for_proof_spec(proof_spec, nonce.as_deref())?;
 
         // Get each sub-protocol's challenge contribution
         for p in sub_protocols.iter() {
-            p.challenge_contribution(&mut challenge_bytes)?;
+            p.challenge_contribution(&mut challenge_hasher)?;
         }
 
         // Generate the challenge
-        let challenge = Self::generate_challenge_from_bytes::<D>(&challenge_bytes);
+        let challenge = challenge_hasher.finalize::<E::ScalarField>();
 
         // Get each sub-protocol's proof
         let mut statement_proofs = Vec::with_capacity(sub_protocols.len());
         for mut p in sub_protocols {
             statement_proofs.push(p.gen_proof_contribution(&challenge)?);
+            if let Some(f) = progress.as_mut() {
+                f(ProofProgress {
+                    statement_index: p.id(),
+                    total: proof_spec.statements.len(),
+                    phase: ProofPhase::Response,
+                });
+            }
         }
 
         // TODO: Revisit - aggregating after challenge generation, is this correct?
@@ -521,8 +1050,8 @@ where
         if aggregate_snarks {
             // The validity of `ProofSpec` ensures that statements are not being repeated
 
-            let srs = match proof_spec.snark_aggregation_srs {
-                Some(SnarkpackSRS::ProverSrs(srs)) => srs,
+            let srs = match &proof_spec.snark_aggregation_srs {
+                Some(SnarkpackSRS::ProverSrs(srs)) => srs.clone(),
                 _ => return Err(ProofSystemError::SnarckpackSrsNotProvided),
             };
             let prepared_srs = PreparedProverSRS::from(srs);
@@ -531,11 +1060,10 @@ where
             let mut aggr_transcript = new_merlin_transcript(b"aggregation");
             aggr_transcript.append(b"challenge", &challenge);
 
-            if proof_spec.aggregate_groth16.is_some() {
-                let to_aggr = proof_spec.aggregate_groth16.unwrap();
+            if let Some(to_aggr) = &proof_spec.aggregate_groth16 {
                 let mut proofs = vec![];
                 for a in to_aggr {
-                    for i in &a {
+                    for i in a {
                         let p = match statement_proofs.get(*i).unwrap() {
                             StatementProof::Saver(s) => &s.snark_proof,
                             _ => return Err(ProofSystemError::NotASaverStatementProof),
@@ -550,16 +1078,15 @@ where
                     .map_err(|e| ProofSystemError::LegoGroth16Error(e.into()))?;
                     aggregated_groth16.push(AggregatedGroth16 {
                         proof: ag_proof,
-                        statements: a,
+                        statements: a.clone(),
                     });
                 }
             }
 
-            if proof_spec.aggregate_legogroth16.is_some() {
-                let to_aggr = proof_spec.aggregate_legogroth16.unwrap();
+            if let Some(to_aggr) = &proof_spec.aggregate_legogroth16 {
                 let mut proofs = vec![];
                 for a in to_aggr {
-                    for i in &a {
+                    for i in a {
                         let p = match statement_proofs.get(*i).unwrap() {
                             StatementProof::BoundCheckLegoGroth16(s) => &s.snark_proof,
                             StatementProof::R1CSLegoGroth16(s) => &s.snark_proof,
@@ -576,7 +1103,7 @@ where
                         .map_err(|e| ProofSystemError::LegoGroth16Error(e.into()))?;
                     aggregated_legogroth16.push(AggregatedGroth16 {
                         proof: ag_proof,
-                        statements: a,
+                        statements: a.clone(),
                     });
                 }
             }
@@ -598,6 +1125,7 @@ where
                 },
             },
             commitment_randomness,
+            audit,
         ))
     }
 
@@ -615,12 +1143,24 @@ where
         &self.nonce
     }
 
+    /// Number of bytes in the (compressed) serialization of this proof. Can be used to allocate the
+    /// buffer before serializing the proof.
+    pub fn compressed_len(&self) -> usize {
+        CanonicalSerialize::compressed_size(self)
+    }
+
     /// Hash bytes to a field element. This is vulnerable to timing attack and is only used input
     /// is public anyway like when generating setup parameters or challenge
     pub fn generate_challenge_from_bytes<D: Digest>(bytes: &[u8]) -> E::ScalarField {
         field_elem_from_try_and_incr::<E::ScalarField, D>(bytes)
     }
 
+    /// Same as `generate_challenge_from_bytes` for the bytes that `hasher` has been updated with, like by writing
+    /// challenge contributions to a `DigestWriter`
+    pub fn generate_challenge_from_digest<D: Digest + Clone>(hasher: D) -> E::ScalarField {
+        field_elem_from_try_and_incr_given_digest::<E::ScalarField, D>(hasher)
+    }
+
     pub fn get_saver_ciphertext_and_proof(
         &self,
         index: usize,
@@ -645,6 +1185,73 @@ where
         }
     }
 
+    impl_statement_proof_accessor!(as_bbs_plus, PoKBBSSignatureG1, PoKOfSignatureG1Proof<E>);
+    impl_statement_proof_accessor!(as_bbs_plus_full_reveal, BBSPlusFullReveal, SignatureG1<E>);
+    impl_statement_proof_accessor!(as_bbs, PoKBBSSignature23G1, PoKOfSignature23G1Proof<E>);
+    impl_statement_proof_accessor!(
+        as_accumulator_membership,
+        AccumulatorMembership,
+        MembershipProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_accumulator_non_membership,
+        AccumulatorNonMembership,
+        NonMembershipProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_pedersen_commitment,
+        PedersenCommitment,
+        PedersenCommitmentProof<G>
+    );
+    impl_statement_proof_accessor!(as_bound_check_bpp, BoundCheckBpp, BoundCheckBppProof<G>);
+    impl_statement_proof_accessor!(as_bound_check_smc, BoundCheckSmc, BoundCheckSmcProof<E>);
+    impl_statement_proof_accessor!(
+        as_bound_check_smc_kv,
+        BoundCheckSmcWithKV,
+        BoundCheckSmcWithKVProof<E>
+    );
+    impl_statement_proof_accessor!(as_inequality, Inequality, InequalityProof<G>);
+    impl_statement_proof_accessor!(
+        as_bbs_plus_hidden_pk,
+        PoKBBSSignatureG1HiddenPK,
+        PoKBBSSignatureG1HiddenPKProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_linear_relation,
+        SchnorrLinearRelation,
+        SchnorrLinearRelationProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_coalesced_pedersen_commitment,
+        CoalescedPedersenCommitment,
+        CoalescedPedersenCommitmentProof<G>
+    );
+    impl_statement_proof_accessor!(
+        as_merkle_membership,
+        MerkleMembership,
+        MerkleMembershipProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_batch_accumulator_non_membership,
+        BatchAccumulatorNonMembership,
+        BatchAccumulatorNonMembershipProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_batch_accumulator_membership,
+        BatchAccumulatorMembership,
+        BatchAccumulatorMembershipProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_committed_accumulator_membership,
+        CommittedAccumulatorMembership,
+        CommittedAccumulatorMembershipProof<E>
+    );
+    impl_statement_proof_accessor!(
+        as_bbs_plus_disjunct,
+        PoKBBSSignatureG1Disjunct,
+        PoKBBSSignatureG1HiddenPKProof<E>
+    );
+
     pub fn for_aggregate(&self) -> Self {
         let mut statement_proofs = vec![];
         for sp in self.statement_proofs() {
@@ -6,16 +6,17 @@
 //! serialization and de-serialization can be avoided.
 
 use crate::{
-    prelude::bound_check_smc::SmcParamsAndCommitmentKey,
+    error::ProofSystemError, prelude::bound_check_smc::SmcParamsAndCommitmentKey,
     statement::bound_check_smc_with_kv::SmcParamsAndCommitmentKeyAndSecretKey,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_std::vec::Vec;
+use ark_std::{collections::BTreeMap, vec::Vec};
 use bbs_plus::prelude::{
     PublicKeyG2 as BBSPublicKeyG2, SignatureParams23G1 as BBSSignatureParams23G1,
     SignatureParamsG1 as BBSSignatureParamsG1,
 };
 use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
+use digest::Digest;
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
 use legogroth16::{
     circom::R1CS,
@@ -66,6 +67,69 @@ pub enum SetupParams<E: Pairing, G: AffineRepr> {
     CommitmentKey(#[serde_as(as = "ArkObjectBytes")] CommitmentKey<G>),
 }
 
+impl<E: Pairing, G: AffineRepr> SetupParams<E, G> {
+    /// The setup param the verifier needs in place of this one. SNARK proving keys are replaced by their
+    /// verifying keys and everything else is kept as it is.
+    pub fn for_verifier(&self) -> Self {
+        match self {
+            Self::SaverProvingKey(pk) => Self::SaverVerifyingKey(pk.pk.vk.clone()),
+            Self::LegoSnarkProvingKey(pk) => Self::LegoSnarkVerifyingKey(pk.vk.clone()),
+            _ => self.clone(),
+        }
+    }
+
+    /// Hash of the compressed serialization of this setup param. Used to refer to setup params the verifier
+    /// already has, see `Proof::serialize_with_external_setup`.
+    pub fn content_hash<D: Digest>(&self) -> Result<Vec<u8>, ProofSystemError> {
+        let mut bytes = Vec::new();
+        ark_serialize::CanonicalSerialize::serialize_compressed(self, &mut bytes)?;
+        Ok(D::digest(&bytes).to_vec())
+    }
+}
+
+/// Setup params known to the verifier, keyed by their content hash (see `SetupParams::content_hash`) which is
+/// computed once when the setup param is inserted. Used by `Proof::deserialize_with_external_setup` to fill in
+/// the setup params left out of the serialized proof spec.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalSetupCache<E: Pairing, G: AffineRepr>(BTreeMap<Vec<u8>, SetupParams<E, G>>);
+
+impl<E: Pairing, G: AffineRepr> ExternalSetupCache<E, G> {
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Build the cache by hashing each of the `setup_params` with `D`
+    pub fn from_setup_params<D: Digest>(
+        setup_params: impl IntoIterator<Item = SetupParams<E, G>>,
+    ) -> Result<Self, ProofSystemError> {
+        let mut cache = Self::new();
+        for p in setup_params {
+            cache.insert::<D>(p)?;
+        }
+        Ok(cache)
+    }
+
+    /// Add a setup param and return its content hash. `D` must be the digest that the spec is serialized with.
+    pub fn insert<D: Digest>(
+        &mut self,
+        setup_param: SetupParams<E, G>,
+    ) -> Result<Vec<u8>, ProofSystemError> {
+        let hash = setup_param.content_hash::<D>()?;
+        self.0.insert(hash.clone(), setup_param);
+        Ok(hash)
+    }
+
+    pub fn get(&self, hash: &[u8]) -> Option<&SetupParams<E, G>> {
+        self.0.get(hash)
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> Default for ExternalSetupCache<E, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 macro_rules! delegate {
     ($([$idx: ident])?$self: ident $($tt: tt)+) => {{
         $crate::delegate_indexed! {
@@ -1,20 +1,116 @@
 use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    vec::Vec,
+};
+use digest::Digest;
 use dock_crypto_utils::serde_utils::*;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use vb_accumulator::prelude::{
-    MembershipProvingKey, NonMembershipProvingKey, PublicKey, SetupParams as AccumParams,
+    MembershipProvingKey, NonMembershipProvingKey, PreparedPublicKey, PublicKey,
+    SetupParams as AccumParams,
 };
 
+/// Accumulator public key along with its prepared (for pairing) form. A verifier checking many proofs
+/// against the same public key can create this once and avoid repeating the G2 Miller-loop precomputation
+/// for every proof. Only the public key is serialized and the prepared form is created again from it during
+/// deserialization so the two can't be inconsistent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "", from = "PublicKey<E>", into = "PublicKey<E>")]
+pub struct PreparedAccumulatorPublicKey<E: Pairing> {
+    public_key: PublicKey<E>,
+    prepared: E::G2Prepared,
+}
+
+impl<E: Pairing> PreparedAccumulatorPublicKey<E> {
+    pub fn public_key(&self) -> &PublicKey<E> {
+        &self.public_key
+    }
+
+    pub fn to_prepared_public_key(&self) -> PreparedPublicKey<E> {
+        PreparedPublicKey(self.prepared.clone())
+    }
+
+    /// Deserialize a key serialized with format version 3 in which the prepared form was serialized after the
+    /// public key. The serialized prepared form is ignored.
+    fn deserialize_v3<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let public_key = PublicKey::<E>::deserialize_with_mode(&mut reader, compress, validate)?;
+        E::G2Prepared::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self::from(public_key))
+    }
+}
+
+impl<E: Pairing> From<PublicKey<E>> for PreparedAccumulatorPublicKey<E> {
+    fn from(public_key: PublicKey<E>) -> Self {
+        let prepared = E::G2Prepared::from(public_key.0);
+        Self {
+            public_key,
+            prepared,
+        }
+    }
+}
+
+impl<E: Pairing> From<PreparedAccumulatorPublicKey<E>> for PublicKey<E> {
+    fn from(prepared_key: PreparedAccumulatorPublicKey<E>) -> Self {
+        prepared_key.public_key
+    }
+}
+
+impl<E: Pairing> CanonicalSerialize for PreparedAccumulatorPublicKey<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.public_key.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.public_key.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> Valid for PreparedAccumulatorPublicKey<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.public_key.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for PreparedAccumulatorPublicKey<E> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self::from(PublicKey::<E>::deserialize_with_mode(
+            reader, compress, validate,
+        )?))
+    }
+}
+
+/// The prepared key is derived from the public key so comparing public keys is sufficient.
+impl<E: Pairing> PartialEq for PreparedAccumulatorPublicKey<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.public_key == other.public_key
+    }
+}
+
+impl<E: Pairing> Eq for PreparedAccumulatorPublicKey<E> {}
+
 /// Public values like setup params, public key, proving key and accumulator for proving membership
 /// in positive and universal accumulator.
 #[serde_as]
-#[derive(
-    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
-)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct AccumulatorMembership<E: Pairing> {
     #[serde_as(as = "ArkObjectBytes")]
@@ -25,6 +121,15 @@ pub struct AccumulatorMembership<E: Pairing> {
     pub params_ref: Option<usize>,
     pub public_key_ref: Option<usize>,
     pub proving_key_ref: Option<usize>,
+    /// If the statement was created by passing a prepared public key, then it will not be None
+    pub prepared_public_key: Option<PreparedAccumulatorPublicKey<E>>,
+    /// If true, the default proving key for the params is used and neither `proving_key` nor `proving_key_ref` is
+    /// set. See `MembershipProvingKey::default_for`.
+    pub default_proving_key: bool,
+    /// The default proving key if it was created along with the statement. This is not serialized and is created
+    /// again by the prover and verifier when missing.
+    #[serde(skip)]
+    pub cached_default_proving_key: Option<MembershipProvingKey<E::G1Affine>>,
 }
 
 /// Public values like setup params, public key, proving key and accumulator for proving non-membership
@@ -61,6 +166,54 @@ impl<E: Pairing> AccumulatorMembership<E> {
             params_ref: None,
             public_key_ref: None,
             proving_key_ref: None,
+            prepared_public_key: None,
+            default_proving_key: false,
+            cached_default_proving_key: None,
+        })
+    }
+
+    /// Same as `Self::new_statement_from_params` but uses the default proving key for the params, created by hashing
+    /// them with `D`, rather than embedding one in the statement. The prover and verifier must use the same `D`. The
+    /// created key is kept with the statement but is not serialized.
+    pub fn new_statement_with_default_proving_key<G: AffineRepr, D: Digest>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        accumulator_value: E::G1Affine,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let default_proving_key = MembershipProvingKey::default_for::<D, E>(&params)?;
+        Ok(Statement::AccumulatorMembership(Self {
+            accumulator_value,
+            params: Some(params),
+            public_key: Some(public_key),
+            proving_key: None,
+            params_ref: None,
+            public_key_ref: None,
+            proving_key_ref: None,
+            prepared_public_key: None,
+            default_proving_key: true,
+            cached_default_proving_key: Some(default_proving_key),
+        }))
+    }
+
+    /// Same as `Self::new_statement_from_params` but takes a prepared public key which makes the verification
+    /// cheaper when the same public key is used to verify several proofs.
+    pub fn from_prepared_params<G: AffineRepr>(
+        accumulator_value: E::G1Affine,
+        prepared_key: PreparedAccumulatorPublicKey<E>,
+        params: AccumParams<E>,
+        proving_key: MembershipProvingKey<E::G1Affine>,
+    ) -> Statement<E, G> {
+        Statement::AccumulatorMembership(Self {
+            accumulator_value,
+            params: Some(params),
+            public_key: None,
+            proving_key: Some(proving_key),
+            params_ref: None,
+            public_key_ref: None,
+            proving_key_ref: None,
+            prepared_public_key: Some(prepared_key),
+            default_proving_key: false,
+            cached_default_proving_key: None,
         })
     }
 
@@ -79,6 +232,9 @@ impl<E: Pairing> AccumulatorMembership<E> {
             params_ref: Some(params_ref),
             public_key_ref: Some(public_key_ref),
             proving_key_ref: Some(proving_key_ref),
+            prepared_public_key: None,
+            default_proving_key: false,
+            cached_default_proving_key: None,
         })
     }
 
@@ -104,6 +260,9 @@ impl<E: Pairing> AccumulatorMembership<E> {
         setup_params: &'a [SetupParams<E, G>],
         st_idx: usize,
     ) -> Result<&'a PublicKey<E>, ProofSystemError> {
+        if let Some(pk) = &self.prepared_public_key {
+            return Ok(&pk.public_key);
+        }
         extract_param!(
             setup_params,
             &self.public_key,
@@ -114,6 +273,13 @@ impl<E: Pairing> AccumulatorMembership<E> {
         )
     }
 
+    /// Get the prepared public key if the statement was created with one
+    pub fn get_prepared_public_key(&self) -> Option<PreparedPublicKey<E>> {
+        self.prepared_public_key
+            .as_ref()
+            .map(|pk| pk.to_prepared_public_key())
+    }
+
     /// Get membership proving key for the statement index `s_idx` either from `self` or from given `setup_params`
     pub fn get_proving_key<'a, G: AffineRepr>(
         &'a self,
@@ -129,6 +295,158 @@ impl<E: Pairing> AccumulatorMembership<E> {
             st_idx
         )
     }
+
+    /// Same as `Self::get_proving_key` but if the statement uses the default proving key, get the one created with
+    /// the statement or else the one derived for the `ProofSpec`
+    pub fn get_proving_key_or_default<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        default_proving_keys: &'a BTreeMap<usize, MembershipProvingKey<E::G1Affine>>,
+        st_idx: usize,
+    ) -> Result<&'a MembershipProvingKey<E::G1Affine>, ProofSystemError> {
+        if self.default_proving_key {
+            return self
+                .cached_default_proving_key
+                .as_ref()
+                .or_else(|| default_proving_keys.get(&st_idx))
+                .ok_or(ProofSystemError::NeitherParamsNorRefGiven(st_idx));
+        }
+        self.get_proving_key(setup_params, st_idx)
+    }
+
+    /// Deserialize a statement serialized with format version 3 which did not have the `default_proving_key` flag
+    /// and serialized the prepared form of the prepared public key
+    pub(crate) fn deserialize_v3<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Self::deserialize_with_version(reader, compress, validate, true)
+    }
+
+    fn deserialize_with_version<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+        v3: bool,
+    ) -> Result<Self, SerializationError> {
+        let accumulator_value =
+            CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let params = CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let public_key =
+            CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let proving_key =
+            CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let params_ref =
+            CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let public_key_ref =
+            CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let proving_key_ref =
+            CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let (prepared_public_key, default_proving_key) = if v3 {
+            let has_prepared_public_key =
+                bool::deserialize_with_mode(&mut reader, compress, validate)?;
+            let prepared_public_key = if has_prepared_public_key {
+                Some(PreparedAccumulatorPublicKey::deserialize_v3(
+                    &mut reader,
+                    compress,
+                    validate,
+                )?)
+            } else {
+                None
+            };
+            (prepared_public_key, false)
+        } else {
+            (
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+            )
+        };
+        Ok(Self {
+            accumulator_value,
+            params,
+            public_key,
+            proving_key,
+            params_ref,
+            public_key_ref,
+            proving_key_ref,
+            prepared_public_key,
+            default_proving_key,
+            cached_default_proving_key: None,
+        })
+    }
+}
+
+/// The cached default proving key is derived from the params so it's not compared
+impl<E: Pairing> PartialEq for AccumulatorMembership<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.accumulator_value == other.accumulator_value
+            && self.params == other.params
+            && self.public_key == other.public_key
+            && self.proving_key == other.proving_key
+            && self.params_ref == other.params_ref
+            && self.public_key_ref == other.public_key_ref
+            && self.proving_key_ref == other.proving_key_ref
+            && self.prepared_public_key == other.prepared_public_key
+            && self.default_proving_key == other.default_proving_key
+    }
+}
+
+impl<E: Pairing> Eq for AccumulatorMembership<E> {}
+
+/// The cached default proving key is not serialized
+impl<E: Pairing> CanonicalSerialize for AccumulatorMembership<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.accumulator_value
+            .serialize_with_mode(&mut writer, compress)?;
+        self.params.serialize_with_mode(&mut writer, compress)?;
+        self.public_key.serialize_with_mode(&mut writer, compress)?;
+        self.proving_key.serialize_with_mode(&mut writer, compress)?;
+        self.params_ref.serialize_with_mode(&mut writer, compress)?;
+        self.public_key_ref.serialize_with_mode(&mut writer, compress)?;
+        self.proving_key_ref.serialize_with_mode(&mut writer, compress)?;
+        self.prepared_public_key
+            .serialize_with_mode(&mut writer, compress)?;
+        self.default_proving_key
+            .serialize_with_mode(&mut writer, compress)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.accumulator_value.serialized_size(compress)
+            + self.params.serialized_size(compress)
+            + self.public_key.serialized_size(compress)
+            + self.proving_key.serialized_size(compress)
+            + self.params_ref.serialized_size(compress)
+            + self.public_key_ref.serialized_size(compress)
+            + self.proving_key_ref.serialized_size(compress)
+            + self.prepared_public_key.serialized_size(compress)
+            + self.default_proving_key.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> Valid for AccumulatorMembership<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.accumulator_value.check()?;
+        self.params.check()?;
+        self.public_key.check()?;
+        self.proving_key.check()?;
+        self.prepared_public_key.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for AccumulatorMembership<E> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Self::deserialize_with_version(reader, compress, validate, false)
+    }
 }
 
 impl<E: Pairing> AccumulatorNonMembership<E> {
@@ -211,3 +529,325 @@ impl<E: Pairing> AccumulatorNonMembership<E> {
         )
     }
 }
+
+/// Public values like setup params, public key, proving key and accumulator for proving membership of several
+/// elements in the same accumulator. The element at index `i` of the witness is the witness at index `i` of the
+/// statement so each element can be proven equal to other witnesses.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BatchAccumulatorMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    /// Number of elements proven to be members
+    pub num_elements: usize,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub proving_key: Option<MembershipProvingKey<E::G1Affine>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+    pub proving_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> BatchAccumulatorMembership<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        proving_key: MembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::BatchAccumulatorMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: Some(params),
+            public_key: Some(public_key),
+            proving_key: Some(proving_key),
+            params_ref: None,
+            public_key_ref: None,
+            proving_key_ref: None,
+        })
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+        proving_key_ref: usize,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::BatchAccumulatorMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: None,
+            public_key: None,
+            proving_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+            proving_key_ref: Some(proving_key_ref),
+        })
+    }
+
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_public_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PublicKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.public_key,
+            self.public_key_ref,
+            VbAccumulatorPublicKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a MembershipProvingKey<E::G1Affine>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.proving_key,
+            self.proving_key_ref,
+            VbAccumulatorMemProvingKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
+
+/// Public values like setup params, public key, proving key and accumulator for proving non-membership of several
+/// elements in the same universal accumulator. The element at index `i` of the witness is the witness at index `i`
+/// of the statement so each element can be proven equal to other witnesses.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BatchAccumulatorNonMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub accumulator_value: E::G1Affine,
+    /// Number of elements proven to be non-members
+    pub num_elements: usize,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub proving_key: Option<NonMembershipProvingKey<E::G1Affine>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+    pub proving_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> BatchAccumulatorNonMembership<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        proving_key: NonMembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::BatchAccumulatorNonMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: Some(params),
+            public_key: Some(public_key),
+            proving_key: Some(proving_key),
+            params_ref: None,
+            public_key_ref: None,
+            proving_key_ref: None,
+        })
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+        proving_key_ref: usize,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Statement<E, G> {
+        Statement::BatchAccumulatorNonMembership(Self {
+            accumulator_value,
+            num_elements,
+            params: None,
+            public_key: None,
+            proving_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+            proving_key_ref: Some(proving_key_ref),
+        })
+    }
+
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_public_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PublicKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.public_key,
+            self.public_key_ref,
+            VbAccumulatorPublicKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a NonMembershipProvingKey<E::G1Affine>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.proving_key,
+            self.proving_key_ref,
+            VbAccumulatorNonMemProvingKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
+
+/// Public values like setup params, public key and proving key for proving membership in an accumulator whose value
+/// isn't public but committed in the Pedersen commitment `commitment = accumulator_value + randomness * commitment_key`.
+/// The accumulator value and the randomness are never revealed to the verifier.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CommittedAccumulatorMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub commitment: E::G1Affine,
+    /// Base for the randomness in the commitment. Its discrete log with respect to the accumulator params should not
+    /// be known to anyone.
+    #[serde_as(as = "ArkObjectBytes")]
+    pub commitment_key: E::G1Affine,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub proving_key: Option<MembershipProvingKey<E::G1Affine>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+    pub proving_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> CommittedAccumulatorMembership<E> {
+    /// Create a statement by passing the accumulator params, public key and proving key directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        proving_key: MembershipProvingKey<E::G1Affine>,
+        commitment: E::G1Affine,
+        commitment_key: E::G1Affine,
+    ) -> Statement<E, G> {
+        Statement::CommittedAccumulatorMembership(Self {
+            commitment,
+            commitment_key,
+            params: Some(params),
+            public_key: Some(public_key),
+            proving_key: Some(proving_key),
+            params_ref: None,
+            public_key_ref: None,
+            proving_key_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the indices of accumulator params, public key and proving key in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        params_ref: usize,
+        public_key_ref: usize,
+        proving_key_ref: usize,
+        commitment: E::G1Affine,
+        commitment_key: E::G1Affine,
+    ) -> Statement<E, G> {
+        Statement::CommittedAccumulatorMembership(Self {
+            commitment,
+            commitment_key,
+            params: None,
+            public_key: None,
+            proving_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+            proving_key_ref: Some(proving_key_ref),
+        })
+    }
+
+    pub fn get_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a AccumParams<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.params,
+            self.params_ref,
+            VbAccumulatorParams,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_public_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a PublicKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.public_key,
+            self.public_key_ref,
+            VbAccumulatorPublicKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    pub fn get_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a MembershipProvingKey<E::G1Affine>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.proving_key,
+            self.proving_key_ref,
+            VbAccumulatorMemProvingKey,
+            IncompatibleAccumulatorSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
@@ -1,11 +1,15 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeMap, vec::Vec};
+use ark_std::{collections::BTreeMap, marker::PhantomData, vec::Vec};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Same};
 
 use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
-use bbs_plus::prelude::{PublicKeyG2, SignatureParamsG1};
+use bbs_plus::{
+    prelude::{PublicKeyG2, SignatureParamsG1},
+    setup::MultiMessageSignatureParams,
+};
+use digest::DynDigest;
 use dock_crypto_utils::serde_utils::*;
 
 /// Public values like setup params, public key and revealed messages for proving knowledge of BBS+ signature.
@@ -28,6 +32,50 @@ pub struct PoKBBSSignatureG1<E: Pairing> {
     pub public_key_ref: Option<usize>,
 }
 
+/// Revealed messages given when creating a statement for proving knowledge of BBS+ or BBS signature. These
+/// are either field elements, as a `BTreeMap` or `PreHashedMessages`, or bytes as `RawMessages` which
+/// are hashed to field elements.
+pub trait RevealedMessages<E: Pairing> {
+    fn into_field_elements(self) -> BTreeMap<usize, E::ScalarField>;
+}
+
+/// Revealed messages which are already field elements, like when the caller has hashed them itself
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreHashedMessages<E: Pairing>(pub BTreeMap<usize, E::ScalarField>);
+
+/// Revealed messages as bytes, like strings or encoded dates. These are hashed to field elements using
+/// `SignatureParamsG1::hash_messages` with the hash function `D` so the signer must have hashed them the same way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawMessages<D, M: AsRef<[u8]>>(pub BTreeMap<usize, M>, PhantomData<D>);
+
+impl<D, M: AsRef<[u8]>> RawMessages<D, M> {
+    pub fn new(messages: BTreeMap<usize, M>) -> Self {
+        Self(messages, PhantomData)
+    }
+}
+
+impl<E: Pairing> RevealedMessages<E> for BTreeMap<usize, E::ScalarField> {
+    fn into_field_elements(self) -> BTreeMap<usize, E::ScalarField> {
+        self
+    }
+}
+
+impl<E: Pairing> RevealedMessages<E> for PreHashedMessages<E> {
+    fn into_field_elements(self) -> BTreeMap<usize, E::ScalarField> {
+        self.0
+    }
+}
+
+impl<E: Pairing, D: Default + DynDigest + Clone, M: AsRef<[u8]>> RevealedMessages<E>
+    for RawMessages<D, M>
+{
+    fn into_field_elements(self) -> BTreeMap<usize, E::ScalarField> {
+        let (indices, messages): (Vec<_>, Vec<_>) = self.0.into_iter().unzip();
+        let hashed = SignatureParamsG1::<E>::hash_messages::<D>(&messages);
+        indices.into_iter().zip(hashed).collect()
+    }
+}
+
 #[macro_export]
 macro_rules! impl_bbs_statement {
     ($params: ident, $stmt: ident, $setup_param_name: ident) => {
@@ -35,10 +83,13 @@ macro_rules! impl_bbs_statement {
         pub fn new_statement_from_params<G: AffineRepr>(
             signature_params: $params<E>,
             public_key: PublicKeyG2<E>,
-            revealed_messages: BTreeMap<usize, E::ScalarField>,
+            revealed_messages: impl $crate::statement::bbs_plus::RevealedMessages<E>,
         ) -> Statement<E, G> {
             Statement::$stmt(Self {
-                revealed_messages,
+                revealed_messages:
+                    $crate::statement::bbs_plus::RevealedMessages::into_field_elements(
+                        revealed_messages,
+                    ),
                 signature_params: Some(signature_params),
                 public_key: Some(public_key),
                 signature_params_ref: None,
@@ -50,10 +101,13 @@ macro_rules! impl_bbs_statement {
         pub fn new_statement_from_params_ref<G: AffineRepr>(
             signature_params_ref: usize,
             public_key_ref: usize,
-            revealed_messages: BTreeMap<usize, E::ScalarField>,
+            revealed_messages: impl $crate::statement::bbs_plus::RevealedMessages<E>,
         ) -> Statement<E, G> {
             Statement::$stmt(Self {
-                revealed_messages,
+                revealed_messages:
+                    $crate::statement::bbs_plus::RevealedMessages::into_field_elements(
+                        revealed_messages,
+                    ),
                 signature_params: None,
                 public_key: None,
                 signature_params_ref: Some(signature_params_ref),
@@ -97,4 +151,38 @@ macro_rules! impl_bbs_statement {
 
 impl<E: Pairing> PoKBBSSignatureG1<E> {
     impl_bbs_statement!(SignatureParamsG1, PoKBBSSignatureG1, BBSPlusSignatureParams);
+
+    /// Reveal the message at index `idx`, like when the attributes to reveal are selected one at a time. The
+    /// statement can't be changed once a sub-protocol has been created for it since the sub-protocol borrows it.
+    /// Errors if the message is already revealed or, when the signature params are part of the statement, if the
+    /// index is out of bounds.
+    pub fn add_revealed_message(
+        &mut self,
+        idx: usize,
+        msg: E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if let Some(params) = &self.signature_params {
+            let count = params.supported_message_count();
+            if idx >= count {
+                return Err(ProofSystemError::RevealedMessageIndexOutOfBounds(
+                    idx, count,
+                ));
+            }
+        }
+        if self.revealed_messages.contains_key(&idx) {
+            return Err(ProofSystemError::MessageAlreadyRevealed(idx));
+        }
+        self.revealed_messages.insert(idx, msg);
+        Ok(())
+    }
+
+    /// Stop revealing the message at index `idx` and return it. Errors if the message is not revealed.
+    pub fn remove_revealed_message(
+        &mut self,
+        idx: usize,
+    ) -> Result<E::ScalarField, ProofSystemError> {
+        self.revealed_messages
+            .remove(&idx)
+            .ok_or(ProofSystemError::MessageNotRevealed(idx))
+    }
 }
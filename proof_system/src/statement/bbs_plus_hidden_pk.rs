@@ -0,0 +1,84 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{collections::BTreeMap, vec::Vec};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Same};
+
+use crate::{
+    error::ProofSystemError,
+    setup_params::SetupParams,
+    statement::{bbs_plus::RevealedMessages, Statement},
+};
+use bbs_plus::prelude::{PublicKeyG2, SignatureParamsG1};
+use dock_crypto_utils::serde_utils::*;
+
+/// Public values for proving knowledge of a BBS+ signature created by one of the given public keys without
+/// revealing which one. The public keys are a registry of the valid issuers and the prover proves that the
+/// randomized public key in the proof was created from one of them.
+///
+/// The registry is the list of public keys itself and not an accumulator. Accumulators like those in `vb_accumulator`
+/// hold scalars, so a membership proof for a public key, which is a G2 element, isn't possible with them. Instead,
+/// the proof has an OR over all the public keys, so the proof size and the prover's and verifier's work are linear in
+/// the number of public keys.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKBBSSignatureG1HiddenPK<E: Pairing> {
+    /// Messages being revealed.
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub revealed_messages: BTreeMap<usize, E::ScalarField>,
+    /// If the statement was created by passing the signature params directly, then it will not be None
+    pub signature_params: Option<SignatureParamsG1<E>>,
+    /// Public keys of the issuers, one of which created the signature
+    pub public_keys: Vec<PublicKeyG2<E>>,
+    /// If the statement was created by passing the index of signature params in `SetupParams`, then it will not be None
+    pub signature_params_ref: Option<usize>,
+}
+
+impl<E: Pairing> PoKBBSSignatureG1HiddenPK<E> {
+    /// Create a statement by passing the signature parameters directly.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        signature_params: SignatureParamsG1<E>,
+        public_keys: Vec<PublicKeyG2<E>>,
+        revealed_messages: impl RevealedMessages<E>,
+    ) -> Statement<E, G> {
+        Statement::PoKBBSSignatureG1HiddenPK(Self {
+            revealed_messages: revealed_messages.into_field_elements(),
+            signature_params: Some(signature_params),
+            public_keys,
+            signature_params_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the index of signature parameters in `SetupParams`.
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        signature_params_ref: usize,
+        public_keys: Vec<PublicKeyG2<E>>,
+        revealed_messages: impl RevealedMessages<E>,
+    ) -> Statement<E, G> {
+        Statement::PoKBBSSignatureG1HiddenPK(Self {
+            revealed_messages: revealed_messages.into_field_elements(),
+            signature_params: None,
+            public_keys,
+            signature_params_ref: Some(signature_params_ref),
+        })
+    }
+
+    /// Get signature params for the statement index `s_idx` either from `self` or from given `setup_params`.
+    pub fn get_sig_params<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a SignatureParamsG1<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.signature_params,
+            self.signature_params_ref,
+            BBSPlusSignatureParams,
+            IncompatibleBBSPlusSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
@@ -10,7 +10,10 @@ use crate::{
     error::ProofSystemError,
     setup_params::SetupParams,
     statement::Statement,
-    sub_protocols::{bound_check_legogroth16::BoundCheckLegoGrothProtocol, validate_bounds},
+    sub_protocols::{
+        bound_check_legogroth16::{bound_check_public_inputs, BoundCheckLegoGrothProtocol},
+        validate_bounds,
+    },
 };
 use dock_crypto_utils::serde_utils::ArkObjectBytes;
 
@@ -87,6 +90,17 @@ impl<E: Pairing> BoundCheckLegoGroth16Prover<E> {
             st_idx
         )
     }
+
+    /// The statement the verifier uses to verify the proof created using this statement. If the proving key
+    /// is referenced from the setup params, the verifying key is referenced from the same index.
+    pub fn for_verifier(&self) -> BoundCheckLegoGroth16Verifier<E> {
+        BoundCheckLegoGroth16Verifier {
+            min: self.min,
+            max: self.max,
+            snark_verifying_key: self.snark_proving_key.as_ref().map(|pk| pk.vk.clone()),
+            snark_verifying_key_ref: self.snark_proving_key_ref,
+        }
+    }
 }
 
 impl<E: Pairing> BoundCheckLegoGroth16Verifier<E> {
@@ -134,13 +148,32 @@ impl<E: Pairing> BoundCheckLegoGroth16Verifier<E> {
             st_idx
         )
     }
+
+    /// Public inputs of the LegoGroth16 proof as used in its verification, i.e. `[min, max]`. The message isn't
+    /// a public input but is committed in the proof so these can be forwarded to another verifier along with the
+    /// proof.
+    pub fn groth16_public_inputs(&self) -> Vec<E::ScalarField> {
+        bound_check_public_inputs(self.min, self.max)
+    }
+
+    /// Check that the public inputs received along with a proof, like ones forwarded by another verifier, are
+    /// for the bounds of this statement. This doesn't verify the proof.
+    pub fn verify_public_inputs_only(
+        &self,
+        inputs: &[E::ScalarField],
+    ) -> Result<(), ProofSystemError> {
+        if inputs != self.groth16_public_inputs().as_slice() {
+            return Err(ProofSystemError::InvalidBoundCheckPublicInputs);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check;
-    use ark_bls12_381::Bls12_381;
+    use ark_bls12_381::{Bls12_381, Fr};
     use ark_std::rand::{rngs::StdRng, SeedableRng};
 
     #[test]
@@ -172,4 +205,32 @@ mod tests {
         >(5, 6, snark_pk.vk)
         .is_ok());
     }
+
+    #[test]
+    fn groth16_public_inputs_of_verifier_statement() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+        let statement = match BoundCheckLegoGroth16Verifier::new_statement_from_params::<
+            <Bls12_381 as Pairing>::G1Affine,
+        >(5, 10, snark_pk.vk)
+        .unwrap()
+        {
+            Statement::BoundCheckLegoGroth16Verifier(s) => s,
+            _ => unreachable!(),
+        };
+        let inputs = statement.groth16_public_inputs();
+        assert_eq!(inputs, vec![Fr::from(5u64), Fr::from(10u64)]);
+        statement.verify_public_inputs_only(&inputs).unwrap();
+        for wrong in [
+            vec![Fr::from(10u64), Fr::from(5u64)],
+            vec![Fr::from(5u64), Fr::from(11u64)],
+            vec![Fr::from(5u64)],
+            vec![],
+        ] {
+            assert!(matches!(
+                statement.verify_public_inputs_only(&wrong),
+                Err(ProofSystemError::InvalidBoundCheckPublicInputs)
+            ));
+        }
+    }
 }
@@ -2,16 +2,25 @@ use crate::{
     error::ProofSystemError,
     setup_params::SetupParams,
     statement::{bound_check_smc::SmcParamsAndCommitmentKey, Statement},
-    sub_protocols::validate_bounds,
+    sub_protocols::{validate_bounds, validate_smc_base},
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{rand::RngCore, vec::Vec};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    io::{Read, Write},
+    rand::RngCore,
+    vec::Vec,
+};
 use digest::Digest;
-use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use dock_crypto_utils::{msm::WindowTable, serde_utils::ArkObjectBytes};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use smc_range_proof::prelude::{MemberCommitmentKey, SecretKey, SetMembershipCheckParams};
+use smc_range_proof::{
+    ccs_range_proof::util::find_l_greater_than,
+    prelude::{MemberCommitmentKey, SecretKey, SetMembershipCheckParams},
+};
 
 /// Used by the verifier as it knows the secret key. Should not be shared with the prover
 #[serde_as]
@@ -45,6 +54,53 @@ impl<E: Pairing> SmcParamsAndCommitmentKeyAndSecretKey<E> {
     pub fn get_comm_key(&self) -> &MemberCommitmentKey<E::G1Affine> {
         &self.params_and_comm_key.comm_key
     }
+
+    /// Precompute what the verifier needs for checking each proof so that it's done once when verifying many proofs
+    pub fn precompute(&self) -> PreparedSmcKey<E> {
+        PreparedSmcKey::from(self.clone())
+    }
+
+    /// Same as `Self::precompute` but for verifying proofs of bounds that fit in a `u32`. The window table is sized for
+    /// the fewer digits of such bounds so it's smaller unless the base gives both the same window size.
+    pub fn precompute_for_u32_range(&self) -> PreparedSmcKey<E> {
+        PreparedSmcKey::new(self.clone(), u32::MAX as u64)
+    }
+}
+
+/// Same as `SmcParamsAndCommitmentKeyAndSecretKey` but with the window table of the generator in the params which
+/// is otherwise created during verification of each proof. Used by the verifier as it contains the secret key.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PreparedSmcKey<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub params_and_comm_key_and_sk: SmcParamsAndCommitmentKeyAndSecretKey<E>,
+    pub g1_table: WindowTable<E::G1>,
+}
+
+impl<E: Pairing> PreparedSmcKey<E> {
+    /// Prepare the params for verifying proofs of bounds not larger than `max_bound`
+    pub fn new(params: SmcParamsAndCommitmentKeyAndSecretKey<E>, max_bound: u64) -> Self {
+        // Number of multiplications is an approximation, twice the number of digits of the largest supported upper
+        // bound, and only affects performance
+        let base = params.get_smc_params().get_supported_base_for_range_proof();
+        let g1_table = WindowTable::new(
+            2 * find_l_greater_than(max_bound, base) as usize,
+            params.get_smc_params().bb_sig_params.g1.into_group(),
+        );
+        Self {
+            params_and_comm_key_and_sk: params,
+            g1_table,
+        }
+    }
+}
+
+impl<E: Pairing> From<SmcParamsAndCommitmentKeyAndSecretKey<E>> for PreparedSmcKey<E> {
+    fn from(params: SmcParamsAndCommitmentKeyAndSecretKey<E>) -> Self {
+        Self::new(params, u64::MAX)
+    }
 }
 
 #[serde_as]
@@ -61,9 +117,7 @@ pub struct BoundCheckSmcWithKVProver<E: Pairing> {
 }
 
 #[serde_as]
-#[derive(
-    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
-)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct BoundCheckSmcWithKVVerifier<E: Pairing> {
     pub min: u64,
@@ -71,6 +125,68 @@ pub struct BoundCheckSmcWithKVVerifier<E: Pairing> {
     #[serde_as(as = "Option<ArkObjectBytes>")]
     pub params: Option<SmcParamsAndCommitmentKeyAndSecretKey<E>>,
     pub params_ref: Option<usize>,
+    /// If the statement was created by passing the prepared params, then it will not be None. This is not
+    /// serialized as it's derived from `params` and the deserialized statement verifies without it.
+    #[serde(skip)]
+    pub prepared_params: Option<PreparedSmcKey<E>>,
+}
+
+/// The prepared params are derived from `params` so they are not compared
+impl<E: Pairing> PartialEq for BoundCheckSmcWithKVVerifier<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.min == other.min
+            && self.max == other.max
+            && self.params == other.params
+            && self.params_ref == other.params_ref
+    }
+}
+
+/// The prepared params are not serialized
+impl<E: Pairing> CanonicalSerialize for BoundCheckSmcWithKVVerifier<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.min.serialize_with_mode(&mut writer, compress)?;
+        self.max.serialize_with_mode(&mut writer, compress)?;
+        self.params.serialize_with_mode(&mut writer, compress)?;
+        self.params_ref.serialize_with_mode(&mut writer, compress)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.min.serialized_size(compress)
+            + self.max.serialized_size(compress)
+            + self.params.serialized_size(compress)
+            + self.params_ref.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> Valid for BoundCheckSmcWithKVVerifier<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.params.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for BoundCheckSmcWithKVVerifier<E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            min: CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+            max: CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+            params: CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?,
+            params_ref: CanonicalDeserialize::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            prepared_params: None,
+        })
+    }
 }
 
 impl<E: Pairing> BoundCheckSmcWithKVProver<E> {
@@ -79,7 +195,7 @@ impl<E: Pairing> BoundCheckSmcWithKVProver<E> {
         max: u64,
         params: SmcParamsAndCommitmentKey<E>,
     ) -> Result<Statement<E, G>, ProofSystemError> {
-        validate_bounds(min, max)?;
+        validate_smc_base(min, max, params.params.get_supported_base_for_range_proof())?;
 
         Ok(Statement::BoundCheckSmcWithKVProver(Self {
             min,
@@ -103,6 +219,17 @@ impl<E: Pairing> BoundCheckSmcWithKVProver<E> {
         }))
     }
 
+    /// For bounds that fit in a `u32` like age or year. As the number of digits in the range proof depends on `max`,
+    /// the proof is smaller than for a `u64` range. The prover should use `Witness::BoundCheckSmcWithKVU32` so that
+    /// a message not fitting in a `u32` is rejected early.
+    pub fn new_u32_range<G: AffineRepr>(
+        min: u32,
+        max: u32,
+        params: SmcParamsAndCommitmentKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::new_statement_from_params(min as u64, max as u64, params)
+    }
+
     pub fn get_params_and_comm_key<'a, G: AffineRepr>(
         &'a self,
         setup_params: &'a [SetupParams<E, G>],
@@ -133,13 +260,18 @@ impl<E: Pairing> BoundCheckSmcWithKVVerifier<E> {
         max: u64,
         params: SmcParamsAndCommitmentKeyAndSecretKey<E>,
     ) -> Result<Statement<E, G>, ProofSystemError> {
-        validate_bounds(min, max)?;
+        validate_smc_base(
+            min,
+            max,
+            params.get_smc_params().get_supported_base_for_range_proof(),
+        )?;
 
         Ok(Statement::BoundCheckSmcWithKVVerifier(Self {
             min,
             max,
             params: Some(params),
             params_ref: None,
+            prepared_params: None,
         }))
     }
 
@@ -154,14 +286,57 @@ impl<E: Pairing> BoundCheckSmcWithKVVerifier<E> {
             max,
             params: None,
             params_ref: Some(params_ref),
+            prepared_params: None,
+        }))
+    }
+
+    /// Create the statement with params prepared using `SmcParamsAndCommitmentKeyAndSecretKey::precompute`.
+    /// Should be preferred when verifying many proofs with the same params. The prepared params are not serialized
+    /// so a deserialized statement verifies with the unprepared params.
+    pub fn new_statement_from_prepared_params<G: AffineRepr>(
+        min: u64,
+        max: u64,
+        prepared_params: PreparedSmcKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        validate_smc_base(
+            min,
+            max,
+            prepared_params
+                .params_and_comm_key_and_sk
+                .get_smc_params()
+                .get_supported_base_for_range_proof(),
+        )?;
+        Ok(Statement::BoundCheckSmcWithKVVerifier(Self {
+            min,
+            max,
+            params: Some(prepared_params.params_and_comm_key_and_sk.clone()),
+            params_ref: None,
+            prepared_params: Some(prepared_params),
         }))
     }
 
+    /// For bounds that fit in a `u32`. The params are prepared using
+    /// `SmcParamsAndCommitmentKeyAndSecretKey::precompute_for_u32_range`.
+    pub fn new_u32_range<G: AffineRepr>(
+        min: u32,
+        max: u32,
+        params: SmcParamsAndCommitmentKeyAndSecretKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Self::new_statement_from_prepared_params(
+            min as u64,
+            max as u64,
+            params.precompute_for_u32_range(),
+        )
+    }
+
     pub fn get_params_and_comm_key_and_sk<'a, G: AffineRepr>(
         &'a self,
         setup_params: &'a [SetupParams<E, G>],
         st_idx: usize,
     ) -> Result<&'a SmcParamsAndCommitmentKeyAndSecretKey<E>, ProofSystemError> {
+        if let Some(p) = &self.prepared_params {
+            return Ok(&p.params_and_comm_key_and_sk);
+        }
         extract_param!(
             setup_params,
             &self.params,
@@ -181,4 +356,9 @@ impl<E: Pairing> BoundCheckSmcWithKVVerifier<E> {
             .get_params_and_comm_key_and_sk(setup_params, st_idx)?
             .get_comm_key())
     }
+
+    /// Window table of the generator in the params if the statement was created with prepared params
+    pub fn get_g1_table(&self) -> Option<&WindowTable<E::G1>> {
+        self.prepared_params.as_ref().map(|p| &p.g1_table)
+    }
 }
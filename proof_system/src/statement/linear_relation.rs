@@ -0,0 +1,81 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{error::ProofSystemError, statement::Statement};
+use dock_crypto_utils::serde_utils::*;
+
+/// Proving that witnesses `x_0, x_1, ..., x_{n-1}` satisfy the linear equations
+/// `a_{i,0} * x_0 + a_{i,1} * x_1 + ... + a_{i,n-1} * x_{n-1} = t_i` with public coefficients `a_{i,j}` and
+/// targets `t_i`. The witnesses are not committed by this statement so this only makes sense when each witness
+/// is proven equal to a witness of another statement, like a signed message, using `EqualWitnesses`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct SchnorrLinearRelation<E: Pairing> {
+    /// Coefficients `a_{i,j}` with a row for each equation and a column for each witness
+    #[serde_as(as = "Vec<Vec<ArkObjectBytes>>")]
+    pub coefficients: Vec<Vec<E::ScalarField>>,
+    /// Target `t_i` of each equation
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub targets: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> SchnorrLinearRelation<E> {
+    /// Errors if there are no equations, the number of targets is not the number of equations or the equations
+    /// don't have the same non-zero number of coefficients.
+    pub fn new_statement_from_params<G: AffineRepr>(
+        coefficients: Vec<Vec<E::ScalarField>>,
+        targets: Vec<E::ScalarField>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        let s = Self {
+            coefficients,
+            targets,
+        };
+        s.validate()?;
+        Ok(Statement::SchnorrLinearRelation(s))
+    }
+
+    /// Number of witnesses in each equation
+    pub fn witness_count(&self) -> usize {
+        self.coefficients.first().map(|r| r.len()).unwrap_or(0)
+    }
+
+    /// Whether the given witnesses satisfy all equations
+    pub fn is_satisfied_by(&self, witnesses: &[E::ScalarField]) -> bool {
+        witnesses.len() == self.witness_count()
+            && self
+                .coefficients
+                .iter()
+                .zip(self.targets.iter())
+                .all(|(row, t)| Self::combine(row, witnesses) == *t)
+    }
+
+    pub fn validate(&self) -> Result<(), ProofSystemError> {
+        let witness_count = self.witness_count();
+        if self.coefficients.is_empty()
+            || witness_count == 0
+            || self.coefficients.len() != self.targets.len()
+            || self.coefficients.iter().any(|r| r.len() != witness_count)
+        {
+            return Err(ProofSystemError::InvalidLinearRelation);
+        }
+        Ok(())
+    }
+
+    /// `a_0 * x_0 + a_1 * x_1 + ... + a_{n-1} * x_{n-1}` for coefficients `a_i` and values `x_i`
+    pub(crate) fn combine(
+        coefficients: &[E::ScalarField],
+        values: &[E::ScalarField],
+    ) -> E::ScalarField {
+        coefficients
+            .iter()
+            .zip(values.iter())
+            .map(|(a, x)| *a * x)
+            .sum()
+    }
+}
@@ -0,0 +1,144 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+pub use legogroth16::{ProvingKey, VerifyingKey};
+
+use crate::{
+    error::ProofSystemError,
+    setup_params::SetupParams,
+    statement::Statement,
+    sub_protocols::merkle_membership::{MerkleHashParams, MerkleMembershipProtocol},
+};
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+
+/// Proving knowledge of a leaf of a Merkle tree with the given root using LegoGroth16. The leaf and its path are
+/// hidden and the leaf can be proven equal to a signed message.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MerkleMembershipProver<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub root: E::ScalarField,
+    /// Number of levels above the leaves, the proving key must have been generated for this depth
+    pub depth: u8,
+    pub hash_params: MerkleHashParams<E::ScalarField>,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub snark_proving_key: Option<ProvingKey<E>>,
+    pub snark_proving_key_ref: Option<usize>,
+}
+
+/// Proving knowledge of a leaf of a Merkle tree with the given root using LegoGroth16
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MerkleMembershipVerifier<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub root: E::ScalarField,
+    #[serde_as(as = "Option<ArkObjectBytes>")]
+    pub snark_verifying_key: Option<VerifyingKey<E>>,
+    pub snark_verifying_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> MerkleMembershipProver<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        root: E::ScalarField,
+        depth: u8,
+        hash_params: MerkleHashParams<E::ScalarField>,
+        snark_proving_key: ProvingKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        MerkleMembershipProtocol::validate_verification_key(&snark_proving_key.vk)?;
+        Ok(Statement::MerkleMembershipProver(Self {
+            root,
+            depth,
+            hash_params,
+            snark_proving_key: Some(snark_proving_key),
+            snark_proving_key_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        root: E::ScalarField,
+        depth: u8,
+        hash_params: MerkleHashParams<E::ScalarField>,
+        snark_proving_key_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Ok(Statement::MerkleMembershipProver(Self {
+            root,
+            depth,
+            hash_params,
+            snark_proving_key: None,
+            snark_proving_key_ref: Some(snark_proving_key_ref),
+        }))
+    }
+
+    pub fn get_proving_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a ProvingKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.snark_proving_key,
+            self.snark_proving_key_ref,
+            LegoSnarkProvingKey,
+            IncompatibleMerkleMembershipSetupParamAtIndex,
+            st_idx
+        )
+    }
+
+    /// The statement the verifier uses to verify the proof created using this statement. If the proving key
+    /// is referenced from the setup params, the verifying key is referenced from the same index.
+    pub fn for_verifier(&self) -> MerkleMembershipVerifier<E> {
+        MerkleMembershipVerifier {
+            root: self.root,
+            snark_verifying_key: self.snark_proving_key.as_ref().map(|pk| pk.vk.clone()),
+            snark_verifying_key_ref: self.snark_proving_key_ref,
+        }
+    }
+}
+
+impl<E: Pairing> MerkleMembershipVerifier<E> {
+    pub fn new_statement_from_params<G: AffineRepr>(
+        root: E::ScalarField,
+        snark_verifying_key: VerifyingKey<E>,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        MerkleMembershipProtocol::validate_verification_key(&snark_verifying_key)?;
+        Ok(Statement::MerkleMembershipVerifier(Self {
+            root,
+            snark_verifying_key: Some(snark_verifying_key),
+            snark_verifying_key_ref: None,
+        }))
+    }
+
+    pub fn new_statement_from_params_ref<G: AffineRepr>(
+        root: E::ScalarField,
+        snark_verifying_key_ref: usize,
+    ) -> Result<Statement<E, G>, ProofSystemError> {
+        Ok(Statement::MerkleMembershipVerifier(Self {
+            root,
+            snark_verifying_key: None,
+            snark_verifying_key_ref: Some(snark_verifying_key_ref),
+        }))
+    }
+
+    pub fn get_verifying_key<'a, G: AffineRepr>(
+        &'a self,
+        setup_params: &'a [SetupParams<E, G>],
+        st_idx: usize,
+    ) -> Result<&'a VerifyingKey<E>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.snark_verifying_key,
+            self.snark_verifying_key_ref,
+            LegoSnarkVerifyingKey,
+            IncompatibleMerkleMembershipSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
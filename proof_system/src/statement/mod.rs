@@ -1,20 +1,30 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
+    collections::BTreeMap,
     io::{Read, Write},
     vec::Vec,
 };
 use serde::{Deserialize, Serialize};
+use vb_accumulator::prelude::{
+    MembershipProvingKey, PublicKey as AccumPublicKey, SetupParams as AccumParams,
+};
+
+use crate::{error::ProofSystemError, sub_protocols::SubProtocolType};
 
 pub mod accumulator;
 pub mod bbs_23;
 #[macro_use]
 pub mod bbs_plus;
+pub mod bbs_plus_hidden_pk;
 pub mod bound_check_bpp;
 pub mod bound_check_legogroth16;
 pub mod bound_check_smc;
 pub mod bound_check_smc_with_kv;
 pub mod inequality;
+pub mod linear_relation;
+pub mod merkle_membership;
+pub mod noop;
 pub mod ped_comm;
 pub mod ps_signature;
 pub mod r1cs_legogroth16;
@@ -60,6 +70,22 @@ pub enum Statement<E: Pairing, G: AffineRepr> {
     BoundCheckSmcWithKVVerifier(bound_check_smc_with_kv::BoundCheckSmcWithKVVerifier<E>),
     /// To prove inequality of a signed message with a public value
     PublicInequality(inequality::PublicInequality<G>),
+    /// For proof of knowledge of BBS+ signature by one of several public keys without revealing which one
+    PoKBBSSignatureG1HiddenPK(bbs_plus_hidden_pk::PoKBBSSignatureG1HiddenPK<E>),
+    /// To prove that witnesses satisfy public linear equations
+    SchnorrLinearRelation(linear_relation::SchnorrLinearRelation<E>),
+    /// Used by prover to create proof that a hidden leaf is in a Merkle tree with a public root using LegoGroth16
+    MerkleMembershipProver(merkle_membership::MerkleMembershipProver<E>),
+    /// Used by verifier to verify proof that a hidden leaf is in a Merkle tree with a public root using LegoGroth16
+    MerkleMembershipVerifier(merkle_membership::MerkleMembershipVerifier<E>),
+    /// For proof of knowledge of several accumulator non-members and their corresponding witnesses
+    BatchAccumulatorNonMembership(accumulator::BatchAccumulatorNonMembership<E>),
+    /// For proof of knowledge of several accumulator members and their corresponding witnesses
+    BatchAccumulatorMembership(accumulator::BatchAccumulatorMembership<E>),
+    /// For proof of knowledge of a member of an accumulator whose value is committed, and of the member's witness
+    CommittedAccumulatorMembership(accumulator::CommittedAccumulatorMembership<E>),
+    /// Placeholder for a statement which is filled later
+    Noop(noop::Noop),
 }
 
 /// A collection of statements
@@ -93,6 +119,338 @@ where
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Statements for the common case of proving knowledge of a BBS+ signature and membership in some
+    /// accumulators. The BBS+ statement is at index 0 and the membership statement for the `i`th element of
+    /// `memberships` at index `i + 1`. Each element of `memberships` is the accumulator's params, public key and
+    /// proving key, and the accumulator value. Use `Witnesses::new_bbs_with_memberships` for the witnesses. The
+    /// equality of the members with signed messages should be added as meta statements.
+    pub fn new_bbs_with_memberships(
+        signature_params: ::bbs_plus::prelude::SignatureParamsG1<E>,
+        public_key: ::bbs_plus::prelude::PublicKeyG2<E>,
+        revealed_messages: BTreeMap<usize, E::ScalarField>,
+        memberships: Vec<(
+            AccumParams<E>,
+            AccumPublicKey<E>,
+            MembershipProvingKey<E::G1Affine>,
+            E::G1Affine,
+        )>,
+    ) -> Self {
+        let mut statements = Self(Vec::with_capacity(1 + memberships.len()));
+        statements.add(bbs_plus::PoKBBSSignatureG1::new_statement_from_params(
+            signature_params,
+            public_key,
+            revealed_messages,
+        ));
+        for (params, public_key, proving_key, accumulator_value) in memberships {
+            statements.add(
+                accumulator::AccumulatorMembership::new_statement_from_params(
+                    params,
+                    public_key,
+                    proving_key,
+                    accumulator_value,
+                ),
+            );
+        }
+        statements
+    }
+
+    /// Statement at index `idx` as the type `S`. Returns `None` if there is no statement at that index or it is
+    /// of a different type.
+    pub fn get_as<S: StatementType<E, G>>(&self, idx: usize) -> Option<&S> {
+        self.0.get(idx).and_then(S::from_statement)
+    }
+}
+
+mod sealed {
+    pub trait Sealed<E, G> {}
+}
+
+/// Implemented by the types wrapped by the variants of `Statement` to get them out of a `Statement`
+pub trait StatementType<E: Pairing, G: AffineRepr>: sealed::Sealed<E, G> {
+    /// Returns `None` if the statement is of a different type
+    fn from_statement(statement: &Statement<E, G>) -> Option<&Self>;
+}
+
+/// Identifies the type of a statement in the challenge. It's the type of the sub-protocol used for the statement,
+/// which is written as a byte at the start of the sub-protocol's challenge contribution, see `SubProtocolType`.
+/// Unlike the index of a statement in a proof spec, it's the same for all statements of a type. The prover's and
+/// the verifier's variants of a statement have the same id as both need the same challenge contribution. Pedersen
+/// commitment statements proven together (see `ProverConfig::coalesce_pedersen_commitments`) contribute the
+/// types of the coalesced sub-protocols instead.
+pub trait StatementId<E: Pairing, G: AffineRepr>: StatementType<E, G> {
+    fn statement_id() -> u8;
+}
+
+macro_rules! impl_statement_type {
+    ($variant: ident, $typ: ty, $protocol_type: ident) => {
+        impl<E: Pairing, G: AffineRepr> sealed::Sealed<E, G> for $typ {}
+
+        impl<E: Pairing, G: AffineRepr> StatementType<E, G> for $typ {
+            fn from_statement(statement: &Statement<E, G>) -> Option<&Self> {
+                match statement {
+                    Statement::$variant(s) => Some(s),
+                    _ => None,
+                }
+            }
+        }
+
+        impl<E: Pairing, G: AffineRepr> StatementId<E, G> for $typ {
+            fn statement_id() -> u8 {
+                SubProtocolType::$protocol_type as u8
+            }
+        }
+    };
+}
+
+impl_statement_type!(
+    PoKBBSSignatureG1,
+    bbs_plus::PoKBBSSignatureG1<E>,
+    PoKBBSSignatureG1
+);
+impl_statement_type!(
+    PedersenCommitment,
+    ped_comm::PedersenCommitment<G>,
+    PoKDiscreteLogs
+);
+impl_statement_type!(
+    AccumulatorMembership,
+    accumulator::AccumulatorMembership<E>,
+    AccumulatorMembership
+);
+impl_statement_type!(
+    AccumulatorNonMembership,
+    accumulator::AccumulatorNonMembership<E>,
+    AccumulatorNonMembership
+);
+impl_statement_type!(SaverProver, saver::SaverProver<E>, Saver);
+impl_statement_type!(SaverVerifier, saver::SaverVerifier<E>, Saver);
+impl_statement_type!(
+    BoundCheckLegoGroth16Prover,
+    bound_check_legogroth16::BoundCheckLegoGroth16Prover<E>,
+    BoundCheckLegoGroth16
+);
+impl_statement_type!(
+    BoundCheckLegoGroth16Verifier,
+    bound_check_legogroth16::BoundCheckLegoGroth16Verifier<E>,
+    BoundCheckLegoGroth16
+);
+impl_statement_type!(
+    R1CSCircomProver,
+    r1cs_legogroth16::R1CSCircomProver<E>,
+    R1CSLegogroth16
+);
+impl_statement_type!(
+    R1CSCircomVerifier,
+    r1cs_legogroth16::R1CSCircomVerifier<E>,
+    R1CSLegogroth16
+);
+impl_statement_type!(
+    PoKPSSignature,
+    ps_signature::PoKPSSignatureStatement<E>,
+    PSSignaturePoK
+);
+impl_statement_type!(
+    PoKBBSSignature23G1,
+    bbs_23::PoKBBSSignature23G1<E>,
+    PoKBBSSignature23G1
+);
+impl_statement_type!(
+    BoundCheckBpp,
+    bound_check_bpp::BoundCheckBpp<G>,
+    BoundCheckBpp
+);
+impl_statement_type!(
+    BoundCheckSmc,
+    bound_check_smc::BoundCheckSmc<E>,
+    BoundCheckSmc
+);
+impl_statement_type!(
+    BoundCheckSmcWithKVProver,
+    bound_check_smc_with_kv::BoundCheckSmcWithKVProver<E>,
+    BoundCheckSmcWithKV
+);
+impl_statement_type!(
+    BoundCheckSmcWithKVVerifier,
+    bound_check_smc_with_kv::BoundCheckSmcWithKVVerifier<E>,
+    BoundCheckSmcWithKV
+);
+impl_statement_type!(
+    PublicInequality,
+    inequality::PublicInequality<G>,
+    Inequality
+);
+impl_statement_type!(
+    PoKBBSSignatureG1HiddenPK,
+    bbs_plus_hidden_pk::PoKBBSSignatureG1HiddenPK<E>,
+    PoKBBSSignatureG1HiddenPK
+);
+impl_statement_type!(
+    SchnorrLinearRelation,
+    linear_relation::SchnorrLinearRelation<E>,
+    SchnorrLinearRelation
+);
+impl_statement_type!(
+    MerkleMembershipProver,
+    merkle_membership::MerkleMembershipProver<E>,
+    MerkleMembership
+);
+impl_statement_type!(
+    MerkleMembershipVerifier,
+    merkle_membership::MerkleMembershipVerifier<E>,
+    MerkleMembership
+);
+impl_statement_type!(
+    BatchAccumulatorNonMembership,
+    accumulator::BatchAccumulatorNonMembership<E>,
+    BatchAccumulatorNonMembership
+);
+impl_statement_type!(
+    BatchAccumulatorMembership,
+    accumulator::BatchAccumulatorMembership<E>,
+    BatchAccumulatorMembership
+);
+impl_statement_type!(
+    CommittedAccumulatorMembership,
+    accumulator::CommittedAccumulatorMembership<E>,
+    CommittedAccumulatorMembership
+);
+impl_statement_type!(Noop, noop::Noop, Noop);
+
+impl<E, G> Statement<E, G>
+where
+    E: Pairing,
+    G: AffineRepr,
+{
+    /// The statement the verifier uses to verify the proof created using this statement. Statements used only
+    /// by the prover are converted to their verifier counterparts, which don't have the prover-only data like
+    /// SNARK proving keys, and others are kept as they are. `R1CSCircomProver` and `BoundCheckSmcWithKVProver`
+    /// can't be converted as their verifier counterparts need the public inputs and the secret key respectively.
+    pub fn for_verifier(&self, st_idx: usize) -> Result<Self, ProofSystemError> {
+        match self {
+            Self::SaverProver(s) => Ok(Self::SaverVerifier(s.for_verifier())),
+            Self::BoundCheckLegoGroth16Prover(s) => {
+                Ok(Self::BoundCheckLegoGroth16Verifier(s.for_verifier()))
+            }
+            Self::MerkleMembershipProver(s) => Ok(Self::MerkleMembershipVerifier(s.for_verifier())),
+            Self::R1CSCircomProver(_) | Self::BoundCheckSmcWithKVProver(_) => {
+                Err(ProofSystemError::NoVerifierStatement(st_idx))
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+
+    /// Id of the type of this statement, see `StatementId`
+    pub fn statement_id(&self) -> u8 {
+        self.protocol_type() as u8
+    }
+
+    /// Type of the sub-protocol used for this statement. The prover's and the verifier's variants of a statement
+    /// use the same sub-protocol.
+    pub fn protocol_type(&self) -> SubProtocolType {
+        match self {
+            Self::PoKBBSSignatureG1(_) => SubProtocolType::PoKBBSSignatureG1,
+            Self::PedersenCommitment(_) => SubProtocolType::PoKDiscreteLogs,
+            Self::AccumulatorMembership(_) => SubProtocolType::AccumulatorMembership,
+            Self::AccumulatorNonMembership(_) => SubProtocolType::AccumulatorNonMembership,
+            Self::SaverProver(_) | Self::SaverVerifier(_) => SubProtocolType::Saver,
+            Self::BoundCheckLegoGroth16Prover(_) | Self::BoundCheckLegoGroth16Verifier(_) => {
+                SubProtocolType::BoundCheckLegoGroth16
+            }
+            Self::R1CSCircomProver(_) | Self::R1CSCircomVerifier(_) => {
+                SubProtocolType::R1CSLegogroth16
+            }
+            Self::PoKPSSignature(_) => SubProtocolType::PSSignaturePoK,
+            Self::PoKBBSSignature23G1(_) => SubProtocolType::PoKBBSSignature23G1,
+            Self::BoundCheckBpp(_) => SubProtocolType::BoundCheckBpp,
+            Self::BoundCheckSmc(_) => SubProtocolType::BoundCheckSmc,
+            Self::BoundCheckSmcWithKVProver(_) | Self::BoundCheckSmcWithKVVerifier(_) => {
+                SubProtocolType::BoundCheckSmcWithKV
+            }
+            Self::PublicInequality(_) => SubProtocolType::Inequality,
+            Self::PoKBBSSignatureG1HiddenPK(_) => SubProtocolType::PoKBBSSignatureG1HiddenPK,
+            Self::SchnorrLinearRelation(_) => SubProtocolType::SchnorrLinearRelation,
+            Self::MerkleMembershipProver(_) | Self::MerkleMembershipVerifier(_) => {
+                SubProtocolType::MerkleMembership
+            }
+            Self::BatchAccumulatorNonMembership(_) => {
+                SubProtocolType::BatchAccumulatorNonMembership
+            }
+            Self::BatchAccumulatorMembership(_) => SubProtocolType::BatchAccumulatorMembership,
+            Self::CommittedAccumulatorMembership(_) => {
+                SubProtocolType::CommittedAccumulatorMembership
+            }
+            Self::Noop(_) => SubProtocolType::Noop,
+        }
+    }
+
+    /// Whether the witnesses of this statement are elements of the scalar field of the pairing `E`. Otherwise, they
+    /// are elements of the scalar field of the group `G` which can be a different field.
+    pub fn has_pairing_scalar_field_witnesses(&self) -> bool {
+        !matches!(
+            self,
+            Self::PedersenCommitment(_) | Self::BoundCheckBpp(_) | Self::PublicInequality(_)
+        )
+    }
+
+    /// Writes the public values of this statement which are known to both the prover and the verifier, like the
+    /// revealed messages or the bounds. Setup params are not written as the sub-protocols contribute the ones
+    /// they use to the challenge. Nothing is written for R1CS statements as the prover's statement doesn't have
+    /// the public inputs, or for `Noop` statements. Revealed messages are maps so they are always written in
+    /// ascending order of the message index, irrespective of the order they were inserted in.
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        match self {
+            Self::PoKBBSSignatureG1(s) => s.revealed_messages.serialize_compressed(&mut writer)?,
+            Self::PoKBBSSignatureG1HiddenPK(s) => {
+                s.revealed_messages.serialize_compressed(&mut writer)?
+            }
+            Self::PoKBBSSignature23G1(s) => {
+                s.revealed_messages.serialize_compressed(&mut writer)?
+            }
+            Self::PoKPSSignature(s) => s.revealed_messages.serialize_compressed(&mut writer)?,
+            Self::PedersenCommitment(s) => s.commitment.serialize_compressed(&mut writer)?,
+            Self::AccumulatorMembership(s) => {
+                s.accumulator_value.serialize_compressed(&mut writer)?
+            }
+            Self::AccumulatorNonMembership(s) => {
+                s.accumulator_value.serialize_compressed(&mut writer)?
+            }
+            Self::SaverProver(s) => s.chunk_bit_size.serialize_compressed(&mut writer)?,
+            Self::SaverVerifier(s) => s.chunk_bit_size.serialize_compressed(&mut writer)?,
+            Self::BoundCheckLegoGroth16Prover(s) => {
+                (s.min, s.max).serialize_compressed(&mut writer)?
+            }
+            Self::BoundCheckLegoGroth16Verifier(s) => {
+                (s.min, s.max).serialize_compressed(&mut writer)?
+            }
+            Self::BoundCheckBpp(s) => (s.min, s.max).serialize_compressed(&mut writer)?,
+            Self::BoundCheckSmc(s) => (s.min, s.max).serialize_compressed(&mut writer)?,
+            Self::BoundCheckSmcWithKVProver(s) => {
+                (s.min, s.max).serialize_compressed(&mut writer)?
+            }
+            Self::BoundCheckSmcWithKVVerifier(s) => {
+                (s.min, s.max).serialize_compressed(&mut writer)?
+            }
+            Self::PublicInequality(s) => s.inequal_to.serialize_compressed(&mut writer)?,
+            Self::SchnorrLinearRelation(s) => {
+                s.coefficients.serialize_compressed(&mut writer)?;
+                s.targets.serialize_compressed(&mut writer)?
+            }
+            Self::MerkleMembershipProver(s) => s.root.serialize_compressed(&mut writer)?,
+            Self::MerkleMembershipVerifier(s) => s.root.serialize_compressed(&mut writer)?,
+            Self::BatchAccumulatorNonMembership(s) => {
+                (s.accumulator_value, s.num_elements).serialize_compressed(&mut writer)?
+            }
+            Self::BatchAccumulatorMembership(s) => {
+                (s.accumulator_value, s.num_elements).serialize_compressed(&mut writer)?
+            }
+            Self::CommittedAccumulatorMembership(s) => {
+                (s.commitment, s.commitment_key).serialize_compressed(&mut writer)?
+            }
+            Self::R1CSCircomProver(_) | Self::R1CSCircomVerifier(_) | Self::Noop(_) => (),
+        }
+        Ok(())
+    }
 }
 
 macro_rules! delegate {
@@ -115,7 +473,15 @@ macro_rules! delegate {
                 BoundCheckSmc,
                 BoundCheckSmcWithKVProver,
                 BoundCheckSmcWithKVVerifier,
-                PublicInequality
+                PublicInequality,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                MerkleMembershipProver,
+                MerkleMembershipVerifier,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                Noop
             : $($tt)+
         }
     }}
@@ -141,7 +507,15 @@ macro_rules! delegate_reverse {
                 BoundCheckSmc,
                 BoundCheckSmcWithKVProver,
                 BoundCheckSmcWithKVVerifier,
-                PublicInequality
+                PublicInequality,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                MerkleMembershipProver,
+                MerkleMembershipVerifier,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                Noop
             : $($tt)+
         }
 
@@ -188,13 +562,51 @@ mod serialization {
         ) -> Result<Self, SerializationError> {
             let idx: u8 =
                 CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            Self::deserialize_variant(idx, reader, compress, validate)
+        }
+    }
 
+    impl<E: Pairing, G: AffineRepr> Statement<E, G> {
+        /// Deserialize the statement with variant index `idx` which has already been read from the `reader`
+        fn deserialize_variant<R: Read>(
+            idx: u8,
+            mut reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
             delegate_reverse!(
                 idx or else Err(SerializationError::InvalidData) => with variant as build
                 CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate).map(build)
             )
         }
     }
+
+    impl<E: Pairing, G: AffineRepr> Statements<E, G> {
+        /// Deserialize compressed statements serialized with format version 3 in which `AccumulatorMembership` did
+        /// not have the `default_proving_key` flag
+        pub(crate) fn deserialize_v3<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+            let (compress, validate) = (Compress::Yes, Validate::Yes);
+            let len: u64 =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let mut statements = Vec::new();
+            for _ in 0..len {
+                let idx: u8 =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                statements.push(match idx {
+                    // Variant index of `Statement::AccumulatorMembership`
+                    1 => Statement::AccumulatorMembership(
+                        accumulator::AccumulatorMembership::deserialize_v3(
+                            &mut reader,
+                            compress,
+                            validate,
+                        )?,
+                    ),
+                    _ => Statement::deserialize_variant(idx, &mut reader, compress, validate)?,
+                });
+            }
+            Ok(Self(statements))
+        }
+    }
 }
 
 #[cfg(test)]
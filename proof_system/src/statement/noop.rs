@@ -0,0 +1,27 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::statement::Statement;
+
+/// Holds the slot of a statement which is filled later, like in a proof spec template where not all statements are
+/// known in advance. Its proof always verifies so it proves nothing and should be replaced with a real statement
+/// using `ProofSpec::replace_noop` before the proof is created. Its witness is `Witness::Noop`.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct Noop {}
+
+impl Noop {
+    pub fn new_statement<E: Pairing, G: AffineRepr>() -> Statement<E, G> {
+        Statement::Noop(Self {})
+    }
+}
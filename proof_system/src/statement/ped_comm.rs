@@ -33,6 +33,13 @@ impl<G: AffineRepr> PedersenCommitment<G> {
         })
     }
 
+    /// Statement for proving that the witness `x` is the discrete log of `value` to the base `base`, i.e.
+    /// `base * x = value`. This is a commitment with a single base so the witness is
+    /// `Witness::PedersenCommitment(vec![x])`.
+    pub fn new_statement_for_discrete_log<E: Pairing>(base: G, value: G) -> Statement<E, G> {
+        Self::new_statement_from_params(vec![base], value)
+    }
+
     pub fn new_statement_from_params_refs<E: Pairing>(
         key_ref: usize,
         commitment: G,
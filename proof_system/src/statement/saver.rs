@@ -150,6 +150,23 @@ impl<E: Pairing> SaverProver<E> {
             st_idx
         )
     }
+
+    /// The statement the verifier uses to verify the proof created using this statement. The verifier needs
+    /// only the SNARK verifying key which is part of the proving key. If the proving key is referenced from the
+    /// setup params, the verifying key is referenced from the same index.
+    pub fn for_verifier(&self) -> SaverVerifier<E> {
+        SaverVerifier {
+            chunk_bit_size: self.chunk_bit_size,
+            encryption_gens: self.encryption_gens.clone(),
+            chunked_commitment_gens: self.chunked_commitment_gens.clone(),
+            encryption_key: self.encryption_key.clone(),
+            snark_verifying_key: self.snark_proving_key.as_ref().map(|pk| pk.pk.vk.clone()),
+            encryption_gens_ref: self.encryption_gens_ref,
+            chunked_commitment_gens_ref: self.chunked_commitment_gens_ref,
+            encryption_key_ref: self.encryption_key_ref,
+            snark_verifying_key_ref: self.snark_proving_key_ref,
+        }
+    }
 }
 
 impl<E: Pairing> SaverVerifier<E> {
@@ -1,20 +1,33 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
+    collections::BTreeSet,
+    format,
     io::{Read, Write},
+    string::String,
     vec::Vec,
 };
-use bbs_plus::prelude::{PoKOfSignature23G1Proof, PoKOfSignatureG1Proof};
+use bbs_plus::prelude::{
+    MultiMessageSignatureParams, PoKOfSignature23G1Proof, PoKOfSignatureG1Proof, SignatureG1,
+};
 use bulletproofs_plus_plus::prelude::ProofArbitraryRange;
 use coconut_crypto::SignaturePoK as PSSignaturePoK;
 use dock_crypto_utils::serde_utils::*;
 use saver::encryption::Ciphertext;
-use schnorr_pok::SchnorrResponse;
+use schnorr_pok::{error::SchnorrError, SchnorrResponse};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use vb_accumulator::prelude::{MembershipProof, NonMembershipProof};
+use vb_accumulator::prelude::{
+    MembershipProof, MembershipProvingKey, NonMembershipProof, PublicKey as AccumPublicKey,
+    SetupParams as AccumParams,
+};
 
-use crate::error::ProofSystemError;
+use crate::{
+    error::ProofSystemError,
+    setup_params::SetupParams,
+    statement::Statement,
+    sub_protocols::{should_use_cls, SubProtocolType},
+};
 pub use serialization::*;
 
 /// Proof corresponding to one `Statement`
@@ -37,6 +50,194 @@ pub enum StatementProof<E: Pairing, G: AffineRepr> {
     BoundCheckSmc(BoundCheckSmcProof<E>),
     BoundCheckSmcWithKV(BoundCheckSmcWithKVProof<E>),
     Inequality(InequalityProof<G>),
+    PoKBBSSignatureG1HiddenPK(PoKBBSSignatureG1HiddenPKProof<E>),
+    SchnorrLinearRelation(SchnorrLinearRelationProof<E>),
+    CoalescedPedersenCommitment(CoalescedPedersenCommitmentProof<G>),
+    Coalesced(CoalescedStatementProof),
+    MerkleMembership(MerkleMembershipProof<E>),
+    Noop(NoopProof),
+    BatchAccumulatorNonMembership(BatchAccumulatorNonMembershipProof<E>),
+    BatchAccumulatorMembership(BatchAccumulatorMembershipProof<E>),
+    CommittedAccumulatorMembership(CommittedAccumulatorMembershipProof<E>),
+    /// The BBS+ signature itself, sent instead of a proof of knowledge when all messages are revealed
+    BBSPlusFullReveal(SignatureG1<E>),
+    /// Proof of knowledge of BBS+ signature for a statement of a disjunction, which might be simulated. Has the same
+    /// form as a proof with a hidden public key that is one of a single public key.
+    PoKBBSSignatureG1Disjunct(PoKBBSSignatureG1HiddenPKProof<E>),
+}
+
+impl<E: Pairing, G: AffineRepr> StatementProof<E, G> {
+    /// Type of the sub-protocol that creates this proof
+    pub fn protocol_type(&self) -> SubProtocolType {
+        match self {
+            Self::PoKBBSSignatureG1(_) | Self::BBSPlusFullReveal(_) => {
+                SubProtocolType::PoKBBSSignatureG1
+            }
+            Self::AccumulatorMembership(_) => SubProtocolType::AccumulatorMembership,
+            Self::AccumulatorNonMembership(_) => SubProtocolType::AccumulatorNonMembership,
+            Self::PedersenCommitment(_) => SubProtocolType::PoKDiscreteLogs,
+            Self::Saver(_) | Self::SaverWithAggregation(_) => SubProtocolType::Saver,
+            Self::BoundCheckLegoGroth16(_) | Self::BoundCheckLegoGroth16WithAggregation(_) => {
+                SubProtocolType::BoundCheckLegoGroth16
+            }
+            Self::R1CSLegoGroth16(_) | Self::R1CSLegoGroth16WithAggregation(_) => {
+                SubProtocolType::R1CSLegogroth16
+            }
+            Self::PoKPSSignature(_) => SubProtocolType::PSSignaturePoK,
+            Self::PoKBBSSignature23G1(_) => SubProtocolType::PoKBBSSignature23G1,
+            Self::BoundCheckBpp(_) => SubProtocolType::BoundCheckBpp,
+            Self::BoundCheckSmc(_) => SubProtocolType::BoundCheckSmc,
+            Self::BoundCheckSmcWithKV(_) => SubProtocolType::BoundCheckSmcWithKV,
+            Self::Inequality(_) => SubProtocolType::Inequality,
+            Self::PoKBBSSignatureG1HiddenPK(_) => SubProtocolType::PoKBBSSignatureG1HiddenPK,
+            Self::SchnorrLinearRelation(_) => SubProtocolType::SchnorrLinearRelation,
+            Self::CoalescedPedersenCommitment(_) => SubProtocolType::CoalescedPoKDiscreteLogs,
+            Self::Coalesced(_) => SubProtocolType::Coalesced,
+            Self::MerkleMembership(_) => SubProtocolType::MerkleMembership,
+            Self::Noop(_) => SubProtocolType::Noop,
+            Self::BatchAccumulatorNonMembership(_) => {
+                SubProtocolType::BatchAccumulatorNonMembership
+            }
+            Self::BatchAccumulatorMembership(_) => SubProtocolType::BatchAccumulatorMembership,
+            Self::CommittedAccumulatorMembership(_) => {
+                SubProtocolType::CommittedAccumulatorMembership
+            }
+            Self::PoKBBSSignatureG1Disjunct(_) => SubProtocolType::PoKBBSSignatureG1Disjunct,
+        }
+    }
+
+    /// Name of the variant of this proof
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::PoKBBSSignatureG1(_) => "PoKBBSSignatureG1",
+            Self::AccumulatorMembership(_) => "AccumulatorMembership",
+            Self::AccumulatorNonMembership(_) => "AccumulatorNonMembership",
+            Self::PedersenCommitment(_) => "PedersenCommitment",
+            Self::Saver(_) => "Saver",
+            Self::BoundCheckLegoGroth16(_) => "BoundCheckLegoGroth16",
+            Self::R1CSLegoGroth16(_) => "R1CSLegoGroth16",
+            Self::SaverWithAggregation(_) => "SaverWithAggregation",
+            Self::BoundCheckLegoGroth16WithAggregation(_) => "BoundCheckLegoGroth16WithAggregation",
+            Self::R1CSLegoGroth16WithAggregation(_) => "R1CSLegoGroth16WithAggregation",
+            Self::PoKPSSignature(_) => "PoKPSSignature",
+            Self::PoKBBSSignature23G1(_) => "PoKBBSSignature23G1",
+            Self::BoundCheckBpp(_) => "BoundCheckBpp",
+            Self::BoundCheckSmc(_) => "BoundCheckSmc",
+            Self::BoundCheckSmcWithKV(_) => "BoundCheckSmcWithKV",
+            Self::Inequality(_) => "Inequality",
+            Self::PoKBBSSignatureG1HiddenPK(_) => "PoKBBSSignatureG1HiddenPK",
+            Self::SchnorrLinearRelation(_) => "SchnorrLinearRelation",
+            Self::CoalescedPedersenCommitment(_) => "CoalescedPedersenCommitment",
+            Self::Coalesced(_) => "Coalesced",
+            Self::MerkleMembership(_) => "MerkleMembership",
+            Self::Noop(_) => "Noop",
+            Self::BatchAccumulatorNonMembership(_) => "BatchAccumulatorNonMembership",
+            Self::BatchAccumulatorMembership(_) => "BatchAccumulatorMembership",
+            Self::CommittedAccumulatorMembership(_) => "CommittedAccumulatorMembership",
+            Self::BBSPlusFullReveal(_) => "BBSPlusFullReveal",
+            Self::PoKBBSSignatureG1Disjunct(_) => "PoKBBSSignatureG1Disjunct",
+        }
+    }
+
+    /// Number of bytes in the (compressed) serialization of this proof
+    pub fn compressed_len(&self) -> usize {
+        CanonicalSerialize::compressed_size(self)
+    }
+
+    /// Check that this proof has the structure expected for the statement at index `s_idx`, like a Schnorr response
+    /// for each hidden message of a signature or a proof for each element of a batch accumulator statement. This
+    /// does no group operations so it can be used to reject malformed proofs before verifying them. Proofs which
+    /// aren't of the statement's type are left to the verifier to reject.
+    pub fn validate_structure(
+        &self,
+        s_idx: usize,
+        statement: &Statement<E, G>,
+        setup_params: &[SetupParams<E, G>],
+    ) -> Result<(), ProofSystemError> {
+        let malformed = |reason: String| ProofSystemError::MalformedStatementProof {
+            statement: s_idx,
+            reason,
+        };
+        let check_count = |what: &str, expected: usize, found: usize| {
+            if expected != found {
+                return Err(malformed(format!(
+                    "expected {} {} but found {}",
+                    expected, what, found
+                )));
+            }
+            Ok(())
+        };
+        match (statement, self) {
+            (Statement::PoKBBSSignatureG1(s), Self::PoKBBSSignatureG1(p)) => {
+                let hidden = s
+                    .get_sig_params(setup_params, s_idx)?
+                    .supported_message_count()
+                    .saturating_sub(s.revealed_messages.len());
+                // Responses for `e` and `r2`, and then for `r3`, `s'` and the hidden messages
+                check_count("responses in the 1st Schnorr proof", 2, p.sc_resp_1.len())?;
+                check_count(
+                    "responses in the 2nd Schnorr proof",
+                    2 + hidden,
+                    p.sc_resp_2.len(),
+                )
+            }
+            (Statement::PoKBBSSignature23G1(s), Self::PoKBBSSignature23G1(p)) => {
+                let hidden = s
+                    .get_sig_params(setup_params, s_idx)?
+                    .supported_message_count()
+                    .saturating_sub(s.revealed_messages.len());
+                // Responses for `e` and `r1`, and then for `r3` and the hidden messages
+                check_count("responses in the 1st Schnorr proof", 2, p.sc_resp_1.len())?;
+                check_count(
+                    "responses in the 2nd Schnorr proof",
+                    1 + hidden,
+                    p.sc_resp_2.len(),
+                )
+            }
+            (Statement::PedersenCommitment(s), Self::PedersenCommitment(p)) => check_count(
+                "responses in the Schnorr proof",
+                s.get_commitment_key(setup_params, s_idx)?.len(),
+                p.response.len(),
+            ),
+            (Statement::BoundCheckSmc(s), Self::BoundCheckSmc(p)) => {
+                if s.min < s.max {
+                    let expected_cls = should_use_cls(s.min, s.max);
+                    if expected_cls != matches!(p.proof, BoundCheckSmcInnerProof::CLS(_)) {
+                        return Err(malformed(format!(
+                            "expected a {} range proof for bounds [{}, {})",
+                            if expected_cls { "CLS" } else { "CCS" },
+                            s.min,
+                            s.max
+                        )));
+                    }
+                }
+                // Responses for the message and the randomness of the commitment
+                check_count("responses in the Schnorr proof", 2, p.sp.response.len())
+            }
+            (Statement::BoundCheckSmcWithKVVerifier(s), Self::BoundCheckSmcWithKV(p)) => {
+                if s.min < s.max {
+                    let expected_cls = should_use_cls(s.min, s.max);
+                    if expected_cls != matches!(p.proof, BoundCheckSmcWithKVInnerProof::CLS(_)) {
+                        return Err(malformed(format!(
+                            "expected a {} range proof for bounds [{}, {})",
+                            if expected_cls { "CLS" } else { "CCS" },
+                            s.min,
+                            s.max
+                        )));
+                    }
+                }
+                check_count("responses in the Schnorr proof", 2, p.sp.response.len())
+            }
+            (Statement::BatchAccumulatorMembership(s), Self::BatchAccumulatorMembership(p)) => {
+                check_count("membership proofs", s.num_elements, p.proofs.len())
+            }
+            (
+                Statement::BatchAccumulatorNonMembership(s),
+                Self::BatchAccumulatorNonMembership(p),
+            ) => check_count("non-membership proofs", s.num_elements, p.proofs.len()),
+            _ => Ok(()),
+        }
+    }
 }
 
 macro_rules! delegate {
@@ -58,7 +259,18 @@ macro_rules! delegate {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                Inequality
+                Inequality,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                CoalescedPedersenCommitment,
+                Coalesced,
+                MerkleMembership,
+                Noop,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                BBSPlusFullReveal,
+                PoKBBSSignatureG1Disjunct
             : $($tt)+
         }
     }};
@@ -83,7 +295,18 @@ macro_rules! delegate_reverse {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                Inequality
+                Inequality,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                CoalescedPedersenCommitment,
+                Coalesced,
+                MerkleMembership,
+                Noop,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                BBSPlusFullReveal,
+                PoKBBSSignatureG1Disjunct
             : $($tt)+
         }
 
@@ -356,6 +579,191 @@ impl<G: AffineRepr> InequalityProof<G> {
     }
 }
 
+/// Proof of knowledge of BBS+ signature where the public key of the signer is randomized by the prover. The
+/// signature is proven to be valid under the randomized public key which is proven to be created from one of the
+/// public keys of the statement.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKBBSSignatureG1HiddenPKProof<E: Pairing> {
+    pub proof: PoKOfSignatureG1Proof<E>,
+    /// Public key of the signer multiplied by a random scalar
+    #[serde_as(as = "ArkObjectBytes")]
+    pub randomized_pk: E::G2Affine,
+    /// Proof that `randomized_pk` is a multiple of one of the public keys
+    pub pk_proof: RandomizedPublicKeyProof<E>,
+}
+
+/// Proof of knowledge of `r` such that `randomized_pk = pk_i * r` for one of the public keys `pk_i` without
+/// revealing `i`. There is a Schnorr proof for each public key, all but one of which are simulated, and the
+/// challenges of the Schnorr proofs add up to the challenge of the composite proof.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct RandomizedPublicKeyProof<E: Pairing> {
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub t: Vec<E::G2Affine>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub challenges: Vec<E::ScalarField>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub responses: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> PoKBBSSignatureG1HiddenPKProof<E> {
+    pub fn get_resp_for_message(
+        &self,
+        msg_idx: usize,
+        revealed_msg_ids: &BTreeSet<usize>,
+    ) -> Result<&E::ScalarField, ProofSystemError> {
+        self.proof
+            .get_resp_for_message(msg_idx, revealed_msg_ids)
+            .map_err(|e| e.into())
+    }
+}
+
+/// Proof that the witnesses satisfy the linear equations of the statement. There is a commitment to the
+/// blindings for each equation and a response for each witness.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct SchnorrLinearRelationProof<E: Pairing> {
+    /// `t_i = a_{i,0} * r_0 + ... + a_{i,n-1} * r_{n-1}` for blindings `r_j` and coefficients `a_{i,j}`
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub t: Vec<E::ScalarField>,
+    /// `s_j = r_j + c * x_j` for blinding `r_j` and witness `x_j`
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub responses: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> SchnorrLinearRelationProof<E> {
+    pub fn get_resp_for_message(&self, idx: usize) -> Result<&E::ScalarField, ProofSystemError> {
+        self.responses
+            .get(idx)
+            .ok_or_else(|| SchnorrError::IndexOutOfBounds(idx, self.responses.len()).into())
+    }
+}
+
+/// Proof of knowledge of the openings of the commitments of several Pedersen commitment statements with the same
+/// commitment key using a single commitment to randomness. This is the proof for the statement with the smallest
+/// index and the other statements have a `CoalescedStatementProof` referring to it. For challenge `c`, commitments
+/// `C_0, C_1, ..., C_{k-1}` and witnesses `x_{j,i}` of commitment `C_j`, the response for base `i` is
+/// `r_i + c * x_{0,i} + c^2 * x_{1,i} + ... + c^k * x_{k-1,i}`.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CoalescedPedersenCommitmentProof<G: AffineRepr> {
+    /// Indices of the other statements whose commitments are proven, in increasing order
+    pub coalesced: Vec<usize>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t: G,
+    pub response: SchnorrResponse<G>,
+}
+
+/// Proof for a statement that is proven by the `CoalescedPedersenCommitmentProof` of the statement at index `into`
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct CoalescedStatementProof {
+    pub into: usize,
+}
+
+/// Proof that a leaf is in a Merkle tree with a public root. The leaf is committed in the LegoGroth16 proof
+/// and its knowledge is proven with the Schnorr protocol.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MerkleMembershipProof<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub snark_proof: legogroth16::Proof<E>,
+    pub sp: PedersenCommitmentProof<E::G1Affine>,
+}
+
+impl<E: Pairing> MerkleMembershipProof<E> {
+    pub fn get_schnorr_response_for_message(&self) -> Result<&E::ScalarField, ProofSystemError> {
+        self.sp.response.get_response(0).map_err(|e| e.into())
+    }
+}
+
+/// Proof of non-membership of several elements in the same accumulator, one proof for each element in the order of
+/// the witness indices
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BatchAccumulatorNonMembershipProof<E: Pairing> {
+    pub proofs: Vec<NonMembershipProof<E>>,
+}
+
+/// Proof of membership of several elements in the same accumulator, one proof for each element in the order of the
+/// witness indices
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct BatchAccumulatorMembershipProof<E: Pairing> {
+    pub proofs: Vec<MembershipProof<E>>,
+}
+
+/// Proof of membership in an accumulator whose value is committed. `proof` is a membership proof whose pairing
+/// check commits to the randomness of the accumulator's commitment and `randomness_response` is the Schnorr
+/// response for that randomness.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CommittedAccumulatorMembershipProof<E: Pairing> {
+    pub proof: MembershipProof<E>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub randomness_response: E::ScalarField,
+}
+
+impl<E: Pairing> CommittedAccumulatorMembershipProof<E> {
+    pub fn get_schnorr_response_for_element(&self) -> &E::ScalarField {
+        self.proof.get_schnorr_response_for_element()
+    }
+
+    /// Same as the challenge contribution of `CommittedAccumulatorMembershipSubProtocol`
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        commitment: &E::G1Affine,
+        commitment_key: &E::G1Affine,
+        pk: &AccumPublicKey<E>,
+        params: &AccumParams<E>,
+        prk: &MembershipProvingKey<E::G1Affine>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        self.proof
+            .challenge_contribution(commitment, pk, params, prk, &mut writer)?;
+        commitment_key.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+/// Proof of a `Noop` sub-protocol, has no data
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct NoopProof {}
+
 mod serialization {
     use super::{
         AffineRepr, CanonicalDeserialize, CanonicalSerialize, Pairing, Read, SerializationError,
@@ -1,13 +1,27 @@
-use crate::{error::ProofSystemError, statement_proof::StatementProof};
-use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_std::{io::Write, rand::RngCore};
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{
+        BatchAccumulatorMembershipProof, BatchAccumulatorNonMembershipProof,
+        CommittedAccumulatorMembershipProof, StatementProof,
+    },
+    sub_protocols::{bbs_plus::PoKBBSSigG1SubProtocol, SubProtocolType},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{format, io::Write, rand::RngCore, vec::Vec, UniformRand};
 use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
-use vb_accumulator::prelude::{
-    MembershipProof, MembershipProofProtocol, MembershipProvingKey, NonMembershipProof,
-    NonMembershipProofProtocol, NonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams,
-    PublicKey, SetupParams as AccumParams,
+use vb_accumulator::{
+    error::VBAccumulatorError,
+    prelude::{
+        MembershipProof, MembershipProofProtocol, MembershipProvingKey, NonMembershipProof,
+        NonMembershipProofProtocol, NonMembershipProvingKey, PreparedPublicKey,
+        PreparedSetupParams, PublicKey, SetupParams as AccumParams,
+    },
 };
 
+/// Cloning an initialized sub-protocol copies the protocol's randomness as well so the original and the clone
+/// must not be used to respond to different challenges.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AccumulatorMembershipSubProtocol<'a, E: Pairing> {
     pub id: usize,
@@ -18,6 +32,8 @@ pub struct AccumulatorMembershipSubProtocol<'a, E: Pairing> {
     pub protocol: Option<MembershipProofProtocol<E>>,
 }
 
+/// Cloning an initialized sub-protocol copies the protocol's randomness as well so the original and the clone
+/// must not be used to respond to different challenges.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AccumulatorNonMembershipSubProtocol<'a, E: Pairing> {
     pub id: usize,
@@ -28,6 +44,55 @@ pub struct AccumulatorNonMembershipSubProtocol<'a, E: Pairing> {
     pub protocol: Option<NonMembershipProofProtocol<E>>,
 }
 
+/// Membership of several elements in the same accumulator. Each element is proven with its own protocol but the
+/// pairing checks of all elements are verified together. The blinding of the element at index `i` is the blinding
+/// of the witness at index `i` of the statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchAccumulatorMembershipSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub public_key: &'a PublicKey<E>,
+    pub proving_key: &'a MembershipProvingKey<E::G1Affine>,
+    pub accumulator_value: E::G1Affine,
+    pub num_elements: usize,
+    pub protocols: Option<Vec<MembershipProofProtocol<E>>>,
+}
+
+/// Non-membership of several elements in the same accumulator. Each element is proven with its own protocol but
+/// the pairing checks of all elements are verified together. The blinding of the element at index `i` is the
+/// blinding of the witness at index `i` of the statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchAccumulatorNonMembershipSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub public_key: &'a PublicKey<E>,
+    pub proving_key: &'a NonMembershipProvingKey<E::G1Affine>,
+    pub accumulator_value: E::G1Affine,
+    pub num_elements: usize,
+    pub protocols: Option<Vec<NonMembershipProofProtocol<E>>>,
+}
+
+/// Membership in an accumulator whose value `V` isn't public but committed as `C = V + r * H` where `H` is the
+/// commitment key. `V` only appears in the pairing check of the membership protocol, as `e(V, P_tilde)^-c` for the
+/// challenge `c`, so it's replaced by `e(C, P_tilde)^-c * e(H, P_tilde)^{c * r}` and the knowledge of `r` is proven
+/// in the same pairing check. For this, the prover multiplies `R_E` with `e(H, P_tilde)^{r_r}` for a random `r_r`
+/// and responds with `s_r = r_r + c * r`. The verifier then verifies the membership proof against `C - s_r/c * H`
+/// rather than `V`.
+/// Cloning an initialized sub-protocol copies the protocol's randomness as well so the original and the clone
+/// must not be used to respond to different challenges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommittedAccumulatorMembershipSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub public_key: &'a PublicKey<E>,
+    pub proving_key: &'a MembershipProvingKey<E::G1Affine>,
+    pub commitment: E::G1Affine,
+    pub commitment_key: E::G1Affine,
+    pub protocol: Option<MembershipProofProtocol<E>>,
+    /// Randomness of the commitment and its blinding
+    pub randomness: Option<(E::ScalarField, E::ScalarField)>,
+}
+
 impl<'a, E: Pairing> AccumulatorMembershipSubProtocol<'a, E> {
     pub fn new(
         id: usize,
@@ -68,6 +133,29 @@ impl<'a, E: Pairing> AccumulatorMembershipSubProtocol<'a, E> {
         Ok(())
     }
 
+    /// Same as `init` but uses the blinding of the message at index `msg_idx` in the initialized BBS+ sub-protocol
+    /// `linked_bbs_protocol` so the blinding doesn't have to be passed around. The accumulator member must be that
+    /// message. This only makes the responses for the member and the message equal, the verifier must still check
+    /// that they are, like by having the member and message in an `EqualWitnesses`.
+    pub fn init_linked<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        witness: crate::witness::Membership<E>,
+        linked_bbs_protocol: &PoKBBSSigG1SubProtocol<E>,
+        msg_idx: usize,
+    ) -> Result<(), ProofSystemError> {
+        let bbs_protocol = linked_bbs_protocol.protocol.as_ref().ok_or(
+            ProofSystemError::SubProtocolNotReadyToGenerateChallenge(linked_bbs_protocol.id),
+        )?;
+        let revealed_msg_ids = linked_bbs_protocol
+            .revealed_messages
+            .keys()
+            .copied()
+            .collect();
+        let blinding = *bbs_protocol.get_blinding_for_message(msg_idx, &revealed_msg_ids)?;
+        self.init(rng, Some(blinding), witness)
+    }
+
     pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
         if self.protocol.is_none() {
             return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
@@ -154,6 +242,13 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
         if self.protocol.is_some() {
             return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
         }
+        if witness.witness.d.is_zero() {
+            return Err(ProofSystemError::ArithmeticError(format!(
+                "d of the non-membership witness for {} statement {} is 0 so it can't be inverted",
+                SubProtocolType::AccumulatorNonMembership.label(),
+                self.id
+            )));
+        }
         let protocol = NonMembershipProofProtocol::init(
             rng,
             &witness.element,
@@ -205,6 +300,13 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
         params: impl Into<PreparedSetupParams<E>>,
         pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
     ) -> Result<(), ProofSystemError> {
+        // A non-membership proof can't be meaningful for an accumulator with the identity as its value
+        if self.accumulator_value.is_zero() {
+            return Err(ProofSystemError::InvalidAccumulatorValue(self.id));
+        }
+        // `d` isn't part of the proof, only blinded commitments to `d` and `d^-1` are, so there is no `d` to compare
+        // with 0 here. A proof for `d = 0` is rejected by the relation proven for `E_d_inv`, `d * E_d_inv + w * K = P`,
+        // which can't be satisfied with `d = 0` without knowing the discrete log of `P` with respect to `K`.
         match pairing_checker {
             Some(c) => proof.verify_with_randomized_pairing_checker(
                 &self.accumulator_value,
@@ -225,3 +327,407 @@ impl<'a, E: Pairing> AccumulatorNonMembershipSubProtocol<'a, E> {
         Ok(())
     }
 }
+
+impl<'a, E: Pairing> BatchAccumulatorNonMembershipSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        params: &'a AccumParams<E>,
+        public_key: &'a PublicKey<E>,
+        proving_key: &'a NonMembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Self {
+        Self {
+            id,
+            params,
+            public_key,
+            proving_key,
+            accumulator_value,
+            num_elements,
+            protocols: None,
+        }
+    }
+
+    /// `blindings[i]` is the blinding for the element at index `i` of the witness
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        mut blindings: Vec<Option<E::ScalarField>>,
+        witness: Vec<crate::witness::NonMembership<E>>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocols.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if witness.len() != self.num_elements {
+            return Err(ProofSystemError::BatchNonMembershipCountMismatch(
+                self.id,
+                self.num_elements,
+                witness.len(),
+            ));
+        }
+        let mut protocols = Vec::with_capacity(self.num_elements);
+        for (i, w) in witness.iter().enumerate() {
+            if w.witness.d.is_zero() {
+                return Err(ProofSystemError::ArithmeticError(format!(
+                    "d of the non-membership witness at index {} for {} statement {} is 0 so it can't be inverted",
+                    i,
+                    SubProtocolType::BatchAccumulatorNonMembership.label(),
+                    self.id
+                )));
+            }
+            protocols.push(NonMembershipProofProtocol::init(
+                rng,
+                &w.element,
+                blindings.get_mut(i).and_then(Option::take),
+                &w.witness,
+                self.public_key,
+                self.params,
+                self.proving_key,
+            ));
+        }
+        self.protocols = Some(protocols);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.protocols.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        for p in self.protocols.as_ref().unwrap() {
+            p.challenge_contribution(
+                &self.accumulator_value,
+                self.public_key,
+                self.params,
+                self.proving_key,
+                &mut writer,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocols.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocols = self.protocols.take().unwrap();
+        Ok(StatementProof::BatchAccumulatorNonMembership(
+            BatchAccumulatorNonMembershipProof {
+                proofs: protocols
+                    .into_iter()
+                    .map(|p| p.gen_proof(challenge))
+                    .collect(),
+            },
+        ))
+    }
+
+    /// Verifies the proof of each element except the pairing checks which are added to `pairing_checker` if
+    /// given. Otherwise, the pairing checks of all elements are combined using a randomized pairing check and
+    /// verified at once. This saves a final exponentiation per element but the number of miller loops still grows
+    /// linearly with the number of elements as each proof has its own G1 elements in the pairings.
+    pub fn verify_proof_contribution<R: RngCore>(
+        &self,
+        rng: &mut R,
+        challenge: &E::ScalarField,
+        proof: &BatchAccumulatorNonMembershipProof<E>,
+        pk: PreparedPublicKey<E>,
+        params: PreparedSetupParams<E>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        if proof.proofs.len() != self.num_elements {
+            return Err(ProofSystemError::BatchNonMembershipCountMismatch(
+                self.id,
+                self.num_elements,
+                proof.proofs.len(),
+            ));
+        }
+        // Same check as for a single non-membership proof, see `AccumulatorNonMembershipSubProtocol`
+        if self.accumulator_value.is_zero() {
+            return Err(ProofSystemError::InvalidAccumulatorValue(self.id));
+        }
+        let mut own_checker = None;
+        let checker = match pairing_checker {
+            Some(c) => c,
+            None => own_checker.insert(RandomizedPairingChecker::new_using_rng(rng, true)),
+        };
+        for p in &proof.proofs {
+            p.verify_with_randomized_pairing_checker(
+                &self.accumulator_value,
+                challenge,
+                pk.clone(),
+                params.clone(),
+                self.proving_key,
+                checker,
+            )?;
+        }
+        if let Some(c) = own_checker {
+            if !c.verify() {
+                return Err(ProofSystemError::InvalidNonMembershipProof);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, E: Pairing> BatchAccumulatorMembershipSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        params: &'a AccumParams<E>,
+        public_key: &'a PublicKey<E>,
+        proving_key: &'a MembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+        num_elements: usize,
+    ) -> Self {
+        Self {
+            id,
+            params,
+            public_key,
+            proving_key,
+            accumulator_value,
+            num_elements,
+            protocols: None,
+        }
+    }
+
+    /// `blindings[i]` is the blinding for the element at index `i` of the witness
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        mut blindings: Vec<Option<E::ScalarField>>,
+        witness: Vec<crate::witness::Membership<E>>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocols.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if witness.len() != self.num_elements {
+            return Err(ProofSystemError::BatchMembershipCountMismatch(
+                self.id,
+                self.num_elements,
+                witness.len(),
+            ));
+        }
+        let protocols = witness
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                MembershipProofProtocol::init(
+                    rng,
+                    &w.element,
+                    blindings.get_mut(i).and_then(Option::take),
+                    &w.witness,
+                    self.public_key,
+                    self.params,
+                    self.proving_key,
+                )
+            })
+            .collect();
+        self.protocols = Some(protocols);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.protocols.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        for p in self.protocols.as_ref().unwrap() {
+            p.challenge_contribution(
+                &self.accumulator_value,
+                self.public_key,
+                self.params,
+                self.proving_key,
+                &mut writer,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocols.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocols = self.protocols.take().unwrap();
+        Ok(StatementProof::BatchAccumulatorMembership(
+            BatchAccumulatorMembershipProof {
+                proofs: protocols
+                    .into_iter()
+                    .map(|p| p.gen_proof(challenge))
+                    .collect(),
+            },
+        ))
+    }
+
+    /// Verifies the proof of each element except the pairing checks which are added to `pairing_checker` if
+    /// given. Otherwise, the pairing checks of all elements are combined using a randomized pairing check and
+    /// verified with a single multi-pairing.
+    pub fn verify_proof_contribution<R: RngCore>(
+        &self,
+        rng: &mut R,
+        challenge: &E::ScalarField,
+        proof: &BatchAccumulatorMembershipProof<E>,
+        pk: PreparedPublicKey<E>,
+        params: PreparedSetupParams<E>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        if proof.proofs.len() != self.num_elements {
+            return Err(ProofSystemError::BatchMembershipCountMismatch(
+                self.id,
+                self.num_elements,
+                proof.proofs.len(),
+            ));
+        }
+        let mut own_checker = None;
+        let checker = match pairing_checker {
+            Some(c) => c,
+            None => own_checker.insert(RandomizedPairingChecker::new_using_rng(rng, true)),
+        };
+        for p in &proof.proofs {
+            p.verify_with_randomized_pairing_checker(
+                &self.accumulator_value,
+                challenge,
+                pk.clone(),
+                params.clone(),
+                self.proving_key,
+                checker,
+            )?;
+        }
+        if let Some(c) = own_checker {
+            if !c.verify() {
+                return Err(VBAccumulatorError::PairingResponseInvalid.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, E: Pairing> CommittedAccumulatorMembershipSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        params: &'a AccumParams<E>,
+        public_key: &'a PublicKey<E>,
+        proving_key: &'a MembershipProvingKey<E::G1Affine>,
+        commitment: E::G1Affine,
+        commitment_key: E::G1Affine,
+    ) -> Self {
+        Self {
+            id,
+            params,
+            public_key,
+            proving_key,
+            commitment,
+            commitment_key,
+            protocol: None,
+            randomness: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blinding: Option<E::ScalarField>,
+        witness: crate::witness::CommittedMembership<E>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let mut protocol = MembershipProofProtocol::init(
+            rng,
+            &witness.element,
+            blinding,
+            &witness.witness,
+            self.public_key,
+            self.params,
+            self.proving_key,
+        );
+        // R_E = R_E * e(H, P_tilde)^r_r
+        let randomness_blinding = E::ScalarField::rand(rng);
+        protocol.schnorr_commit.0.R_E += E::pairing(
+            self.commitment_key * randomness_blinding,
+            self.params.P_tilde,
+        );
+        self.protocol = Some(protocol);
+        self.randomness = Some((witness.randomness, randomness_blinding));
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol.as_ref().unwrap().challenge_contribution(
+            &self.commitment,
+            self.public_key,
+            self.params,
+            self.proving_key,
+            &mut writer,
+        )?;
+        self.commitment_key.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let protocol = self.protocol.take().unwrap();
+        let (randomness, randomness_blinding) = self.randomness.take().unwrap();
+        Ok(StatementProof::CommittedAccumulatorMembership(
+            CommittedAccumulatorMembershipProof {
+                proof: protocol.gen_proof(challenge),
+                randomness_response: randomness_blinding + *challenge * randomness,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &CommittedAccumulatorMembershipProof<E>,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        // C - s_r/c * H
+        let challenge_inv = challenge
+            .inverse()
+            .ok_or(VBAccumulatorError::PairingResponseInvalid)?;
+        let accumulator_value = (self.commitment.into_group()
+            - self.commitment_key * (proof.randomness_response * challenge_inv))
+            .into_affine();
+        match pairing_checker {
+            Some(c) => proof.proof.verify_with_randomized_pairing_checker(
+                &accumulator_value,
+                challenge,
+                pk,
+                params,
+                self.proving_key,
+                c,
+            )?,
+            None => {
+                proof
+                    .proof
+                    .verify(&accumulator_value, challenge, pk, params, self.proving_key)?
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,21 +1,18 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_std::{collections::BTreeMap, io::Write, rand::RngCore};
+use ark_std::{collections::BTreeMap, format, io::Write, rand::RngCore};
 use bbs_plus::{
+    error::BBSPlusError,
     prelude::{
         MultiMessageSignatureParams, PoKOfSignature23G1Proof, PoKOfSignature23G1Protocol,
         PreparedPublicKeyG2, PreparedSignatureParams23G1, PublicKeyG2, SignatureParams23G1,
     },
     proof::MessageOrBlinding,
 };
-use dock_crypto_utils::{
-    iter::take_while_satisfy, misc::seq_inc_by_n_from,
-    randomized_pairing_check::RandomizedPairingChecker, try_iter::CheckLeft,
-};
-use itertools::Itertools;
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
 
 use crate::{error::ProofSystemError, statement_proof::StatementProof};
 
-use super::merge_indexed_messages_with_blindings;
+use super::{merge_indexed_messages_with_blindings, merge_revealed_and_unrevealed_messages};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PoKBBSSigG1SubProtocol<'a, E: Pairing> {
@@ -1,21 +1,31 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_std::{collections::BTreeMap, io::Write, rand::RngCore};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    io::Write,
+    rand::RngCore,
+    vec::Vec,
+};
 use bbs_plus::{
+    error::BBSPlusError,
     prelude::{
         MultiMessageSignatureParams, PoKOfSignatureG1Proof, PreparedPublicKeyG2,
-        PreparedSignatureParamsG1, PublicKeyG2, SignatureParamsG1,
+        PreparedSignatureParamsG1, PublicKeyG2, SignatureG1, SignatureParamsG1,
     },
     proof::{MessageOrBlinding, PoKOfSignatureG1Protocol},
 };
-use dock_crypto_utils::{
-    iter::take_while_satisfy, misc::seq_inc_by_n_from,
-    randomized_pairing_check::RandomizedPairingChecker, try_iter::CheckLeft,
-};
-use itertools::Itertools;
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+use zeroize::Zeroize;
 
-use crate::{error::ProofSystemError, statement_proof::StatementProof};
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{PedersenCommitmentProof, StatementProof},
+};
 
-use super::merge_indexed_messages_with_blindings;
+use super::{
+    merge_indexed_messages_with_blindings, merge_revealed_and_unrevealed_messages,
+    schnorr::SchnorrProtocol,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PoKBBSSigG1SubProtocol<'a, E: Pairing> {
@@ -24,11 +34,19 @@ pub struct PoKBBSSigG1SubProtocol<'a, E: Pairing> {
     pub signature_params: &'a SignatureParamsG1<E>,
     pub public_key: &'a PublicKeyG2<E>,
     pub protocol: Option<PoKOfSignatureG1Protocol<E>>,
+    /// Indices of the hidden messages committed in `message_commitment`, in the order of its bases
+    pub committed_message_indices: Vec<usize>,
+    /// Proves knowledge of the hidden messages at `committed_message_indices` in a commitment under a basis other
+    /// than the signature params' bases, like one agreed with the signers during distributed issuance
+    pub message_commitment: Option<SchnorrProtocol<'a, E::G1Affine>>,
+    /// The signature when all messages are revealed and the prover chose to send it as it is, without a proof of
+    /// knowledge. See `Self::init_full_reveal`.
+    pub full_reveal_signature: Option<SignatureG1<E>>,
 }
 
 #[macro_export]
 macro_rules! impl_bbs_subprotocol {
-    ($params: ident, $wit: ident, $protocol: ident, $stmt_proof: ident, $proof: ident, $prepared_params: ident) => {
+    ($params: ident, $wit: ident, $protocol: ident, $stmt_proof: ident, $proof: ident, $prepared_params: ident $(, $extra_field: ident)* $(; $full_reveal_field: ident => $full_reveal_proof: ident)?) => {
         pub fn new(
             id: usize,
             revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
@@ -41,6 +59,8 @@ macro_rules! impl_bbs_subprotocol {
                 signature_params,
                 public_key,
                 protocol: None,
+                $($extra_field: Default::default(),)*
+                $($full_reveal_field: None,)?
             }
         }
 
@@ -50,7 +70,7 @@ macro_rules! impl_bbs_subprotocol {
             blindings: BTreeMap<usize, E::ScalarField>,
             witness: crate::witness::$wit<E>,
         ) -> Result<(), ProofSystemError> {
-            if self.protocol.is_some() {
+            if self.protocol.is_some() $(|| self.$full_reveal_field.is_some())? {
                 return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
             }
             let total_message_count =
@@ -72,17 +92,13 @@ macro_rules! impl_bbs_subprotocol {
                 &mut invalid_blinding_idx,
             );
             let mut non_seq_idx = None;
-            let all_messages = take_while_satisfy(
-                messages_to_commit.merge_by(
-                    self.revealed_messages
-                        .iter()
-                        .map(|(idx, msg)| (*idx, MessageOrBlinding::RevealMessage(msg))),
-                    |(a, _), (b, _)| a < b,
-                ),
-                CheckLeft(seq_inc_by_n_from(1, 0)),
+            let all_messages = merge_revealed_and_unrevealed_messages(
+                messages_to_commit,
+                self.revealed_messages
+                    .iter()
+                    .map(|(idx, msg)| (*idx, MessageOrBlinding::RevealMessage(msg))),
                 &mut non_seq_idx,
-            )
-            .map(|(_, msg)| msg);
+            );
 
             let protocol =
                 $protocol::init(rng, &witness.signature, self.signature_params, all_messages);
@@ -95,11 +111,24 @@ macro_rules! impl_bbs_subprotocol {
                 ))?
             }
 
-            self.protocol = Some(protocol?);
+            self.protocol = Some(protocol.map_err(|e| match e {
+                BBSPlusError::CannotInvert0 => ProofSystemError::ArithmeticError(format!(
+                    "randomness used for the signature of {} statement {} is 0 so it can't be inverted",
+                    $crate::sub_protocols::SubProtocolType::$stmt_proof.label(),
+                    self.id
+                )),
+                e => e.into(),
+            })?);
             Ok(())
         }
 
         pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+            $(
+                if let Some(signature) = &self.$full_reveal_field {
+                    ark_serialize::CanonicalSerialize::serialize_compressed(signature, writer)?;
+                    return Ok(());
+                }
+            )?
             if self.protocol.is_none() {
                 return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
                     self.id,
@@ -117,6 +146,11 @@ macro_rules! impl_bbs_subprotocol {
             &mut self,
             challenge: &E::ScalarField,
         ) -> Result<StatementProof<E, G>, ProofSystemError> {
+            $(
+                if let Some(signature) = self.$full_reveal_field.take() {
+                    return Ok(StatementProof::$full_reveal_proof(signature));
+                }
+            )?
             if self.protocol.is_none() {
                 return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
                     self.id,
@@ -127,6 +161,7 @@ macro_rules! impl_bbs_subprotocol {
             Ok(StatementProof::$stmt_proof(proof))
         }
 
+        /// Verify the proof and return the number of revealed messages it was verified against
         pub fn verify_proof_contribution(
             &self,
             challenge: &E::ScalarField,
@@ -134,7 +169,7 @@ macro_rules! impl_bbs_subprotocol {
             pk: impl Into<PreparedPublicKeyG2<E>>,
             params: impl Into<$prepared_params<E>>,
             pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
-        ) -> Result<(), ProofSystemError> {
+        ) -> Result<usize, ProofSystemError> {
             match pairing_checker {
                 Some(c) => proof.verify_with_randomized_pairing_checker(
                     self.revealed_messages,
@@ -145,7 +180,7 @@ macro_rules! impl_bbs_subprotocol {
                 )?,
                 None => proof.verify(self.revealed_messages, challenge, pk, params)?,
             }
-            Ok(())
+            Ok(self.revealed_messages.len())
         }
     };
 }
@@ -157,6 +192,453 @@ impl<'a, E: Pairing> PoKBBSSigG1SubProtocol<'a, E> {
         PoKOfSignatureG1Protocol,
         PoKBBSSignatureG1,
         PoKOfSignatureG1Proof,
-        PreparedSignatureParamsG1
+        PreparedSignatureParamsG1,
+        committed_message_indices,
+        message_commitment;
+        full_reveal_signature => BBSPlusFullReveal
     );
+
+    /// Initialize to send the signature itself rather than a proof of knowledge of it. Only possible when all
+    /// messages are revealed. Unlike a proof of knowledge, the signature is not bound to the challenge so it is
+    /// not bound to the nonce and anyone who sees it can replay it, and all proofs sending the same signature are
+    /// linkable.
+    pub fn init_full_reveal(
+        &mut self,
+        witness: crate::witness::PoKBBSSignatureG1<E>,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() || self.full_reveal_signature.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        // All messages must be revealed
+        let msg_count = self.signature_params.supported_message_count();
+        if !witness.unrevealed_messages.is_empty() || self.revealed_messages.len() != msg_count {
+            return Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                self.revealed_messages.len() + witness.unrevealed_messages.len(),
+                msg_count,
+            ));
+        }
+        self.full_reveal_signature = Some(witness.signature.clone());
+        Ok(())
+    }
+
+    /// Verify the signature sent instead of a proof of knowledge when all messages are revealed and return the
+    /// number of revealed messages
+    pub fn verify_full_reveal(
+        &self,
+        signature: &SignatureG1<E>,
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+    ) -> Result<usize, ProofSystemError> {
+        let msg_count = self.signature_params.supported_message_count();
+        if self.revealed_messages.len() != msg_count {
+            return Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                self.revealed_messages.len(),
+                msg_count,
+            ));
+        }
+        // As there are as many revealed messages as supported ones, they are in order iff none is out of bounds
+        if let Some(idx) = self.revealed_messages.keys().find(|i| **i >= msg_count) {
+            return Err(ProofSystemError::RevealedMessageIndexOutOfBounds(
+                *idx, msg_count,
+            ));
+        }
+        let messages = self.revealed_messages.values().copied().collect::<Vec<_>>();
+        signature.verify(&messages, pk, params)?;
+        Ok(self.revealed_messages.len())
+    }
+
+    /// Same as `init` but takes the blinded signature the signer created in blind issuance along with the `blinding`
+    /// used to commit to the hidden messages. The signature is unblinded here so the unblinded signature is only
+    /// part of the witness which is zeroized when `init` returns.
+    pub fn init_with_blinded_sig<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: BTreeMap<usize, E::ScalarField>,
+        blinded_sig: SignatureG1<E>,
+        blinding: &E::ScalarField,
+        unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        self.init(
+            rng,
+            blindings,
+            crate::witness::PoKBBSSignatureG1 {
+                signature: blinded_sig.unblind(blinding),
+                unrevealed_messages,
+            },
+        )
+    }
+
+    /// Also prove knowledge of the hidden messages at `message_indices` in `commitment` which is
+    /// `basis[0] * m_{message_indices[0]} + basis[1] * m_{message_indices[1]} + ... + basis[n] * r` for
+    /// randomness `r`. The commitment's protocol uses the same blindings as the signature's protocol for these
+    /// messages so the responses of both are equal. Both the prover and the verifier call this.
+    pub fn with_message_commitment(
+        mut self,
+        message_indices: Vec<usize>,
+        basis: &'a [E::G1Affine],
+        commitment: E::G1Affine,
+    ) -> Result<Self, ProofSystemError> {
+        if basis.len() != message_indices.len() + 1 {
+            return Err(ProofSystemError::InvalidMessageCommitmentBasis(
+                message_indices.len(),
+                basis.len(),
+            ));
+        }
+        self.message_commitment = Some(SchnorrProtocol::new(self.id, basis, commitment));
+        self.committed_message_indices = message_indices;
+        Ok(self)
+    }
+
+    /// Same as `init` but also initializes the protocol for the commitment given in `with_message_commitment`.
+    /// `commitment_randomness` is the randomness `r` of the commitment.
+    pub fn init_with_message_commitment<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: BTreeMap<usize, E::ScalarField>,
+        witness: crate::witness::PoKBBSSignatureG1<E>,
+        commitment_randomness: E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if self.message_commitment.is_none() {
+            return Err(ProofSystemError::MessageCommitmentNotGiven(self.id));
+        }
+        let mut witnesses = self
+            .committed_message_indices
+            .iter()
+            .map(|i| {
+                witness
+                    .unrevealed_messages
+                    .get(i)
+                    .copied()
+                    .ok_or(BBSPlusError::InvalidMsgIdxForResponse(*i))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        witnesses.push(commitment_randomness);
+        self.init(rng, blindings, witness)?;
+
+        let revealed_msg_ids = self
+            .revealed_messages
+            .keys()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        // There is no protocol when all messages are revealed but then no message can be committed either
+        let commitment_blindings = match self.protocol.as_ref() {
+            Some(protocol) => self
+                .committed_message_indices
+                .iter()
+                .enumerate()
+                .map(|(j, i)| {
+                    Ok((
+                        j,
+                        *protocol.get_blinding_for_message(*i, &revealed_msg_ids)?,
+                    ))
+                })
+                .collect::<Result<BTreeMap<_, _>, BBSPlusError>>()?,
+            None => BTreeMap::new(),
+        };
+        self.message_commitment
+            .as_mut()
+            .unwrap()
+            .init(rng, commitment_blindings, witnesses)
+    }
+
+    pub fn message_commitment_challenge_contribution<W: Write>(
+        &self,
+        writer: W,
+    ) -> Result<(), ProofSystemError> {
+        self.message_commitment
+            .as_ref()
+            .ok_or(ProofSystemError::MessageCommitmentNotGiven(self.id))?
+            .challenge_contribution(writer)
+    }
+
+    pub fn gen_message_commitment_proof(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<PedersenCommitmentProof<E::G1Affine>, ProofSystemError> {
+        self.message_commitment
+            .as_mut()
+            .ok_or(ProofSystemError::MessageCommitmentNotGiven(self.id))?
+            .gen_proof_contribution_as_struct(challenge)
+    }
+
+    /// Verify the proof of knowledge of the committed messages and that its responses for the messages are the
+    /// responses in the signature's `proof`, i.e. the committed messages are the signed ones
+    pub fn verify_message_commitment_proof(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &PoKOfSignatureG1Proof<E>,
+        commitment_proof: &PedersenCommitmentProof<E::G1Affine>,
+    ) -> Result<(), ProofSystemError> {
+        let message_commitment = self
+            .message_commitment
+            .as_ref()
+            .ok_or(ProofSystemError::MessageCommitmentNotGiven(self.id))?;
+        let revealed_msg_ids = self
+            .revealed_messages
+            .keys()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        for (j, i) in self.committed_message_indices.iter().enumerate() {
+            if proof.get_resp_for_message(*i, &revealed_msg_ids)?
+                != commitment_proof.response.get_response(j)?
+            {
+                return Err(ProofSystemError::WitnessResponseNotEqual(self.id, *i));
+            }
+        }
+        message_commitment.verify_proof_contribution_as_struct(challenge, commitment_proof)
+    }
+}
+
+impl<'a, E: Pairing> Zeroize for PoKBBSSigG1SubProtocol<'a, E> {
+    fn zeroize(&mut self) {
+        // Zeroizes the blindings of the protocols and sets them to `None`
+        self.protocol.zeroize();
+        self.message_commitment.zeroize();
+    }
+}
+
+impl<'a, E: Pairing> Drop for PoKBBSSigG1SubProtocol<'a, E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{proof_context::ProofContext, witness::PoKBBSSignatureG1 as PoKBBSSignatureG1Wit};
+    use ark_bls12_381::{Fr, G1Affine};
+    use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+    use ark_std::{
+        rand::{prelude::StdRng, SeedableRng},
+        vec, UniformRand,
+    };
+    use blake2::Blake2b512;
+    use test_utils::bbs::bbs_plus_sig_setup;
+
+    #[test]
+    fn zeroize_protocol() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+        let revealed_messages = BTreeMap::new();
+
+        let mut sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key);
+        sp.init(
+            &mut rng,
+            BTreeMap::new(),
+            PoKBBSSignatureG1Wit {
+                signature: sig,
+                unrevealed_messages: msgs.into_iter().enumerate().collect(),
+            },
+        )
+        .unwrap();
+        assert!(sp.protocol.is_some());
+
+        // Dropping the sub-protocol calls this
+        sp.zeroize();
+        assert!(sp.protocol.is_none());
+    }
+
+    #[test]
+    fn hidden_messages_committed_under_given_basis() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+        let revealed_messages = BTreeMap::from([(0, msgs[0])]);
+        let unrevealed_messages = msgs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, m)| (i, *m))
+            .collect::<BTreeMap<_, _>>();
+
+        // Messages 1 and 3 are committed under a basis unrelated to the signature params, like one agreed during
+        // distributed issuance, with the last base for the randomness
+        let committed = vec![1, 3];
+        let basis = (0..3).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+        let randomness = Fr::rand(&mut rng);
+        let commitment =
+            <G1Affine as AffineRepr>::Group::msm_unchecked(&basis, &[msgs[1], msgs[3], randomness])
+                .into_affine();
+
+        let mut prover_sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key)
+                .with_message_commitment(committed.clone(), &basis, commitment)
+                .unwrap();
+        prover_sp
+            .init_with_message_commitment(
+                &mut rng,
+                BTreeMap::new(),
+                PoKBBSSignatureG1Wit {
+                    signature: sig.clone(),
+                    unrevealed_messages: unrevealed_messages.clone(),
+                },
+                randomness,
+            )
+            .unwrap();
+        let mut ctx = ProofContext::<Blake2b512>::new();
+        prover_sp.challenge_contribution(&mut ctx).unwrap();
+        prover_sp
+            .message_commitment_challenge_contribution(&mut ctx)
+            .unwrap();
+        let challenge = ctx.finalize::<Fr>();
+        let proof = match prover_sp
+            .gen_proof_contribution::<G1Affine>(&challenge)
+            .unwrap()
+        {
+            StatementProof::PoKBBSSignatureG1(p) => p,
+            _ => panic!("expected a proof of knowledge of BBS+ signature"),
+        };
+        let commitment_proof = prover_sp.gen_message_commitment_proof(&challenge).unwrap();
+
+        let verifier_sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key)
+                .with_message_commitment(committed, &basis, commitment)
+                .unwrap();
+        verifier_sp
+            .verify_proof_contribution(
+                &challenge,
+                &proof,
+                keypair.public_key.clone(),
+                params.clone(),
+                &mut None,
+            )
+            .unwrap();
+        verifier_sp
+            .verify_message_commitment_proof(&challenge, &proof, &commitment_proof)
+            .unwrap();
+
+        // The commitment is not to the messages at these indices
+        let verifier_sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key)
+                .with_message_commitment(vec![1, 2], &basis, commitment)
+                .unwrap();
+        assert!(matches!(
+            verifier_sp.verify_message_commitment_proof(&challenge, &proof, &commitment_proof),
+            Err(ProofSystemError::WitnessResponseNotEqual(0, 2))
+        ));
+
+        // The prover can't commit to a message other than the signed one
+        let other_commitment =
+            <G1Affine as AffineRepr>::Group::msm_unchecked(&basis, &[msgs[1], msgs[2], randomness])
+                .into_affine();
+        let mut prover_sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key)
+                .with_message_commitment(vec![1, 3], &basis, other_commitment)
+                .unwrap();
+        prover_sp
+            .init_with_message_commitment(
+                &mut rng,
+                BTreeMap::new(),
+                PoKBBSSignatureG1Wit {
+                    signature: sig,
+                    unrevealed_messages,
+                },
+                randomness,
+            )
+            .unwrap();
+        let proof = match prover_sp
+            .gen_proof_contribution::<G1Affine>(&challenge)
+            .unwrap()
+        {
+            StatementProof::PoKBBSSignatureG1(p) => p,
+            _ => panic!("expected a proof of knowledge of BBS+ signature"),
+        };
+        let commitment_proof = prover_sp.gen_message_commitment_proof(&challenge).unwrap();
+        let verifier_sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key)
+                .with_message_commitment(vec![1, 3], &basis, other_commitment)
+                .unwrap();
+        assert!(verifier_sp
+            .verify_message_commitment_proof(&challenge, &proof, &commitment_proof)
+            .is_err());
+
+        // A base is needed for each committed message and the randomness
+        assert!(matches!(
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key)
+                .with_message_commitment(vec![1, 2, 3], &basis, commitment),
+            Err(ProofSystemError::InvalidMessageCommitmentBasis(3, 3))
+        ));
+    }
+
+    #[test]
+    fn init_with_blinded_signature() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (msgs, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+
+        // Messages 1 and 3 are hidden from the signer
+        let blinding = Fr::rand(&mut rng);
+        let committed = BTreeMap::from([(1, &msgs[1]), (3, &msgs[3])]);
+        let uncommitted = msgs
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !committed.contains_key(i))
+            .collect::<BTreeMap<_, _>>();
+        let commitment = params
+            .commit_to_messages(committed.clone(), &blinding)
+            .unwrap();
+        let blinded_sig = SignatureG1::new_with_committed_messages(
+            &mut rng,
+            &commitment,
+            uncommitted,
+            &keypair.secret_key,
+            &params,
+        )
+        .unwrap();
+        assert!(blinded_sig
+            .verify(&msgs, keypair.public_key.clone(), params.clone())
+            .is_err());
+
+        let revealed_messages = BTreeMap::from([(0, msgs[0])]);
+        let unrevealed_messages = msgs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, m)| (i, *m))
+            .collect::<BTreeMap<_, _>>();
+        let prove = |blinding: &Fr, rng: &mut StdRng| {
+            let mut sp =
+                PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key);
+            sp.init_with_blinded_sig(
+                rng,
+                BTreeMap::new(),
+                blinded_sig.clone(),
+                blinding,
+                unrevealed_messages.clone(),
+            )
+            .unwrap();
+            let mut ctx = ProofContext::<Blake2b512>::new();
+            sp.challenge_contribution(&mut ctx).unwrap();
+            let challenge = ctx.finalize::<Fr>();
+            match sp.gen_proof_contribution::<G1Affine>(&challenge).unwrap() {
+                StatementProof::PoKBBSSignatureG1(p) => (challenge, p),
+                _ => panic!("expected a proof of knowledge of BBS+ signature"),
+            }
+        };
+        let verifier_sp =
+            PoKBBSSigG1SubProtocol::new(0, &revealed_messages, &params, &keypair.public_key);
+
+        let (challenge, proof) = prove(&blinding, &mut rng);
+        verifier_sp
+            .verify_proof_contribution(
+                &challenge,
+                &proof,
+                keypair.public_key.clone(),
+                params.clone(),
+                &mut None,
+            )
+            .unwrap();
+
+        // Unblinding with a different blinding doesn't give a valid signature
+        let (challenge, proof) = prove(&Fr::rand(&mut rng), &mut rng);
+        assert!(verifier_sp
+            .verify_proof_contribution(
+                &challenge,
+                &proof,
+                keypair.public_key.clone(),
+                params.clone(),
+                &mut None,
+            )
+            .is_err());
+    }
 }
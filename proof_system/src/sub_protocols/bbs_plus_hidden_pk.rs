@@ -0,0 +1,409 @@
+//! Proof of knowledge of a BBS+ signature by one of several public keys without revealing which one.
+//!
+//! For a signature `(A, e, s)` valid under public key `W = g2 * x`, the prover picks a random `r` and proves knowledge
+//! of the signature `(A * 1/r, e * r, s)` using the BBS+ protocol under the randomized public key `W * r`. The
+//! randomized signature is a valid BBS+ signature under the secret key `x * r`, whose public key is `W * r`, as
+//! `(A * 1/r) * (e * r + x * r) = A * (e + x)`. The prover additionally proves that `W * r` is a multiple of one of
+//! the public keys using an OR composition of Schnorr proofs where the proofs for all public keys except the
+//! signer's are simulated.
+//!
+//! The proof shouldn't reveal the signer's public key through its structure or through the time taken to create it.
+//! The real and simulated Schnorr proofs have the same form, and the same random values and group operations are
+//! used whichever public key is the signer's. Which branch is real is selected using constant-time equality of
+//! indices and arithmetic selection rather than branching on the index. The group and field arithmetic itself
+//! comes from arkworks which doesn't guarantee constant-time operations.
+//!
+//! To prove that several signatures are by the same issuer without revealing it, as required by
+//! `MetaStatement::IssuerConsistency`, the prover uses the same `r` for all of them so their randomized public keys
+//! are equal and the verifier compares them. A cheating prover with signatures by different issuers `W1` and `W2`
+//! would need to know `r1` and `r2` with `W1 * r1 = W2 * r2`, i.e. the discrete log of `W2` with respect to `W1`.
+//! The randomized public keys of proofs not in the same issuer consistency are unlinkable as before.
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, format, io::Write, rand::RngCore, vec::Vec, UniformRand};
+use bbs_plus::{
+    error::BBSPlusError,
+    prelude::{
+        MultiMessageSignatureParams, PreparedPublicKeyG2, PreparedSignatureParamsG1, PublicKeyG2,
+        SignatureG1, SignatureParamsG1,
+    },
+    proof::{MessageOrBlinding, PoKOfSignatureG1Protocol},
+};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{PoKBBSSignatureG1HiddenPKProof, RandomizedPublicKeyProof, StatementProof},
+    witness::PoKBBSSignatureG1HiddenPK,
+};
+
+use super::{
+    merge_indexed_messages_with_blindings, merge_revealed_and_unrevealed_messages, SubProtocolType,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoKBBSSigG1HiddenPKSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+    pub signature_params: &'a SignatureParamsG1<E>,
+    pub public_keys: &'a [PublicKeyG2<E>],
+    pub protocol: Option<PoKOfSignatureG1Protocol<E>>,
+    pub pk_protocol: Option<RandomizedPublicKeyProtocol<E>>,
+}
+
+/// Pre-challenge state of the proof that the randomized public key is a multiple of one of the public keys
+#[derive(Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct RandomizedPublicKeyProtocol<E: Pairing> {
+    #[zeroize(skip)]
+    pub randomized_pk: E::G2Affine,
+    #[zeroize(skip)]
+    pub t: Vec<E::G2Affine>,
+    /// Index of the signer's public key
+    index: usize,
+    /// The scalar the signer's public key is multiplied with
+    randomizer: E::ScalarField,
+    /// Randomness for the signer's public key's Schnorr proof
+    blinding: E::ScalarField,
+    /// Challenges and responses of the simulated Schnorr proofs. The entries at `index` are 0 and the blinding and
+    /// are replaced when creating the proof.
+    simulated_challenges: Vec<E::ScalarField>,
+    simulated_responses: Vec<E::ScalarField>,
+}
+
+impl<'a, E: Pairing> PoKBBSSigG1HiddenPKSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+        signature_params: &'a SignatureParamsG1<E>,
+        public_keys: &'a [PublicKeyG2<E>],
+    ) -> Self {
+        Self {
+            id,
+            revealed_messages,
+            signature_params,
+            public_keys,
+            protocol: None,
+            pk_protocol: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: BTreeMap<usize, E::ScalarField>,
+        witness: PoKBBSSignatureG1HiddenPK<E>,
+    ) -> Result<(), ProofSystemError> {
+        let randomizer = E::ScalarField::rand(rng);
+        self.init_with_randomizer(rng, blindings, witness, randomizer)
+    }
+
+    /// Same as `Self::init` but the public key is randomized with the given `randomizer`. Proofs of signatures by
+    /// the same public key created with the same randomizer have the same randomized public key, which is how
+    /// `MetaStatement::IssuerConsistency` is proven. The randomizer should be random and not used for any other
+    /// public key.
+    pub fn init_with_randomizer<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        blindings: BTreeMap<usize, E::ScalarField>,
+        witness: PoKBBSSignatureG1HiddenPK<E>,
+        randomizer: E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if self.public_keys.is_empty() {
+            return Err(ProofSystemError::NoPublicKeysForHiddenPK(self.id));
+        }
+        if witness.public_key_index >= self.public_keys.len() {
+            return Err(ProofSystemError::InvalidHiddenPKIndex(
+                self.id,
+                witness.public_key_index,
+            ));
+        }
+        let total_message_count = self.revealed_messages.len() + witness.unrevealed_messages.len();
+        if total_message_count != self.signature_params.supported_message_count() {
+            Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                total_message_count,
+                self.signature_params.supported_message_count(),
+            ))?
+        }
+
+        let randomizer_inv = randomizer.inverse().ok_or_else(|| {
+            ProofSystemError::ArithmeticError(format!(
+                "randomizer of the public key of {} statement {} is 0 so it can't be inverted",
+                SubProtocolType::PoKBBSSignatureG1HiddenPK.label(),
+                self.id
+            ))
+        })?;
+        // (A * 1/r, e * r, s)
+        let randomized_sig = SignatureG1::<E> {
+            A: (witness.signature.A * randomizer_inv).into_affine(),
+            e: witness.signature.e * randomizer,
+            s: witness.signature.s,
+        };
+
+        // Create messages from revealed messages in statement and unrevealed in witness
+        let mut invalid_blinding_idx = None;
+        let messages_to_commit = merge_indexed_messages_with_blindings(
+            &witness.unrevealed_messages,
+            blindings,
+            MessageOrBlinding::BlindMessageRandomly,
+            MessageOrBlinding::blind_message_with,
+            &mut invalid_blinding_idx,
+        );
+        let mut non_seq_idx = None;
+        let all_messages = merge_revealed_and_unrevealed_messages(
+            messages_to_commit,
+            self.revealed_messages
+                .iter()
+                .map(|(idx, msg)| (*idx, MessageOrBlinding::RevealMessage(msg))),
+            &mut non_seq_idx,
+        );
+
+        let protocol = PoKOfSignatureG1Protocol::init(
+            rng,
+            &randomized_sig,
+            self.signature_params,
+            all_messages,
+        );
+        if let Some(idx) = invalid_blinding_idx {
+            Err(ProofSystemError::BBSProtocolInvalidBlindingIndex(idx))?
+        } else if let Some(invalid) = non_seq_idx {
+            Err(invalid.over(
+                ProofSystemError::BBSProtocolMessageIndicesMustStartFromZero,
+                ProofSystemError::BBSProtocolNonSequentialMessageIndices,
+            ))?
+        }
+        self.protocol = Some(protocol.map_err(|e| match e {
+            BBSPlusError::CannotInvert0 => ProofSystemError::ArithmeticError(format!(
+                "randomness used for the signature of {} statement {} is 0 so it can't be inverted",
+                SubProtocolType::PoKBBSSignatureG1HiddenPK.label(),
+                self.id
+            )),
+            e => e.into(),
+        })?);
+        self.pk_protocol = Some(RandomizedPublicKeyProtocol::init(
+            rng,
+            self.public_keys,
+            witness.public_key_index,
+            randomizer,
+        ));
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol.as_ref().unwrap().challenge_contribution(
+            self.revealed_messages,
+            self.signature_params,
+            &mut writer,
+        )?;
+        let pk_protocol = self.pk_protocol.as_ref().unwrap();
+        Self::pk_challenge_contribution(
+            self.public_keys,
+            &pk_protocol.randomized_pk,
+            &pk_protocol.t,
+            writer,
+        )
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let proof = self.protocol.take().unwrap().gen_proof(challenge)?;
+        let pk_protocol = self.pk_protocol.take().unwrap();
+        Ok(StatementProof::PoKBBSSignatureG1HiddenPK(
+            PoKBBSSignatureG1HiddenPKProof {
+                proof,
+                randomized_pk: pk_protocol.randomized_pk,
+                pk_proof: pk_protocol.gen_proof(challenge),
+            },
+        ))
+    }
+
+    /// Verify the proof and return the number of revealed messages it was verified against
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &PoKBBSSignatureG1HiddenPKProof<E>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<usize, ProofSystemError> {
+        self.verify_randomized_pk(challenge, proof)?;
+        let pk = PreparedPublicKeyG2::from(PublicKeyG2::<E>(proof.randomized_pk));
+        match pairing_checker {
+            Some(c) => proof.proof.verify_with_randomized_pairing_checker(
+                self.revealed_messages,
+                challenge,
+                pk,
+                params,
+                c,
+            )?,
+            None => proof
+                .proof
+                .verify(self.revealed_messages, challenge, pk, params)?,
+        }
+        Ok(self.revealed_messages.len())
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        revealed_messages: &BTreeMap<usize, E::ScalarField>,
+        signature_params: &SignatureParamsG1<E>,
+        public_keys: &[PublicKeyG2<E>],
+        proof: &PoKBBSSignatureG1HiddenPKProof<E>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .proof
+            .challenge_contribution(revealed_messages, signature_params, &mut writer)?;
+        Self::pk_challenge_contribution(
+            public_keys,
+            &proof.randomized_pk,
+            &proof.pk_proof.t,
+            writer,
+        )
+    }
+
+    /// Check that the randomized public key is a multiple of one of the public keys, i.e. for each public key
+    /// `pk_j`, `pk_j * response_j == t_j + randomized_pk * challenge_j` and the challenges add up to `challenge`
+    fn verify_randomized_pk(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &PoKBBSSignatureG1HiddenPKProof<E>,
+    ) -> Result<(), ProofSystemError> {
+        let pk_proof = &proof.pk_proof;
+        let n = self.public_keys.len();
+        if n == 0 {
+            return Err(ProofSystemError::NoPublicKeysForHiddenPK(self.id));
+        }
+        // A randomized key of 0 is a multiple of every key
+        if proof.randomized_pk.is_zero()
+            || pk_proof.t.len() != n
+            || pk_proof.challenges.len() != n
+            || pk_proof.responses.len() != n
+        {
+            return Err(ProofSystemError::InvalidRandomizedPublicKeyProof(self.id));
+        }
+        let challenge_sum = pk_proof
+            .challenges
+            .iter()
+            .fold(E::ScalarField::zero(), |s, c| s + c);
+        if challenge_sum != *challenge {
+            return Err(ProofSystemError::InvalidRandomizedPublicKeyProof(self.id));
+        }
+        for (j, pk) in self.public_keys.iter().enumerate() {
+            if pk.0 * pk_proof.responses[j]
+                != pk_proof.t[j].into_group() + proof.randomized_pk * pk_proof.challenges[j]
+            {
+                return Err(ProofSystemError::InvalidRandomizedPublicKeyProof(self.id));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn pk_challenge_contribution<W: Write>(
+        public_keys: &[PublicKeyG2<E>],
+        randomized_pk: &E::G2Affine,
+        t: &[E::G2Affine],
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        public_keys.serialize_compressed(&mut writer)?;
+        randomized_pk.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl<E: Pairing> RandomizedPublicKeyProtocol<E> {
+    /// Randomize the public key at `index` with `randomizer`, commit to the randomness of its Schnorr proof and
+    /// simulate the Schnorr proofs of the other public keys
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        public_keys: &[PublicKeyG2<E>],
+        index: usize,
+        randomizer: E::ScalarField,
+    ) -> Self {
+        let randomized_pk = (public_keys[index].0 * randomizer).into_affine();
+        let blinding = E::ScalarField::rand(rng);
+        let mut simulated_challenges = Vec::with_capacity(public_keys.len());
+        let mut simulated_responses = Vec::with_capacity(public_keys.len());
+        // A challenge and response is sampled even for the signer's public key so that the randomness and the group
+        // operations don't depend on `index`. For the signer's public key, the challenge is 0 and the response is
+        // the blinding which makes `t` a commitment to the blinding.
+        let t = public_keys
+            .iter()
+            .enumerate()
+            .map(|(j, pk)| {
+                let is_real = j.ct_eq(&index);
+                let c = select(is_real, E::ScalarField::zero(), E::ScalarField::rand(rng));
+                let z = select(is_real, blinding, E::ScalarField::rand(rng));
+                simulated_challenges.push(c);
+                simulated_responses.push(z);
+                pk.0 * z - randomized_pk * c
+            })
+            .collect::<Vec<_>>();
+        Self {
+            randomized_pk,
+            t: E::G2::normalize_batch(&t),
+            index,
+            randomizer,
+            blinding,
+            simulated_challenges,
+            simulated_responses,
+        }
+    }
+
+    /// The challenge of the signer's public key's Schnorr proof is `challenge` minus the challenges of the
+    /// simulated proofs
+    pub fn gen_proof(mut self, challenge: &E::ScalarField) -> RandomizedPublicKeyProof<E> {
+        let mut challenges = core::mem::take(&mut self.simulated_challenges);
+        let mut responses = core::mem::take(&mut self.simulated_responses);
+        // The challenge at `index` is 0 so it doesn't contribute to the sum
+        let c = challenges.iter().fold(*challenge, |c, c_j| c - c_j);
+        let z = self.blinding + c * self.randomizer;
+        // Every entry is written to rather than only the one at `index`
+        for (j, (c_j, z_j)) in challenges.iter_mut().zip(responses.iter_mut()).enumerate() {
+            let is_real = j.ct_eq(&self.index);
+            *c_j = select(is_real, c, *c_j);
+            *z_j = select(is_real, z, *z_j);
+        }
+        RandomizedPublicKeyProof {
+            t: core::mem::take(&mut self.t),
+            challenges,
+            responses,
+        }
+    }
+}
+
+/// `a` if `choice` is set, `b` otherwise, computed without branching on `choice`
+fn select<F: PrimeField>(choice: Choice, a: F, b: F) -> F {
+    let s = F::from(choice.unwrap_u8() as u64);
+    s * a + (F::one() - s) * b
+}
+
+impl<'a, E: Pairing> Zeroize for PoKBBSSigG1HiddenPKSubProtocol<'a, E> {
+    fn zeroize(&mut self) {
+        self.protocol.zeroize();
+        self.pk_protocol.zeroize();
+    }
+}
+
+impl<'a, E: Pairing> Drop for PoKBBSSigG1HiddenPKSubProtocol<'a, E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
@@ -5,7 +5,6 @@ use crate::{
     sub_protocols::{enforce_and_get_u64, schnorr::SchnorrProtocol},
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::CanonicalSerialize;
 use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, UniformRand};
 use bulletproofs_plus_plus::{prelude::ProofArbitraryRange, setup::SetupParams};
 use dock_crypto_utils::transcript::Transcript;
@@ -104,21 +103,17 @@ impl<'a, G: AffineRepr> BoundCheckBppProtocol<'a, G> {
     }
 
     /// Generate challenge contribution for both the Schnorr protocols
-    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
         if self.sp1.is_none() || self.sp2.is_none() {
             return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
                 self.id,
             ));
         }
-        self.sp1
-            .as_ref()
-            .unwrap()
-            .challenge_contribution(&mut writer)?;
-        self.sp2
-            .as_ref()
-            .unwrap()
-            .challenge_contribution(&mut writer)?;
-        Ok(())
+        // Both protocols use the same commitment key
+        SchnorrProtocol::batch_challenge_contribution(
+            &[self.sp1.as_ref().unwrap(), self.sp2.as_ref().unwrap()],
+            writer,
+        )
     }
 
     /// Generate responses for both the Schnorr protocols
@@ -182,11 +177,11 @@ impl<'a, G: AffineRepr> BoundCheckBppProtocol<'a, G> {
             .bpp_proof
             .get_commitments_to_values_given_g(vec![(min, max)], &comm_key[0])?;
         let (comm_1, comm_2) = comms.remove(0);
-        comm_key.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
         comm_1.serialize_compressed(&mut writer)?;
         proof.sp1.t.serialize_compressed(&mut writer)?;
         // Serializing `comm_key` twice to match what happens in `Self::challenge_contribution`
-        comm_key.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
         comm_2.serialize_compressed(&mut writer)?;
         proof.sp2.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -3,7 +3,7 @@ use crate::{
     statement_proof::{
         BoundCheckLegoGroth16Proof, BoundCheckLegoGroth16ProofWhenAggregatingSnarks, StatementProof,
     },
-    sub_protocols::schnorr::SchnorrProtocol,
+    sub_protocols::{schnorr::SchnorrProtocol, validate_bounds},
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_ff::{Field, PrimeField};
@@ -87,6 +87,7 @@ impl<'a, E: Pairing> BoundCheckLegoGrothProtocol<'a, E> {
         if self.sp.is_some() {
             return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
         }
+        validate_bounds(self.min, self.max)?;
         let proving_key = self
             .proving_key
             .ok_or(ProofSystemError::LegoGroth16ProvingKeyNotProvided)?;
@@ -117,6 +118,7 @@ impl<'a, E: Pairing> BoundCheckLegoGrothProtocol<'a, E> {
         if self.sp.is_some() {
             return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
         }
+        validate_bounds(self.min, self.max)?;
         let proving_key = self
             .proving_key
             .ok_or(ProofSystemError::LegoGroth16ProvingKeyNotProvided)?;
@@ -181,10 +183,7 @@ impl<'a, E: Pairing> BoundCheckLegoGrothProtocol<'a, E> {
         pvk: &PreparedVerifyingKey<E>,
         pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
     ) -> Result<(), ProofSystemError> {
-        let pub_inp = &[
-            E::ScalarField::from(self.min),
-            E::ScalarField::from(self.max),
-        ];
+        let pub_inp = &self.public_inputs();
         let snark_proof = &proof.snark_proof;
         match pairing_checker {
             Some(c) => {
@@ -219,12 +218,18 @@ impl<'a, E: Pairing> BoundCheckLegoGrothProtocol<'a, E> {
         sp.verify_proof_contribution_as_struct(challenge, &proof.sp)
     }
 
+    /// Public inputs of the LegoGroth16 proof, i.e. the bounds. The message isn't a public input but is committed
+    /// in the proof.
+    pub fn public_inputs(&self) -> Vec<E::ScalarField> {
+        bound_check_public_inputs(self.min, self.max)
+    }
+
     pub fn compute_challenge_contribution<W: Write>(
         comm_key: &[E::G1Affine],
         proof: &BoundCheckLegoGroth16Proof<E>,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        comm_key.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
         proof.snark_proof.d.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -235,7 +240,7 @@ impl<'a, E: Pairing> BoundCheckLegoGrothProtocol<'a, E> {
         proof: &BoundCheckLegoGroth16ProofWhenAggregatingSnarks<E>,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        comm_key.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
         proof.commitment.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -329,6 +334,11 @@ impl<ConstraintF: PrimeField> ConstraintSynthesizer<ConstraintF>
     }
 }
 
+/// Public inputs of the LegoGroth16 proof for bounds `[min, max)` in the order used in its verification
+pub fn bound_check_public_inputs<F: PrimeField>(min: u64, max: u64) -> Vec<F> {
+    vec![F::from(min), F::from(max)]
+}
+
 /// Generate SNARK proving key and verification key for a circuit that checks that given a witness
 /// `w` and public inputs `min` and `max`, `min <= w < max`
 pub fn generate_snark_srs_bound_check<E, R>(rng: &mut R) -> Result<ProvingKey<E>, ProofSystemError>
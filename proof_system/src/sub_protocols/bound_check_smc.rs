@@ -1,17 +1,27 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::CanonicalSerialize;
-use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, UniformRand};
+use ark_std::{
+    collections::BTreeMap, format, io::Write, rand::RngCore, vec, vec::Vec, UniformRand,
+};
 
 use crate::{
     error::ProofSystemError,
     prelude::bound_check_smc::SmcParamsWithPairingAndCommitmentKey,
     statement::bound_check_smc::SmcParamsAndCommitmentKey,
     statement_proof::{BoundCheckSmcInnerProof, BoundCheckSmcProof, StatementProof},
-    sub_protocols::{enforce_and_get_u64, schnorr::SchnorrProtocol, should_use_cls},
+    sub_protocols::{
+        enforce_and_get_u64, schnorr::SchnorrProtocol, should_use_cls, validate_bounds,
+        SubProtocolType,
+    },
 };
 use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
-use smc_range_proof::prelude::{
-    CCSArbitraryRangeProofProtocol, CLSRangeProofProtocol, SetMembershipCheckParamsWithPairing,
+use smc_range_proof::{
+    ccs_range_proof::util::find_l_greater_than,
+    common::base_n_digits,
+    prelude::{
+        decompose_value as cls_decompose_value, CCSArbitraryRangeProofProtocol,
+        CLSRangeProofProtocol, SetMembershipCheckParamsWithPairing, SmcRangeProofError,
+    },
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -46,6 +56,37 @@ impl<'a, E: Pairing> BoundCheckSmcProtocol<'a, E> {
         }
     }
 
+    /// Digits `value` is decomposed into when proving `min <= value < max`. Each digit is the index of a member of
+    /// the set in `params` whose signature the proof randomizes. Uses the same protocol as `Self::init` and for the
+    /// CCS protocol, returns the decomposition of `value - min` which is checked against the lower bound. Meant for
+    /// debugging the decomposition before creating the proof.
+    pub fn decompose_value(
+        value: u64,
+        min: u64,
+        max: u64,
+        params: &SmcParamsAndCommitmentKey<E>,
+    ) -> Result<Vec<usize>, ProofSystemError> {
+        validate_bounds(min, max)?;
+        if value < min || value >= max {
+            return Err(SmcRangeProofError::IncorrectBounds(format!(
+                "value={} should be in [{}, {})",
+                value, min, max
+            ))
+            .into());
+        }
+        let base = params.params.get_supported_base_for_range_proof();
+        let digits = if should_use_cls(min, max) {
+            cls_decompose_value(value, min, max, base)
+                .ok_or(SmcRangeProofError::InvalidRange(max - min, base))?
+        } else {
+            let l = find_l_greater_than(max, base) as usize;
+            let mut digits = base_n_digits(value - min, base);
+            digits.resize(l, 0);
+            digits
+        };
+        Ok(digits.into_iter().map(|d| d as usize).collect())
+    }
+
     pub fn init<R: RngCore>(
         &mut self,
         rng: &mut R,
@@ -56,6 +97,8 @@ impl<'a, E: Pairing> BoundCheckSmcProtocol<'a, E> {
         if self.sp.is_some() {
             return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
         }
+        // The statement checks the bounds when created but its bounds are public fields so check again
+        validate_bounds(self.min, self.max)?;
         let msg_as_u64 = enforce_and_get_u64::<E::ScalarField>(&message)?;
         let randomness = E::ScalarField::rand(rng);
         let params_with_pairing =
@@ -74,6 +117,21 @@ impl<'a, E: Pairing> BoundCheckSmcProtocol<'a, E> {
             )?;
             SmcProtocol::CLS(p)
         } else {
+            // The CCS protocol uses `base^l > max` which must fit in a u64
+            let base = self
+                .params_and_comm_key
+                .params
+                .get_supported_base_for_range_proof();
+            let l = find_l_greater_than(self.max, base);
+            if (base as u64).checked_pow(l as u32).is_none() {
+                return Err(ProofSystemError::ArithmeticError(format!(
+                    "{}^{} for the range proof of {} statement {} overflows a u64",
+                    base,
+                    l,
+                    SubProtocolType::BoundCheckSmc.label(),
+                    self.id
+                )));
+            }
             let p = CCSArbitraryRangeProofProtocol::init(
                 rng,
                 msg_as_u64,
@@ -248,7 +306,7 @@ impl<'a, E: Pairing> BoundCheckSmcProtocol<'a, E> {
                 c.challenge_contribution(&proof.comm, comm_key, params.params, &mut writer)?
             }
         }
-        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key_as_slice, &mut writer)?;
         proof.comm.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -1,6 +1,6 @@
 use crate::{
     error::ProofSystemError,
-    prelude::bound_check_smc_with_kv::SmcParamsAndCommitmentKeyAndSecretKey,
+    prelude::bound_check_smc_with_kv::{PreparedSmcKey, SmcParamsAndCommitmentKeyAndSecretKey},
     statement::bound_check_smc::SmcParamsAndCommitmentKey,
     statement_proof::{BoundCheckSmcWithKVInnerProof, BoundCheckSmcWithKVProof, StatementProof},
     sub_protocols::{enforce_and_get_u64, schnorr::SchnorrProtocol, should_use_cls},
@@ -8,6 +8,7 @@ use crate::{
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::CanonicalSerialize;
 use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, UniformRand};
+use dock_crypto_utils::msm::WindowTable;
 use smc_range_proof::{
     ccs_range_proof::kv_arbitrary_range::CCSArbitraryRangeProofWithKVProtocol,
     prelude::CLSRangeProofWithKVProtocol,
@@ -28,6 +29,8 @@ pub struct BoundCheckSmcWithKVProtocol<'a, E: Pairing> {
     pub max: u64,
     pub params_and_comm_key: Option<&'a SmcParamsAndCommitmentKey<E>>,
     pub params_and_comm_key_and_sk: Option<&'a SmcParamsAndCommitmentKeyAndSecretKey<E>>,
+    /// Window table of the generator in the params, only present for the verifier when using prepared params
+    pub g1_table: Option<&'a WindowTable<E::G1>>,
     pub comm: Option<E::G1Affine>,
     pub smc_protocol: Option<SmcProtocolWithKV<E>>,
     pub sp: Option<SchnorrProtocol<'a, E::G1Affine>>,
@@ -46,6 +49,7 @@ impl<'a, E: Pairing> BoundCheckSmcWithKVProtocol<'a, E> {
             max,
             params_and_comm_key: Some(params),
             params_and_comm_key_and_sk: None,
+            g1_table: None,
             comm: None,
             smc_protocol: None,
             sp: None,
@@ -64,12 +68,24 @@ impl<'a, E: Pairing> BoundCheckSmcWithKVProtocol<'a, E> {
             max,
             params_and_comm_key: None,
             params_and_comm_key_and_sk: Some(params),
+            g1_table: None,
             comm: None,
             smc_protocol: None,
             sp: None,
         }
     }
 
+    pub fn new_for_verifier_with_prepared_params(
+        id: usize,
+        min: u64,
+        max: u64,
+        params: &'a PreparedSmcKey<E>,
+    ) -> Self {
+        let mut p = Self::new_for_verifier(id, min, max, &params.params_and_comm_key_and_sk);
+        p.g1_table = Some(&params.g1_table);
+        p
+    }
+
     pub fn init<R: RngCore>(
         &mut self,
         rng: &mut R,
@@ -215,23 +231,44 @@ impl<'a, E: Pairing> BoundCheckSmcWithKVProtocol<'a, E> {
             .params_and_comm_key_and_sk
             .ok_or(ProofSystemError::SmcParamsNotProvided)?;
         let comm_key = params.get_comm_key();
-        match &proof.proof {
-            BoundCheckSmcWithKVInnerProof::CCS(c) => c.verify(
+        let smc_params = params.get_smc_params();
+        match (&proof.proof, self.g1_table) {
+            (BoundCheckSmcWithKVInnerProof::CCS(c), Some(g1_table)) => c.verify_given_g1_table(
+                &proof.comm,
+                challenge,
+                self.min,
+                self.max,
+                comm_key,
+                smc_params,
+                &params.sk,
+                g1_table,
+            )?,
+            (BoundCheckSmcWithKVInnerProof::CCS(c), None) => c.verify(
+                &proof.comm,
+                challenge,
+                self.min,
+                self.max,
+                comm_key,
+                smc_params,
+                &params.sk,
+            )?,
+            (BoundCheckSmcWithKVInnerProof::CLS(c), Some(g1_table)) => c.verify_given_g1_table(
                 &proof.comm,
                 challenge,
                 self.min,
                 self.max,
                 comm_key,
-                params.get_smc_params(),
+                smc_params,
                 &params.sk,
+                g1_table,
             )?,
-            BoundCheckSmcWithKVInnerProof::CLS(c) => c.verify(
+            (BoundCheckSmcWithKVInnerProof::CLS(c), None) => c.verify(
                 &proof.comm,
                 challenge,
                 self.min,
                 self.max,
                 comm_key,
-                params.get_smc_params(),
+                smc_params,
                 &params.sk,
             )?,
         }
@@ -258,7 +295,7 @@ impl<'a, E: Pairing> BoundCheckSmcWithKVProtocol<'a, E> {
                 c.challenge_contribution(&proof.comm, comm_key, get_smc_params, &mut writer)?
             }
         }
-        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key_as_slice, &mut writer)?;
         proof.comm.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -0,0 +1,223 @@
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::Zero;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{cfg_iter_mut, io::Write, rand::RngCore, vec::Vec, UniformRand};
+use dock_crypto_utils::ff::powers;
+use schnorr_pok::{error::SchnorrError, SchnorrCommitment};
+use zeroize::Zeroize;
+
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{CoalescedPedersenCommitmentProof, CoalescedStatementProof, StatementProof},
+    sub_protocols::schnorr::SchnorrProtocol,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Proves knowledge of the openings of several Pedersen commitments with the same commitment key using a single
+/// commitment to randomness `t`. For challenge `c`, the opening of the `j`-th commitment is weighted by `c^{j+1}`
+/// so the responses are `r_i + c * x_{0,i} + c^2 * x_{1,i} + ...` and the verifier checks them against
+/// `t + c * C_0 + c^2 * C_1 + ...`. As the responses are of the weighted sum of the witnesses and not of each
+/// witness, no witness of the commitments can be proven equal to another witness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoalescedSchnorrProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    /// Indices of the other statements whose commitments are proven, in increasing order
+    pub coalesced: Vec<usize>,
+    pub commitment_key: &'a [G],
+    /// Commitment of the statement `id` followed by the commitments of the statements in `coalesced`
+    pub commitments: Vec<G>,
+    pub commitment_to_randomness: Option<SchnorrCommitment<G>>,
+    /// Openings of `commitments` in the same order
+    pub witnesses: Option<Vec<Vec<G::ScalarField>>>,
+}
+
+/// Sub-protocol of a statement proven by the `CoalescedSchnorrProtocol` of the statement `into`. It contributes
+/// only the index `into` to the challenge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoalescedStatementProtocol {
+    pub id: usize,
+    pub into: usize,
+}
+
+impl<'a, G: AffineRepr> CoalescedSchnorrProtocol<'a, G> {
+    pub fn new(
+        id: usize,
+        coalesced: Vec<usize>,
+        commitment_key: &'a [G],
+        commitments: Vec<G>,
+    ) -> Self {
+        Self {
+            id,
+            coalesced,
+            commitment_key,
+            commitments,
+            commitment_to_randomness: None,
+            witnesses: None,
+        }
+    }
+
+    /// `witnesses` are the openings of `self.commitments` in the same order. Errors if any opening doesn't have a
+    /// witness for each base.
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        witnesses: Vec<Vec<G::ScalarField>>,
+    ) -> Result<(), ProofSystemError> {
+        if self.commitment_to_randomness.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if witnesses.is_empty() || witnesses.len() != self.commitments.len() {
+            return Err(ProofSystemError::InvalidCoalescedProof(self.id));
+        }
+        for w in &witnesses {
+            if w.len() != self.commitment_key.len() {
+                return Err(SchnorrError::ExpectedSameSizeSequences(
+                    self.commitment_key.len(),
+                    w.len(),
+                )
+                .into());
+            }
+        }
+        let blindings = (0..self.commitment_key.len())
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        self.commitment_to_randomness =
+            Some(SchnorrCommitment::new(self.commitment_key, blindings));
+        self.witnesses = Some(witnesses);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.commitment_to_randomness.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        Self::compute_challenge_contribution(
+            self.commitment_key,
+            &self.commitments,
+            &self.coalesced,
+            &self.commitment_to_randomness.as_ref().unwrap().t,
+            writer,
+        )
+    }
+
+    pub fn gen_proof_contribution<E: Pairing>(
+        &mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.commitment_to_randomness.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let commitment = self.commitment_to_randomness.take().unwrap();
+        // `x_{0,i} + c * x_{1,i} + c^2 * x_{2,i} + ...` so that the response is `r_i + c * x_{0,i} + c^2 * x_{1,i} + ...`
+        let mut combined = Self::combine(self.witnesses.as_ref().unwrap(), challenge);
+        let response = commitment.response(&combined, challenge)?;
+        combined.zeroize();
+        Ok(StatementProof::CoalescedPedersenCommitment(
+            CoalescedPedersenCommitmentProof {
+                coalesced: self.coalesced.clone(),
+                t: commitment.t,
+                response,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &CoalescedPedersenCommitmentProof<G>,
+    ) -> Result<(), ProofSystemError> {
+        if proof.coalesced != self.coalesced {
+            return Err(ProofSystemError::InvalidCoalescedProof(self.id));
+        }
+        // `C_0 + c * C_1 + c^2 * C_2 + ...` which is checked against `t` and the response using challenge `c`
+        let powers = powers(challenge, self.commitments.len() as u32);
+        let combined = G::Group::msm_unchecked(&self.commitments, &powers).into_affine();
+        proof
+            .response
+            .is_valid(self.commitment_key, &combined, &proof.t, challenge)
+            .map_err(|e| e.into())
+    }
+
+    /// Writes the commitment key, the number of commitments followed by the commitments, the indices of the
+    /// coalesced statements and the commitment to randomness `t`
+    pub fn compute_challenge_contribution<W: Write>(
+        bases: &[G],
+        commitments: &[G],
+        coalesced: &[usize],
+        t: &G,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        SchnorrProtocol::serialize_commitment_key(bases, &mut writer)?;
+        commitments.serialize_compressed(&mut writer)?;
+        coalesced.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(writer)?;
+        Ok(())
+    }
+
+    /// `w_0 + c * w_1 + c^2 * w_2 + ...` for each position in the vectors `w_j`
+    fn combine(
+        witnesses: &[Vec<G::ScalarField>],
+        challenge: &G::ScalarField,
+    ) -> Vec<G::ScalarField> {
+        let powers = powers(challenge, witnesses.len() as u32);
+        let mut combined = Vec::with_capacity(witnesses[0].len());
+        for i in 0..witnesses[0].len() {
+            let mut sum = G::ScalarField::zero();
+            for (w, p) in witnesses.iter().zip(powers.iter()) {
+                sum += w[i] * p;
+            }
+            combined.push(sum);
+        }
+        combined
+    }
+}
+
+impl CoalescedStatementProtocol {
+    pub fn new(id: usize, into: usize) -> Self {
+        Self { id, into }
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        Self::compute_challenge_contribution(self.into, writer)
+    }
+
+    pub fn gen_proof_contribution<E: Pairing, G: AffineRepr>(
+        &mut self,
+        _challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        Ok(StatementProof::Coalesced(CoalescedStatementProof {
+            into: self.into,
+        }))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        into: usize,
+        writer: W,
+    ) -> Result<(), ProofSystemError> {
+        into.serialize_compressed(writer)?;
+        Ok(())
+    }
+}
+
+impl<'a, G: AffineRepr> Zeroize for CoalescedSchnorrProtocol<'a, G> {
+    fn zeroize(&mut self) {
+        if let Some(c) = self.commitment_to_randomness.as_mut() {
+            c.zeroize()
+        }
+        if let Some(w) = self.witnesses.as_mut() {
+            cfg_iter_mut!(w).for_each(|v| v.zeroize())
+        }
+    }
+}
+
+impl<'a, G: AffineRepr> Drop for CoalescedSchnorrProtocol<'a, G> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
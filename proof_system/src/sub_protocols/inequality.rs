@@ -4,7 +4,6 @@ use crate::{
     sub_protocols::schnorr::SchnorrProtocol,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::CanonicalSerialize;
 use ark_std::{collections::BTreeMap, io::Write, rand::RngCore, vec, UniformRand};
 use schnorr_pok::inequality::{CommitmentKey, DiscreteLogInequalityProtocol};
 
@@ -162,7 +161,7 @@ impl<'a, G: AffineRepr> InequalityProtocol<'a, G> {
             comm_key,
             &mut writer,
         )?;
-        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key_as_slice, &mut writer)?;
         proof.comm.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
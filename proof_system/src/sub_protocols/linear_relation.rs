@@ -0,0 +1,164 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{
+    cfg_iter_mut, collections::BTreeMap, io::Write, rand::RngCore, vec::Vec, UniformRand,
+};
+use zeroize::Zeroize;
+
+use crate::{
+    error::ProofSystemError,
+    statement::linear_relation::SchnorrLinearRelation,
+    statement_proof::{SchnorrLinearRelationProof, StatementProof},
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Schnorr protocol proving that the witnesses satisfy the linear equations of the statement. For each equation
+/// `a_0 * x_0 + ... + a_{n-1} * x_{n-1} = t`, the prover sends `a_0 * r_0 + ... + a_{n-1} * r_{n-1}` for
+/// blindings `r_j` and the verifier checks it against the responses `r_j + c * x_j` and the target. The protocol
+/// doesn't bind the prover to any witnesses on its own and is only sound when each witness is proven equal to a
+/// witness of another statement, so that the responses are checked against the other statement's responses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchnorrLinearRelationProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub statement: &'a SchnorrLinearRelation<E>,
+    pub t: Option<Vec<E::ScalarField>>,
+    pub blindings: Option<Vec<E::ScalarField>>,
+    pub witnesses: Option<Vec<E::ScalarField>>,
+}
+
+impl<'a, E: Pairing> SchnorrLinearRelationProtocol<'a, E> {
+    pub fn new(id: usize, statement: &'a SchnorrLinearRelation<E>) -> Self {
+        Self {
+            id,
+            statement,
+            t: None,
+            blindings: None,
+            witnesses: None,
+        }
+    }
+
+    /// `blindings` specifies the randomness to use. If some index is not present, new randomness is generated for it.
+    /// Errors if the witnesses don't satisfy the equations.
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        mut blindings: BTreeMap<usize, E::ScalarField>,
+        witnesses: Vec<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.t.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        self.statement.validate()?;
+        if !self.statement.is_satisfied_by(&witnesses) {
+            return Err(ProofSystemError::UnsatisfiedLinearRelation(self.id));
+        }
+        let blindings = (0..witnesses.len())
+            .map(|i| {
+                blindings
+                    .remove(&i)
+                    .unwrap_or_else(|| E::ScalarField::rand(rng))
+            })
+            .collect::<Vec<_>>();
+        self.t = Some(
+            self.statement
+                .coefficients
+                .iter()
+                .map(|row| SchnorrLinearRelation::<E>::combine(row, &blindings))
+                .collect(),
+        );
+        self.blindings = Some(blindings);
+        self.witnesses = Some(witnesses);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.t.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        Self::compute_challenge_contribution(self.statement, self.t.as_ref().unwrap(), writer)
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.t.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let t = self.t.take().unwrap();
+        let mut blindings = self.blindings.take().unwrap();
+        let responses = blindings
+            .iter()
+            .zip(self.witnesses.as_ref().unwrap().iter())
+            .map(|(r, x)| *r + *challenge * x)
+            .collect();
+        blindings.zeroize();
+        Ok(StatementProof::SchnorrLinearRelation(
+            SchnorrLinearRelationProof { t, responses },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &SchnorrLinearRelationProof<E>,
+    ) -> Result<(), ProofSystemError> {
+        self.statement.validate()?;
+        if proof.t.len() != self.statement.targets.len()
+            || proof.responses.len() != self.statement.witness_count()
+        {
+            return Err(ProofSystemError::InvalidLinearRelationProof(self.id));
+        }
+        for ((row, target), t) in self
+            .statement
+            .coefficients
+            .iter()
+            .zip(self.statement.targets.iter())
+            .zip(proof.t.iter())
+        {
+            if SchnorrLinearRelation::<E>::combine(row, &proof.responses)
+                != *t + *challenge * target
+            {
+                return Err(ProofSystemError::InvalidLinearRelationProof(self.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the coefficients and targets of the equations followed by the prover's commitments `t`
+    pub fn compute_challenge_contribution<W: Write>(
+        statement: &SchnorrLinearRelation<E>,
+        t: &[E::ScalarField],
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        statement.coefficients.serialize_compressed(&mut writer)?;
+        statement.targets.serialize_compressed(&mut writer)?;
+        for t_i in t {
+            t_i.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, E: Pairing> Zeroize for SchnorrLinearRelationProtocol<'a, E> {
+    fn zeroize(&mut self) {
+        if let Some(b) = self.blindings.as_mut() {
+            cfg_iter_mut!(b).for_each(|v| v.zeroize())
+        }
+        if let Some(w) = self.witnesses.as_mut() {
+            cfg_iter_mut!(w).for_each(|v| v.zeroize())
+        }
+    }
+}
+
+impl<'a, E: Pairing> Drop for SchnorrLinearRelationProtocol<'a, E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
@@ -0,0 +1,488 @@
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{MerkleMembershipProof, StatementProof},
+    sub_protocols::schnorr::SchnorrProtocol,
+    witness::MerkleMembership,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, AllocationMode},
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    io::Write,
+    rand::{Rng, RngCore},
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use digest::Digest;
+use dock_crypto_utils::{
+    concat_slices, hashing_utils::field_elem_from_try_and_incr,
+    randomized_pairing_check::RandomizedPairingChecker, serde_utils::ArkObjectBytes,
+};
+use legogroth16::{
+    calculate_d, create_random_proof, generate_random_parameters, verify_proof,
+    PreparedVerifyingKey, Proof, ProvingKey, VerifyingKey,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Number of rounds of the hash function. The S-box is `x^5` so this many rounds are needed for a 255-bit field
+/// like the scalar field of BLS12-381.
+pub const MERKLE_HASH_ROUNDS: usize = 110;
+
+/// Round constants of the 2-to-1 hash used for the Merkle tree. The hash is MiMC with the S-box `x^5` in the
+/// Miyaguchi-Preneel mode where the right input is the key, i.e. for inputs `l` and `r`, `x_0 = l`,
+/// `x_{i+1} = (x_i + r + c_i)^5` and the output is `x_n + r + l`. `x^5` is a permutation only when `gcd(5, p-1) = 1`
+/// for the field's modulus `p`, which is true for the scalar fields of BLS12-381 and BN254.
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MerkleHashParams<F: PrimeField> {
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> MerkleHashParams<F> {
+    /// Generate round constants deterministically from the given label
+    pub fn new<D: Digest>(label: &[u8]) -> Self {
+        let round_constants = (0..MERKLE_HASH_ROUNDS)
+            .map(|i| {
+                field_elem_from_try_and_incr::<F, D>(&concat_slices![
+                    label,
+                    b" : c_",
+                    (i as u64).to_le_bytes()
+                ])
+            })
+            .collect();
+        Self { round_constants }
+    }
+
+    /// Hash a pair of nodes to get their parent
+    pub fn hash(&self, left: &F, right: &F) -> F {
+        let mut x = *left;
+        for c in &self.round_constants {
+            let t = x + right + c;
+            x = t.square().square() * t;
+        }
+        x + right + left
+    }
+
+    /// Compute the root of the tree from a leaf, its index and the siblings on its path, starting at the leaf level
+    pub fn compute_root(&self, leaf: &F, leaf_index: u64, siblings: &[F]) -> F {
+        let mut current = *leaf;
+        for (i, sibling) in siblings.iter().enumerate() {
+            current = if (leaf_index >> i) & 1 == 1 {
+                self.hash(sibling, &current)
+            } else {
+                self.hash(&current, sibling)
+            };
+        }
+        current
+    }
+
+    /// Same as `hash` but in the circuit
+    pub fn hash_gadget(
+        &self,
+        left: &FpVar<F>,
+        right: &FpVar<F>,
+    ) -> Result<FpVar<F>, SynthesisError> {
+        let mut x = left.clone();
+        for c in &self.round_constants {
+            let t = (&x + right) + *c;
+            x = t.square()?.square()? * &t;
+        }
+        Ok(x + right + left)
+    }
+}
+
+/// A Merkle tree over a power of 2 number of leaves using the hash given by `MerkleHashParams`. All levels are kept
+/// so that the path of any leaf can be created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree<F: PrimeField> {
+    /// Level 0 has the leaves and the last level has only the root
+    pub levels: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MerkleTree<F> {
+    pub fn new(
+        hash_params: &MerkleHashParams<F>,
+        leaves: Vec<F>,
+    ) -> Result<Self, ProofSystemError> {
+        if leaves.len() < 2 || !leaves.len().is_power_of_two() {
+            return Err(ProofSystemError::InvalidMerkleTreeSize(leaves.len()));
+        }
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|c| hash_params.hash(&c[0], &c[1]))
+                .collect();
+            levels.push(next);
+        }
+        Ok(Self { levels })
+    }
+
+    pub fn root(&self) -> &F {
+        &self.levels.last().unwrap()[0]
+    }
+
+    /// Number of levels above the leaves, i.e. the number of siblings in a path
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Siblings on the path from the leaf at the given index to the root, starting at the leaf level
+    pub fn path(&self, leaf_index: usize) -> Result<Vec<F>, ProofSystemError> {
+        if leaf_index >= self.levels[0].len() {
+            return Err(ProofSystemError::InvalidMerkleTreeLeafIndex(leaf_index));
+        }
+        Ok(self.levels[..self.depth()]
+            .iter()
+            .enumerate()
+            .map(|(i, level)| level[(leaf_index >> i) ^ 1])
+            .collect())
+    }
+}
+
+/// Runs the LegoGroth16 protocol for proving that a leaf is in a Merkle tree with a public root and a Schnorr
+/// protocol for proving knowledge of the leaf committed in the LegoGroth16 proof.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleMembershipProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub root: E::ScalarField,
+    /// The SNARK proving key, will be `None` if invoked by verifier.
+    pub proving_key: Option<&'a ProvingKey<E>>,
+    /// The SNARK verifying key, will be `None` if invoked by prover.
+    pub verifying_key: Option<&'a VerifyingKey<E>>,
+    pub snark_proof: Option<Proof<E>>,
+    pub sp: Option<SchnorrProtocol<'a, E::G1Affine>>,
+}
+
+impl<'a, E: Pairing> MerkleMembershipProtocol<'a, E> {
+    /// Create an instance of this protocol for the prover.
+    pub fn new_for_prover(id: usize, root: E::ScalarField, proving_key: &'a ProvingKey<E>) -> Self {
+        Self {
+            id,
+            root,
+            proving_key: Some(proving_key),
+            verifying_key: None,
+            snark_proof: None,
+            sp: None,
+        }
+    }
+
+    /// Create an instance of this protocol for the verifier.
+    pub fn new_for_verifier(
+        id: usize,
+        root: E::ScalarField,
+        verifying_key: &'a VerifyingKey<E>,
+    ) -> Self {
+        Self {
+            id,
+            root,
+            proving_key: None,
+            verifying_key: Some(verifying_key),
+            snark_proof: None,
+            sp: None,
+        }
+    }
+
+    /// Runs the LegoGroth16 protocol to prove that the leaf is in the tree and initialize a Schnorr proof of
+    /// knowledge protocol to prove knowledge of the committed leaf. Fails if the path in the witness doesn't
+    /// lead to the root as the SNARK proof would then not verify.
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key: &'a [E::G1Affine],
+        hash_params: &MerkleHashParams<E::ScalarField>,
+        depth: usize,
+        witness: &MerkleMembership<E>,
+        blinding: Option<E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let proving_key = self
+            .proving_key
+            .ok_or(ProofSystemError::LegoGroth16ProvingKeyNotProvided)?;
+        if witness.siblings.len() != depth
+            || (depth < 64 && (witness.leaf_index >> depth) != 0)
+            || hash_params.compute_root(&witness.leaf, witness.leaf_index, &witness.siblings)
+                != self.root
+        {
+            return Err(ProofSystemError::InvalidMerkleMembershipWitness(self.id));
+        }
+
+        // blinding for the commitment in the snark proof
+        let v = E::ScalarField::rand(rng);
+
+        let circuit = MerkleMembershipCircuit {
+            hash_params,
+            depth,
+            root: Some(self.root),
+            leaf: Some(witness.leaf),
+            leaf_index: Some(witness.leaf_index),
+            siblings: Some(witness.siblings.clone()),
+        };
+        let snark_proof = create_random_proof(circuit, v, proving_key, rng)?;
+
+        // blinding used to prove knowledge of the leaf in `snark_proof.d`. The caller of this method ensures
+        // that this will be same as the one used proving knowledge of the corresponding message in a signature,
+        // thus allowing them to be proved equal.
+        let blinding = blinding.unwrap_or_else(|| E::ScalarField::rand(rng));
+        // NOTE: value of id is dummy
+        let mut sp = SchnorrProtocol::new(10000, comm_key, snark_proof.d);
+        let mut blindings = BTreeMap::new();
+        blindings.insert(0, blinding);
+        sp.init(rng, blindings, vec![witness.leaf, v])?;
+        self.snark_proof = Some(snark_proof);
+        self.sp = Some(sp);
+        Ok(())
+    }
+
+    /// Generate challenge contribution for the Schnorr protocol
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.sp
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        Ok(())
+    }
+
+    /// Generate responses for the Schnorr protocol
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        Ok(StatementProof::MerkleMembership(MerkleMembershipProof {
+            snark_proof: self.snark_proof.take().unwrap(),
+            sp: self
+                .sp
+                .take()
+                .unwrap()
+                .gen_proof_contribution_as_struct(challenge)?,
+        }))
+    }
+
+    /// Verify that the snark proof and the Schnorr proof are valid.
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &MerkleMembershipProof<E>,
+        comm_key: &[E::G1Affine],
+        pvk: &PreparedVerifyingKey<E>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        let pub_inp = &self.public_inputs();
+        let snark_proof = &proof.snark_proof;
+        match pairing_checker {
+            Some(c) => {
+                let d = calculate_d(pvk, snark_proof, pub_inp)?;
+                c.add_multiple_sources_and_target(
+                    &[snark_proof.a, snark_proof.c, d],
+                    vec![
+                        snark_proof.b.into(),
+                        pvk.delta_g2_neg_pc.clone(),
+                        pvk.gamma_g2_neg_pc.clone(),
+                    ],
+                    &pvk.alpha_g1_beta_g2,
+                );
+            }
+            None => verify_proof(pvk, snark_proof, pub_inp)?,
+        }
+
+        // NOTE: value of id is dummy
+        let sp = SchnorrProtocol::new(10000, comm_key, proof.snark_proof.d);
+
+        sp.verify_proof_contribution_as_struct(challenge, &proof.sp)
+    }
+
+    /// Public inputs of the LegoGroth16 proof, i.e. the root. The leaf isn't a public input but is committed
+    /// in the proof.
+    pub fn public_inputs(&self) -> Vec<E::ScalarField> {
+        vec![self.root]
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key: &[E::G1Affine],
+        proof: &MerkleMembershipProof<E>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
+        proof.snark_proof.d.serialize_compressed(&mut writer)?;
+        proof.sp.t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// The verifying key should have 1 public input, the root, and 1 committed witness, the leaf
+    pub fn validate_verification_key(vk: &VerifyingKey<E>) -> Result<(), ProofSystemError> {
+        if vk.gamma_abc_g1.len() < 3 || vk.commit_witness_count != 1 {
+            return Err(ProofSystemError::LegoGroth16Error(
+                legogroth16::error::Error::SynthesisError(SynthesisError::MalformedVerifyingKey),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Enforce that hashing the leaf with the siblings, in the order given by the bits of the leaf index, gives the root
+#[derive(Clone)]
+pub struct MerkleMembershipCircuit<'a, F: PrimeField> {
+    hash_params: &'a MerkleHashParams<F>,
+    depth: usize,
+    root: Option<F>,
+    leaf: Option<F>,
+    leaf_index: Option<u64>,
+    siblings: Option<Vec<F>>,
+}
+
+impl<'a, ConstraintF: PrimeField> ConstraintSynthesizer<ConstraintF>
+    for MerkleMembershipCircuit<'a, ConstraintF>
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<ConstraintF>,
+    ) -> Result<(), SynthesisError> {
+        // The leaf is the first witness so that it's the one committed in the proof
+        let leaf = FpVar::new_variable(
+            cs.clone(),
+            || self.leaf.ok_or(SynthesisError::AssignmentMissing),
+            AllocationMode::Witness,
+        )?;
+
+        let root = FpVar::new_variable(
+            cs.clone(),
+            || self.root.ok_or(SynthesisError::AssignmentMissing),
+            AllocationMode::Input,
+        )?;
+
+        let mut current = leaf;
+        for i in 0..self.depth {
+            let sibling = FpVar::new_variable(
+                cs.clone(),
+                || {
+                    self.siblings
+                        .as_ref()
+                        .and_then(|s| s.get(i).copied())
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+                AllocationMode::Witness,
+            )?;
+            // Whether the current node is the right child of its parent
+            let is_right = Boolean::new_variable(
+                cs.clone(),
+                || {
+                    self.leaf_index
+                        .map(|idx| (idx >> i) & 1 == 1)
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+                AllocationMode::Witness,
+            )?;
+            let left = is_right.select(&sibling, &current)?;
+            let right = is_right.select(&current, &sibling)?;
+            current = self.hash_params.hash_gadget(&left, &right)?;
+        }
+        current.enforce_equal(&root)
+    }
+}
+
+/// Generate SNARK proving key and verification key for a circuit that checks that given a witness leaf
+/// and a public root, the leaf is in a Merkle tree of the given depth with that root
+pub fn generate_snark_srs_merkle_membership<E, R>(
+    hash_params: &MerkleHashParams<E::ScalarField>,
+    depth: usize,
+    rng: &mut R,
+) -> Result<ProvingKey<E>, ProofSystemError>
+where
+    E: Pairing,
+    R: Rng,
+{
+    let circuit = MerkleMembershipCircuit::<E::ScalarField> {
+        hash_params,
+        depth,
+        root: None,
+        leaf: None,
+        leaf_index: None,
+        siblings: None,
+    };
+    generate_random_parameters::<E, _, R>(circuit, 1, rng).map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Bls12_381;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::rand::{prelude::StdRng, SeedableRng};
+    use blake2::Blake2b512;
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+    #[test]
+    fn merkle_tree_paths() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let hash_params = MerkleHashParams::<Fr>::new::<Blake2b512>(b"test");
+        let leaves = (0..8).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let tree = MerkleTree::new(&hash_params, leaves.clone()).unwrap();
+        assert_eq!(tree.depth(), 3);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = tree.path(i).unwrap();
+            assert_eq!(
+                hash_params.compute_root(leaf, i as u64, &path),
+                *tree.root()
+            );
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let circuit = MerkleMembershipCircuit {
+                hash_params: &hash_params,
+                depth: 3,
+                root: Some(*tree.root()),
+                leaf: Some(*leaf),
+                leaf_index: Some(i as u64),
+                siblings: Some(path.clone()),
+            };
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert!(cs.is_satisfied().unwrap());
+
+            // Wrong leaf index
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let circuit = MerkleMembershipCircuit {
+                hash_params: &hash_params,
+                depth: 3,
+                root: Some(*tree.root()),
+                leaf: Some(*leaf),
+                leaf_index: Some(i as u64 ^ 1),
+                siblings: Some(path),
+            };
+            circuit.generate_constraints(cs.clone()).unwrap();
+            assert!(!cs.is_satisfied().unwrap());
+        }
+
+        assert!(tree.path(8).is_err());
+        assert!(MerkleTree::new(&hash_params, leaves[..6].to_vec()).is_err());
+        assert!(MerkleTree::new(&hash_params, leaves[..1].to_vec()).is_err());
+    }
+}
@@ -2,22 +2,34 @@ pub mod accumulator;
 #[macro_use]
 pub mod bbs_plus;
 pub mod bbs_23;
+pub mod bbs_plus_hidden_pk;
 pub mod bound_check_bpp;
 pub mod bound_check_legogroth16;
 pub mod bound_check_smc;
 pub mod bound_check_smc_with_kv;
+pub mod coalesced_schnorr;
 pub mod inequality;
+pub mod linear_relation;
+pub mod merkle_membership;
+pub mod noop;
 pub mod ps_signature;
 pub mod r1cs_legogorth16;
 pub mod saver;
 pub mod schnorr;
+pub mod statement_disjunction;
 
 use core::borrow::Borrow;
 
 use crate::error::ProofSystemError;
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_ff::PrimeField;
-use ark_std::{format, io::Write};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeSet, format, io::Write};
+use dock_crypto_utils::{
+    iter::take_while_satisfy,
+    misc::seq_inc_by_n_from,
+    try_iter::{CheckLeft, InvalidPairOrSingle},
+};
 use itertools::{EitherOrBoth, Itertools};
 
 use crate::{
@@ -30,7 +42,11 @@ use crate::{
         r1cs_legogorth16::R1CSLegogroth16Protocol,
     },
 };
-use accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol};
+use accumulator::{
+    AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol,
+    BatchAccumulatorMembershipSubProtocol, BatchAccumulatorNonMembershipSubProtocol,
+    CommittedAccumulatorMembershipSubProtocol,
+};
 
 /// Various sub-protocols that are executed to create a `StatementProof` which are then combined to
 /// form a `Proof`
@@ -57,6 +73,90 @@ pub enum SubProtocol<'a, E: Pairing, G: AffineRepr> {
     BoundCheckSmcWithKV(BoundCheckSmcWithKVProtocol<'a, E>),
     /// To prove inequality of a signed message with a public value
     Inequality(InequalityProtocol<'a, G>),
+    /// For BBS+ signature in group G1 without revealing the public key
+    PoKBBSSignatureG1HiddenPK(bbs_plus_hidden_pk::PoKBBSSigG1HiddenPKSubProtocol<'a, E>),
+    /// To prove that witnesses satisfy public linear equations
+    SchnorrLinearRelation(linear_relation::SchnorrLinearRelationProtocol<'a, E>),
+    /// For several Pedersen commitments with the same commitment key proven with a single commitment to randomness
+    CoalescedPoKDiscreteLogs(coalesced_schnorr::CoalescedSchnorrProtocol<'a, G>),
+    /// For a statement proven by the `CoalescedPoKDiscreteLogs` of another statement
+    Coalesced(coalesced_schnorr::CoalescedStatementProtocol),
+    /// For membership of a leaf in a Merkle tree using LegoGroth16
+    MerkleMembership(merkle_membership::MerkleMembershipProtocol<'a, E>),
+    /// Placeholder for a statement which is filled later
+    Noop(noop::NoopProtocol),
+    BatchAccumulatorNonMembership(BatchAccumulatorNonMembershipSubProtocol<'a, E>),
+    BatchAccumulatorMembership(BatchAccumulatorMembershipSubProtocol<'a, E>),
+    /// For membership in an accumulator whose value is committed
+    CommittedAccumulatorMembership(CommittedAccumulatorMembershipSubProtocol<'a, E>),
+    /// For BBS+ signature in group G1 as one of the statements of a disjunction
+    PoKBBSSignatureG1Disjunct(statement_disjunction::PoKBBSSigG1DisjunctSubProtocol<'a, E>),
+}
+
+/// Type of a sub-protocol. It's written as a single byte before the challenge contribution of each sub-protocol so
+/// that the challenge contributions of different sub-protocols can never be the same byte sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum SubProtocolType {
+    PoKBBSSignatureG1 = 0,
+    AccumulatorMembership = 1,
+    AccumulatorNonMembership = 2,
+    PoKDiscreteLogs = 3,
+    Saver = 4,
+    BoundCheckLegoGroth16 = 5,
+    R1CSLegogroth16 = 6,
+    PSSignaturePoK = 7,
+    PoKBBSSignature23G1 = 8,
+    BoundCheckBpp = 9,
+    BoundCheckSmc = 10,
+    BoundCheckSmcWithKV = 11,
+    Inequality = 12,
+    PoKBBSSignatureG1HiddenPK = 13,
+    SchnorrLinearRelation = 14,
+    CoalescedPoKDiscreteLogs = 15,
+    Coalesced = 16,
+    MerkleMembership = 17,
+    Noop = 18,
+    BatchAccumulatorNonMembership = 19,
+    BatchAccumulatorMembership = 20,
+    CommittedAccumulatorMembership = 21,
+    PoKBBSSignatureG1Disjunct = 22,
+}
+
+impl SubProtocolType {
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        (*self as u8).serialize_compressed(writer)?;
+        Ok(())
+    }
+
+    /// Name of the sub-protocol type for use in logs and error messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::PoKBBSSignatureG1 => "PoKBBSSignatureG1",
+            Self::AccumulatorMembership => "AccumulatorMembership",
+            Self::AccumulatorNonMembership => "AccumulatorNonMembership",
+            Self::PoKDiscreteLogs => "PoKDiscreteLogs",
+            Self::Saver => "Saver",
+            Self::BoundCheckLegoGroth16 => "BoundCheckLegoGroth16",
+            Self::R1CSLegogroth16 => "R1CSLegogroth16",
+            Self::PSSignaturePoK => "PSSignaturePoK",
+            Self::PoKBBSSignature23G1 => "PoKBBSSignature23G1",
+            Self::BoundCheckBpp => "BoundCheckBpp",
+            Self::BoundCheckSmc => "BoundCheckSmc",
+            Self::BoundCheckSmcWithKV => "BoundCheckSmcWithKV",
+            Self::Inequality => "Inequality",
+            Self::PoKBBSSignatureG1HiddenPK => "PoKBBSSignatureG1HiddenPK",
+            Self::SchnorrLinearRelation => "SchnorrLinearRelation",
+            Self::CoalescedPoKDiscreteLogs => "CoalescedPoKDiscreteLogs",
+            Self::Coalesced => "Coalesced",
+            Self::MerkleMembership => "MerkleMembership",
+            Self::Noop => "Noop",
+            Self::BatchAccumulatorNonMembership => "BatchAccumulatorNonMembership",
+            Self::BatchAccumulatorMembership => "BatchAccumulatorMembership",
+            Self::CommittedAccumulatorMembership => "CommittedAccumulatorMembership",
+            Self::PoKBBSSignatureG1Disjunct => "PoKBBSSignatureG1Disjunct",
+        }
+    }
 }
 
 macro_rules! delegate {
@@ -75,7 +175,17 @@ macro_rules! delegate {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                Inequality
+                Inequality,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                CoalescedPoKDiscreteLogs,
+                Coalesced,
+                MerkleMembership,
+                Noop,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                PoKBBSSignatureG1Disjunct
             : $($tt)+
         }
     }};
@@ -90,7 +200,78 @@ pub trait ProofSubProtocol<E: Pairing, G: AffineRepr<ScalarField = E::ScalarFiel
 }
 
 impl<'a, E: Pairing, G: AffineRepr<ScalarField = E::ScalarField>> SubProtocol<'a, E, G> {
-    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+    pub fn protocol_type(&self) -> SubProtocolType {
+        match self {
+            Self::PoKBBSSignatureG1(_) => SubProtocolType::PoKBBSSignatureG1,
+            Self::AccumulatorMembership(_) => SubProtocolType::AccumulatorMembership,
+            Self::AccumulatorNonMembership(_) => SubProtocolType::AccumulatorNonMembership,
+            Self::PoKDiscreteLogs(_) => SubProtocolType::PoKDiscreteLogs,
+            Self::Saver(_) => SubProtocolType::Saver,
+            Self::BoundCheckLegoGroth16(_) => SubProtocolType::BoundCheckLegoGroth16,
+            Self::R1CSLegogroth16Protocol(_) => SubProtocolType::R1CSLegogroth16,
+            Self::PSSignaturePoK(_) => SubProtocolType::PSSignaturePoK,
+            Self::PoKBBSSignature23G1(_) => SubProtocolType::PoKBBSSignature23G1,
+            Self::BoundCheckBpp(_) => SubProtocolType::BoundCheckBpp,
+            Self::BoundCheckSmc(_) => SubProtocolType::BoundCheckSmc,
+            Self::BoundCheckSmcWithKV(_) => SubProtocolType::BoundCheckSmcWithKV,
+            Self::Inequality(_) => SubProtocolType::Inequality,
+            Self::PoKBBSSignatureG1HiddenPK(_) => SubProtocolType::PoKBBSSignatureG1HiddenPK,
+            Self::SchnorrLinearRelation(_) => SubProtocolType::SchnorrLinearRelation,
+            Self::CoalescedPoKDiscreteLogs(_) => SubProtocolType::CoalescedPoKDiscreteLogs,
+            Self::Coalesced(_) => SubProtocolType::Coalesced,
+            Self::MerkleMembership(_) => SubProtocolType::MerkleMembership,
+            Self::Noop(_) => SubProtocolType::Noop,
+            Self::BatchAccumulatorNonMembership(_) => {
+                SubProtocolType::BatchAccumulatorNonMembership
+            }
+            Self::BatchAccumulatorMembership(_) => SubProtocolType::BatchAccumulatorMembership,
+            Self::CommittedAccumulatorMembership(_) => {
+                SubProtocolType::CommittedAccumulatorMembership
+            }
+            Self::PoKBBSSignatureG1Disjunct(_) => SubProtocolType::PoKBBSSignatureG1Disjunct,
+        }
+    }
+
+    /// Name of the sub-protocol for use in logs and error messages, same as the label of its `SubProtocolType`
+    pub fn label(&self) -> &str {
+        self.protocol_type().label()
+    }
+
+    /// Full name of the Rust type implementing the sub-protocol, including its generic parameters
+    pub fn type_name(&self) -> &'static str {
+        fn type_name_of<T>(_: &T) -> &'static str {
+            core::any::type_name::<T>()
+        }
+        match self {
+            Self::PoKBBSSignatureG1(p) => type_name_of(p),
+            Self::AccumulatorMembership(p) => type_name_of(p),
+            Self::AccumulatorNonMembership(p) => type_name_of(p),
+            Self::PoKDiscreteLogs(p) => type_name_of(p),
+            Self::Saver(p) => type_name_of(p),
+            Self::BoundCheckLegoGroth16(p) => type_name_of(p),
+            Self::R1CSLegogroth16Protocol(p) => type_name_of(p),
+            Self::PSSignaturePoK(p) => type_name_of(p),
+            Self::PoKBBSSignature23G1(p) => type_name_of(p),
+            Self::BoundCheckBpp(p) => type_name_of(p),
+            Self::BoundCheckSmc(p) => type_name_of(p),
+            Self::BoundCheckSmcWithKV(p) => type_name_of(p),
+            Self::Inequality(p) => type_name_of(p),
+            Self::PoKBBSSignatureG1HiddenPK(p) => type_name_of(p),
+            Self::SchnorrLinearRelation(p) => type_name_of(p),
+            Self::CoalescedPoKDiscreteLogs(p) => type_name_of(p),
+            Self::Coalesced(p) => type_name_of(p),
+            Self::MerkleMembership(p) => type_name_of(p),
+            Self::Noop(p) => type_name_of(p),
+            Self::BatchAccumulatorNonMembership(p) => type_name_of(p),
+            Self::BatchAccumulatorMembership(p) => type_name_of(p),
+            Self::CommittedAccumulatorMembership(p) => type_name_of(p),
+            Self::PoKBBSSignatureG1Disjunct(p) => type_name_of(p),
+        }
+    }
+
+    /// Writes the type of the sub-protocol followed by the sub-protocol's challenge contribution
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        self.protocol_type().challenge_contribution(&mut writer)?;
         delegate!(self.challenge_contribution(writer))
     }
 
@@ -100,6 +281,30 @@ impl<'a, E: Pairing, G: AffineRepr<ScalarField = E::ScalarField>> SubProtocol<'a
     ) -> Result<StatementProof<E, G>, ProofSystemError> {
         delegate!(self.gen_proof_contribution(challenge))
     }
+
+    /// Index of the statement this sub-protocol is for
+    pub fn id(&self) -> usize {
+        delegate!(self.id)
+    }
+
+    /// Check that the ids of the sub-protocols are unique and are `0, 1, ..., n-1` for `n` sub-protocols, in any
+    /// order. Witness equalities refer to statements by index so sub-protocols sharing an id would be treated as the
+    /// same statement.
+    pub fn check_ids(sub_protocols: &[Self]) -> Result<(), ProofSystemError> {
+        let mut seen = BTreeSet::new();
+        for p in sub_protocols {
+            if !seen.insert(p.id()) {
+                return Err(ProofSystemError::DuplicateStatementId(p.id()));
+            }
+        }
+        // As the ids are unique, they are `0, 1, ..., n-1` iff the largest is less than `n`
+        match seen.last() {
+            Some(max) if *max >= sub_protocols.len() => {
+                Err(ProofSystemError::NonContiguousStatementIds)
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Merges indexed messages sorted by index with indexed blindings sorted by index.
@@ -141,9 +346,44 @@ fn merge_indexed_messages_with_blindings<'a, M, B, R: 'a>(
         .flatten()
 }
 
+/// Assembles all messages in order of their indices by merging the messages to commit (unrevealed messages) with
+/// the revealed messages, both sorted by index. Only the indices are compared when merging and as the revealed
+/// indices are public and the unrevealed indices are their complement, the choice between a revealed and an
+/// unrevealed message at each position never depends on the value of any message.
+/// In case the indices aren't sequential starting from 0, `non_seq_idx` will be set to the offending index (or
+/// pair of indices) and iteration will be aborted.
+fn merge_revealed_and_unrevealed_messages<'a, M: 'a>(
+    messages_to_commit_sorted_by_index: impl Iterator<Item = (usize, M)> + 'a,
+    revealed_msgs_sorted_by_index: impl Iterator<Item = (usize, M)> + 'a,
+    non_seq_idx: &'a mut Option<InvalidPairOrSingle<usize>>,
+) -> impl Iterator<Item = M> + 'a {
+    take_while_satisfy(
+        messages_to_commit_sorted_by_index
+            .merge_by(revealed_msgs_sorted_by_index, |(a, _), (b, _)| a < b),
+        CheckLeft(seq_inc_by_n_from(1, 0)),
+        non_seq_idx,
+    )
+    .map(|(_, msg)| msg)
+}
+
 pub fn validate_bounds(min: u64, max: u64) -> Result<(), ProofSystemError> {
     if max <= min {
-        return Err(ProofSystemError::BoundCheckMaxNotGreaterThanMin);
+        return Err(ProofSystemError::EmptyRange { min, max });
+    }
+    Ok(())
+}
+
+/// Check that set-membership check params with `base` members can be used for a range proof of `[min, max)`.
+/// The range is decomposed into `ceil(log_b(max - min))` digits in base `b`, each of which is a member of the set
+/// `0..b`, so the decomposition, and thus the proof, is only possible with a base of at least 2.
+pub fn validate_smc_base(min: u64, max: u64, base: u16) -> Result<(), ProofSystemError> {
+    validate_bounds(min, max)?;
+    let required = 2;
+    if base < required {
+        return Err(ProofSystemError::InsufficientSmcBases {
+            required,
+            available: base,
+        });
     }
     Ok(())
 }
@@ -162,9 +402,351 @@ pub fn enforce_and_get_u64<F: PrimeField>(val: &F) -> Result<u64, ProofSystemErr
     Ok(limbs[0])
 }
 
+pub fn enforce_and_get_u32<F: PrimeField>(val: &F) -> Result<u32, ProofSystemError> {
+    let v = enforce_and_get_u64(val)?;
+    u32::try_from(v).map_err(|_| {
+        ProofSystemError::UnsupportedValue(format!("Expected a 32 bit value but found {}", val))
+    })
+}
+
 pub fn should_use_cls(min: u64, max: u64) -> bool {
     assert!(max > min);
     let diff = max - min;
     let bits = diff.ilog2();
     bits < 20
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::{collections::BTreeMap, vec, vec::Vec};
+    use dock_crypto_utils::try_iter::InvalidPair;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Msg {
+        Revealed(u64),
+        Unrevealed(u64),
+    }
+
+    /// Assembles messages by checking at each index whether the message is unrevealed or revealed
+    fn assemble_by_lookup(
+        revealed: &BTreeMap<usize, u64>,
+        unrevealed: &BTreeMap<usize, u64>,
+    ) -> Vec<Msg> {
+        (0..revealed.len() + unrevealed.len())
+            .map(|i| {
+                if unrevealed.contains_key(&i) {
+                    Msg::Unrevealed(unrevealed[&i])
+                } else {
+                    Msg::Revealed(revealed[&i])
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sub_protocol_type_challenge_contribution() {
+        let types = [
+            SubProtocolType::PoKBBSSignatureG1,
+            SubProtocolType::AccumulatorMembership,
+            SubProtocolType::AccumulatorNonMembership,
+            SubProtocolType::PoKDiscreteLogs,
+            SubProtocolType::Saver,
+            SubProtocolType::BoundCheckLegoGroth16,
+            SubProtocolType::R1CSLegogroth16,
+            SubProtocolType::PSSignaturePoK,
+            SubProtocolType::PoKBBSSignature23G1,
+            SubProtocolType::BoundCheckBpp,
+            SubProtocolType::BoundCheckSmc,
+            SubProtocolType::BoundCheckSmcWithKV,
+            SubProtocolType::Inequality,
+            SubProtocolType::PoKBBSSignatureG1HiddenPK,
+            SubProtocolType::SchnorrLinearRelation,
+            SubProtocolType::CoalescedPoKDiscreteLogs,
+            SubProtocolType::Coalesced,
+            SubProtocolType::MerkleMembership,
+            SubProtocolType::Noop,
+            SubProtocolType::BatchAccumulatorNonMembership,
+            SubProtocolType::BatchAccumulatorMembership,
+            SubProtocolType::CommittedAccumulatorMembership,
+            SubProtocolType::PoKBBSSignatureG1Disjunct,
+        ];
+        // Each type contributes a single distinct byte
+        for (i, t) in types.iter().enumerate() {
+            let mut bytes = vec![];
+            t.challenge_contribution(&mut bytes).unwrap();
+            assert_eq!(bytes, vec![i as u8]);
+        }
+    }
+
+    #[test]
+    fn sub_protocol_labels() {
+        use ark_bls12_381::{Bls12_381, G1Affine};
+
+        let expected = [
+            (SubProtocolType::PoKBBSSignatureG1, "PoKBBSSignatureG1"),
+            (
+                SubProtocolType::AccumulatorMembership,
+                "AccumulatorMembership",
+            ),
+            (
+                SubProtocolType::AccumulatorNonMembership,
+                "AccumulatorNonMembership",
+            ),
+            (SubProtocolType::PoKDiscreteLogs, "PoKDiscreteLogs"),
+            (SubProtocolType::Saver, "Saver"),
+            (
+                SubProtocolType::BoundCheckLegoGroth16,
+                "BoundCheckLegoGroth16",
+            ),
+            (SubProtocolType::R1CSLegogroth16, "R1CSLegogroth16"),
+            (SubProtocolType::PSSignaturePoK, "PSSignaturePoK"),
+            (SubProtocolType::PoKBBSSignature23G1, "PoKBBSSignature23G1"),
+            (SubProtocolType::BoundCheckBpp, "BoundCheckBpp"),
+            (SubProtocolType::BoundCheckSmc, "BoundCheckSmc"),
+            (SubProtocolType::BoundCheckSmcWithKV, "BoundCheckSmcWithKV"),
+            (SubProtocolType::Inequality, "Inequality"),
+            (
+                SubProtocolType::PoKBBSSignatureG1HiddenPK,
+                "PoKBBSSignatureG1HiddenPK",
+            ),
+            (
+                SubProtocolType::SchnorrLinearRelation,
+                "SchnorrLinearRelation",
+            ),
+            (
+                SubProtocolType::CoalescedPoKDiscreteLogs,
+                "CoalescedPoKDiscreteLogs",
+            ),
+            (SubProtocolType::Coalesced, "Coalesced"),
+            (SubProtocolType::MerkleMembership, "MerkleMembership"),
+            (SubProtocolType::Noop, "Noop"),
+            (
+                SubProtocolType::BatchAccumulatorNonMembership,
+                "BatchAccumulatorNonMembership",
+            ),
+            (
+                SubProtocolType::BatchAccumulatorMembership,
+                "BatchAccumulatorMembership",
+            ),
+            (
+                SubProtocolType::CommittedAccumulatorMembership,
+                "CommittedAccumulatorMembership",
+            ),
+            (
+                SubProtocolType::PoKBBSSignatureG1Disjunct,
+                "PoKBBSSignatureG1Disjunct",
+            ),
+        ];
+        for (t, label) in expected {
+            assert_eq!(t.label(), label);
+        }
+
+        let sp = SubProtocol::<Bls12_381, G1Affine>::Noop(noop::NoopProtocol::new(0));
+        assert_eq!(sp.label(), "Noop");
+        assert!(sp.type_name().ends_with("NoopProtocol"));
+        let sp = SubProtocol::<Bls12_381, G1Affine>::Coalesced(
+            coalesced_schnorr::CoalescedStatementProtocol::new(1, 0),
+        );
+        assert_eq!(sp.label(), "Coalesced");
+        assert!(sp.type_name().ends_with("CoalescedStatementProtocol"));
+    }
+
+    #[test]
+    fn statement_id_starts_challenge_contribution() {
+        use crate::{
+            statement::{bbs_plus::PoKBBSSignatureG1, ped_comm::PedersenCommitment, StatementId},
+            witness::PoKBBSSignatureG1 as PoKBBSSignatureG1Wit,
+        };
+        use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective};
+        use ark_ec::{CurveGroup, VariableBaseMSM};
+        use ark_std::{
+            rand::{rngs::StdRng, SeedableRng},
+            UniformRand,
+        };
+        use test_utils::bbs::bbs_plus_sig_setup;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+        let revealed_messages = BTreeMap::new();
+        let mut bbs_sp = bbs_plus::PoKBBSSigG1SubProtocol::new(
+            0,
+            &revealed_messages,
+            &params,
+            &keypair.public_key,
+        );
+        bbs_sp
+            .init(
+                &mut rng,
+                BTreeMap::new(),
+                PoKBBSSignatureG1Wit {
+                    signature: sig,
+                    unrevealed_messages: msgs.into_iter().enumerate().collect(),
+                },
+            )
+            .unwrap();
+
+        let bases = (0..3)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+        let mut schnorr_sp = schnorr::SchnorrProtocol::new(1, &bases, commitment);
+        schnorr_sp.init(&mut rng, BTreeMap::new(), scalars).unwrap();
+
+        let bbs_id =
+            <PoKBBSSignatureG1<Bls12_381> as StatementId<Bls12_381, G1Affine>>::statement_id();
+        let ped_comm_id =
+            <PedersenCommitment<G1Affine> as StatementId<Bls12_381, G1Affine>>::statement_id();
+        assert_ne!(bbs_id, ped_comm_id);
+
+        for (sp, id) in [
+            (
+                SubProtocol::<Bls12_381, G1Affine>::PoKBBSSignatureG1(bbs_sp),
+                bbs_id,
+            ),
+            (SubProtocol::PoKDiscreteLogs(schnorr_sp), ped_comm_id),
+        ] {
+            let mut bytes = vec![];
+            sp.challenge_contribution(&mut bytes).unwrap();
+            assert_eq!(bytes[0], id);
+        }
+    }
+
+    #[test]
+    fn noop_sub_protocol() {
+        use crate::statement_proof::NoopProof;
+        use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+        use ark_serialize::CanonicalDeserialize;
+
+        let mut sp = SubProtocol::<Bls12_381, G1Affine>::Noop(noop::NoopProtocol::new(1));
+        assert_eq!(sp.id(), 1);
+        let mut bytes = vec![];
+        sp.challenge_contribution(&mut bytes).unwrap();
+        assert_eq!(bytes, vec![SubProtocolType::Noop as u8, 0]);
+
+        let proof = sp.gen_proof_contribution(&Fr::from(5u64)).unwrap();
+        assert_eq!(proof, StatementProof::Noop(NoopProof {}));
+        assert_eq!(proof.protocol_type(), SubProtocolType::Noop);
+        noop::NoopProtocol::new(1)
+            .verify_proof_contribution(&NoopProof {})
+            .unwrap();
+        let mut ser = vec![];
+        proof.serialize_compressed(&mut ser).unwrap();
+        assert_eq!(
+            StatementProof::<Bls12_381, G1Affine>::deserialize_compressed(&ser[..]).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn merging_revealed_and_unrevealed_messages() {
+        // Try all reveal patterns for message counts up to 7
+        for count in 1..8 {
+            for pattern in 0..(1u32 << count) {
+                let mut revealed = BTreeMap::new();
+                let mut unrevealed = BTreeMap::new();
+                for i in 0..count {
+                    let msg = 100 + i as u64;
+                    if pattern & (1 << i) != 0 {
+                        revealed.insert(i, msg);
+                    } else {
+                        unrevealed.insert(i, msg);
+                    }
+                }
+
+                let mut non_seq_idx = None;
+                let merged = merge_revealed_and_unrevealed_messages(
+                    unrevealed.iter().map(|(i, m)| (*i, Msg::Unrevealed(*m))),
+                    revealed.iter().map(|(i, m)| (*i, Msg::Revealed(*m))),
+                    &mut non_seq_idx,
+                )
+                .collect::<Vec<_>>();
+                assert!(non_seq_idx.is_none());
+                assert_eq!(merged, assemble_by_lookup(&revealed, &unrevealed));
+            }
+        }
+
+        // Missing index 2
+        let revealed = BTreeMap::from([(0, 100), (3, 103)]);
+        let unrevealed = BTreeMap::from([(1, 101), (4, 104)]);
+        let mut non_seq_idx = None;
+        let merged = merge_revealed_and_unrevealed_messages(
+            unrevealed.iter().map(|(i, m)| (*i, Msg::Unrevealed(*m))),
+            revealed.iter().map(|(i, m)| (*i, Msg::Revealed(*m))),
+            &mut non_seq_idx,
+        )
+        .collect::<Vec<_>>();
+        assert_eq!(merged, vec![Msg::Revealed(100), Msg::Unrevealed(101)]);
+        assert!(matches!(
+            non_seq_idx,
+            Some(InvalidPairOrSingle::Pair(InvalidPair(1, 3)))
+        ));
+
+        // Indices not starting from 0
+        let revealed = BTreeMap::from([(1, 101)]);
+        let unrevealed = BTreeMap::from([(2, 102)]);
+        let mut non_seq_idx = None;
+        let merged = merge_revealed_and_unrevealed_messages(
+            unrevealed.iter().map(|(i, m)| (*i, Msg::Unrevealed(*m))),
+            revealed.iter().map(|(i, m)| (*i, Msg::Revealed(*m))),
+            &mut non_seq_idx,
+        )
+        .collect::<Vec<_>>();
+        assert!(merged.is_empty());
+        assert!(matches!(non_seq_idx, Some(InvalidPairOrSingle::Single(1))));
+    }
+    #[test]
+    fn sub_protocol_ids() {
+        use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+        use ark_std::{
+            rand::{prelude::StdRng, SeedableRng},
+            UniformRand,
+        };
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let key = vec![G1Affine::rand(&mut rng)];
+        let commitment = G1Affine::rand(&mut rng);
+        let protocols = |ids: &[usize]| {
+            ids.iter()
+                .map(|id| {
+                    SubProtocol::<Bls12_381, G1Affine>::PoKDiscreteLogs(
+                        schnorr::SchnorrProtocol::new(*id, &key, commitment),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        SubProtocol::check_ids(&protocols(&[])).unwrap();
+        SubProtocol::check_ids(&protocols(&[0, 1, 2])).unwrap();
+        SubProtocol::check_ids(&protocols(&[2, 0, 1])).unwrap();
+
+        // Like when statements of merged specs aren't re-indexed
+        assert!(matches!(
+            SubProtocol::check_ids(&protocols(&[0, 1, 1])),
+            Err(ProofSystemError::DuplicateStatementId(1))
+        ));
+        assert!(matches!(
+            SubProtocol::check_ids(&protocols(&[0, 2])),
+            Err(ProofSystemError::NonContiguousStatementIds)
+        ));
+        assert!(matches!(
+            SubProtocol::check_ids(&protocols(&[1])),
+            Err(ProofSystemError::NonContiguousStatementIds)
+        ));
+
+        let linear_relation = crate::statement::linear_relation::SchnorrLinearRelation::<Bls12_381> {
+            coefficients: vec![vec![Fr::from(1u64)]],
+            targets: vec![Fr::from(1u64)],
+        };
+        let mut mixed = protocols(&[0]);
+        mixed.push(SubProtocol::SchnorrLinearRelation(
+            linear_relation::SchnorrLinearRelationProtocol::new(0, &linear_relation),
+        ));
+        assert!(matches!(
+            SubProtocol::check_ids(&mixed),
+            Err(ProofSystemError::DuplicateStatementId(0))
+        ));
+    }
+}
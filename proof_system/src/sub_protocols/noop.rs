@@ -0,0 +1,41 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::CanonicalSerialize;
+use ark_std::io::Write;
+
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{NoopProof, StatementProof},
+};
+
+/// Sub-protocol for a `Noop` statement, which holds the slot of a statement filled later. It contributes only a zero
+/// byte to the challenge and its proof always verifies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoopProtocol {
+    pub id: usize,
+}
+
+impl NoopProtocol {
+    pub fn new(id: usize) -> Self {
+        Self { id }
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        Self::compute_challenge_contribution(writer)
+    }
+
+    pub fn gen_proof_contribution<E: Pairing, G: AffineRepr>(
+        &mut self,
+        _challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        Ok(StatementProof::Noop(NoopProof {}))
+    }
+
+    pub fn verify_proof_contribution(&self, _proof: &NoopProof) -> Result<(), ProofSystemError> {
+        Ok(())
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(writer: W) -> Result<(), ProofSystemError> {
+        0u8.serialize_compressed(writer)?;
+        Ok(())
+    }
+}
@@ -1,17 +1,13 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_std::{collections::BTreeMap, io::Write, rand::RngCore};
 
-use dock_crypto_utils::{
-    iter::take_while_satisfy, misc::seq_inc_by_n_from,
-    randomized_pairing_check::RandomizedPairingChecker, try_iter::CheckLeft,
-};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
 
 use coconut_crypto::{proof::*, setup::*};
-use itertools::Itertools;
 
 use crate::{error::ProofSystemError, statement_proof::StatementProof};
 
-use super::merge_indexed_messages_with_blindings;
+use super::{merge_indexed_messages_with_blindings, merge_revealed_and_unrevealed_messages};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PSSignaturePoK<'a, E: Pairing> {
@@ -64,17 +60,13 @@ impl<'a, E: Pairing> PSSignaturePoK<'a, E> {
             &mut invalid_blinding_idx,
         );
         let mut non_seq_idx = None;
-        let all_messages = take_while_satisfy(
-            messages_to_commit.merge_by(
-                self.revealed_messages
-                    .iter()
-                    .map(|(idx, _)| (*idx, CommitMessage::RevealMessage)),
-                |(a, _), (b, _)| a < b,
-            ),
-            CheckLeft(seq_inc_by_n_from(1, 0)),
+        let all_messages = merge_revealed_and_unrevealed_messages(
+            messages_to_commit,
+            self.revealed_messages
+                .iter()
+                .map(|(idx, _)| (*idx, CommitMessage::RevealMessage)),
             &mut non_seq_idx,
-        )
-        .map(|(_, msg)| msg);
+        );
 
         let protocol = SignaturePoKGenerator::init(
             rng,
@@ -209,7 +209,7 @@ impl<'a, E: Pairing> R1CSLegogroth16Protocol<'a, E> {
         proof: &R1CSLegoGroth16Proof<E>,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        comm_key.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
         proof.snark_proof.d.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -220,7 +220,7 @@ impl<'a, E: Pairing> R1CSLegogroth16Protocol<'a, E> {
         proof: &R1CSLegoGroth16ProofWhenAggregatingSnarks<E>,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        comm_key.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(comm_key, &mut writer)?;
         proof.commitment.serialize_compressed(&mut writer)?;
         proof.sp.t.serialize_compressed(&mut writer)?;
         Ok(())
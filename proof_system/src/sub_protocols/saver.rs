@@ -364,18 +364,18 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         proof: &SaverProof<E>,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        ck_comm_ct.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(ck_comm_ct, &mut writer)?;
         proof
             .ciphertext
             .commitment
             .serialize_compressed(&mut writer)?;
         proof.sp_ciphertext.t.serialize_compressed(&mut writer)?;
 
-        ck_comm_chunks.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(ck_comm_chunks, &mut writer)?;
         proof.comm_chunks.serialize_compressed(&mut writer)?;
         proof.sp_chunks.t.serialize_compressed(&mut writer)?;
 
-        ck_comm_combined.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(ck_comm_combined, &mut writer)?;
         proof.comm_combined.serialize_compressed(&mut writer)?;
         proof.sp_combined.t.serialize_compressed(&mut writer)?;
         Ok(())
@@ -388,18 +388,18 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         proof: &SaverProofWhenAggregatingSnarks<E>,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        ck_comm_ct.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(ck_comm_ct, &mut writer)?;
         proof
             .ciphertext
             .commitment
             .serialize_compressed(&mut writer)?;
         proof.sp_ciphertext.t.serialize_compressed(&mut writer)?;
 
-        ck_comm_chunks.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(ck_comm_chunks, &mut writer)?;
         proof.comm_chunks.serialize_compressed(&mut writer)?;
         proof.sp_chunks.t.serialize_compressed(&mut writer)?;
 
-        ck_comm_combined.serialize_compressed(&mut writer)?;
+        SchnorrProtocol::serialize_commitment_key(ck_comm_combined, &mut writer)?;
         proof.comm_combined.serialize_compressed(&mut writer)?;
         proof.sp_combined.t.serialize_compressed(&mut writer)?;
         Ok(())
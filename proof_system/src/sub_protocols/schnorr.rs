@@ -1,9 +1,9 @@
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{CanonicalSerialize, SerializationError};
 use ark_std::{
-    cfg_iter_mut, collections::BTreeMap, io::Write, rand::RngCore, vec::Vec, UniformRand,
+    cfg_iter_mut, collections::BTreeMap, format, io::Write, rand::RngCore, vec::Vec, UniformRand,
 };
-use schnorr_pok::{SchnorrChallengeContributor, SchnorrCommitment};
+use schnorr_pok::{SchnorrChallengeContributor, SchnorrCommitment, SchnorrResponse};
 use zeroize::Zeroize;
 
 use crate::{
@@ -63,7 +63,7 @@ impl<'a, G: AffineRepr> SchnorrProtocol<'a, G> {
                 self.id,
             ));
         }
-        self.commitment_key.serialize_compressed(&mut writer)?;
+        Self::serialize_commitment_key(self.commitment_key, &mut writer)?;
         self.commitment.serialize_compressed(&mut writer)?;
         self.commitment_to_randomness
             .as_ref()
@@ -72,6 +72,39 @@ impl<'a, G: AffineRepr> SchnorrProtocol<'a, G> {
         Ok(())
     }
 
+    /// Write the challenge contributions of all `protocols` in the given order. The written bytes are the same
+    /// as when `challenge_contribution` is called for each protocol but a commitment key shared by several
+    /// protocols, i.e. the same slice, is serialized only once and its bytes are reused.
+    pub fn batch_challenge_contribution<W: Write>(
+        protocols: &[&Self],
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        // Serialized commitment keys, identified by the address and length of the slice
+        let mut serialized_keys = BTreeMap::<(usize, usize), Vec<u8>>::new();
+        for p in protocols {
+            if p.commitment_to_randomness.is_none() {
+                return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                    p.id,
+                ));
+            }
+            let key_id = (p.commitment_key.as_ptr() as usize, p.commitment_key.len());
+            if !serialized_keys.contains_key(&key_id) {
+                let mut key_bytes = Vec::new();
+                Self::serialize_commitment_key(p.commitment_key, &mut key_bytes)?;
+                serialized_keys.insert(key_id, key_bytes);
+            }
+            writer
+                .write_all(&serialized_keys[&key_id])
+                .map_err(SerializationError::from)?;
+            p.commitment.serialize_compressed(&mut writer)?;
+            p.commitment_to_randomness
+                .as_ref()
+                .unwrap()
+                .challenge_contribution(&mut writer)?;
+        }
+        Ok(())
+    }
+
     pub fn gen_proof_contribution<E: Pairing>(
         &mut self,
         challenge: &G::ScalarField,
@@ -95,6 +128,32 @@ impl<'a, G: AffineRepr> SchnorrProtocol<'a, G> {
         Ok(PedersenCommitmentProof::new(commitment.t, responses))
     }
 
+    /// Generate responses for several challenges using the same commitment to randomness, like in batch
+    /// identification protocols. The responses are in the same order as `challenges` and each can be verified
+    /// against the commitment `t` of `self.commitment_to_randomness`. The commitment to randomness is consumed
+    /// only after responses for all challenges are generated.
+    ///
+    /// Responses to different challenges for the same commitment reveal the witnesses so this must only be used
+    /// with protocols where this is intended, like threshold protocols where each party only learns one response.
+    pub fn gen_responses_for_challenges(
+        &mut self,
+        challenges: &[G::ScalarField],
+    ) -> Result<Vec<SchnorrResponse<G>>, ProofSystemError> {
+        if self.commitment_to_randomness.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let witnesses = self.witnesses.as_ref().unwrap();
+        let commitment = self.commitment_to_randomness.as_ref().unwrap();
+        let responses = challenges
+            .iter()
+            .map(|c| commitment.response(witnesses, c))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.commitment_to_randomness.take();
+        Ok(responses)
+    }
+
     pub fn verify_proof_contribution<E: Pairing>(
         &self,
         challenge: &G::ScalarField,
@@ -125,11 +184,30 @@ impl<'a, G: AffineRepr> SchnorrProtocol<'a, G> {
         t: &G,
         mut writer: W,
     ) -> Result<(), ProofSystemError> {
-        bases.serialize_compressed(&mut writer)?;
+        Self::serialize_commitment_key(bases, &mut writer)?;
         y.serialize_compressed(&mut writer)?;
         t.serialize_compressed(writer)?;
         Ok(())
     }
+
+    /// Write the number of bases as a `u32` followed by the bases so that prover and verifier using
+    /// commitment keys of different sizes never hash the same bytes.
+    pub(crate) fn serialize_commitment_key<W: Write>(
+        commitment_key: &[G],
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        let len = u32::try_from(commitment_key.len()).map_err(|_| {
+            ProofSystemError::UnsupportedValue(format!(
+                "commitment key has {} bases which is more than u32::MAX",
+                commitment_key.len()
+            ))
+        })?;
+        len.serialize_compressed(&mut writer)?;
+        for b in commitment_key {
+            b.serialize_compressed(&mut writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl<'a, G: AffineRepr> Zeroize for SchnorrProtocol<'a, G> {
@@ -148,3 +226,88 @@ impl<'a, G: AffineRepr> Drop for SchnorrProtocol<'a, G> {
         self.zeroize();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{G1Affine, G1Projective};
+    use ark_ec::{CurveGroup, VariableBaseMSM};
+    use ark_std::{
+        rand::{prelude::StdRng, SeedableRng},
+        vec,
+    };
+
+    #[test]
+    fn batch_challenge_contribution() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let key_1 = (0..3).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+        let key_2 = (0..2).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+
+        // 1st and 3rd protocols share the commitment key
+        let mut protocols = vec![
+            SchnorrProtocol::new(0, &key_1, G1Affine::rand(&mut rng)),
+            SchnorrProtocol::new(1, &key_2, G1Affine::rand(&mut rng)),
+            SchnorrProtocol::new(2, &key_1, G1Affine::rand(&mut rng)),
+        ];
+        let protocol_refs = protocols.iter().collect::<Vec<_>>();
+        assert!(
+            SchnorrProtocol::batch_challenge_contribution(&protocol_refs, Vec::<u8>::new())
+                .is_err()
+        );
+
+        for p in protocols.iter_mut() {
+            let witnesses = (0..p.commitment_key.len())
+                .map(|_| <G1Affine as AffineRepr>::ScalarField::rand(&mut rng))
+                .collect();
+            p.init(&mut rng, BTreeMap::new(), witnesses).unwrap();
+        }
+
+        let mut sequential_bytes = vec![];
+        for p in &protocols {
+            p.challenge_contribution(&mut sequential_bytes).unwrap();
+        }
+        let mut batch_bytes = vec![];
+        SchnorrProtocol::batch_challenge_contribution(
+            &protocols.iter().collect::<Vec<_>>(),
+            &mut batch_bytes,
+        )
+        .unwrap();
+        assert_eq!(batch_bytes, sequential_bytes);
+    }
+
+    #[test]
+    fn responses_for_multiple_challenges() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let key = (0..3).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+        let witnesses = (0..3)
+            .map(|_| <G1Affine as AffineRepr>::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let commitment = G1Projective::msm_unchecked(&key, &witnesses).into_affine();
+        let challenges = (0..3)
+            .map(|_| <G1Affine as AffineRepr>::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+
+        let mut protocol = SchnorrProtocol::new(0, &key, commitment);
+        assert!(protocol.gen_responses_for_challenges(&challenges).is_err());
+        protocol
+            .init(&mut rng, BTreeMap::new(), witnesses.clone())
+            .unwrap();
+        let t = protocol.commitment_to_randomness.as_ref().unwrap().t;
+
+        let responses = protocol.gen_responses_for_challenges(&challenges).unwrap();
+        assert_eq!(responses.len(), challenges.len());
+        for (response, challenge) in responses.iter().zip(challenges.iter()) {
+            response.is_valid(&key, &commitment, &t, challenge).unwrap();
+        }
+        // Responses are for different challenges
+        assert_ne!(responses[0], responses[1]);
+        assert!(responses[0]
+            .is_valid(&key, &commitment, &t, &challenges[1])
+            .is_err());
+
+        // Commitment to randomness is consumed but witnesses are kept
+        assert!(protocol.commitment_to_randomness.is_none());
+        assert_eq!(protocol.witnesses.as_ref().unwrap(), &witnesses);
+        assert!(protocol.gen_responses_for_challenges(&challenges).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Proof of knowledge of a BBS+ signature as one of the statements of a `MetaStatement::StatementDisjunction`, where
+//! the prover has a signature for one of the statements and the proofs of the others are simulated.
+//!
+//! This is an OR composition as in "Proofs of Partial Knowledge and Simplified Design of Witness Hiding Protocols" by
+//! Cramer, Damgård and Schoenmakers (CDS). For each statement with public key `W`, the prover proves knowledge of a
+//! BBS+ signature under a randomized public key `W'` using the challenge of the composite proof and proves knowledge
+//! of `r` such that `W' = W * r` with a Schnorr proof using its own challenge `c_i`. The challenges `c_i` of the
+//! statements of the disjunction add up to the challenge of the composite proof.
+//!
+//! - For the satisfied statement, with signature `(A, e, s)`, `W' = W * r` for a random `r` and the signature is
+//!   proven as `(A * 1/r, e * r, s)` as done by `PoKBBSSigG1HiddenPKSubProtocol`. Its challenge `c_i` is the
+//!   challenge of the composite proof minus the challenges of the other statements.
+//! - For every other statement, the prover creates a key `W' = g2 * k` for a random `k` and signs the revealed
+//!   messages, and random values for the hidden messages, with `k`. The signature is proven under `W'` and the Schnorr
+//!   proof for `W' = W * r` is simulated with a challenge `c_i` chosen before the challenge of the composite proof.
+//!
+//! As the challenges must add up to the challenge of the composite proof, the prover can choose all but one of them,
+//! so at least one Schnorr proof is real and that statement's signature is under a multiple of its public key. The
+//! randomized public keys are uniformly random and the real and simulated proofs have the same form, so the proof
+//! doesn't reveal which statement is satisfied. Unlike `PoKBBSSigG1HiddenPKSubProtocol`, the real and simulated
+//! proofs are created differently so the time taken to create them isn't the same.
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, Zero};
+use ark_std::{
+    collections::BTreeMap, format, io::Write, rand::RngCore, vec, vec::Vec, UniformRand,
+};
+use bbs_plus::{
+    error::BBSPlusError,
+    prelude::{
+        MultiMessageSignatureParams, PreparedPublicKeyG2, PreparedSignatureParamsG1, PublicKeyG2,
+        SecretKey, SignatureG1, SignatureParamsG1,
+    },
+    proof::{MessageOrBlinding, PoKOfSignatureG1Protocol},
+};
+use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{PoKBBSSignatureG1HiddenPKProof, RandomizedPublicKeyProof, StatementProof},
+    sub_protocols::bbs_plus_hidden_pk::PoKBBSSigG1HiddenPKSubProtocol,
+    witness::PoKBBSSignatureG1 as PoKBBSSignatureG1Witness,
+};
+
+use super::{merge_revealed_and_unrevealed_messages, SubProtocolType};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoKBBSSigG1DisjunctSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+    pub signature_params: &'a SignatureParamsG1<E>,
+    pub public_key: &'a PublicKeyG2<E>,
+    pub protocol: Option<PoKOfSignatureG1Protocol<E>>,
+    pub pk_protocol: Option<DisjunctPublicKeyProtocol<E>>,
+}
+
+/// Pre-challenge state of the proof that the randomized public key is a multiple of the public key of the statement
+#[derive(Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct DisjunctPublicKeyProtocol<E: Pairing> {
+    #[zeroize(skip)]
+    pub randomized_pk: E::G2Affine,
+    #[zeroize(skip)]
+    pub t: E::G2Affine,
+    /// Whether the statement is satisfied, i.e. the Schnorr proof is real
+    satisfied: bool,
+    /// For the satisfied statement, the sum of the challenges of the other statements of the disjunction. Otherwise
+    /// the challenge of the simulated Schnorr proof.
+    challenge: E::ScalarField,
+    /// For the satisfied statement, the scalar its public key is multiplied with. Otherwise 0.
+    randomizer: E::ScalarField,
+    /// For the satisfied statement, the randomness of the Schnorr proof. Otherwise the response of the simulated
+    /// Schnorr proof.
+    blinding: E::ScalarField,
+}
+
+impl<'a, E: Pairing> PoKBBSSigG1DisjunctSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+        signature_params: &'a SignatureParamsG1<E>,
+        public_key: &'a PublicKeyG2<E>,
+    ) -> Self {
+        Self {
+            id,
+            revealed_messages,
+            signature_params,
+            public_key,
+            protocol: None,
+            pk_protocol: None,
+        }
+    }
+
+    /// Initialize for the satisfied statement of the disjunction. `other_challenges` is the sum of the challenges
+    /// of the other statements of the disjunction.
+    pub fn init_satisfied<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        witness: PoKBBSSignatureG1Witness<E>,
+        other_challenges: E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        self.check_message_count(witness.unrevealed_messages.len())?;
+
+        let randomizer = E::ScalarField::rand(rng);
+        let randomizer_inv = randomizer.inverse().ok_or_else(|| {
+            ProofSystemError::ArithmeticError(format!(
+                "randomizer of the public key of {} statement {} is 0 so it can't be inverted",
+                SubProtocolType::PoKBBSSignatureG1Disjunct.label(),
+                self.id
+            ))
+        })?;
+        // (A * 1/r, e * r, s)
+        let randomized_sig = SignatureG1::<E> {
+            A: (witness.signature.A * randomizer_inv).into_affine(),
+            e: witness.signature.e * randomizer,
+            s: witness.signature.s,
+        };
+        self.init_protocol(rng, &randomized_sig, &witness.unrevealed_messages)?;
+
+        let blinding = E::ScalarField::rand(rng);
+        self.pk_protocol = Some(DisjunctPublicKeyProtocol {
+            randomized_pk: (self.public_key.0 * randomizer).into_affine(),
+            t: (self.public_key.0 * blinding).into_affine(),
+            satisfied: true,
+            challenge: other_challenges,
+            randomizer,
+            blinding,
+        });
+        Ok(())
+    }
+
+    /// Initialize for a statement of the disjunction which isn't satisfied. `challenge` is the challenge of its
+    /// simulated Schnorr proof.
+    pub fn init_simulated<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        challenge: E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if self.protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let message_count = self.signature_params.supported_message_count();
+        if self.revealed_messages.len() > message_count {
+            return Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                self.revealed_messages.len(),
+                message_count,
+            ));
+        }
+
+        // Sign the revealed messages and random hidden messages with a new key
+        let sk = SecretKey(E::ScalarField::rand(rng));
+        let randomized_pk = (self.signature_params.g2 * sk.0).into_affine();
+        let unrevealed_messages = (0..message_count)
+            .filter(|i| !self.revealed_messages.contains_key(i))
+            .map(|i| (i, E::ScalarField::rand(rng)))
+            .collect::<BTreeMap<_, _>>();
+        let messages = (0..message_count)
+            .map(|i| match self.revealed_messages.get(&i) {
+                Some(m) => *m,
+                None => unrevealed_messages[&i],
+            })
+            .collect::<Vec<_>>();
+        let signature = SignatureG1::new(rng, &messages, &sk, self.signature_params)?;
+        self.init_protocol(rng, &signature, &unrevealed_messages)?;
+
+        // t = W * z - W' * c for random response z
+        let response = E::ScalarField::rand(rng);
+        self.pk_protocol = Some(DisjunctPublicKeyProtocol {
+            randomized_pk,
+            t: (self.public_key.0 * response - randomized_pk * challenge).into_affine(),
+            satisfied: false,
+            challenge,
+            randomizer: E::ScalarField::zero(),
+            blinding: response,
+        });
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.protocol.as_ref().unwrap().challenge_contribution(
+            self.revealed_messages,
+            self.signature_params,
+            &mut writer,
+        )?;
+        let pk_protocol = self.pk_protocol.as_ref().unwrap();
+        PoKBBSSigG1HiddenPKSubProtocol::pk_challenge_contribution(
+            core::slice::from_ref(self.public_key),
+            &pk_protocol.randomized_pk,
+            core::slice::from_ref(&pk_protocol.t),
+            writer,
+        )
+    }
+
+    pub fn gen_proof_contribution<G: AffineRepr>(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E, G>, ProofSystemError> {
+        if self.protocol.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let proof = self.protocol.take().unwrap().gen_proof(challenge)?;
+        let pk_protocol = self.pk_protocol.take().unwrap();
+        Ok(StatementProof::PoKBBSSignatureG1Disjunct(
+            PoKBBSSignatureG1HiddenPKProof {
+                proof,
+                randomized_pk: pk_protocol.randomized_pk,
+                pk_proof: pk_protocol.gen_proof(challenge),
+            },
+        ))
+    }
+
+    /// Verify the proof except that the challenges of the statements of the disjunction add up to `challenge`, which
+    /// is checked by the verifier of the composite proof
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &PoKBBSSignatureG1HiddenPKProof<E>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
+        self.verify_randomized_pk(proof)?;
+        let pk = PreparedPublicKeyG2::from(PublicKeyG2::<E>(proof.randomized_pk));
+        match pairing_checker {
+            Some(c) => proof.proof.verify_with_randomized_pairing_checker(
+                self.revealed_messages,
+                challenge,
+                pk,
+                params,
+                c,
+            )?,
+            None => proof
+                .proof
+                .verify(self.revealed_messages, challenge, pk, params)?,
+        }
+        Ok(())
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        revealed_messages: &BTreeMap<usize, E::ScalarField>,
+        signature_params: &SignatureParamsG1<E>,
+        public_key: &PublicKeyG2<E>,
+        proof: &PoKBBSSignatureG1HiddenPKProof<E>,
+        writer: W,
+    ) -> Result<(), ProofSystemError> {
+        PoKBBSSigG1HiddenPKSubProtocol::compute_challenge_contribution(
+            revealed_messages,
+            signature_params,
+            core::slice::from_ref(public_key),
+            proof,
+            writer,
+        )
+    }
+
+    /// Check that the randomized public key is a multiple of the public key, i.e.
+    /// `pk * response == t + randomized_pk * challenge` for the statement's own challenge
+    fn verify_randomized_pk(
+        &self,
+        proof: &PoKBBSSignatureG1HiddenPKProof<E>,
+    ) -> Result<(), ProofSystemError> {
+        let pk_proof = &proof.pk_proof;
+        // A randomized key of 0 is a multiple of every key
+        if proof.randomized_pk.is_zero()
+            || pk_proof.t.len() != 1
+            || pk_proof.challenges.len() != 1
+            || pk_proof.responses.len() != 1
+        {
+            return Err(ProofSystemError::InvalidRandomizedPublicKeyProof(self.id));
+        }
+        if self.public_key.0 * pk_proof.responses[0]
+            != pk_proof.t[0].into_group() + proof.randomized_pk * pk_proof.challenges[0]
+        {
+            return Err(ProofSystemError::InvalidRandomizedPublicKeyProof(self.id));
+        }
+        Ok(())
+    }
+
+    fn check_message_count(&self, unrevealed_count: usize) -> Result<(), ProofSystemError> {
+        let total_message_count = self.revealed_messages.len() + unrevealed_count;
+        if total_message_count != self.signature_params.supported_message_count() {
+            Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(
+                total_message_count,
+                self.signature_params.supported_message_count(),
+            ))?
+        }
+        Ok(())
+    }
+
+    /// Initialize the proof of knowledge of `signature` over the revealed messages of the statement and the given
+    /// unrevealed messages, all of which are blinded randomly
+    fn init_protocol<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        signature: &SignatureG1<E>,
+        unrevealed_messages: &BTreeMap<usize, E::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        let mut non_seq_idx = None;
+        let all_messages = merge_revealed_and_unrevealed_messages(
+            unrevealed_messages
+                .iter()
+                .map(|(idx, msg)| (*idx, MessageOrBlinding::BlindMessageRandomly(msg))),
+            self.revealed_messages
+                .iter()
+                .map(|(idx, msg)| (*idx, MessageOrBlinding::RevealMessage(msg))),
+            &mut non_seq_idx,
+        );
+        let protocol =
+            PoKOfSignatureG1Protocol::init(rng, signature, self.signature_params, all_messages);
+        if let Some(invalid) = non_seq_idx {
+            Err(invalid.over(
+                ProofSystemError::BBSProtocolMessageIndicesMustStartFromZero,
+                ProofSystemError::BBSProtocolNonSequentialMessageIndices,
+            ))?
+        }
+        self.protocol = Some(protocol.map_err(|e| match e {
+            BBSPlusError::CannotInvert0 => ProofSystemError::ArithmeticError(format!(
+                "randomness used for the signature of {} statement {} is 0 so it can't be inverted",
+                SubProtocolType::PoKBBSSignatureG1Disjunct.label(),
+                self.id
+            )),
+            e => e.into(),
+        })?);
+        Ok(())
+    }
+}
+
+impl<E: Pairing> DisjunctPublicKeyProtocol<E> {
+    /// For the satisfied statement, the challenge of the Schnorr proof is `challenge` minus the challenges of the
+    /// other statements of the disjunction. Otherwise the simulated proof is returned as it is.
+    pub fn gen_proof(self, challenge: &E::ScalarField) -> RandomizedPublicKeyProof<E> {
+        let (c, z) = if self.satisfied {
+            let c = *challenge - self.challenge;
+            (c, self.blinding + c * self.randomizer)
+        } else {
+            (self.challenge, self.blinding)
+        };
+        RandomizedPublicKeyProof {
+            t: vec![self.t],
+            challenges: vec![c],
+            responses: vec![z],
+        }
+    }
+}
+
+impl<'a, E: Pairing> Zeroize for PoKBBSSigG1DisjunctSubProtocol<'a, E> {
+    fn zeroize(&mut self) {
+        self.protocol.zeroize();
+        self.pk_protocol.zeroize();
+    }
+}
+
+impl<'a, E: Pairing> Drop for PoKBBSSigG1DisjunctSubProtocol<'a, E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
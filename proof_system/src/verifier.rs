@@ -1,27 +1,44 @@
 use crate::{
     error::ProofSystemError,
     proof::Proof,
+    proof_context::ProofContext,
     proof_spec::{ProofSpec, SnarkpackSRS},
     statement::Statement,
     statement_proof::StatementProof,
     sub_protocols::{
-        accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol},
+        accumulator::{
+            AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol,
+            BatchAccumulatorMembershipSubProtocol, BatchAccumulatorNonMembershipSubProtocol,
+            CommittedAccumulatorMembershipSubProtocol,
+        },
         bbs_23::PoKBBSSigG1SubProtocol as PoKBBSSig23G1SubProtocol,
         bbs_plus::PoKBBSSigG1SubProtocol,
+        bbs_plus_hidden_pk::PoKBBSSigG1HiddenPKSubProtocol,
         bound_check_bpp::BoundCheckBppProtocol,
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
+        coalesced_schnorr::{CoalescedSchnorrProtocol, CoalescedStatementProtocol},
         inequality::InequalityProtocol,
+        linear_relation::SchnorrLinearRelationProtocol,
+        merkle_membership::MerkleMembershipProtocol,
+        noop::NoopProtocol,
         ps_signature::PSSignaturePoK,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         saver::SaverProtocol,
         schnorr::SchnorrProtocol,
+        statement_disjunction::PoKBBSSigG1DisjunctSubProtocol,
     },
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeMap, format, rand::RngCore, vec, vec::Vec};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    rand::RngCore,
+    vec,
+    vec::Vec,
+};
 use bbs_plus::prelude::MultiMessageSignatureParams;
 use digest::Digest;
 use dock_crypto_utils::{
@@ -30,12 +47,21 @@ use dock_crypto_utils::{
 };
 use saver::encryption::Ciphertext;
 
+#[cfg(feature = "parallel")]
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 /// Passed to the verifier during proof verification
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Default)]
 pub struct VerifierConfig {
     /// Uses `RandomizedPairingChecker` to speed up pairing checks.
     /// If true, uses lazy `RandomizedPairingChecker` that trades-off memory for compute time
     pub use_lazy_randomized_pairing_checks: Option<bool>,
+    /// Accept the BBS+ signature itself instead of a proof of knowledge of it for `PoKBBSSignatureG1` statements
+    /// revealing all messages, see `ProverConfig::send_fully_revealed_bbs_plus_signatures`. Such a signature is not
+    /// bound to the nonce so it might be replayed by anyone who has seen it before.
+    pub accept_fully_revealed_bbs_plus_signatures: bool,
 }
 
 macro_rules! err_incompat_proof {
@@ -92,29 +118,136 @@ where
     G: AffineRepr<ScalarField = E::ScalarField>,
 {
     /// Verify the `Proof` given the `ProofSpec`, `nonce` and `config`
-    pub fn verify<R: RngCore, D: Digest>(
+    pub fn verify<R: RngCore, D: Digest + Clone>(
         self,
         rng: &mut R,
-        proof_spec: ProofSpec<E, G>,
+        proof_spec: &ProofSpec<E, G>,
         nonce: Option<Vec<u8>>,
         config: VerifierConfig,
     ) -> Result<(), ProofSystemError> {
+        let accept_full_reveal = config.accept_fully_revealed_bbs_plus_signatures;
         match config.use_lazy_randomized_pairing_checks {
             Some(b) => {
                 let pairing_checker = RandomizedPairingChecker::new_using_rng(rng, b);
-                self._verify::<R, D>(rng, proof_spec, nonce, Some(pairing_checker))
+                self._verify::<R, D>(
+                    rng,
+                    proof_spec,
+                    nonce,
+                    Some(pairing_checker),
+                    accept_full_reveal,
+                )
             }
-            None => self._verify::<R, D>(rng, proof_spec, nonce, None),
+            None => self._verify::<R, D>(rng, proof_spec, nonce, None, accept_full_reveal),
         }
     }
 
-    fn _verify<R: RngCore, D: Digest>(
+    /// Verify several proofs where each proof has its own `ProofSpec` and nonce. Pairing checks of all
+    /// proofs are aggregated using a single `RandomizedPairingChecker` so only one final exponentiation
+    /// is done. Returns the error of the first proof that fails its other checks or
+    /// `RandomizedPairingCheckFailed` if the aggregated pairing check fails. Proofs sending a fully revealed BBS+
+    /// signature are not accepted.
+    pub fn batch_verify<R: RngCore, D: Digest + Clone>(
+        rng: &mut R,
+        proofs: Vec<(Self, &ProofSpec<E, G>, Option<Vec<u8>>)>,
+        use_lazy_randomized_pairing_checks: bool,
+    ) -> Result<(), ProofSystemError> {
+        let mut pairing_checker = Some(RandomizedPairingChecker::new_using_rng(
+            rng,
+            use_lazy_randomized_pairing_checks,
+        ));
+        for (proof, proof_spec, nonce) in proofs {
+            pairing_checker = proof.verify_except_final_pairing_check::<R, D>(
+                rng,
+                proof_spec,
+                nonce,
+                pairing_checker,
+                false,
+            )?;
+        }
+        Self::final_pairing_check(pairing_checker)
+    }
+
+    /// Same as `Self::batch_verify` but the proofs are verified in parallel, each with its own
+    /// `RandomizedPairingChecker`. The checkers are combined once all proofs are verified so only the
+    /// final pairing check is done serially. Gives the same result as `Self::batch_verify`.
+    #[cfg(feature = "parallel")]
+    pub fn batch_verify_parallel<R: RngCore, D: Digest + Clone>(
+        rng: &mut R,
+        proofs: Vec<(Self, &ProofSpec<E, G>, Option<Vec<u8>>)>,
+        use_lazy_randomized_pairing_checks: bool,
+    ) -> Result<(), ProofSystemError>
+    where
+        Self: Send,
+        ProofSpec<E, G>: Sync,
+    {
+        // Each proof gets its own rng seeded from the given one as proofs can be verified on any thread
+        let seeds = (0..proofs.len())
+            .map(|_| {
+                let mut seed = <StdRng as SeedableRng>::Seed::default();
+                rng.fill_bytes(&mut seed);
+                seed
+            })
+            .collect::<Vec<_>>();
+        let results = proofs
+            .into_par_iter()
+            .zip(seeds.into_par_iter())
+            .map(|((proof, proof_spec, nonce), seed)| {
+                let mut rng = StdRng::from_seed(seed);
+                let pairing_checker = RandomizedPairingChecker::new_using_rng(
+                    &mut rng,
+                    use_lazy_randomized_pairing_checks,
+                );
+                proof.verify_except_final_pairing_check::<StdRng, D>(
+                    &mut rng,
+                    proof_spec,
+                    nonce,
+                    Some(pairing_checker),
+                    false,
+                )
+            })
+            .collect::<Vec<_>>();
+        // Go over results in order of proofs so that the error returned is deterministic
+        let mut pairing_checker: Option<RandomizedPairingChecker<E>> = None;
+        for result in results {
+            if let Some(c) = result? {
+                match pairing_checker.as_mut() {
+                    Some(p) => p.combine(c),
+                    None => pairing_checker = Some(c),
+                }
+            }
+        }
+        Self::final_pairing_check(pairing_checker)
+    }
+
+    fn _verify<R: RngCore, D: Digest + Clone>(
         self,
         rng: &mut R,
-        proof_spec: ProofSpec<E, G>,
+        proof_spec: &ProofSpec<E, G>,
         nonce: Option<Vec<u8>>,
-        mut pairing_checker: Option<RandomizedPairingChecker<E>>,
+        pairing_checker: Option<RandomizedPairingChecker<E>>,
+        accept_full_reveal: bool,
     ) -> Result<(), ProofSystemError> {
+        let pairing_checker = self.verify_except_final_pairing_check::<R, D>(
+            rng,
+            proof_spec,
+            nonce,
+            pairing_checker,
+            accept_full_reveal,
+        )?;
+        Self::final_pairing_check(pairing_checker)
+    }
+
+    /// Does all checks of the proof except checking the pairings added to `pairing_checker`, which is
+    /// returned so that the caller can do the final check, possibly after adding pairings of other proofs.
+    /// `accept_full_reveal` is `VerifierConfig::accept_fully_revealed_bbs_plus_signatures`.
+    fn verify_except_final_pairing_check<R: RngCore, D: Digest + Clone>(
+        self,
+        rng: &mut R,
+        proof_spec: &ProofSpec<E, G>,
+        nonce: Option<Vec<u8>>,
+        mut pairing_checker: Option<RandomizedPairingChecker<E>>,
+        accept_full_reveal: bool,
+    ) -> Result<Option<RandomizedPairingChecker<E>>, ProofSystemError> {
         proof_spec.validate()?;
 
         // Number of statement proofs is less than number of statements which means some statements
@@ -169,6 +302,7 @@ where
             bound_check_smc_comm,
             ineq_comm,
         ) = proof_spec.derive_commitment_keys()?;
+        let default_accum_prk = proof_spec.derive_default_accumulator_proving_keys::<D>()?;
 
         // Prepare required parameters for pairings
         let (
@@ -196,19 +330,52 @@ where
             }
         }
 
+        // Proofs of statements whose signatures should be by the same issuer should have the same randomized public
+        // key. Proofs of other types are rejected when their statement is verified.
+        for same in proof_spec.meta_statements.issuer_consistencies() {
+            let mut randomized_pks =
+                same.0
+                    .iter()
+                    .filter_map(|i| match self.statement_proofs.get(*i) {
+                        Some(StatementProof::PoKBBSSignatureG1HiddenPK(p)) => {
+                            Some((*i, &p.randomized_pk))
+                        }
+                        _ => None,
+                    });
+            if let Some((first_idx, first_pk)) = randomized_pks.next() {
+                for (s_idx, pk) in randomized_pks {
+                    if pk != first_pk {
+                        return Err(ProofSystemError::InvalidIssuerConsistencyProof(
+                            first_idx, s_idx,
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Statements of disjunctions. Their proofs might be simulated so they have their own type of proof.
+        let disjunct_stmts = proof_spec
+            .meta_statements
+            .statement_disjunctions()
+            .flat_map(|(_, either)| either.0.iter().copied())
+            .collect::<BTreeSet<_>>();
+
         // This will hold the response for each witness equality. If there is no response for some witness
         // equality, it will contain `None` corresponding to that.
         let mut responses_for_equalities: Vec<Option<&E::ScalarField>> =
             vec![None; witness_equalities.len()];
 
-        // Get nonce's and context's challenge contribution
-        let mut challenge_bytes = vec![];
-        if let Some(n) = nonce.as_ref() {
-            challenge_bytes.extend_from_slice(n)
-        }
-        if let Some(ctx) = &proof_spec.context {
-            challenge_bytes.extend_from_slice(ctx);
-        }
+        // Statements with a witness in a witness equality. Their proofs can't be coalesced with other proofs.
+        let constrained_statements = proof_spec
+            .witness_refs()
+            .map(|(s_idx, _)| s_idx)
+            .collect::<BTreeSet<_>>();
+        // Commitments proven by each coalesced Pedersen commitment proof
+        let mut coalesced_commitments = BTreeMap::<usize, Vec<G>>::new();
+
+        // Get nonce's, context's and the proof spec's challenge contribution
+        let mut challenge_hasher =
+            ProofContext::<D>::new_for_proof_spec(proof_spec, nonce.as_deref())?;
 
         // Get challenge contribution for each statement and check if response is equal for all witnesses.
         for (s_idx, (statement, proof)) in proof_spec
@@ -218,7 +385,25 @@ where
             .zip(self.statement_proofs.iter())
             .enumerate()
         {
+            proof
+                .protocol_type()
+                .challenge_contribution(&mut challenge_hasher)?;
             match statement {
+                // Witnesses of a statement of a disjunction can't be in a witness equality
+                Statement::PoKBBSSignatureG1(s) if disjunct_stmts.contains(&s_idx) => match proof {
+                    StatementProof::PoKBBSSignatureG1Disjunct(p) => {
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        PoKBBSSigG1DisjunctSubProtocol::compute_challenge_contribution(
+                            &s.revealed_messages,
+                            sig_params,
+                            pk,
+                            p,
+                            &mut challenge_hasher,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::PoKBBSSignatureG1(s) => match proof {
                     StatementProof::PoKBBSSignatureG1(p) => {
                         let revealed_msg_ids = s.revealed_messages.keys().copied().collect();
@@ -242,7 +427,44 @@ where
                         p.challenge_contribution(
                             &s.revealed_messages,
                             sig_params,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
+                        )?;
+                    }
+                    // All messages are revealed so none of them can be in a witness equality
+                    StatementProof::BBSPlusFullReveal(sig) => {
+                        if !accept_full_reveal {
+                            return Err(ProofSystemError::FullRevealSignatureNotAccepted(s_idx));
+                        }
+                        sig.serialize_compressed(&mut challenge_hasher)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::PoKBBSSignatureG1HiddenPK(s) => match proof {
+                    StatementProof::PoKBBSSignatureG1HiddenPK(p) => {
+                        let revealed_msg_ids = s.revealed_messages.keys().copied().collect();
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        // Check witness equalities for this statement.
+                        for i in 0..sig_params.supported_message_count() {
+                            let w_ref = (s_idx, i);
+                            for j in 0..witness_equalities.len() {
+                                if witness_equalities[j].contains(&w_ref) {
+                                    let resp = p.get_resp_for_message(i, &revealed_msg_ids)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        resp,
+                                    )?;
+                                }
+                            }
+                        }
+                        PoKBBSSigG1HiddenPKSubProtocol::compute_challenge_contribution(
+                            &s.revealed_messages,
+                            sig_params,
+                            &s.public_keys,
+                            p,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -270,7 +492,7 @@ where
                         p.challenge_contribution(
                             &s.revealed_messages,
                             sig_params,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -287,13 +509,17 @@ where
                         );
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key_or_default(
+                            &proof_spec.setup_params,
+                            &default_accum_prk,
+                            s_idx,
+                        )?;
                         p.challenge_contribution(
                             &s.accumulator_value,
                             pk,
                             params,
                             prk,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -316,7 +542,7 @@ where
                             pk,
                             params,
                             prk,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -344,7 +570,38 @@ where
                             comm_key,
                             &s.commitment,
                             &p.t,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
+                        )?;
+                    }
+                    StatementProof::CoalescedPedersenCommitment(p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let commitments = Self::get_coalesced_commitments(
+                            proof_spec,
+                            &self.statement_proofs,
+                            &constrained_statements,
+                            s_idx,
+                            comm_key,
+                            &p.coalesced,
+                        )?;
+                        CoalescedSchnorrProtocol::compute_challenge_contribution(
+                            comm_key,
+                            &commitments,
+                            &p.coalesced,
+                            &p.t,
+                            &mut challenge_hasher,
+                        )?;
+                        coalesced_commitments.insert(s_idx, commitments);
+                    }
+                    StatementProof::Coalesced(p) => {
+                        // The statement's commitment is checked by the proof of statement `p.into`
+                        match self.statement_proofs.get(p.into) {
+                            Some(StatementProof::CoalescedPedersenCommitment(c))
+                                if c.coalesced.contains(&s_idx) => {}
+                            _ => return Err(ProofSystemError::InvalidCoalescedProof(s_idx)),
+                        }
+                        CoalescedStatementProtocol::compute_challenge_contribution(
+                            p.into,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -366,7 +623,7 @@ where
                             &cc_keys.0,
                             &cc_keys.1,
                             p,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     StatementProof::SaverWithAggregation(p) => {
@@ -385,7 +642,7 @@ where
                             &cc_keys.0,
                             &cc_keys.1,
                             p,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -405,7 +662,7 @@ where
                         BoundCheckLegoGrothProtocol::compute_challenge_contribution(
                             comm_key,
                             p,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     StatementProof::BoundCheckLegoGroth16WithAggregation(p) => {
@@ -422,7 +679,7 @@ where
                         BoundCheckLegoGrothProtocol::compute_challenge_contribution_when_aggregating_snark(
                             comm_key,
                             p,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -449,7 +706,7 @@ where
                             R1CSLegogroth16Protocol::compute_challenge_contribution(
                                 r1cs_comm_keys.get(s_idx).unwrap(),
                                 p,
-                                &mut challenge_bytes,
+                                &mut challenge_hasher,
                             )?;
                         }
                         StatementProof::R1CSLegoGroth16WithAggregation(p) => {
@@ -471,7 +728,7 @@ where
                             R1CSLegogroth16Protocol::compute_challenge_contribution_when_aggregating_snark(
                                 r1cs_comm_keys.get(s_idx).unwrap(),
                                 p,
-                                &mut challenge_bytes,
+                                &mut challenge_hasher,
                             )?;
                         }
                         _ => err_incompat_proof!(s_idx, s, proof),
@@ -502,7 +759,7 @@ where
                                 }
                             }
                         }
-                        p.challenge_contribution(&mut challenge_bytes, pk, sig_params)?;
+                        p.challenge_contribution(&mut challenge_hasher, pk, sig_params)?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
@@ -523,7 +780,7 @@ where
                             s.max,
                             comm_key.as_slice(),
                             p,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -544,7 +801,7 @@ where
                             comm_key_slice.as_slice(),
                             p,
                             derived_smc_param.get(s_idx).unwrap().clone(),
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -565,7 +822,7 @@ where
                             comm_key_slice.as_slice(),
                             p,
                             s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
@@ -587,11 +844,165 @@ where
                             p,
                             &s.inequal_to,
                             s.get_comm_key(&proof_spec.setup_params, s_idx)?,
-                            &mut challenge_bytes,
+                            &mut challenge_hasher,
                         )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::SchnorrLinearRelation(s) => match proof {
+                    StatementProof::SchnorrLinearRelation(p) => {
+                        for i in 0..s.witness_count() {
+                            // Check witness equalities for this statement.
+                            for j in 0..witness_equalities.len() {
+                                if witness_equalities[j].contains(&(s_idx, i)) {
+                                    let r = p.get_resp_for_message(i)?;
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        r,
+                                    )?;
+                                }
+                            }
+                        }
+
+                        SchnorrLinearRelationProtocol::compute_challenge_contribution(
+                            s,
+                            &p.t,
+                            &mut challenge_hasher,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::MerkleMembershipVerifier(s) => match proof {
+                    StatementProof::MerkleMembership(p) => {
+                        check_resp_for_equalities_with_err!(
+                            witness_equalities,
+                            s_idx,
+                            p,
+                            get_schnorr_response_for_message,
+                            Self,
+                            responses_for_equalities
+                        );
+
+                        let comm_key = r1cs_comm_keys.get(s_idx).unwrap();
+                        MerkleMembershipProtocol::compute_challenge_contribution(
+                            comm_key,
+                            p,
+                            &mut challenge_hasher,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BatchAccumulatorNonMembership(s) => match proof {
+                    StatementProof::BatchAccumulatorNonMembership(p) => {
+                        if p.proofs.len() != s.num_elements {
+                            return Err(ProofSystemError::BatchNonMembershipCountMismatch(
+                                s_idx,
+                                s.num_elements,
+                                p.proofs.len(),
+                            ));
+                        }
+                        // Check witness equalities for this statement. The element at index `i` is the witness
+                        // at index `i`
+                        for (i, elem_proof) in p.proofs.iter().enumerate() {
+                            for j in 0..witness_equalities.len() {
+                                if witness_equalities[j].contains(&(s_idx, i)) {
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        elem_proof.get_schnorr_response_for_element(),
+                                    )?;
+                                }
+                            }
+                        }
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        for elem_proof in &p.proofs {
+                            elem_proof.challenge_contribution(
+                                &s.accumulator_value,
+                                pk,
+                                params,
+                                prk,
+                                &mut challenge_hasher,
+                            )?;
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BatchAccumulatorMembership(s) => match proof {
+                    StatementProof::BatchAccumulatorMembership(p) => {
+                        if p.proofs.len() != s.num_elements {
+                            return Err(ProofSystemError::BatchMembershipCountMismatch(
+                                s_idx,
+                                s.num_elements,
+                                p.proofs.len(),
+                            ));
+                        }
+                        // Check witness equalities for this statement. The element at index `i` is the witness
+                        // at index `i`
+                        for (i, elem_proof) in p.proofs.iter().enumerate() {
+                            for j in 0..witness_equalities.len() {
+                                if witness_equalities[j].contains(&(s_idx, i)) {
+                                    Self::check_response_for_equality(
+                                        s_idx,
+                                        i,
+                                        j,
+                                        &mut responses_for_equalities,
+                                        elem_proof.get_schnorr_response_for_element(),
+                                    )?;
+                                }
+                            }
+                        }
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        for elem_proof in &p.proofs {
+                            elem_proof.challenge_contribution(
+                                &s.accumulator_value,
+                                pk,
+                                params,
+                                prk,
+                                &mut challenge_hasher,
+                            )?;
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::CommittedAccumulatorMembership(s) => match proof {
+                    StatementProof::CommittedAccumulatorMembership(p) => {
+                        check_resp_for_equalities!(
+                            witness_equalities,
+                            s_idx,
+                            p,
+                            get_schnorr_response_for_element,
+                            Self,
+                            responses_for_equalities
+                        );
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        p.challenge_contribution(
+                            &s.commitment,
+                            &s.commitment_key,
+                            pk,
+                            params,
+                            prk,
+                            &mut challenge_hasher,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::Noop(s) => match proof {
+                    StatementProof::Noop(_) => {
+                        NoopProtocol::compute_challenge_contribution(&mut challenge_hasher)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
         }
@@ -612,7 +1023,25 @@ where
         }
 
         // Verifier independently generates challenge
-        let challenge = Self::generate_challenge_from_bytes::<D>(&challenge_bytes);
+        let challenge = challenge_hasher.finalize::<E::ScalarField>();
+
+        // The challenges of the proofs of the statements of each disjunction should add up to the challenge. Proofs of
+        // other types are rejected when their statement is verified.
+        for (i, either) in proof_spec.meta_statements.statement_disjunctions() {
+            let challenge_sum = either
+                .0
+                .iter()
+                .filter_map(|s_idx| match self.statement_proofs.get(*s_idx) {
+                    Some(StatementProof::PoKBBSSignatureG1Disjunct(p)) => {
+                        p.pk_proof.challenges.first()
+                    }
+                    _ => None,
+                })
+                .sum::<E::ScalarField>();
+            if challenge_sum != challenge {
+                return Err(ProofSystemError::InvalidStatementDisjunctionProof(i));
+            }
+        }
 
         // Verify the proof for each statement
         for (s_idx, (statement, proof)) in proof_spec
@@ -623,6 +1052,25 @@ where
             .enumerate()
         {
             match statement {
+                Statement::PoKBBSSignatureG1(s) if disjunct_stmts.contains(&s_idx) => match proof {
+                    StatementProof::PoKBBSSignatureG1Disjunct(ref p) => {
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = PoKBBSSigG1DisjunctSubProtocol::new(
+                            s_idx,
+                            &s.revealed_messages,
+                            sig_params,
+                            pk,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_bbs_plus_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::PoKBBSSignatureG1(s) => match proof {
                     StatementProof::PoKBBSSignatureG1(ref p) => {
                         let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
@@ -639,7 +1087,40 @@ where
                             derived_bbs_pk.get(s_idx).unwrap().clone(),
                             derived_bbs_plus_param.get(s_idx).unwrap().clone(),
                             &mut pairing_checker,
-                        )?
+                        )?;
+                    }
+                    StatementProof::BBSPlusFullReveal(ref sig) => {
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = PoKBBSSigG1SubProtocol::new(
+                            s_idx,
+                            &s.revealed_messages,
+                            sig_params,
+                            pk,
+                        );
+                        sp.verify_full_reveal(
+                            sig,
+                            derived_bbs_pk.get(s_idx).unwrap().clone(),
+                            derived_bbs_plus_param.get(s_idx).unwrap().clone(),
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::PoKBBSSignatureG1HiddenPK(s) => match proof {
+                    StatementProof::PoKBBSSignatureG1HiddenPK(ref p) => {
+                        let sig_params = s.get_sig_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = PoKBBSSigG1HiddenPKSubProtocol::new(
+                            s_idx,
+                            &s.revealed_messages,
+                            sig_params,
+                            &s.public_keys,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_bbs_plus_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
@@ -659,7 +1140,7 @@ where
                             derived_bbs_pk.get(s_idx).unwrap().clone(),
                             derived_bbs_param.get(s_idx).unwrap().clone(),
                             &mut pairing_checker,
-                        )?
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
@@ -667,7 +1148,11 @@ where
                     StatementProof::AccumulatorMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key_or_default(
+                            &proof_spec.setup_params,
+                            &default_accum_prk,
+                            s_idx,
+                        )?;
                         let sp = AccumulatorMembershipSubProtocol::new(
                             s_idx,
                             params,
@@ -675,10 +1160,14 @@ where
                             prk,
                             s.accumulator_value,
                         );
+                        let prepared_pk = match s.get_prepared_public_key() {
+                            Some(prepared_pk) => prepared_pk,
+                            None => derived_accum_pk.get(s_idx).unwrap().clone(),
+                        };
                         sp.verify_proof_contribution(
                             &challenge,
                             p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
+                            prepared_pk,
                             derived_accum_param.get(s_idx).unwrap().clone(),
                             &mut pairing_checker,
                         )?
@@ -713,6 +1202,19 @@ where
                         let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
                         sp.verify_proof_contribution(&challenge, &proof)?
                     }
+                    StatementProof::CoalescedPedersenCommitment(ref p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let commitments = coalesced_commitments.remove(&s_idx).unwrap();
+                        let sp = CoalescedSchnorrProtocol::new(
+                            s_idx,
+                            p.coalesced.clone(),
+                            comm_key,
+                            commitments,
+                        );
+                        sp.verify_proof_contribution(&challenge, p)?
+                    }
+                    // Verified along with the statement it's coalesced into
+                    StatementProof::Coalesced(_) => (),
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::SaverVerifier(s) => {
@@ -785,8 +1287,7 @@ where
                                 &mut pairing_checker,
                             )?,
                         StatementProof::BoundCheckLegoGroth16WithAggregation(ref bc_proof) => {
-                            let pub_inp =
-                                vec![E::ScalarField::from(sp.min), E::ScalarField::from(sp.max)];
+                            let pub_inp = sp.public_inputs();
                             let agg_idx = agg_lego_stmts.get(&s_idx).ok_or_else(|| {
                                 ProofSystemError::InvalidStatementProofIndex(s_idx)
                             })?;
@@ -892,14 +1393,22 @@ where
                 },
                 Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
                     StatementProof::BoundCheckSmcWithKV(ref bc_proof) => {
-                        let setup_params =
-                            s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckSmcWithKVProtocol::new_for_verifier(
-                            s_idx,
-                            s.min,
-                            s.max,
-                            setup_params,
-                        );
+                        let sp = match &s.prepared_params {
+                            Some(prepared_params) => {
+                                BoundCheckSmcWithKVProtocol::new_for_verifier_with_prepared_params(
+                                    s_idx,
+                                    s.min,
+                                    s.max,
+                                    prepared_params,
+                                )
+                            }
+                            None => BoundCheckSmcWithKVProtocol::new_for_verifier(
+                                s_idx,
+                                s.min,
+                                s.max,
+                                s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?,
+                            ),
+                        };
                         let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
                         sp.verify_proof_contribution(
                             &challenge,
@@ -918,6 +1427,108 @@ where
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::SchnorrLinearRelation(s) => match proof {
+                    StatementProof::SchnorrLinearRelation(ref p) => {
+                        let sp = SchnorrLinearRelationProtocol::new(s_idx, s);
+                        sp.verify_proof_contribution(&challenge, p)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::MerkleMembershipVerifier(s) => match proof {
+                    StatementProof::MerkleMembership(ref mm_proof) => {
+                        let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = MerkleMembershipProtocol::new_for_verifier(
+                            s_idx,
+                            s.root,
+                            verifying_key,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            mm_proof,
+                            r1cs_comm_keys.get(s_idx).unwrap(),
+                            derived_lego_vk.get(s_idx).unwrap(),
+                            &mut pairing_checker,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BatchAccumulatorNonMembership(s) => match proof {
+                    StatementProof::BatchAccumulatorNonMembership(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = BatchAccumulatorNonMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.accumulator_value,
+                            s.num_elements,
+                        );
+                        sp.verify_proof_contribution(
+                            rng,
+                            &challenge,
+                            p,
+                            derived_accum_pk.get(s_idx).unwrap().clone(),
+                            derived_accum_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BatchAccumulatorMembership(s) => match proof {
+                    StatementProof::BatchAccumulatorMembership(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = BatchAccumulatorMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.accumulator_value,
+                            s.num_elements,
+                        );
+                        sp.verify_proof_contribution(
+                            rng,
+                            &challenge,
+                            p,
+                            derived_accum_pk.get(s_idx).unwrap().clone(),
+                            derived_accum_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::CommittedAccumulatorMembership(s) => match proof {
+                    StatementProof::CommittedAccumulatorMembership(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = CommittedAccumulatorMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.commitment,
+                            s.commitment_key,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.get(s_idx).unwrap().clone(),
+                            derived_accum_param.get(s_idx).unwrap().clone(),
+                            &mut pairing_checker,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::Noop(s) => match proof {
+                    StatementProof::Noop(ref p) => {
+                        NoopProtocol::new(s_idx).verify_proof_contribution(p)?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
         }
@@ -925,7 +1536,7 @@ where
         if aggregate_snarks {
             // The validity of `ProofSpec` ensures that statements are not being repeated
 
-            let srs = match proof_spec.snark_aggregation_srs {
+            let srs = match &proof_spec.snark_aggregation_srs {
                 Some(SnarkpackSRS::VerifierSrs(srs)) => srs,
                 _ => return Err(ProofSystemError::SnarckpackSrsNotProvided),
             };
@@ -933,7 +1544,7 @@ where
             let mut transcript = new_merlin_transcript(b"aggregation");
             transcript.append(b"challenge", &challenge);
 
-            if let Some(to_aggregate) = proof_spec.aggregate_groth16 {
+            if let Some(to_aggregate) = &proof_spec.aggregate_groth16 {
                 if let Some(aggr_proofs) = self.aggregated_groth16 {
                     if to_aggregate.len() != aggr_proofs.len() {
                         return Err(ProofSystemError::InvalidNumberOfAggregateGroth16Proofs(
@@ -961,7 +1572,7 @@ where
                             &mut pairing_checker,
                         )?;
                         saver::saver_groth16::verify_aggregate_proof(
-                            &srs,
+                            srs,
                             pvk,
                             &a.proof,
                             ciphertexts,
@@ -975,7 +1586,7 @@ where
                 }
             }
 
-            if let Some(to_aggregate) = proof_spec.aggregate_legogroth16 {
+            if let Some(to_aggregate) = &proof_spec.aggregate_legogroth16 {
                 if let Some(aggr_proofs) = self.aggregated_legogroth16 {
                     if to_aggregate.len() != aggr_proofs.len() {
                         return Err(ProofSystemError::InvalidNumberOfAggregateLegoGroth16Proofs(
@@ -990,7 +1601,7 @@ where
                         let s_id = a.statements.into_iter().next().unwrap();
                         let pvk = derived_lego_vk.get(s_id).unwrap();
                         legogroth16::aggregation::legogroth16::using_groth16::verify_aggregate_proof(
-                            &srs,
+                            srs,
                             pvk,
                             &agg_lego[i].1,
                             &a.proof,
@@ -1007,6 +1618,12 @@ where
             }
         }
 
+        Ok(pairing_checker)
+    }
+
+    fn final_pairing_check(
+        pairing_checker: Option<RandomizedPairingChecker<E>>,
+    ) -> Result<(), ProofSystemError> {
         if let Some(c) = pairing_checker {
             if !c.verify() {
                 return Err(ProofSystemError::RandomizedPairingCheckFailed);
@@ -1015,6 +1632,46 @@ where
         Ok(())
     }
 
+    /// Commitments of the Pedersen commitment statement `s_idx` followed by those of the statements `coalesced`.
+    /// Errors if the statements `coalesced` aren't in increasing order after `s_idx`, aren't Pedersen commitment
+    /// statements with the same commitment key or don't have proofs referring to `s_idx`, or if any of the
+    /// statements has a witness in a witness equality.
+    fn get_coalesced_commitments(
+        proof_spec: &ProofSpec<E, G>,
+        statement_proofs: &[StatementProof<E, G>],
+        constrained_statements: &BTreeSet<usize>,
+        s_idx: usize,
+        comm_key: &[G],
+        coalesced: &[usize],
+    ) -> Result<Vec<G>, ProofSystemError> {
+        if coalesced.is_empty() || constrained_statements.contains(&s_idx) {
+            return Err(ProofSystemError::InvalidCoalescedProof(s_idx));
+        }
+        let mut commitments = Vec::with_capacity(coalesced.len() + 1);
+        let mut prev = s_idx;
+        for i in coalesced {
+            if *i <= prev || constrained_statements.contains(i) {
+                return Err(ProofSystemError::InvalidCoalescedProof(s_idx));
+            }
+            prev = *i;
+            match (proof_spec.statements.0.get(*i), statement_proofs.get(*i)) {
+                (Some(Statement::PedersenCommitment(st)), Some(StatementProof::Coalesced(p)))
+                    if p.into == s_idx
+                        && st.get_commitment_key(&proof_spec.setup_params, *i)? == comm_key =>
+                {
+                    commitments.push(st.commitment)
+                }
+                _ => return Err(ProofSystemError::InvalidCoalescedProof(s_idx)),
+            }
+        }
+        // Commitment of `s_idx` comes first
+        match &proof_spec.statements.0[s_idx] {
+            Statement::PedersenCommitment(st) => commitments.insert(0, st.commitment),
+            _ => return Err(ProofSystemError::InvalidCoalescedProof(s_idx)),
+        }
+        Ok(commitments)
+    }
+
     /// Used to check if response (from Schnorr protocol) for a witness is equal to other witnesses that
     /// it must be equal to. This is required when the `ProofSpec` demands certain witnesses to be equal.
     fn check_response_for_equality<'a>(
@@ -0,0 +1,195 @@
+//! Serialization of `Proof`, `ProofSpec` and `PresentationToken` with a format version header. The serialized
+//! bytes start with the 2 byte (little endian) format version followed by the compressed canonical serialization.
+//! During deserialization, bytes with an unknown format version are rejected rather than being
+//! deserialized into a possibly different structure.
+
+use crate::{
+    error::ProofSystemError, meta_statement::MetaStatements, presentation::PresentationToken,
+    proof::Proof, proof_spec::ProofSpec, statement::Statements,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    io::{Read, Write},
+    ops::Deref,
+    vec::Vec,
+};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Current format version of serialized `Proof` and `ProofSpec`
+pub const FORMAT_VERSION: u16 = 4;
+
+/// Serialize and deserialize with a format version header.
+pub trait VersionedSerialization: CanonicalSerialize + CanonicalDeserialize {
+    /// Write the format version followed by the serialized object
+    fn serialize_versioned<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        FORMAT_VERSION.serialize_compressed(&mut writer)?;
+        self.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// Read the format version and then the object. Objects serialized with the current format version or the
+    /// immediately previous one are accepted.
+    fn deserialize_versioned<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        let found = u16::deserialize_compressed(&mut reader)?;
+        if found == FORMAT_VERSION {
+            Ok(Self::deserialize_compressed(&mut reader)?)
+        } else if FORMAT_VERSION.checked_sub(1) == Some(found) {
+            Self::migrate_from_previous_version(reader)
+        } else {
+            Err(ProofSystemError::UnsupportedProofVersion {
+                found,
+                supported: FORMAT_VERSION,
+            })
+        }
+    }
+
+    /// Deserialize an object serialized with format version `FORMAT_VERSION - 1`. The version header should
+    /// already have been read from the `reader`. Needs to be updated whenever the format version changes.
+    fn migrate_from_previous_version<R: Read>(reader: R) -> Result<Self, ProofSystemError> {
+        // The serialized proof is unchanged in version 4. Version 4 hashes the statements and meta statements of the
+        // proof spec into the challenge so a version 3 proof still deserializes but will fail verification. Objects
+        // containing statements override this as version 4 also changed the serialization of `AccumulatorMembership`.
+        Ok(Self::deserialize_compressed(reader)?)
+    }
+
+    /// Size of the versioned serialization
+    fn versioned_serialized_size(&self) -> usize {
+        FORMAT_VERSION.compressed_size() + self.compressed_size()
+    }
+
+    /// Versioned serialization into a new vector which is allocated with the exact size
+    fn to_versioned_bytes(&self) -> Result<Vec<u8>, ProofSystemError> {
+        let mut bytes = Vec::with_capacity(self.versioned_serialized_size());
+        self.serialize_versioned(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Wrapper which memoizes the compressed serialized size of an object, like a `ProofSpec` that is serialized
+/// many times, as computing the size walks the whole object. The object can only be mutated through `get_mut`
+/// which invalidates the memoized size.
+#[derive(Debug)]
+pub struct CachedSize<T> {
+    inner: T,
+    /// `usize::MAX` when the size is not computed
+    compressed_size: AtomicUsize,
+}
+
+impl<T> CachedSize<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            compressed_size: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Mutable reference to the object. The size is recomputed when it's needed next.
+    pub fn get_mut(&mut self) -> &mut T {
+        *self.compressed_size.get_mut() = usize::MAX;
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Deref for CachedSize<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Clone> Clone for CachedSize<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            compressed_size: AtomicUsize::new(self.compressed_size.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for CachedSize<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T: CanonicalSerialize> CanonicalSerialize for CachedSize<T> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.inner.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        if compress == Compress::No {
+            return self.inner.serialized_size(compress);
+        }
+        let size = self.compressed_size.load(Ordering::Relaxed);
+        if size != usize::MAX {
+            return size;
+        }
+        let size = self.inner.serialized_size(compress);
+        self.compressed_size.store(size, Ordering::Relaxed);
+        size
+    }
+}
+
+impl<T: Valid> Valid for CachedSize<T> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.inner.check()
+    }
+}
+
+impl<T: CanonicalDeserialize> CanonicalDeserialize for CachedSize<T> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self::new(T::deserialize_with_mode(
+            reader, compress, validate,
+        )?))
+    }
+}
+
+impl<T: VersionedSerialization> VersionedSerialization for CachedSize<T> {
+    fn migrate_from_previous_version<R: Read>(reader: R) -> Result<Self, ProofSystemError> {
+        T::migrate_from_previous_version(reader).map(Self::new)
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> VersionedSerialization for Proof<E, G> {}
+
+impl<E: Pairing, G: AffineRepr> VersionedSerialization for ProofSpec<E, G> {
+    fn migrate_from_previous_version<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        // Only the statements need migration as `AccumulatorMembership` got the `default_proving_key` flag in
+        // version 4
+        Ok(Self {
+            statements: Statements::deserialize_v3(&mut reader)?,
+            meta_statements: MetaStatements::deserialize_compressed(&mut reader)?,
+            setup_params: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            context: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            aggregate_groth16: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            aggregate_legogroth16: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            snark_aggregation_srs: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+        })
+    }
+}
+
+impl<E: Pairing, G: AffineRepr> VersionedSerialization for PresentationToken<E, G> {
+    fn migrate_from_previous_version<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        Ok(Self {
+            proof: Proof::migrate_from_previous_version(&mut reader)?,
+            proof_spec: ProofSpec::migrate_from_previous_version(&mut reader)?,
+        })
+    }
+}
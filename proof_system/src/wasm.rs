@@ -0,0 +1,158 @@
+//! Thin wrappers over the typed API which take and return byte buffers so that they can be called from JS when
+//! compiled to `wasm32-unknown-unknown`. Proof specs and proofs are in the versioned serialization of
+//! `VersionedSerialization` and witnesses are compressed canonical serialized. An empty nonce means no nonce.
+//! Only BLS12-381 with G1 as the group of other protocols like Pedersen commitments is supported and Blake2b512
+//! is used for deriving the challenge.
+//!
+//! The functions returning `ProofSystemError` contain the logic and can be used and tested natively. The ones
+//! exported to JS map errors to their string representation.
+
+use crate::{
+    error::{ProofSystemError, PublicError},
+    proof::Proof,
+    proof_spec::ProofSpec,
+    versioning::VersionedSerialization,
+    witness::Witnesses,
+};
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::CanonicalDeserialize;
+use ark_std::{
+    format,
+    rand::{rngs::StdRng, RngCore, SeedableRng},
+    vec::Vec,
+};
+use blake2::Blake2b512;
+use wasm_bindgen::prelude::*;
+
+fn nonce_from_bytes(nonce_bytes: &[u8]) -> Option<Vec<u8>> {
+    (!nonce_bytes.is_empty()).then(|| nonce_bytes.to_vec())
+}
+
+/// Create a proof for the serialized proof spec and witnesses and return the serialized proof
+pub fn prove<R: RngCore>(
+    rng: &mut R,
+    spec_bytes: &[u8],
+    witness_bytes: &[u8],
+    nonce_bytes: &[u8],
+) -> Result<Vec<u8>, ProofSystemError> {
+    let proof_spec = ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(spec_bytes)?;
+    let witnesses = Witnesses::<Bls12_381>::deserialize_compressed(witness_bytes)?;
+    let (proof, _) = Proof::new::<R, Blake2b512>(
+        rng,
+        &proof_spec,
+        witnesses,
+        nonce_from_bytes(nonce_bytes),
+        Default::default(),
+    )?;
+    proof.to_versioned_bytes()
+}
+
+/// Verify the serialized proof against the serialized proof spec. Returns `Ok(false)` if the proof is invalid
+/// and an error if the inputs are malformed or unsupported.
+pub fn verify<R: RngCore>(
+    rng: &mut R,
+    spec_bytes: &[u8],
+    proof_bytes: &[u8],
+    nonce_bytes: &[u8],
+) -> Result<bool, ProofSystemError> {
+    let proof_spec = ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(spec_bytes)?;
+    let proof = Proof::<Bls12_381, G1Affine>::deserialize_versioned(proof_bytes)?;
+    match proof.verify::<R, Blake2b512>(
+        rng,
+        &proof_spec,
+        nonce_from_bytes(nonce_bytes),
+        Default::default(),
+    ) {
+        Ok(()) => Ok(true),
+        Err(e) if e.to_public() == PublicError::InvalidProof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn to_js_error(err: ProofSystemError) -> JsValue {
+    JsValue::from_str(&format!("{:?}", err))
+}
+
+fn rng_from_entropy() -> Result<StdRng, JsValue> {
+    let mut seed = <StdRng as SeedableRng>::Seed::default();
+    getrandom::getrandom(&mut seed).map_err(|e| JsValue::from_str(&format!("{:?}", e)))?;
+    Ok(StdRng::from_seed(seed))
+}
+
+/// JS callable version of `prove` using an RNG seeded from the environment's entropy
+#[wasm_bindgen]
+pub fn prove_bytes(
+    spec_bytes: &[u8],
+    witness_bytes: &[u8],
+    nonce_bytes: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    let mut rng = rng_from_entropy()?;
+    prove(&mut rng, spec_bytes, witness_bytes, nonce_bytes).map_err(to_js_error)
+}
+
+/// JS callable version of `verify` using an RNG seeded from the environment's entropy
+#[wasm_bindgen]
+pub fn verify_bytes(
+    spec_bytes: &[u8],
+    proof_bytes: &[u8],
+    nonce_bytes: &[u8],
+) -> Result<bool, JsValue> {
+    let mut rng = rng_from_entropy()?;
+    verify(&mut rng, spec_bytes, proof_bytes, nonce_bytes).map_err(to_js_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        meta_statement::MetaStatements,
+        statement::{ped_comm::PedersenCommitment, Statements},
+        witness::Witness,
+    };
+    use ark_bls12_381::{Fr, G1Projective};
+    use ark_ec::{CurveGroup, VariableBaseMSM};
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn prove_and_verify_bytes() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(PedersenCommitment::new_statement_from_params(
+            bases, commitment,
+        ));
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        proof_spec.validate().unwrap();
+        let spec_bytes = proof_spec.to_versioned_bytes().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Witness::PedersenCommitment(scalars));
+        let mut witness_bytes = vec![];
+        witnesses.serialize_compressed(&mut witness_bytes).unwrap();
+
+        let nonce = b"test nonce";
+        let proof_bytes = prove(&mut rng, &spec_bytes, &witness_bytes, nonce).unwrap();
+        assert!(verify(&mut rng, &spec_bytes, &proof_bytes, nonce).unwrap());
+
+        // Proof created without a nonce doesn't verify with a nonce
+        let proof_bytes_without_nonce = prove(&mut rng, &spec_bytes, &witness_bytes, &[]).unwrap();
+        assert!(verify(&mut rng, &spec_bytes, &proof_bytes_without_nonce, &[]).unwrap());
+        assert!(!verify(&mut rng, &spec_bytes, &proof_bytes_without_nonce, nonce).unwrap());
+
+        // Malformed input is an error rather than an invalid proof
+        assert!(verify(
+            &mut rng,
+            &spec_bytes,
+            &proof_bytes[..proof_bytes.len() - 1],
+            nonce
+        )
+        .is_err());
+        assert!(prove(&mut rng, &spec_bytes[1..], &witness_bytes, nonce).is_err());
+    }
+}
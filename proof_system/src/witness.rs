@@ -1,14 +1,33 @@
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{cmp, collections::BTreeMap, fmt::Debug, string::String, vec::Vec};
+#[cfg(feature = "witness-encryption")]
+use ark_std::rand::RngCore;
+use ark_std::{
+    cmp,
+    collections::BTreeMap,
+    fmt::{self, Debug},
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
 use bbs_plus::{
     signature::SignatureG1 as BBSSignatureG1, signature_23::Signature23G1 as BBSSignature23G1,
 };
+#[cfg(feature = "witness-encryption")]
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
 use coconut_crypto::Signature;
 use dock_crypto_utils::serde_utils::*;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Same};
-use vb_accumulator::witness::{MembershipWitness, NonMembershipWitness};
+use vb_accumulator::{
+    batch_utils::Omega,
+    positive::{Accumulator, PositiveAccumulator},
+    setup::{PublicKey as AccumPublicKey, SetupParams as AccumParams},
+    witness::{MembershipWitness, NonMembershipWitness},
+};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::error::ProofSystemError;
@@ -35,6 +54,21 @@ pub enum Witness<E: Pairing> {
     BoundCheckSmc(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
     BoundCheckSmcWithKV(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
     PublicInequality(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    /// Accumulator membership where the membership witness is fetched only when the proof is created
+    AccumulatorMembershipLazy(LazyMembership<E>),
+    PoKBBSSignatureG1HiddenPK(PoKBBSSignatureG1HiddenPK<E>),
+    /// Values satisfying the linear equations
+    SchnorrLinearRelation(#[serde_as(as = "Vec<ArkObjectBytes>")] Vec<E::ScalarField>),
+    /// Same as `BoundCheckSmcWithKV` but the message must fit in a `u32`
+    BoundCheckSmcWithKVU32(#[serde_as(as = "ArkObjectBytes")] E::ScalarField),
+    MerkleMembership(MerkleMembership<E>),
+    /// Elements whose non-membership is proven along with their witnesses, in the order of witness indices
+    BatchAccumulatorNonMembership(Vec<NonMembership<E>>),
+    /// Elements whose membership is proven along with their witnesses, in the order of witness indices
+    BatchAccumulatorMembership(Vec<Membership<E>>),
+    CommittedAccumulatorMembership(CommittedMembership<E>),
+    /// For a `Noop` statement
+    Noop(NoopWitness),
 }
 
 macro_rules! delegate {
@@ -53,7 +87,16 @@ macro_rules! delegate {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                PublicInequality
+                PublicInequality,
+                AccumulatorMembershipLazy,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                BoundCheckSmcWithKVU32,
+                MerkleMembership,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                Noop
             : $($tt)+
         }
     }}
@@ -75,7 +118,16 @@ macro_rules! delegate_reverse {
                 BoundCheckBpp,
                 BoundCheckSmc,
                 BoundCheckSmcWithKV,
-                PublicInequality
+                PublicInequality,
+                AccumulatorMembershipLazy,
+                PoKBBSSignatureG1HiddenPK,
+                SchnorrLinearRelation,
+                BoundCheckSmcWithKVU32,
+                MerkleMembership,
+                BatchAccumulatorNonMembership,
+                BatchAccumulatorMembership,
+                CommittedAccumulatorMembership,
+                Noop
             : $($tt)+
         }
 
@@ -143,6 +195,36 @@ impl<E: Pairing> Drop for PoKBBSSignatureG1<E> {
     }
 }
 
+/// Secret data when proving knowledge of BBS+ sig without revealing the public key of the signer
+#[serde_as]
+#[derive(
+    Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+#[serde(bound = "")]
+pub struct PoKBBSSignatureG1HiddenPK<E: Pairing> {
+    pub signature: BBSSignatureG1<E>,
+    #[serde_as(as = "BTreeMap<Same, ArkObjectBytes>")]
+    pub unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+    /// Index of the signer's public key in the public keys of the statement
+    pub public_key_index: usize,
+}
+
+impl<E: Pairing> Zeroize for PoKBBSSignatureG1HiddenPK<E> {
+    fn zeroize(&mut self) {
+        self.signature.zeroize();
+        self.unrevealed_messages
+            .values_mut()
+            .for_each(|v| v.zeroize());
+        self.public_key_index.zeroize();
+    }
+}
+
+impl<E: Pairing> Drop for PoKBBSSignatureG1HiddenPK<E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 /// Secret data when proving knowledge of BBS sig
 #[serde_as]
 #[derive(
@@ -191,6 +273,30 @@ pub struct Membership<E: Pairing> {
     pub witness: MembershipWitness<E::G1Affine>,
 }
 
+/// Secret data when proving membership in an accumulator whose value is committed. `randomness` is the randomness
+/// used in the commitment to the accumulator value.
+#[serde_as]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Zeroize,
+    ZeroizeOnDrop,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct CommittedMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub element: E::ScalarField,
+    pub witness: MembershipWitness<E::G1Affine>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub randomness: E::ScalarField,
+}
+
 /// Secret data when proving accumulator non-membership
 #[serde_as]
 #[derive(
@@ -212,6 +318,66 @@ pub struct NonMembership<E: Pairing> {
     pub witness: NonMembershipWitness<E::G1Affine>,
 }
 
+/// Secret data when proving membership of a leaf in a Merkle tree
+#[serde_as]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Zeroize,
+    ZeroizeOnDrop,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(bound = "")]
+pub struct MerkleMembership<E: Pairing> {
+    #[serde_as(as = "ArkObjectBytes")]
+    pub leaf: E::ScalarField,
+    /// Position of the leaf in the tree, the lowest bit decides the position at the leaf level
+    pub leaf_index: u64,
+    /// Siblings on the path from the leaf to the root, starting at the leaf level
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub siblings: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> MerkleMembership<E> {
+    /// Create a `Witness` variant for proving membership of a leaf in a Merkle tree
+    pub fn new_as_witness(
+        leaf: E::ScalarField,
+        leaf_index: u64,
+        siblings: Vec<E::ScalarField>,
+    ) -> Witness<E> {
+        Witness::MerkleMembership(MerkleMembership {
+            leaf,
+            leaf_index,
+            siblings,
+        })
+    }
+}
+
+/// Witness of a `Noop` statement, has no data
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct NoopWitness {}
+
+impl NoopWitness {
+    pub fn new_as_witness<E: Pairing>() -> Witness<E> {
+        Witness::Noop(NoopWitness {})
+    }
+}
+
 /// Witness for the Circom program. Only contains circuit wires that are explicitly set by the prover
 #[serde_as]
 #[derive(
@@ -265,8 +431,111 @@ where
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Witnesses for the statements created by `Statements::new_bbs_with_memberships`, i.e. the BBS+ signature
+    /// with its unrevealed messages followed by the members and their membership witnesses, in the same order as
+    /// the statements
+    pub fn new_bbs_with_memberships(
+        signature: BBSSignatureG1<E>,
+        unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+        memberships: Vec<(E::ScalarField, MembershipWitness<E::G1Affine>)>,
+    ) -> Self {
+        let mut witnesses = Self(Vec::with_capacity(1 + memberships.len()));
+        witnesses.add(PoKBBSSignatureG1::new_as_witness(
+            signature,
+            unrevealed_messages,
+        ));
+        for (element, witness) in memberships {
+            witnesses.add(Membership::new_as_witness(element, witness));
+        }
+        witnesses
+    }
+
+    /// Iterate over the witnesses of type `W` along with their index
+    pub fn iter_typed<'a, W: WitnessType<E> + 'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (usize, &'a W)> + 'a {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, w)| W::from_witness(w).map(|w| (i, w)))
+    }
+
+    /// Encrypt the compressed canonical serialization of the witnesses with XChaCha20-Poly1305 under `key` so that
+    /// they can be stored. Returns the random nonce followed by the ciphertext. Needs the `witness-encryption`
+    /// feature.
+    #[cfg(feature = "witness-encryption")]
+    pub fn encrypt_at_rest<R: RngCore>(
+        &self,
+        rng: &mut R,
+        key: &[u8; 32],
+    ) -> Result<Vec<u8>, ProofSystemError> {
+        let mut nonce = XNonce::default();
+        rng.fill_bytes(&mut nonce);
+        let mut plaintext = Vec::with_capacity(self.compressed_size());
+        self.serialize_compressed(&mut plaintext)?;
+        let ciphertext = XChaCha20Poly1305::new(key.into()).encrypt(&nonce, plaintext.as_slice());
+        plaintext.zeroize();
+        let ciphertext = ciphertext.map_err(|_| ProofSystemError::WitnessEncryptionFailed)?;
+        let mut bytes = Vec::with_capacity(nonce.len() + ciphertext.len());
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        Ok(bytes)
+    }
+
+    /// Decrypt witnesses encrypted with `Self::encrypt_at_rest`
+    #[cfg(feature = "witness-encryption")]
+    pub fn decrypt_at_rest(bytes: &[u8], key: &[u8; 32]) -> Result<Self, ProofSystemError> {
+        let nonce_len = XNonce::default().len();
+        if bytes.len() < nonce_len {
+            return Err(ProofSystemError::WitnessDecryptionFailed);
+        }
+        let (nonce, ciphertext) = bytes.split_at(nonce_len);
+        let mut plaintext = XChaCha20Poly1305::new(key.into())
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| ProofSystemError::WitnessDecryptionFailed)?;
+        let witnesses = Self::deserialize_compressed(plaintext.as_slice());
+        plaintext.zeroize();
+        Ok(witnesses?)
+    }
+}
+
+mod sealed {
+    pub trait Sealed<E> {}
+}
+
+/// Implemented by the types wrapped by the variants of `Witness` to get them out of a `Witness`. Not implemented
+/// for the variants wrapping a single field element as the type doesn't identify the variant.
+pub trait WitnessType<E: Pairing>: sealed::Sealed<E> {
+    /// Returns `None` if the witness is of a different type
+    fn from_witness(witness: &Witness<E>) -> Option<&Self>;
 }
 
+macro_rules! impl_witness_type {
+    ($variant: ident, $typ: ty) => {
+        impl<E: Pairing> sealed::Sealed<E> for $typ {}
+
+        impl<E: Pairing> WitnessType<E> for $typ {
+            fn from_witness(witness: &Witness<E>) -> Option<&Self> {
+                match witness {
+                    Witness::$variant(w) => Some(w),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_witness_type!(PoKBBSSignatureG1, PoKBBSSignatureG1<E>);
+impl_witness_type!(AccumulatorMembership, Membership<E>);
+impl_witness_type!(AccumulatorNonMembership, NonMembership<E>);
+impl_witness_type!(PedersenCommitment, Vec<E::ScalarField>);
+impl_witness_type!(R1CSLegoGroth16, R1CSCircomWitness<E>);
+impl_witness_type!(PoKPSSignature, PoKPSSignature<E>);
+impl_witness_type!(PoKBBSSignature23G1, PoKBBSSignature23G1<E>);
+impl_witness_type!(AccumulatorMembershipLazy, LazyMembership<E>);
+impl_witness_type!(PoKBBSSignatureG1HiddenPK, PoKBBSSignatureG1HiddenPK<E>);
+
 impl<E: Pairing> PoKBBSSignatureG1<E> {
     /// Create a `Witness` variant for proving knowledge of BBS+ signature
     pub fn new_as_witness(
@@ -280,6 +549,22 @@ impl<E: Pairing> PoKBBSSignatureG1<E> {
     }
 }
 
+impl<E: Pairing> PoKBBSSignatureG1HiddenPK<E> {
+    /// Create a `Witness` variant for proving knowledge of BBS+ signature by the public key at index
+    /// `public_key_index` of the statement's public keys
+    pub fn new_as_witness(
+        signature: BBSSignatureG1<E>,
+        unrevealed_messages: BTreeMap<usize, E::ScalarField>,
+        public_key_index: usize,
+    ) -> Witness<E> {
+        Witness::PoKBBSSignatureG1HiddenPK(PoKBBSSignatureG1HiddenPK {
+            signature,
+            unrevealed_messages,
+            public_key_index,
+        })
+    }
+}
+
 impl<E: Pairing> PoKBBSSignature23G1<E> {
     /// Create a `Witness` variant for proving knowledge of BBS signature
     pub fn new_as_witness(
@@ -301,6 +586,184 @@ impl<E: Pairing> Membership<E> {
     ) -> Witness<E> {
         Witness::AccumulatorMembership(Membership { element, witness })
     }
+
+    /// Create a `Witness` variant for proving membership of several elements in the same accumulator
+    pub fn new_batch_as_witness(
+        elements: impl IntoIterator<Item = (E::ScalarField, MembershipWitness<E::G1Affine>)>,
+    ) -> Witness<E> {
+        Witness::BatchAccumulatorMembership(
+            elements
+                .into_iter()
+                .map(|(element, witness)| Membership { element, witness })
+                .collect(),
+        )
+    }
+}
+
+impl<E: Pairing> CommittedMembership<E> {
+    /// Create a `Witness` variant for proving membership in an accumulator whose value is committed
+    pub fn new_as_witness(
+        element: E::ScalarField,
+        witness: MembershipWitness<E::G1Affine>,
+        randomness: E::ScalarField,
+    ) -> Witness<E> {
+        Witness::CommittedAccumulatorMembership(CommittedMembership {
+            element,
+            witness,
+            randomness,
+        })
+    }
+}
+
+/// Fetches the accumulator membership witness for the given element, like from a remote witness service
+pub type MembershipWitnessFetcher<E> = dyn Fn(
+        &<E as Pairing>::ScalarField,
+    ) -> Result<MembershipWitness<<E as Pairing>::G1Affine>, ProofSystemError>
+    + Send
+    + Sync;
+
+/// Witness for proving membership in accumulator where the membership witness isn't held in memory but
+/// fetched using a callback while the proof is being created. This is useful when a proof spec has many
+/// accumulator statements. As the callback can't be serialized, serializing this fetches the membership witness
+/// and serializes it like `Membership`, and deserializing gives a `LazyMembership` whose callback returns the
+/// deserialized witness. Serialization fails if the callback does.
+#[derive(Clone)]
+pub struct LazyMembership<E: Pairing> {
+    pub element: E::ScalarField,
+    pub fetch_fn: Arc<MembershipWitnessFetcher<E>>,
+}
+
+impl<E: Pairing> LazyMembership<E> {
+    /// Get the membership witness using the callback
+    pub fn fetch(&self) -> Result<Membership<E>, ProofSystemError> {
+        let witness = (self.fetch_fn)(&self.element)?;
+        Ok(Membership {
+            element: self.element,
+            witness,
+        })
+    }
+}
+
+impl<E: Pairing> Witness<E> {
+    /// Create a `Witness` variant for proving membership in accumulator where the membership witness is
+    /// fetched by calling `fetch_fn` only when the proof is created. An error returned by `fetch_fn` is
+    /// returned by the proof creation.
+    pub fn membership_lazy(
+        element: E::ScalarField,
+        fetch_fn: impl Fn(&E::ScalarField) -> Result<MembershipWitness<E::G1Affine>, ProofSystemError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Witness::AccumulatorMembershipLazy(LazyMembership {
+            element,
+            fetch_fn: Arc::new(fetch_fn),
+        })
+    }
+}
+
+impl<E: Pairing> From<Membership<E>> for LazyMembership<E> {
+    fn from(membership: Membership<E>) -> Self {
+        let witness = membership.witness.clone();
+        Self {
+            element: membership.element,
+            fetch_fn: Arc::new(move |_| Ok(witness.clone())),
+        }
+    }
+}
+
+impl<E: Pairing> Debug for LazyMembership<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyMembership")
+            .field("element", &self.element)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two lazy witnesses are equal only when they use the same callback
+impl<E: Pairing> PartialEq for LazyMembership<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.element == other.element
+            && Arc::as_ptr(&self.fetch_fn) as *const () == Arc::as_ptr(&other.fetch_fn) as *const ()
+    }
+}
+
+impl<E: Pairing> Eq for LazyMembership<E> {}
+
+/// Updates the membership witness of an element after one or more batches of additions and removals to the
+/// accumulator using the update info (`Omega`) published by the accumulator manager for each batch. This avoids
+/// requesting a fresh witness from the manager when re-proving membership after many accumulator changes.
+#[derive(Clone, Debug)]
+pub struct WitnessUpdater<E: Pairing> {
+    pub element: E::ScalarField,
+    pub witness: MembershipWitness<E::G1Affine>,
+    /// Batches of additions and removals with their `Omega` in the order they were applied to the accumulator
+    pub updates: Vec<(Vec<E::ScalarField>, Vec<E::ScalarField>, Omega<E::G1Affine>)>,
+}
+
+impl<E: Pairing> WitnessUpdater<E> {
+    pub fn new(element: E::ScalarField, witness: MembershipWitness<E::G1Affine>) -> Self {
+        Self {
+            element,
+            witness,
+            updates: Vec::new(),
+        }
+    }
+
+    /// Add a batch of updates. Batches must be added in the same order as the accumulator manager applied them.
+    pub fn add_batch(
+        &mut self,
+        additions: Vec<E::ScalarField>,
+        removals: Vec<E::ScalarField>,
+        omega: Omega<E::G1Affine>,
+    ) {
+        self.updates.push((additions, removals, omega))
+    }
+
+    /// Apply all the batches to the witness and check that the updated witness is valid for the accumulator
+    /// value `new_accumulated` before returning it as a `Witness` for proving membership.
+    pub fn update(
+        &self,
+        new_accumulated: &E::G1Affine,
+        pk: &AccumPublicKey<E>,
+        params: &AccumParams<E>,
+    ) -> Result<Witness<E>, ProofSystemError> {
+        let witness = if self.updates.is_empty() {
+            self.witness.clone()
+        } else {
+            self.witness
+                .update_using_public_info_after_multiple_batch_updates(
+                    self.updates
+                        .iter()
+                        .map(|(a, r, o)| (a.as_slice(), r.as_slice(), o))
+                        .collect(),
+                    &self.element,
+                )?
+        };
+        if !PositiveAccumulator::<E>::verify_membership_given_accumulated(
+            new_accumulated,
+            &self.element,
+            &witness,
+            pk,
+            params,
+        ) {
+            return Err(ProofSystemError::InvalidUpdatedAccumulatorWitness);
+        }
+        Ok(Membership::new_as_witness(self.element, witness))
+    }
+}
+
+impl<E: Pairing> Zeroize for WitnessUpdater<E> {
+    fn zeroize(&mut self) {
+        self.element.zeroize();
+        self.witness.zeroize();
+    }
+}
+
+impl<E: Pairing> Drop for WitnessUpdater<E> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
 }
 
 impl<E: Pairing> NonMembership<E> {
@@ -311,6 +774,34 @@ impl<E: Pairing> NonMembership<E> {
     ) -> Witness<E> {
         Witness::AccumulatorNonMembership(NonMembership { element, witness })
     }
+
+    /// Create a `Witness` variant for proving non-membership of several elements in the same accumulator
+    pub fn new_batch_as_witness(
+        elements: impl IntoIterator<Item = (E::ScalarField, NonMembershipWitness<E::G1Affine>)>,
+    ) -> Witness<E> {
+        Witness::BatchAccumulatorNonMembership(
+            elements
+                .into_iter()
+                .map(|(element, witness)| NonMembership { element, witness })
+                .collect(),
+        )
+    }
+
+    /// Update the witness after elements are added to the accumulator one after the other.
+    /// `old_accumulators[i]` is the accumulator before `additions[i]` was added. Doesn't need the secret key but
+    /// needs the accumulator before every addition, see `NonMembershipWitness::update_after_additions`. The
+    /// updated witness is used with `AccumulatorNonMembershipSubProtocol` like any other witness, as the
+    /// sub-protocol only needs a witness valid for the accumulator value of its statement.
+    pub fn update_after_additions(
+        &mut self,
+        additions: &[E::ScalarField],
+        old_accumulators: &[E::G1Affine],
+    ) -> Result<(), ProofSystemError> {
+        self.witness =
+            self.witness
+                .update_after_additions(&self.element, additions, old_accumulators)?;
+        Ok(())
+    }
 }
 
 impl<E: Pairing> R1CSCircomWitness<E> {
@@ -368,10 +859,63 @@ impl<E: Pairing> R1CSCircomWitness<E> {
 
 mod serialization {
     use super::*;
+    use ark_ec::AffineRepr;
     use ark_serialize::{
         CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
     };
-    use ark_std::io::{Read, Write};
+    use ark_std::{
+        format,
+        io::{Read, Write},
+    };
+    use serde::{ser, Deserializer, Serializer};
+
+    impl<E: Pairing> Valid for LazyMembership<E> {
+        fn check(&self) -> Result<(), SerializationError> {
+            Ok(())
+        }
+    }
+
+    impl<E: Pairing> CanonicalSerialize for LazyMembership<E> {
+        fn serialize_with_mode<W: Write>(
+            &self,
+            writer: W,
+            compress: Compress,
+        ) -> Result<(), SerializationError> {
+            self.fetch()
+                .map_err(|_| SerializationError::InvalidData)?
+                .serialize_with_mode(writer, compress)
+        }
+
+        /// Size of the fetched `Membership`, which doesn't depend on the witness so the callback isn't called
+        fn serialized_size(&self, compress: Compress) -> usize {
+            self.element.serialized_size(compress)
+                + MembershipWitness(E::G1Affine::zero()).serialized_size(compress)
+        }
+    }
+
+    impl<E: Pairing> CanonicalDeserialize for LazyMembership<E> {
+        fn deserialize_with_mode<R: Read>(
+            reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
+            Ok(Membership::<E>::deserialize_with_mode(reader, compress, validate)?.into())
+        }
+    }
+
+    impl<E: Pairing> Serialize for LazyMembership<E> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.fetch()
+                .map_err(|e| <S::Error as ser::Error>::custom(format!("{:?}", e)))?
+                .serialize(serializer)
+        }
+    }
+
+    impl<'de, E: Pairing> Deserialize<'de> for LazyMembership<E> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Ok(Membership::<E>::deserialize(deserializer)?.into())
+        }
+    }
 
     impl<E: Pairing> Valid for Witness<E> {
         fn check(&self) -> Result<(), SerializationError> {
@@ -493,4 +1037,97 @@ mod tests {
         witnesses.add(wit_5);
         test_serialization!(Witnesses<Bls12_381>, witnesses);
     }
+
+    #[test]
+    fn iterate_witnesses_of_type() {
+        fn indices<W: WitnessType<Bls12_381>>(witnesses: &Witnesses<Bls12_381>) -> Vec<usize> {
+            witnesses.iter_typed::<W>().map(|(i, _)| i).collect()
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let (msgs, _, _, sig) = bbs_plus_sig_setup(&mut rng, 5);
+        let (msgs_23, _, _, sig_23) = bbs_sig_setup(&mut rng, 5);
+        let bbs_plus_wit =
+            PoKBBSSignatureG1::new_as_witness(sig, msgs.into_iter().enumerate().collect());
+
+        let mut witnesses: Witnesses<Bls12_381> = Witnesses::new();
+        for _ in 0..2 {
+            witnesses.add(Membership::new_as_witness(
+                Fr::rand(&mut rng),
+                MembershipWitness(G1Proj::rand(&mut rng).into_affine()),
+            ));
+            witnesses.add(bbs_plus_wit.clone());
+        }
+        witnesses.add(Witness::PedersenCommitment(vec![Fr::rand(&mut rng)]));
+        witnesses.add(Witness::BoundCheckSmc(Fr::rand(&mut rng)));
+        witnesses.add(PoKBBSSignature23G1::new_as_witness(
+            sig_23,
+            msgs_23.into_iter().enumerate().collect(),
+        ));
+
+        assert_eq!(indices::<Membership<Bls12_381>>(&witnesses), vec![0, 2]);
+        assert_eq!(
+            indices::<PoKBBSSignatureG1<Bls12_381>>(&witnesses),
+            vec![1, 3]
+        );
+        assert_eq!(indices::<Vec<Fr>>(&witnesses), vec![4]);
+        assert_eq!(
+            indices::<PoKBBSSignature23G1<Bls12_381>>(&witnesses),
+            vec![6]
+        );
+        assert!(indices::<NonMembership<Bls12_381>>(&witnesses).is_empty());
+
+        for (_, w) in witnesses.iter_typed::<PoKBBSSignatureG1<Bls12_381>>() {
+            assert_eq!(Witness::PoKBBSSignatureG1(w.clone()), bbs_plus_wit);
+        }
+    }
+
+    #[cfg(feature = "witness-encryption")]
+    #[test]
+    fn witnesses_encrypted_at_rest() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (msgs, _, _, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+        let mut witnesses: Witnesses<Bls12_381> = Witnesses::new();
+        witnesses.add(PoKBBSSignatureG1::new_as_witness(
+            sig,
+            msgs.into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::PedersenCommitment(vec![Fr::rand(&mut rng)]));
+
+        let mut key = [0u8; 32];
+        rng.fill_bytes(&mut key);
+        let encrypted = witnesses.encrypt_at_rest(&mut rng, &key).unwrap();
+        assert_eq!(
+            Witnesses::<Bls12_381>::decrypt_at_rest(&encrypted, &key).unwrap(),
+            witnesses
+        );
+
+        // Nonce is random so encrypting again gives a different ciphertext
+        assert_ne!(
+            witnesses.encrypt_at_rest(&mut rng, &key).unwrap(),
+            encrypted
+        );
+
+        // Flipping a byte anywhere, whether in the nonce or the ciphertext, makes decryption fail
+        for i in [0, encrypted.len() / 2, encrypted.len() - 1] {
+            let mut tampered = encrypted.clone();
+            tampered[i] ^= 1;
+            assert!(matches!(
+                Witnesses::<Bls12_381>::decrypt_at_rest(&tampered, &key),
+                Err(ProofSystemError::WitnessDecryptionFailed)
+            ));
+        }
+
+        let mut wrong_key = key;
+        wrong_key[0] ^= 1;
+        assert!(matches!(
+            Witnesses::<Bls12_381>::decrypt_at_rest(&encrypted, &wrong_key),
+            Err(ProofSystemError::WitnessDecryptionFailed)
+        ));
+        assert!(matches!(
+            Witnesses::<Bls12_381>::decrypt_at_rest(&encrypted[..10], &key),
+            Err(ProofSystemError::WitnessDecryptionFailed)
+        ));
+    }
 }
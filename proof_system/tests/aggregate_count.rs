@@ -0,0 +1,222 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_ff::{One, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams;
+
+use proof_system::{
+    error::ProofSystemError,
+    predicates::aggregate_count::AggregateCountPredicate,
+    prelude::{MetaStatements, ProofSpec, Witness, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bound_check_bpp::BoundCheckBpp as BoundCheckStmt, Statement, Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_count_of_messages_above_threshold() {
+    // Prove that exactly 3 of 5 signed scores are above the passing mark without revealing the scores or which
+    // of them are above it
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let threshold = 60;
+    let max = 101;
+    let scores = [80u64, 45, 92, 60, 75];
+    let msgs = scores.iter().map(|s| Fr::from(*s)).collect::<Vec<_>>();
+    let (sig_params, keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let proof_spec = |pred: &AggregateCountPredicate| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let mut proof_spec =
+            ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+        let indices = pred
+            .add_to_proof_spec(&mut proof_spec, |min, max| {
+                BoundCheckStmt::new_statement_from_params(min, max, bpp_setup_params.clone())
+            })
+            .unwrap();
+        assert_eq!(indices.indicator_bound_checks, vec![1, 2, 3, 4, 5]);
+        assert_eq!(indices.shifted_bound_checks, vec![6, 7, 8, 9, 10]);
+        assert_eq!(indices.value_bound_checks, vec![11, 12, 13, 14, 15]);
+        assert_eq!(indices.linear_relation, 16);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let witnesses = |pred: &AggregateCountPredicate, values: Vec<Fr>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        pred.add_witnesses(&mut witnesses, values, Witness::BoundCheckBpp)
+            .unwrap();
+        witnesses
+    };
+    let predicate = |count| {
+        AggregateCountPredicate::new(
+            (0..scores.len()).map(|i| (0, i)).collect(),
+            threshold,
+            max,
+            count,
+        )
+        .unwrap()
+    };
+
+    // 80, 92 and 75 are above 60
+    let pred = predicate(3);
+    let proof_spec_3 = proof_spec(&pred);
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec_3);
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_3,
+        witnesses(&pred, msgs.clone()),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    // The verifier learns the count from the relation's target
+    match &proof_spec_3.statements.0[16] {
+        Statement::SchnorrLinearRelation(s) => {
+            assert_eq!(s.targets.last().unwrap(), &Fr::from(3u64))
+        }
+        _ => panic!("expected a linear relation"),
+    }
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_3, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Proof for a count of 3 doesn't verify as a proof for a count of 2
+    let pred = predicate(2);
+    let proof_spec_2 = proof_spec(&pred);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_2, nonce.clone(), Default::default())
+        .is_err());
+
+    // And the prover can't create a proof claiming a count of 2
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_2,
+            witnesses(&pred, msgs.clone()),
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::UnsatisfiedLinearRelation(16))
+    ));
+
+    // Values other than the signed ones don't verify, even when they give the claimed count
+    let pred = predicate(2);
+    let mut other_values = msgs.clone();
+    other_values[4] = Fr::from(50u64);
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_2,
+        witnesses(&pred, other_values),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_2, nonce, Default::default())
+        .is_err());
+
+    // Threshold must be less than the upper bound
+    assert!(AggregateCountPredicate::new(vec![(0, 0)], max, max, 0).is_err());
+
+    // At least 1 witness must be counted
+    assert!(matches!(
+        AggregateCountPredicate::new(vec![], threshold, max, 0),
+        Err(ProofSystemError::NoWitnessesToAggregate)
+    ));
+}
+
+#[test]
+fn count_of_messages_above_threshold_rejects_wrapped_value() {
+    // A signed value which wraps around the field modulus satisfies the linear relation with an indicator of 0
+    // so it must be rejected by the bound check on the linked witness
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let threshold = 60u64;
+    let max = 101u64;
+    let msgs = vec![Fr::from(80u64), Fr::zero() - Fr::one()];
+    let (sig_params, keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let pred = AggregateCountPredicate::new(vec![(0, 0), (0, 1)], threshold, max, 1).unwrap();
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let mut proof_spec =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+    let indices = pred
+        .add_to_proof_spec(&mut proof_spec, |min, max| {
+            BoundCheckStmt::new_statement_from_params(min, max, bpp_setup_params.clone())
+        })
+        .unwrap();
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    // `add_witnesses` refuses the wrapped value before adding any witness
+    assert!(pred
+        .add_witnesses(&mut witnesses, msgs.clone(), Witness::BoundCheckBpp)
+        .is_err());
+
+    // So add the witnesses as a cheating prover would, with indicators 1 and 0 and `z_i` computed in the field
+    let indicators = [Fr::one(), Fr::zero()];
+    let shifted = [
+        msgs[0] - Fr::from(threshold + 1),
+        msgs[1] - Fr::from(threshold + 1) + Fr::from(max),
+    ];
+    assert_eq!(shifted[1], Fr::from(max - threshold - 2));
+    for w in indicators.iter().chain(shifted.iter()).chain(msgs.iter()) {
+        witnesses.add(Witness::BoundCheckBpp(*w));
+    }
+    let mut relation_witnesses = msgs.clone();
+    relation_witnesses.extend_from_slice(&indicators);
+    relation_witnesses.extend_from_slice(&shifted);
+    witnesses.add(Witness::SchnorrLinearRelation(relation_witnesses));
+
+    // The linear relation is satisfied but the bound check on the wrapped value can't be proven
+    assert_eq!(indices.value_bound_checks, vec![5, 6]);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::UnsupportedValue(_))
+    ));
+}
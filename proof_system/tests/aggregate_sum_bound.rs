@@ -0,0 +1,150 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use bbs_plus::{prelude::KeypairG2, setup::SignatureParamsG1, signature::SignatureG1};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams;
+
+use proof_system::{
+    error::ProofSystemError,
+    predicates::aggregate_sum_bound::AggregateSumBound,
+    prelude::{MetaStatements, ProofSpec, Witness, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bound_check_bpp::BoundCheckBpp as BoundCheckStmt, Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sigs_and_bounded_sum_of_messages() {
+    // Prove knowledge of 3 BBS+ signatures, each over an amount, and that the sum of the amounts is less than
+    // a limit without revealing the amounts.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let limit = 100;
+    let msg_count = 4;
+    let amount_idx = 1;
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let creds = [30u64, 40, 29]
+        .into_iter()
+        .map(|amount| {
+            let mut msgs = (0..msg_count)
+                .map(|_| Fr::rand(&mut rng))
+                .collect::<Vec<_>>();
+            msgs[amount_idx] = Fr::from(amount);
+            let (params, keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+            (msgs, params, keypair, sig)
+        })
+        .collect::<Vec<_>>();
+
+    fn check(
+        rng: &mut StdRng,
+        limit: u64,
+        amount_idx: usize,
+        creds: &[(
+            Vec<Fr>,
+            SignatureParamsG1<Bls12_381>,
+            KeypairG2<Bls12_381>,
+            SignatureG1<Bls12_381>,
+        )],
+        amounts: Vec<Fr>,
+        bpp_setup_params: SetupParams<G1Affine>,
+    ) -> bool {
+        let mut statements = Statements::new();
+        for (_, params, keypair, _) in creds {
+            statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+                params.clone(),
+                keypair.public_key.clone(),
+                BTreeMap::new(),
+            ));
+        }
+        let agg = AggregateSumBound::<G1Affine>::new_with_label::<Blake2b512>(
+            (0..creds.len()).map(|i| (i, amount_idx)).collect(),
+            b"test",
+        )
+        .unwrap();
+
+        let randomness = Fr::rand(rng);
+        let commitment = agg.commit(&amounts, &randomness);
+
+        let mut proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        let indices = agg
+            .add_to_proof_spec(
+                &mut proof_spec,
+                commitment,
+                BoundCheckStmt::new_statement_from_params(0, limit, bpp_setup_params).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(indices.bound_check, creds.len() + 2);
+        proof_spec.validate().unwrap();
+        test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+        let mut witnesses = Witnesses::new();
+        for (msgs, _, _, sig) in creds {
+            witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+                sig.clone(),
+                msgs.clone().into_iter().enumerate().collect(),
+            ));
+        }
+        agg.add_witnesses(&mut witnesses, amounts, randomness, Witness::BoundCheckBpp)
+            .unwrap();
+
+        // Verifier gets the commitment from the prover and builds the same proof spec
+        ProofG1::new::<StdRng, Blake2b512>(rng, &proof_spec, witnesses, None, Default::default())
+            .and_then(|(proof, _)| {
+                proof.verify::<StdRng, Blake2b512>(rng, &proof_spec, None, Default::default())
+            })
+            .is_ok()
+    }
+
+    let signed_amounts = creds
+        .iter()
+        .map(|(msgs, _, _, _)| msgs[amount_idx])
+        .collect::<Vec<_>>();
+
+    // Sum is 99, just below the limit
+    assert!(check(
+        &mut rng,
+        limit,
+        amount_idx,
+        &creds,
+        signed_amounts.clone(),
+        bpp_setup_params.clone(),
+    ));
+
+    // Sum is 99, which is not less than the limit 99
+    assert!(!check(
+        &mut rng,
+        limit - 1,
+        amount_idx,
+        &creds,
+        signed_amounts,
+        bpp_setup_params.clone(),
+    ));
+
+    // Amounts sum up to less than the limit but one of them is not the signed amount
+    assert!(!check(
+        &mut rng,
+        limit,
+        amount_idx,
+        &creds,
+        vec![Fr::from(30u64), Fr::from(40u64), Fr::from(10u64)],
+        bpp_setup_params,
+    ));
+
+    // At least 1 witness must be summed
+    assert!(matches!(
+        AggregateSumBound::<G1Affine>::new_with_label::<Blake2b512>(vec![], b"test"),
+        Err(ProofSystemError::NoWitnessesToAggregate)
+    ));
+}
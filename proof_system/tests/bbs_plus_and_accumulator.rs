@@ -1,39 +1,67 @@
+use ark_bls12_377::Bls12_377;
 use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
-use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
-use ark_ff::PrimeField;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
     rand::{prelude::StdRng, SeedableRng},
     UniformRand,
 };
-use bbs_plus::prelude::{Signature23G1, SignatureG1};
+use bbs_plus::prelude::{Signature23G1, SignatureG1, SignatureParamsG1};
 use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams as BppSetupParams;
+use dock_crypto_utils::{
+    hashing_utils::DigestWriter, randomized_pairing_check::RandomizedPairingChecker,
+};
 use std::time::Instant;
-use vb_accumulator::prelude::{Accumulator, MembershipProvingKey, NonMembershipProvingKey};
+use vb_accumulator::{
+    error::VBAccumulatorError,
+    prelude::{Accumulator, MembershipProvingKey, NonMembershipProvingKey, Omega},
+};
 
 use proof_system::{
-    prelude::{EqualWitnesses, MetaStatements, VerifierConfig, Witness, WitnessRef, Witnesses},
+    accumulator_manager::AccumulatorManager,
+    error::{ProofSystemError, PublicError},
+    prelude::{
+        EqualWitnesses, MetaStatements, ProverConfig, VerifierConfig, Witness, WitnessRef,
+        Witnesses,
+    },
     proof_spec::ProofSpec,
-    setup_params::SetupParams,
+    setup_params::{ExternalSetupCache, SetupParams},
     statement::{
         accumulator::{
             AccumulatorMembership as AccumulatorMembershipStmt,
             AccumulatorNonMembership as AccumulatorNonMembershipStmt,
+            BatchAccumulatorMembership as BatchAccumulatorMembershipStmt,
+            BatchAccumulatorNonMembership as BatchAccumulatorNonMembershipStmt,
+            CommittedAccumulatorMembership as CommittedAccumulatorMembershipStmt,
+            PreparedAccumulatorPublicKey,
         },
         bbs_23::PoKBBSSignature23G1 as PoKSignatureBBS23G1Stmt,
-        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bbs_plus::{PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, PreHashedMessages, RawMessages},
+        bound_check_bpp::BoundCheckBpp as BoundCheckBppStmt,
         inequality::PublicInequality as InequalityStmt,
         ped_comm::PedersenCommitment as PedersenCommitmentStmt,
-        Statements,
+        Statement, Statements,
+    },
+    statement_proof::StatementProof,
+    sub_protocols::{
+        accumulator::{AccumulatorMembershipSubProtocol, AccumulatorNonMembershipSubProtocol},
+        bbs_plus::PoKBBSSigG1SubProtocol,
+        SubProtocol, SubProtocolType,
     },
+    versioning::{CachedSize, VersionedSerialization, FORMAT_VERSION},
     witness::{
-        Membership as MembershipWit, NonMembership as NonMembershipWit,
-        PoKBBSSignature23G1 as PoKSignatureBBS23G1Wit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+        CommittedMembership as CommittedMembershipWit, Membership as MembershipWit,
+        NonMembership as NonMembershipWit, PoKBBSSignature23G1 as PoKSignatureBBS23G1Wit,
+        PoKBBSSignatureG1 as PoKSignatureBBSG1Wit, WitnessUpdater,
     },
 };
 use schnorr_pok::inequality::CommitmentKey;
-use test_utils::{accumulators::*, bbs::*, test_serialization, Fr, ProofG1};
+use test_utils::{
+    accumulators::*, bbs::*, test_proof_serialized_size, test_serialization, Fr, ProofG1, ProofG1Of,
+};
 
 macro_rules! gen_tests {
     ($test1_name: ident, $test2_name: ident, $test3_name: ident, $test4_name: ident, $test5_name: ident, $test6_name: ident, $setup_fn_name: ident, $sig: ident, $stmt: ident, $wit: ident, $setup_param_name: ident) => {
@@ -186,7 +214,7 @@ macro_rules! gen_tests {
             let nonce = Some(b"some nonce".to_vec());
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses,
                 nonce.clone(),
                 Default::default(),
@@ -197,16 +225,17 @@ macro_rules! gen_tests {
             // Proof with no nonce shouldn't verify
             assert!(proof
                 .clone()
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
                 .is_err());
             assert!(proof
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -216,7 +245,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     Some(b"random...".to_vec()),
                     Default::default()
                 )
@@ -225,15 +254,17 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     Some(b"random...".to_vec()),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
 
             test_serialization!(ProofG1, proof);
+            test_proof_serialized_size!(proof);
 
             // Verifier verifies the proof
             let start = Instant::now();
@@ -241,7 +272,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     Default::default(),
                 )
@@ -255,10 +286,11 @@ macro_rules! gen_tests {
             proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec,
+                    &proof_spec,
                     nonce,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -351,7 +383,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -366,7 +398,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     Default::default(),
                 )
@@ -380,10 +412,11 @@ macro_rules! gen_tests {
             proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -404,7 +437,7 @@ macro_rules! gen_tests {
             );
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_incorrect.clone(),
+                &proof_spec_incorrect,
                 witnesses,
                 nonce.clone(),
                 Default::default(),
@@ -416,7 +449,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec_incorrect.clone(),
+                    &proof_spec_incorrect,
                     nonce.clone(),
                     Default::default()
                 )
@@ -424,10 +457,11 @@ macro_rules! gen_tests {
             assert!(proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec_incorrect,
+                    &proof_spec_incorrect,
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -455,7 +489,7 @@ macro_rules! gen_tests {
             proof_spec.validate().unwrap();
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses_incorrect,
                 nonce.clone(),
                 Default::default(),
@@ -466,7 +500,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     Default::default()
                 )
@@ -474,10 +508,11 @@ macro_rules! gen_tests {
             assert!(proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec,
+                    &proof_spec,
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -549,7 +584,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -564,7 +599,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     Default::default(),
                 )
@@ -579,10 +614,11 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -647,7 +683,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -662,7 +698,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     Default::default(),
                 )
@@ -676,10 +712,11 @@ macro_rules! gen_tests {
             proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -774,7 +811,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -789,7 +826,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec.clone(),
+                    &proof_spec,
                     nonce.clone(),
                     Default::default(),
                 )
@@ -800,10 +837,11 @@ macro_rules! gen_tests {
             proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec,
+                    &proof_spec,
                     nonce,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -872,7 +910,7 @@ macro_rules! gen_tests {
             let nonce = Some(b"test nonce".to_vec());
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -883,7 +921,7 @@ macro_rules! gen_tests {
             test_serialization!(ProofG1, proof);
 
             proof
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce.clone(), Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
                 .unwrap();
 
             // Wrong message equality should fail to verify
@@ -904,7 +942,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_invalid.clone(),
+                &proof_spec_invalid,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -913,7 +951,7 @@ macro_rules! gen_tests {
             .0;
 
             assert!(proof
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_invalid, nonce, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_invalid, nonce, Default::default())
                 .is_err());
         }
 
@@ -1007,7 +1045,7 @@ macro_rules! gen_tests {
 
             let proof_1 = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_1.clone(),
+                &proof_spec_1,
                 witnesses_1.clone(),
                 None,
                 Default::default(),
@@ -1016,7 +1054,7 @@ macro_rules! gen_tests {
                 .0;
 
             proof_1
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_1, None, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_1, None, Default::default())
                 .unwrap();
 
             // Prover proves to verifier 2
@@ -1056,7 +1094,7 @@ macro_rules! gen_tests {
 
             let proof_2 = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_2.clone(),
+                &proof_spec_2,
                 witnesses_2.clone(),
                 None,
                 Default::default(),
@@ -1065,7 +1103,7 @@ macro_rules! gen_tests {
                 .0;
 
             proof_2
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_2, None, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_2, None, Default::default())
                 .unwrap();
 
             // Prover again proves to verifier 1, this time something different like revealing a message but still uses his registration
@@ -1116,7 +1154,7 @@ macro_rules! gen_tests {
 
             let proof_3 = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_3.clone(),
+                &proof_spec_3,
                 witnesses_3.clone(),
                 None,
                 Default::default(),
@@ -1125,7 +1163,7 @@ macro_rules! gen_tests {
                 .0;
 
             proof_3
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_3, None, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_3, None, Default::default())
                 .unwrap();
         }
 
@@ -1218,7 +1256,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses,
                 None,
                 Default::default(),
@@ -1229,7 +1267,7 @@ macro_rules! gen_tests {
             let start = Instant::now();
             proof
                 .clone()
-                .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
                 .unwrap();
             println!(
                 "Time to verify proof with 4 BBS+ signatures: {:?}",
@@ -1240,10 +1278,11 @@ macro_rules! gen_tests {
             proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec,
+                    &proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -1305,7 +1344,7 @@ macro_rules! gen_tests {
             let nonce = Some(b"test nonce".to_vec());
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec.clone(),
+                &proof_spec,
                 witnesses.clone(),
                 nonce.clone(),
                 Default::default(),
@@ -1316,7 +1355,7 @@ macro_rules! gen_tests {
             test_serialization!(ProofG1, proof);
 
             proof
-            .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), nonce.clone(), Default::default())
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
             .unwrap();
 
 
@@ -1353,7 +1392,7 @@ macro_rules! gen_tests {
             // Proof can't be created when the values are equal
             assert!(ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                wrong_proof_spec.clone(),
+                &wrong_proof_spec,
                 witnesses.clone(),
                 None,
                 Default::default(),
@@ -1362,7 +1401,7 @@ macro_rules! gen_tests {
             // Create proof with inequal value
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec,
+                &proof_spec,
                 witnesses.clone(),
                 None,
                 Default::default(),
@@ -1371,7 +1410,7 @@ macro_rules! gen_tests {
             .0;
 
             // Try to verify the proof with equal value
-            assert!(proof.verify::<StdRng, Blake2b512>(&mut rng, wrong_proof_spec, None, Default::default()).is_err())
+            assert!(proof.verify::<StdRng, Blake2b512>(&mut rng, &wrong_proof_spec, None, Default::default()).is_err())
         }
     }
 }
@@ -1458,7 +1497,7 @@ fn requesting_partially_blind_bbs_plus_sig() {
     let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -1469,7 +1508,7 @@ fn requesting_partially_blind_bbs_plus_sig() {
     test_serialization!(ProofG1, proof);
 
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
         .unwrap();
 
     // Now requester picks the messages he is revealing to the signer and prepares `uncommitted_messages`
@@ -1546,7 +1585,7 @@ fn requesting_partially_blind_bbs_sig() {
     let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -1557,7 +1596,7 @@ fn requesting_partially_blind_bbs_sig() {
     test_serialization!(ProofG1, proof);
 
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
         .unwrap();
 
     // Now requester picks the messages he is revealing to the signer and prepares `uncommitted_messages`
@@ -1644,7 +1683,7 @@ fn proof_spec_modification() {
 
     assert!(ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        invalid_proof_spec,
+        &invalid_proof_spec,
         witnesses.clone(),
         None,
         Default::default()
@@ -1660,7 +1699,7 @@ fn proof_spec_modification() {
     let invalid_proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
     assert!(ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        invalid_proof_spec,
+        &invalid_proof_spec,
         witnesses.clone(),
         None,
         Default::default()
@@ -1676,7 +1715,7 @@ fn proof_spec_modification() {
     let invalid_proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
     assert!(ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        invalid_proof_spec,
+        &invalid_proof_spec,
         witnesses.clone(),
         None,
         Default::default()
@@ -1703,7 +1742,7 @@ fn proof_spec_modification() {
     // Proof created using modified proof spec wont be a valid
     let invalid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        modified_proof_spec.clone(),
+        &modified_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1714,16 +1753,16 @@ fn proof_spec_modification() {
     // Above proof is valid if verified using the modified proof spec but not with the original proof spec
     invalid_proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, modified_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &modified_proof_spec, None, Default::default())
         .unwrap();
     assert!(invalid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec.clone(), None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .is_err());
 
     // Proof created using original proof spec will be valid
     let valid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        orig_proof_spec.clone(),
+        &orig_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1731,7 +1770,7 @@ fn proof_spec_modification() {
     .unwrap()
     .0;
     valid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .unwrap();
 
     // Verifier creates proof spec with 2 statements, prover modifies it to remove a statement
@@ -1763,7 +1802,7 @@ fn proof_spec_modification() {
     // Proof created using modified proof spec wont be a valid
     let invalid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        modified_proof_spec.clone(),
+        &modified_proof_spec,
         only_1_witness.clone(),
         None,
         Default::default(),
@@ -1774,16 +1813,16 @@ fn proof_spec_modification() {
     // Above proof is valid if verified using the modified proof spec but not with the original proof spec
     invalid_proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, modified_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &modified_proof_spec, None, Default::default())
         .unwrap();
     assert!(invalid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec.clone(), None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .is_err());
 
     // Proof created using original proof spec will be valid
     let valid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        orig_proof_spec.clone(),
+        &orig_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1791,8 +1830,165 @@ fn proof_spec_modification() {
     .unwrap()
     .0;
     valid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn proof_bound_to_revealed_message_indices() {
+    // Proof created for a spec fails to verify with a spec that differs only in the indices of the revealed messages
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let spec_revealing = |idx: usize| {
+        let mut statements = Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params.clone(),
+            keypair.public_key.clone(),
+            [(idx, msgs[1])].into_iter().collect::<BTreeMap<_, _>>(),
+        ));
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    let proof_spec = spec_revealing(1);
+    let other_proof_spec = spec_revealing(2);
+
+    let mut spec_bytes = vec![];
+    proof_spec.challenge_contribution(&mut spec_bytes).unwrap();
+    let mut other_spec_bytes = vec![];
+    other_proof_spec
+        .challenge_contribution(&mut other_spec_bytes)
+        .unwrap();
+    assert_ne!(spec_bytes, other_spec_bytes);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(i, m)| (i, *m))
+            .collect(),
+    ));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+        .unwrap();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &other_proof_spec, None, Default::default())
+        .is_err());
+}
+
+#[test]
+fn revealed_messages_order_independent_of_insertion() {
+    // Prover and verifier create the revealed messages by inserting them in different orders and still get the
+    // same challenge contribution
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let revealed_indices = [4, 0, 2];
+    let mut prover_revealed = BTreeMap::new();
+    for i in revealed_indices {
+        prover_revealed.insert(i, msgs[i]);
+    }
+    let mut verifier_revealed = BTreeMap::new();
+    for i in revealed_indices.into_iter().rev() {
+        verifier_revealed.insert(i, msgs[i]);
+    }
+    assert_eq!(prover_revealed, verifier_revealed);
+    assert_eq!(
+        prover_revealed.keys().copied().collect::<Vec<_>>(),
+        vec![0, 2, 4]
+    );
+
+    let spec = |revealed: BTreeMap<usize, Fr>| {
+        let mut statements = Statements::<Bls12_381, <Bls12_381 as Pairing>::G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params.clone(),
+            keypair.public_key.clone(),
+            revealed,
+        ));
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None)
+    };
+    let prover_proof_spec = spec(prover_revealed.clone());
+    let verifier_proof_spec = spec(verifier_revealed.clone());
+
+    let mut prover_spec_bytes = vec![];
+    prover_proof_spec
+        .challenge_contribution(&mut prover_spec_bytes)
         .unwrap();
+    let mut verifier_spec_bytes = vec![];
+    verifier_proof_spec
+        .challenge_contribution(&mut verifier_spec_bytes)
+        .unwrap();
+    assert_eq!(prover_spec_bytes, verifier_spec_bytes);
+
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !prover_revealed.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    // Sub-protocols initialized with the same randomness contribute the same bytes
+    let sub_protocol_bytes = |revealed: &BTreeMap<usize, Fr>| {
+        let mut rng = StdRng::seed_from_u64(1u64);
+        let mut sp = PoKBBSSigG1SubProtocol::new(0, revealed, &params, &keypair.public_key);
+        sp.init(
+            &mut rng,
+            BTreeMap::new(),
+            PoKSignatureBBSG1Wit {
+                signature: sig.clone(),
+                unrevealed_messages: unrevealed_msgs.clone(),
+            },
+        )
+        .unwrap();
+        let mut bytes = vec![];
+        sp.challenge_contribution(&mut bytes).unwrap();
+        bytes
+    };
+    assert_eq!(
+        sub_protocol_bytes(&prover_revealed),
+        sub_protocol_bytes(&verifier_revealed)
+    );
+
+    // Proof created with either spec verifies with the other
+    for (prover_spec, verifier_spec) in [
+        (&prover_proof_spec, &verifier_proof_spec),
+        (&verifier_proof_spec, &prover_proof_spec),
+    ] {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            unrevealed_msgs.clone(),
+        ));
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_spec,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, verifier_spec, None, Default::default())
+            .unwrap();
+    }
 }
 
 #[test]
@@ -1869,3 +2065,3143 @@ fn proof_spec_validation() {
     let ps_3 = ProofSpec::new(statements_3, meta_statements_3, vec![], None);
     assert!(ps_3.validate().is_err());
 }
+
+#[test]
+fn witness_equality_across_scalar_fields() {
+    // Witnesses of statements over groups with different scalar fields can't be equal
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    type G = <Bls12_377 as Pairing>::G1Affine;
+
+    let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+    let bases = (0..2)
+        .map(|_| <Bls12_377 as Pairing>::G1::rand(&mut rng).into_affine())
+        .collect::<Vec<G>>();
+    let commitment = <Bls12_377 as Pairing>::G1::rand(&mut rng).into_affine();
+
+    let mut statements = Statements::<Bls12_381, G>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+
+    // Witnesses of the statements over the same group can be equal
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(1, 0), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    assert!(matches!(
+        proof_spec.validate(),
+        Err(ProofSystemError::IncompatibleWitnessFields(0, 1))
+    ));
+}
+
+#[test]
+fn accumulator_membership_with_prepared_public_key() {
+    // Verifier prepares the accumulator public key once and uses it to verify several proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member = Fr::rand(&mut rng);
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params.clone(),
+        pos_accum_keypair.public_key.clone(),
+        mem_prk.clone(),
+        *pos_accumulator.value(),
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+
+    let prepared_pk = PreparedAccumulatorPublicKey::from(pos_accum_keypair.public_key.clone());
+
+    // Only the public key is serialized and the prepared form is created again during deserialization
+    let mut prepared_pk_bytes = vec![];
+    prepared_pk
+        .serialize_compressed(&mut prepared_pk_bytes)
+        .unwrap();
+    assert_eq!(
+        prepared_pk_bytes.len(),
+        pos_accum_keypair.public_key.compressed_size()
+    );
+    let prepared_pk =
+        PreparedAccumulatorPublicKey::<Bls12_381>::deserialize_compressed(&prepared_pk_bytes[..])
+            .unwrap();
+    assert_eq!(prepared_pk.public_key(), &pos_accum_keypair.public_key);
+
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(AccumulatorMembershipStmt::from_prepared_params(
+        *pos_accumulator.value(),
+        prepared_pk,
+        pos_accum_params.clone(),
+        mem_prk.clone(),
+    ));
+    test_serialization!(Statements<Bls12_381, G1Affine>, verifier_statements);
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit.clone()));
+
+    for i in 0..3u8 {
+        let nonce = Some(vec![i; 10]);
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &prover_proof_spec,
+            witnesses.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                &verifier_proof_spec,
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+        proof
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                &verifier_proof_spec,
+                nonce,
+                VerifierConfig {
+                    use_lazy_randomized_pairing_checks: Some(false),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn versioned_proof_and_proof_spec_serialization() {
+    // Proof and proof spec are serialized with a format version header and bytes with an unsupported
+    // version are rejected
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut proof_bytes = vec![];
+    proof.serialize_versioned(&mut proof_bytes).unwrap();
+    assert_eq!(proof_bytes.len(), proof.versioned_serialized_size());
+    assert_eq!(&proof_bytes[0..2], &FORMAT_VERSION.to_le_bytes());
+    let deserialized_proof = ProofG1::deserialize_versioned(&proof_bytes[..]).unwrap();
+    assert_eq!(deserialized_proof, proof);
+
+    let mut spec_bytes = vec![];
+    proof_spec.serialize_versioned(&mut spec_bytes).unwrap();
+    let deserialized_spec =
+        ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(&spec_bytes[..]).unwrap();
+    assert_eq!(deserialized_spec, proof_spec);
+
+    deserialized_proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &deserialized_spec, None, Default::default())
+        .unwrap();
+
+    // Bytes stamped with the previous version are migrated
+    let mut old_proof_bytes = proof_bytes.clone();
+    old_proof_bytes[0..2].copy_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+    assert_eq!(
+        ProofG1::deserialize_versioned(&old_proof_bytes[..]).unwrap(),
+        proof
+    );
+
+    // Bytes stamped with a future version are rejected
+    let mut future_proof_bytes = proof_bytes.clone();
+    future_proof_bytes[0..2].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    match ProofG1::deserialize_versioned(&future_proof_bytes[..]) {
+        Err(ProofSystemError::UnsupportedProofVersion { found, supported }) => {
+            assert_eq!(found, FORMAT_VERSION + 1);
+            assert_eq!(supported, FORMAT_VERSION);
+        }
+        _ => panic!("Proof with a future format version should be rejected"),
+    }
+    let mut future_spec_bytes = spec_bytes.clone();
+    future_spec_bytes[0..2].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert!(
+        ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(&future_spec_bytes[..]).is_err()
+    );
+}
+
+#[test]
+fn proof_spec_with_accumulator_membership_from_previous_version() {
+    // A proof spec serialized with the previous format version, in which accumulator membership statements did not
+    // have the flag for the default proving key, is migrated
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, pos_accumulator, _) = setup_positive_accum(&mut rng);
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        MembershipProvingKey::generate_using_rng(&mut rng),
+        *pos_accumulator.value(),
+    ));
+    let statement_size = statements.0[0].compressed_size();
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+
+    // The statements are serialized after their 8 byte length and the flag is the last byte of the only statement
+    let mut bytes = FORMAT_VERSION.to_le_bytes().to_vec();
+    proof_spec.serialize_compressed(&mut bytes).unwrap();
+    let flag_idx = 2 + 8 + statement_size - 1;
+    assert_eq!(bytes[flag_idx], 0);
+    bytes.remove(flag_idx);
+    bytes[0..2].copy_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+
+    assert_eq!(
+        ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(&bytes[..]).unwrap(),
+        proof_spec
+    );
+    // The previous format isn't accepted with the current version
+    bytes[0..2].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+    assert!(ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(&bytes[..]).is_err());
+}
+
+#[test]
+fn cached_serialized_size_of_proof_spec() {
+    // The cached size of a proof spec is same as a fresh computation and is updated after the spec is mutated
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params.clone(),
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+
+    let mut cached = CachedSize::new(proof_spec.clone());
+    assert_eq!(cached.compressed_size(), proof_spec.compressed_size());
+    // Served from the cache
+    assert_eq!(cached.compressed_size(), proof_spec.compressed_size());
+    assert_eq!(
+        cached.versioned_serialized_size(),
+        proof_spec.versioned_serialized_size()
+    );
+    assert_eq!(cached.uncompressed_size(), proof_spec.uncompressed_size());
+    let bytes = cached.to_versioned_bytes().unwrap();
+    assert_eq!(bytes, proof_spec.to_versioned_bytes().unwrap());
+    assert_eq!(bytes.len(), cached.versioned_serialized_size());
+    assert_eq!(
+        CachedSize::<ProofSpec<Bls12_381, G1Affine>>::deserialize_versioned(&bytes[..]).unwrap(),
+        cached
+    );
+
+    let old_size = cached.compressed_size();
+    cached.get_mut().context = Some(b"test context".to_vec());
+    cached
+        .get_mut()
+        .statements
+        .add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+    assert!(cached.compressed_size() > old_size);
+    assert_eq!(
+        cached.compressed_size(),
+        cached.clone().into_inner().compressed_size()
+    );
+    let bytes = cached.to_versioned_bytes().unwrap();
+    assert_eq!(bytes.len(), cached.versioned_serialized_size());
+    assert_eq!(
+        ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(&bytes[..]).unwrap(),
+        cached.into_inner()
+    );
+}
+
+#[test]
+fn accumulator_membership_with_witness_updated_after_batches() {
+    // Prover updates its membership witness using the public info published by the accumulator manager
+    // after 100 additions and 20 removals done in 2 batches and then proves membership
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member = Fr::rand(&mut rng);
+    let initial = (0..30).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let mut elements = initial.clone();
+    elements.push(member);
+    accumulator = accumulator
+        .add_batch(elements, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut updater = WitnessUpdater::<Bls12_381>::new(member, mem_wit);
+    for i in 0..2 {
+        let additions = (0..50).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let removals = initial[i * 10..(i + 1) * 10].to_vec();
+        let omega = Omega::new(
+            &additions,
+            &removals,
+            accumulator.value(),
+            &accum_keypair.secret_key,
+        );
+        accumulator = accumulator
+            .batch_updates(
+                additions.clone(),
+                &removals,
+                &accum_keypair.secret_key,
+                &mut state,
+            )
+            .unwrap();
+        updater.add_batch(additions, removals, omega);
+    }
+
+    // Updated witness is checked against the accumulator value
+    assert!(matches!(
+        updater.update(
+            &G1Projective::rand(&mut rng).into_affine(),
+            &accum_keypair.public_key,
+            &accum_params
+        ),
+        Err(ProofSystemError::InvalidUpdatedAccumulatorWitness)
+    ));
+    let witness = updater
+        .update(
+            accumulator.value(),
+            &accum_keypair.public_key,
+            &accum_params,
+        )
+        .unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        mem_prk,
+        *accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(witness);
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn prover_cost_estimate_for_bbs_plus_and_accumulator() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    pos_accumulator = pos_accumulator
+        .add(msgs[4], &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&msgs[4], &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let mut revealed_msgs = BTreeMap::new();
+    revealed_msgs.insert(0, msgs[0]);
+    revealed_msgs.insert(1, msgs[1]);
+    let unrevealed_msgs = (2..msg_count)
+        .map(|i| (i, msgs[i]))
+        .collect::<BTreeMap<_, _>>();
+
+    let statements = Statements::<Bls12_381, G1Affine>::new_bbs_with_memberships(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed_msgs,
+        vec![(
+            pos_accum_params,
+            pos_accum_keypair.public_key.clone(),
+            mem_prk,
+            *pos_accumulator.value(),
+        )],
+    );
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 4), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let mut proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    // The BBS+ prover does no pairing and the accumulator prover does a multi-pairing of 2 pairs
+    let estimate = proof_spec.estimate_prover_cost().unwrap();
+    assert_eq!(estimate.per_statement_type.len(), 2);
+    let bbs_cost = estimate.for_statement_type(SubProtocolType::PoKBBSSignatureG1);
+    let accum_cost = estimate.for_statement_type(SubProtocolType::AccumulatorMembership);
+    assert_eq!(bbs_cost.pairings, 0);
+    assert_eq!(accum_cost.pairings, 2);
+    assert_eq!(estimate.total().pairings, 2);
+    assert_eq!(
+        estimate.total().scalar_multiplications,
+        bbs_cost.scalar_multiplications + accum_cost.scalar_multiplications
+    );
+    assert_eq!(
+        estimate
+            .for_statement_type(SubProtocolType::AccumulatorNonMembership)
+            .pairings,
+        0
+    );
+
+    // The estimated spec is a valid one
+    let witnesses =
+        Witnesses::new_bbs_with_memberships(sig, unrevealed_msgs, vec![(msgs[4], mem_wit)]);
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+
+    // Each accumulator statement adds 2 pairings
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, _) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+    proof_spec.add_statement(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params,
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk,
+        *uni_accumulator.value(),
+    ));
+    let estimate = proof_spec.estimate_prover_cost().unwrap();
+    assert_eq!(
+        estimate
+            .for_statement_type(SubProtocolType::AccumulatorNonMembership)
+            .pairings,
+        2
+    );
+    assert_eq!(estimate.total().pairings, 4);
+}
+
+#[test]
+fn accumulator_non_membership_proof_with_zero_d() {
+    // The prover can't create a non-membership proof from a witness with `d = 0` and a proof whose commitment to `d`
+    // is replaced by a commitment to 0 is rejected by the relations of the proof
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 100;
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, max);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let non_member = Fr::rand(&mut rng);
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params,
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk.clone(),
+        *uni_accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut zero_d_wit = non_mem_wit.clone();
+    zero_d_wit.d = Fr::zero();
+    let mut witnesses = Witnesses::new();
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, zero_d_wit));
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::ArithmeticError(_))
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Commitment to 0 with blinding `tau`, i.e. `0 * P + tau * K`, and without any blinding
+    let blinded_zero = (non_mem_prk.K * Fr::rand(&mut rng)).into_affine();
+    for zero_commitment in [blinded_zero, G1Affine::zero()] {
+        let mut tampered = proof.clone();
+        match &mut tampered.statement_proofs[0] {
+            StatementProof::AccumulatorNonMembership(p) => {
+                p.randomized_witness.E_d = zero_commitment;
+            }
+            _ => panic!("Expected an accumulator non-membership proof"),
+        }
+        for config in [
+            VerifierConfig::default(),
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
+            },
+        ] {
+            assert!(tampered
+                .clone()
+                .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+                .is_err());
+        }
+    }
+}
+
+#[test]
+fn accumulator_non_membership_with_identity_accumulator_value() {
+    // Non-membership proof is rejected when the statement has the identity as the accumulator value
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let non_member = Fr::rand(&mut rng);
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let create_spec = |accumulator_value: G1Affine| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+            uni_accum_params.clone(),
+            uni_accum_keypair.public_key.clone(),
+            non_mem_prk.clone(),
+            accumulator_value,
+        ));
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let proof_spec = create_spec(*uni_accumulator.value());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    let identity_spec = create_spec(G1Affine::zero());
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        let err = proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &identity_spec, nonce.clone(), config)
+            .unwrap_err();
+        assert!(matches!(err, ProofSystemError::InvalidAccumulatorValue(0)));
+        assert_eq!(err.to_public(), PublicError::MalformedInput);
+    }
+}
+
+#[test]
+fn accumulator_membership_with_lazily_fetched_witness() {
+    // Membership witness is fetched using a callback only when the proof is created
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member = Fr::rand(&mut rng);
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let nonce = Some(b"test-nonce".to_vec());
+
+    let fetched_wit = mem_wit.clone();
+    let lazy_wit = Witness::membership_lazy(member, move |e| {
+        assert_eq!(*e, member);
+        Ok(fetched_wit.clone())
+    });
+    // Callback can't be serialized so the fetched witness is serialized and the deserialized witness returns it
+    let mut bytes = vec![];
+    lazy_wit.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), lazy_wit.compressed_size());
+    let deserialized_wit = Witness::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap();
+    let json = serde_json::to_string(&lazy_wit).unwrap();
+    let deserialized_json_wit = serde_json::from_str::<Witness<Bls12_381>>(&json).unwrap();
+    for w in [&deserialized_wit, &deserialized_json_wit] {
+        match w {
+            Witness::AccumulatorMembershipLazy(w) => {
+                assert_eq!(
+                    w.fetch().unwrap(),
+                    MembershipWit {
+                        element: member,
+                        witness: mem_wit.clone()
+                    }
+                )
+            }
+            _ => panic!("expected a lazy membership witness"),
+        }
+    }
+
+    for wit in [lazy_wit, deserialized_wit] {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(wit);
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+            .unwrap();
+    }
+
+    // Error from the callback is returned by the proof creation and fails serialization
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::membership_lazy(member, |_| {
+        Err(ProofSystemError::VBAccumError(
+            VBAccumulatorError::ElementAbsent,
+        ))
+    }));
+    let mut bytes = vec![];
+    assert!(witnesses.serialize_compressed(&mut bytes).is_err());
+    assert!(serde_json::to_string(&witnesses).is_err());
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            nonce,
+            Default::default(),
+        ),
+        Err(ProofSystemError::VBAccumError(
+            VBAccumulatorError::ElementAbsent
+        ))
+    ));
+}
+
+#[test]
+fn pok_bbs_plus_sig_with_raw_revealed_messages() {
+    // Messages of the credential are bytes which are hashed to field elements before signing. The verifier
+    // gives the revealed messages as bytes as well.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let raw_msgs = [
+        "John Doe",
+        "1990-01-01",
+        "Berlin",
+        "12345",
+        "john@example.com",
+    ];
+    let msgs = SignatureParamsG1::<Bls12_381>::hash_messages::<Blake2b512>(&raw_msgs);
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let revealed_indices = BTreeSet::from([0, 2]);
+    let raw_revealed_msgs = revealed_indices
+        .iter()
+        .map(|i| (*i, raw_msgs[*i]))
+        .collect::<BTreeMap<_, _>>();
+    let revealed_msgs = revealed_indices
+        .iter()
+        .map(|i| (*i, msgs[*i]))
+        .collect::<BTreeMap<_, _>>();
+    let unrevealed_msgs = (0..raw_msgs.len())
+        .filter(|i| !revealed_indices.contains(i))
+        .map(|i| (i, msgs[i]))
+        .collect::<BTreeMap<_, _>>();
+
+    let stmt = PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        RawMessages::<Blake2b512, _>::new(raw_revealed_msgs),
+    );
+    // Same as passing the hashed messages
+    assert_eq!(
+        stmt,
+        PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            PreHashedMessages(revealed_msgs.clone()),
+        )
+    );
+    assert_eq!(
+        stmt,
+        PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params,
+            sig_keypair.public_key.clone(),
+            revealed_msgs,
+        )
+    );
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(stmt);
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn witness_refs_of_proof_spec() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, _) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (pos_accum_params, pos_accum_keypair, pos_accumulator, _) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let comm_key = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let commitment = G1Projective::rand(&mut rng).into_affine();
+
+    let mut revealed_msgs = BTreeMap::new();
+    revealed_msgs.insert(0, msgs[0]);
+    revealed_msgs.insert(2, msgs[2]);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed_msgs,
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        comm_key, commitment,
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (1, 1), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    // Repeated reference is listed once
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (0, 5)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    assert_eq!(
+        proof_spec.witness_refs().collect::<Vec<_>>(),
+        vec![
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (0, 5),
+            (1, 0),
+            (1, 1),
+            (2, 0)
+        ]
+    );
+
+    // Spec without revealed messages or meta statements doesn't reference any witness
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        vec![commitment],
+        commitment,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    assert_eq!(proof_spec.witness_refs().count(), 0);
+}
+
+#[test]
+fn revealed_message_count_of_verified_bbs_plus_proof() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let revealed_indices = BTreeSet::from([0, 2, 5]);
+    let mut revealed_msgs = BTreeMap::new();
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate() {
+        if revealed_indices.contains(&i) {
+            revealed_msgs.insert(i, *m);
+        } else {
+            unrevealed_msgs.insert(i, *m);
+        }
+    }
+
+    let mut prover_sp =
+        PoKBBSSigG1SubProtocol::new(0, &revealed_msgs, &sig_params, &sig_keypair.public_key);
+    prover_sp
+        .init(
+            &mut rng,
+            BTreeMap::new(),
+            PoKSignatureBBSG1Wit {
+                signature: sig,
+                unrevealed_messages: unrevealed_msgs,
+            },
+        )
+        .unwrap();
+    let challenge = Fr::rand(&mut rng);
+    let proof = match prover_sp
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap()
+    {
+        StatementProof::PoKBBSSignatureG1(p) => p,
+        _ => panic!("expected a proof of knowledge of BBS+ signature"),
+    };
+
+    let verifier_sp =
+        PoKBBSSigG1SubProtocol::new(0, &revealed_msgs, &sig_params, &sig_keypair.public_key);
+    for mut pairing_checker in [
+        None,
+        Some(RandomizedPairingChecker::new_using_rng(&mut rng, true)),
+    ] {
+        let count = verifier_sp
+            .verify_proof_contribution(
+                &challenge,
+                &proof,
+                sig_keypair.public_key.clone(),
+                sig_params.clone(),
+                &mut pairing_checker,
+            )
+            .unwrap();
+        assert_eq!(count, revealed_msgs.len());
+        if let Some(c) = pairing_checker {
+            assert!(c.verify());
+        }
+    }
+}
+
+#[test]
+fn clone_initialized_accumulator_sub_protocols() {
+    // Clones of initialized sub-protocols should independently be able to generate proof contributions
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let member = Fr::rand(&mut rng);
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+    let non_member = Fr::rand(&mut rng);
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let challenge = Fr::rand(&mut rng);
+
+    let mut mem_sp = AccumulatorMembershipSubProtocol::new(
+        0,
+        &pos_accum_params,
+        &pos_accum_keypair.public_key,
+        &mem_prk,
+        *pos_accumulator.value(),
+    );
+    mem_sp
+        .init(
+            &mut rng,
+            None,
+            MembershipWit {
+                element: member,
+                witness: mem_wit,
+            },
+        )
+        .unwrap();
+    let mut mem_sp_clone = mem_sp.clone();
+    assert_eq!(mem_sp, mem_sp_clone);
+
+    let mut chal_bytes = vec![];
+    let mut chal_bytes_clone = vec![];
+    mem_sp.challenge_contribution(&mut chal_bytes).unwrap();
+    mem_sp_clone
+        .challenge_contribution(&mut chal_bytes_clone)
+        .unwrap();
+    assert_eq!(chal_bytes, chal_bytes_clone);
+
+    let proof = mem_sp
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap();
+    // Generating the proof from the original doesn't consume the clone's protocol
+    assert!(mem_sp.protocol.is_none());
+    assert!(mem_sp_clone.protocol.is_some());
+    let proof_clone = mem_sp_clone
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap();
+    assert_eq!(proof, proof_clone);
+    for p in [proof, proof_clone] {
+        match p {
+            StatementProof::AccumulatorMembership(ref p) => mem_sp
+                .verify_proof_contribution(
+                    &challenge,
+                    p,
+                    pos_accum_keypair.public_key.clone(),
+                    pos_accum_params.clone(),
+                    &mut None,
+                )
+                .unwrap(),
+            _ => panic!("expected an accumulator membership proof"),
+        }
+    }
+
+    let mut non_mem_sp = AccumulatorNonMembershipSubProtocol::new(
+        0,
+        &uni_accum_params,
+        &uni_accum_keypair.public_key,
+        &non_mem_prk,
+        *uni_accumulator.value(),
+    );
+    non_mem_sp
+        .init(
+            &mut rng,
+            None,
+            NonMembershipWit {
+                element: non_member,
+                witness: non_mem_wit,
+            },
+        )
+        .unwrap();
+    let mut non_mem_sp_clone = non_mem_sp.clone();
+    assert_eq!(non_mem_sp, non_mem_sp_clone);
+
+    let proof = non_mem_sp
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap();
+    assert!(non_mem_sp.protocol.is_none());
+    assert!(non_mem_sp_clone.protocol.is_some());
+    let proof_clone = non_mem_sp_clone
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap();
+    assert_eq!(proof, proof_clone);
+    for p in [proof, proof_clone] {
+        match p {
+            StatementProof::AccumulatorNonMembership(ref p) => non_mem_sp
+                .verify_proof_contribution(
+                    &challenge,
+                    p,
+                    uni_accum_keypair.public_key.clone(),
+                    uni_accum_params.clone(),
+                    &mut None,
+                )
+                .unwrap(),
+            _ => panic!("expected an accumulator non-membership proof"),
+        }
+    }
+}
+
+#[test]
+fn non_membership_proof_after_public_witness_updates() {
+    // Prover updates its non-membership witness after several additions using only the published accumulator
+    // values and then proves non-membership
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (params, keypair, mut accumulator, initial_elements, mut state) =
+        setup_universal_accum(&mut rng, 200);
+    let prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let non_member = Fr::rand(&mut rng);
+    let wit = accumulator
+        .get_non_membership_witness(&non_member, &keypair.secret_key, &state, &params)
+        .unwrap();
+    let mut witness = NonMembershipWit {
+        element: non_member,
+        witness: wit.clone(),
+    };
+
+    // Accumulator manager adds 50 elements and publishes the accumulator after each addition
+    let mut additions = vec![];
+    let mut old_accumulators = vec![];
+    for _ in 0..50 {
+        let elem = Fr::rand(&mut rng);
+        old_accumulators.push(*accumulator.value());
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &initial_elements, &mut state)
+            .unwrap();
+        additions.push(elem);
+    }
+    assert!(!accumulator.verify_non_membership(&non_member, &wit, &keypair.public_key, &params));
+
+    witness
+        .update_after_additions(&additions, &old_accumulators)
+        .unwrap();
+    assert!(accumulator.verify_non_membership(
+        &non_member,
+        &witness.witness,
+        &keypair.public_key,
+        &params
+    ));
+
+    // Mismatched number of additions and accumulator values
+    assert!(NonMembershipWit::<Bls12_381> {
+        element: non_member,
+        witness: wit.clone(),
+    }
+    .update_after_additions(&additions, &old_accumulators[1..])
+    .is_err());
+    // The non-member can't be added
+    assert!(wit
+        .update_after_additions(&non_member, &[non_member], &[old_accumulators[0]])
+        .is_err());
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::AccumulatorNonMembership(witness));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn get_statements_as_their_types() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, sig_params, sig_keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+    let (pos_accum_params, pos_accum_keypair, pos_accumulator, _) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let mut revealed_msgs = BTreeMap::new();
+    revealed_msgs.insert(0, msgs[0]);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        revealed_msgs.clone(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+
+    let sig_stmt = statements
+        .get_as::<PoKSignatureBBSG1Stmt<Bls12_381>>(0)
+        .unwrap();
+    assert_eq!(sig_stmt.revealed_messages, revealed_msgs);
+    assert_eq!(sig_stmt.signature_params, Some(sig_params));
+
+    let accum_stmt = statements
+        .get_as::<AccumulatorMembershipStmt<Bls12_381>>(1)
+        .unwrap();
+    assert_eq!(accum_stmt.accumulator_value, *pos_accumulator.value());
+
+    // Different type at the index
+    assert!(statements
+        .get_as::<AccumulatorMembershipStmt<Bls12_381>>(0)
+        .is_none());
+    assert!(statements
+        .get_as::<PedersenCommitmentStmt<G1Affine>>(1)
+        .is_none());
+    // No statement at the index
+    assert!(statements
+        .get_as::<PoKSignatureBBSG1Stmt<Bls12_381>>(2)
+        .is_none());
+}
+
+#[test]
+fn accumulator_membership_with_default_proving_key() {
+    // A spec using the default proving key is smaller than one embedding the same proving key and proofs created
+    // with either verify with both
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let default_prk =
+        MembershipProvingKey::default_for::<Blake2b512, _>(&pos_accum_params).unwrap();
+    assert_eq!(
+        default_prk,
+        MembershipProvingKey::default_for::<Blake2b512, _>(&pos_accum_params).unwrap()
+    );
+
+    let member = Fr::rand(&mut rng);
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let mut default_statements = Statements::<Bls12_381, G1Affine>::new();
+    // Same params in 2 statements so the default proving key is created once
+    for _ in 0..2 {
+        default_statements.add(
+            AccumulatorMembershipStmt::new_statement_with_default_proving_key::<_, Blake2b512>(
+                pos_accum_params.clone(),
+                pos_accum_keypair.public_key.clone(),
+                *pos_accumulator.value(),
+            )
+            .unwrap(),
+        );
+    }
+    test_serialization!(Statements<Bls12_381, G1Affine>, default_statements);
+    let default_proof_spec =
+        ProofSpec::new(default_statements, MetaStatements::new(), vec![], None);
+    default_proof_spec.validate().unwrap();
+
+    // The default proving key created with the statements is not serialized so it's created again for the
+    // deserialized spec
+    let mut deserialized_default_proof_spec =
+        ProofSpec::<Bls12_381, G1Affine>::deserialize_versioned(
+            &default_proof_spec.to_versioned_bytes().unwrap()[..],
+        )
+        .unwrap();
+    assert_eq!(deserialized_default_proof_spec, default_proof_spec);
+    let derived_prk = deserialized_default_proof_spec
+        .derive_default_accumulator_proving_keys::<Blake2b512>()
+        .unwrap();
+    assert_eq!(derived_prk.len(), 2);
+    assert_eq!(derived_prk.get(&0).unwrap(), &default_prk);
+    assert_eq!(derived_prk.get(&1).unwrap(), &default_prk);
+    assert!(default_proof_spec
+        .derive_default_accumulator_proving_keys::<Blake2b512>()
+        .unwrap()
+        .is_empty());
+
+    let mut explicit_statements = Statements::<Bls12_381, G1Affine>::new();
+    for _ in 0..2 {
+        explicit_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+            default_prk.clone(),
+            *pos_accumulator.value(),
+        ));
+    }
+    let explicit_proof_spec =
+        ProofSpec::new(explicit_statements, MetaStatements::new(), vec![], None);
+    explicit_proof_spec.validate().unwrap();
+
+    assert!(default_proof_spec.compressed_size() < explicit_proof_spec.compressed_size());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit.clone()));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit.clone()));
+
+    let nonce = Some(b"test nonce".to_vec());
+    for prover_proof_spec in [&default_proof_spec, &explicit_proof_spec] {
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec,
+            witnesses.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        for verifier_proof_spec in [
+            &default_proof_spec,
+            &deserialized_default_proof_spec,
+            &explicit_proof_spec,
+        ] {
+            proof
+                .clone()
+                .verify::<StdRng, Blake2b512>(
+                    &mut rng,
+                    verifier_proof_spec,
+                    nonce.clone(),
+                    Default::default(),
+                )
+                .unwrap();
+        }
+    }
+
+    // A different proving key doesn't verify a proof created with the default one
+    let mut other_statements = Statements::<Bls12_381, G1Affine>::new();
+    for _ in 0..2 {
+        other_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+            MembershipProvingKey::generate_using_rng(&mut rng),
+            *pos_accumulator.value(),
+        ));
+    }
+    let other_proof_spec = ProofSpec::new(other_statements, MetaStatements::new(), vec![], None);
+
+    // Precomputing the default proving keys of the deserialized spec leaves nothing to derive later
+    deserialized_default_proof_spec
+        .precompute_default_accumulator_proving_keys::<Blake2b512>()
+        .unwrap();
+    assert!(deserialized_default_proof_spec
+        .derive_default_accumulator_proving_keys::<Blake2b512>()
+        .unwrap()
+        .is_empty());
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &deserialized_default_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &other_proof_spec, nonce, Default::default())
+        .is_err());
+}
+
+#[test]
+fn arithmetic_error_for_non_membership_witness_with_zero_d() {
+    // Creating a proof with a non-membership witness having `d = 0` fails with an arithmetic error rather than
+    // panicking while inverting `d`
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 100;
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, max);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let non_member = Fr::rand(&mut rng);
+    let mut non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+    non_mem_wit.d = Fr::zero();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params,
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk,
+        *uni_accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+
+    let err = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ProofSystemError::ArithmeticError(_)));
+    assert_eq!(err.to_public(), PublicError::MalformedInput);
+}
+
+/// Prove knowledge of a BBS+ signature with one message in a positive accumulator, another not in a universal
+/// accumulator and another in a range, over the given curve
+fn pok_of_bbs_plus_sig_accumulators_and_bound_check<E: Pairing>() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let (mem_idx, non_mem_idx, bounded_idx) = (1, 2, 3);
+    let (min, max) = (18, 100);
+
+    let mut msgs = (0..msg_count)
+        .map(|_| E::ScalarField::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs[bounded_idx] = E::ScalarField::from(25u64);
+    let (sig_params, sig_keypair, sig) =
+        bbs_plus_sig_setup_given_messages_generic::<E, _>(&mut rng, &msgs);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum_generic::<E>(&mut rng);
+    let mem_prk = MembershipProvingKey::<E::G1Affine>::generate_using_rng(&mut rng);
+    pos_accumulator = pos_accumulator
+        .add(msgs[mem_idx], &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&msgs[mem_idx], &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum_generic::<E>(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::<E::G1Affine>::generate_using_rng(&mut rng);
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &msgs[non_mem_idx],
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let bpp_setup_params = BppSetupParams::<E::G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(
+        b"test", 2, 64, 1,
+    );
+
+    let mut statements = Statements::<E, E::G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params,
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk,
+        *uni_accumulator.value(),
+    ));
+    statements
+        .add(BoundCheckBppStmt::new_statement_from_params(min, max, bpp_setup_params).unwrap());
+
+    let mut meta_statements = MetaStatements::new();
+    for (s_idx, msg_idx) in [(1, mem_idx), (2, non_mem_idx), (3, bounded_idx)] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_idx), (s_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<E, E::G1Affine>, proof_spec);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(msgs[mem_idx], mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(
+        msgs[non_mem_idx],
+        non_mem_wit,
+    ));
+    witnesses.add(Witness::BoundCheckBpp(msgs[bounded_idx]));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1Of::<E>::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1Of<E>, proof);
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .unwrap();
+    }
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_accumulators_and_bound_check_over_bls12_381() {
+    pok_of_bbs_plus_sig_accumulators_and_bound_check::<Bls12_381>()
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_accumulators_and_bound_check_over_bls12_377() {
+    pok_of_bbs_plus_sig_accumulators_and_bound_check::<Bls12_377>()
+}
+
+#[test]
+fn accumulator_membership_linked_to_bbs_plus_message() {
+    // Accumulator membership sub-protocol reuses the blinding of a signed message so the responses for the
+    // member and the message are equal
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let member_idx = 3;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let revealed_msgs = BTreeMap::from([(0, msgs[0]), (2, msgs[2])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut bbs_sp =
+        PoKBBSSigG1SubProtocol::new(0, &revealed_msgs, &sig_params, &sig_keypair.public_key);
+    let mut mem_sp = AccumulatorMembershipSubProtocol::new(
+        1,
+        &accum_params,
+        &accum_keypair.public_key,
+        &mem_prk,
+        *accumulator.value(),
+    );
+    let mem_witness = || MembershipWit {
+        element: member,
+        witness: mem_wit.clone(),
+    };
+
+    // BBS+ sub-protocol must be initialized first
+    assert!(matches!(
+        mem_sp.init_linked(&mut rng, mem_witness(), &bbs_sp, member_idx),
+        Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(0))
+    ));
+
+    bbs_sp
+        .init(
+            &mut rng,
+            BTreeMap::new(),
+            PoKSignatureBBSG1Wit {
+                signature: sig,
+                unrevealed_messages: unrevealed_msgs,
+            },
+        )
+        .unwrap();
+    // Revealed messages have no blinding
+    assert!(mem_sp
+        .init_linked(&mut rng, mem_witness(), &bbs_sp, 2)
+        .is_err());
+    mem_sp
+        .init_linked(&mut rng, mem_witness(), &bbs_sp, member_idx)
+        .unwrap();
+
+    let challenge = Fr::rand(&mut rng);
+    let bbs_proof = match bbs_sp
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap()
+    {
+        StatementProof::PoKBBSSignatureG1(p) => p,
+        _ => panic!("expected a proof of knowledge of BBS+ signature"),
+    };
+    let mem_proof = match mem_sp
+        .gen_proof_contribution::<G1Affine>(&challenge)
+        .unwrap()
+    {
+        StatementProof::AccumulatorMembership(p) => p,
+        _ => panic!("expected an accumulator membership proof"),
+    };
+    mem_sp
+        .verify_proof_contribution(
+            &challenge,
+            &mem_proof,
+            accum_keypair.public_key.clone(),
+            accum_params.clone(),
+            &mut None,
+        )
+        .unwrap();
+    let revealed_msg_ids = revealed_msgs.keys().copied().collect();
+    assert_eq!(
+        bbs_proof
+            .get_resp_for_message(member_idx, &revealed_msg_ids)
+            .unwrap(),
+        mem_proof.get_schnorr_response_for_element()
+    );
+    assert_ne!(
+        bbs_proof
+            .get_resp_for_message(4, &revealed_msg_ids)
+            .unwrap(),
+        mem_proof.get_schnorr_response_for_element()
+    );
+}
+
+#[test]
+fn challenge_from_incremental_digest() {
+    // Hashing the challenge contributions as they are written gives the same challenge as hashing them after
+    // collecting them in a buffer
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let member = msgs[3];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let revealed_msgs = BTreeMap::from([(0, msgs[0]), (2, msgs[2])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        revealed_msgs.clone(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        mem_prk.clone(),
+        *accumulator.value(),
+    ));
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(
+        statements,
+        meta_statements,
+        vec![],
+        Some(b"test-context".to_vec()),
+    );
+    proof_spec.validate().unwrap();
+
+    let mut bbs_sp =
+        PoKBBSSigG1SubProtocol::new(0, &revealed_msgs, &sig_params, &sig_keypair.public_key);
+    bbs_sp
+        .init(
+            &mut rng,
+            BTreeMap::new(),
+            PoKSignatureBBSG1Wit {
+                signature: sig.clone(),
+                unrevealed_messages: unrevealed_msgs.clone(),
+            },
+        )
+        .unwrap();
+    let mut mem_sp = AccumulatorMembershipSubProtocol::new(
+        1,
+        &accum_params,
+        &accum_keypair.public_key,
+        &mem_prk,
+        *accumulator.value(),
+    );
+    mem_sp
+        .init_linked(
+            &mut rng,
+            MembershipWit {
+                element: member,
+                witness: mem_wit.clone(),
+            },
+            &bbs_sp,
+            3,
+        )
+        .unwrap();
+    let sub_protocols: [SubProtocol<Bls12_381, G1Affine>; 2] = [
+        SubProtocol::PoKBBSSignatureG1(bbs_sp),
+        SubProtocol::AccumulatorMembership(mem_sp),
+    ];
+
+    let nonce = b"test-nonce".to_vec();
+    let mut bytes = nonce.clone();
+    bytes.extend_from_slice(proof_spec.context.as_ref().unwrap());
+    let mut hasher = DigestWriter::<Blake2b512>::new();
+    hasher.update(&nonce);
+    hasher.update(proof_spec.context.as_ref().unwrap());
+    proof_spec.challenge_contribution(&mut bytes).unwrap();
+    proof_spec.challenge_contribution(&mut hasher).unwrap();
+    for sp in &sub_protocols {
+        sp.challenge_contribution(&mut bytes).unwrap();
+        sp.challenge_contribution(&mut hasher).unwrap();
+    }
+    assert_eq!(
+        ProofG1::generate_challenge_from_digest(hasher.into_inner()),
+        ProofG1::generate_challenge_from_bytes::<Blake2b512>(&bytes)
+    );
+
+    // Prover and verifier both hash incrementally
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        Some(nonce.clone()),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, Some(nonce), Default::default())
+        .unwrap();
+}
+
+#[test]
+fn revealed_messages_added_to_statement() {
+    // Messages to reveal are selected one at a time after the statement is created
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let mut statement = match PoKSignatureBBSG1Stmt::new_statement_from_params::<G1Affine>(
+        params.clone(),
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ) {
+        Statement::PoKBBSSignatureG1(s) => s,
+        _ => panic!("Expected a BBS+ signature statement"),
+    };
+    statement.add_revealed_message(1, msgs[1]).unwrap();
+    statement.add_revealed_message(3, msgs[3]).unwrap();
+    statement.add_revealed_message(4, msgs[4]).unwrap();
+    assert_eq!(statement.remove_revealed_message(4).unwrap(), msgs[4]);
+
+    assert!(matches!(
+        statement.add_revealed_message(1, msgs[1]),
+        Err(ProofSystemError::MessageAlreadyRevealed(1))
+    ));
+    assert!(matches!(
+        statement.add_revealed_message(msg_count as usize, Fr::rand(&mut rng)),
+        Err(ProofSystemError::RevealedMessageIndexOutOfBounds(5, 5))
+    ));
+    assert!(matches!(
+        statement.remove_revealed_message(4),
+        Err(ProofSystemError::MessageNotRevealed(4))
+    ));
+
+    let revealed = BTreeMap::from([(1, msgs[1]), (3, msgs[3])]);
+    assert_eq!(
+        Statement::PoKBBSSignatureG1(statement.clone()),
+        PoKSignatureBBSG1Stmt::new_statement_from_params::<G1Affine>(
+            params.clone(),
+            keypair.public_key.clone(),
+            revealed.clone(),
+        )
+    );
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(Statement::PoKBBSSignatureG1(statement));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter()
+            .enumerate()
+            .filter(|(i, _)| !revealed.contains_key(i))
+            .collect(),
+    ));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn proof_spec_with_allowed_statement_types() {
+    // Verifier rejects a proof spec having a statement type it doesn't allow
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let accum_member_idx = 3;
+    pos_accumulator = pos_accumulator
+        .add(
+            msgs[accum_member_idx],
+            &pos_accum_keypair.secret_key,
+            &mut pos_state,
+        )
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(
+            &msgs[accum_member_idx],
+            &pos_accum_keypair.secret_key,
+            &pos_state,
+        )
+        .unwrap();
+
+    let statements = Statements::<Bls12_381, G1Affine>::new_bbs_with_memberships(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+        vec![(
+            pos_accum_params,
+            pos_accum_keypair.public_key.clone(),
+            mem_prk,
+            *pos_accumulator.value(),
+        )],
+    );
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, accum_member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let witnesses = Witnesses::new_bbs_with_memberships(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+        vec![(msgs[accum_member_idx], mem_wit)],
+    );
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // All statement types are allowed
+    proof_spec
+        .enforce_allowed_types(&[
+            SubProtocolType::PoKBBSSignatureG1,
+            SubProtocolType::AccumulatorMembership,
+            SubProtocolType::Saver,
+        ])
+        .unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+        .unwrap();
+
+    // Accumulator membership isn't allowed even though the proof is valid
+    let err = proof_spec
+        .enforce_allowed_types(&[SubProtocolType::PoKBBSSignatureG1])
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ProofSystemError::DisallowedStatementType(1, SubProtocolType::AccumulatorMembership)
+    ));
+    assert_eq!(err.to_public(), PublicError::UnsupportedFeature);
+    assert!(matches!(
+        proof_spec.enforce_allowed_types(&[]),
+        Err(ProofSystemError::DisallowedStatementType(
+            0,
+            SubProtocolType::PoKBBSSignatureG1
+        ))
+    ));
+}
+
+// The audit is only recorded in debug builds
+#[cfg(debug_assertions)]
+#[test]
+fn blindings_of_linked_witnesses_in_proof_build_audit() {
+    // Witnesses proven equal share a blinding and witnesses which aren't don't
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (mut msgs_1, params_1, keypair_1, _) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let (mut msgs_2, params_2, keypair_2, _) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    msgs_2[1] = msgs_1[1];
+    msgs_2[3] = msgs_1[2];
+    // Equal messages which aren't proven equal
+    msgs_1[4] = msgs_2[4];
+    let sig_1 =
+        SignatureG1::<Bls12_381>::new(&mut rng, &msgs_1, &keypair_1.secret_key, &params_1).unwrap();
+    let sig_2 =
+        SignatureG1::<Bls12_381>::new(&mut rng, &msgs_2, &keypair_2.secret_key, &params_2).unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_1,
+        keypair_1.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        params_2,
+        keypair_2.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let equalities = vec![
+        vec![(0, 1), (1, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+        vec![(0, 2), (1, 3)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ];
+    let mut meta_statements = MetaStatements::new();
+    for eq in &equalities {
+        meta_statements.add_witness_equality(EqualWitnesses(eq.clone()));
+    }
+    let proof_spec = ProofSpec::new(statements, meta_statements.clone(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1,
+        msgs_1.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_2,
+        msgs_2.into_iter().enumerate().collect(),
+    ));
+    let (proof, _, audit) = ProofG1::new_with_audit::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+        .unwrap();
+
+    // Each equality got its own blinding and no other witness got it
+    assert_eq!(audit.blindings.len(), 2);
+    let mut audited = audit.blindings.values().cloned().collect::<Vec<_>>();
+    audited.sort();
+    assert_eq!(audited, equalities);
+    assert!(audit
+        .unrelated_witnesses_with_same_blinding(&meta_statements)
+        .is_empty());
+
+    // Against meta-statements which only link the first pair of witnesses, the second pair shouldn't have
+    // shared a blinding
+    let mut fewer_meta_statements = MetaStatements::new();
+    fewer_meta_statements.add_witness_equality(EqualWitnesses(equalities[0].clone()));
+    assert_eq!(
+        audit.unrelated_witnesses_with_same_blinding(&fewer_meta_statements),
+        vec![equalities[1].clone()]
+    );
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_batch_accumulator_non_membership() {
+    // Prove non-membership of 4 elements in a universal accumulator with a single statement. 2 of the elements are
+    // messages of a BBS+ signature.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let max = 100;
+    let (uni_accum_params, uni_accum_keypair, mut uni_accumulator, initial_elements, mut uni_state) =
+        setup_universal_accum(&mut rng, max);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member = Fr::rand(&mut rng);
+    uni_accumulator = uni_accumulator
+        .add(
+            member,
+            &uni_accum_keypair.secret_key,
+            &initial_elements,
+            &mut uni_state,
+        )
+        .unwrap();
+    // No non-membership witness exists for a member
+    assert!(uni_accumulator
+        .get_non_membership_witness(
+            &member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .is_err());
+
+    // Elements at index 0 and 2 of the batch are the messages at index 1 and 3 of the signature
+    let non_members = vec![msgs[1], Fr::rand(&mut rng), msgs[3], Fr::rand(&mut rng)];
+    let non_mem_wits = non_members
+        .iter()
+        .map(|e| {
+            uni_accumulator
+                .get_non_membership_witness(
+                    e,
+                    &uni_accum_keypair.secret_key,
+                    &uni_state,
+                    &uni_accum_params,
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(
+        BatchAccumulatorNonMembershipStmt::new_statement_from_params(
+            uni_accum_params.clone(),
+            uni_accum_keypair.public_key.clone(),
+            non_mem_prk.clone(),
+            *uni_accumulator.value(),
+            non_members.len(),
+        ),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (1, 2)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let witnesses_for = |elements: Vec<(Fr, _)>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(NonMembershipWit::new_batch_as_witness(elements));
+        witnesses
+    };
+
+    let witnesses = witnesses_for(
+        non_members
+            .iter()
+            .copied()
+            .zip(non_mem_wits.iter().cloned())
+            .collect(),
+    );
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let start = Instant::now();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    println!(
+        "Time taken to create proof of BBS+ signature and non-membership of {} elements: {:?}",
+        non_members.len(),
+        start.elapsed()
+    );
+    test_serialization!(ProofG1, proof);
+    assert_eq!(
+        proof.statement_proof(1).unwrap().protocol_type(),
+        SubProtocolType::BatchAccumulatorNonMembership
+    );
+
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        let start = Instant::now();
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .unwrap();
+        println!(
+            "Time taken to verify proof of BBS+ signature and non-membership of {} elements: {:?}",
+            non_members.len(),
+            start.elapsed()
+        );
+    }
+
+    // An element which is a member can't be proven to be a non-member even with a valid witness of another element
+    let mut with_member = non_members
+        .iter()
+        .copied()
+        .zip(non_mem_wits.iter().cloned())
+        .collect::<Vec<_>>();
+    with_member[3].0 = member;
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses_for(with_member),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .is_err());
+    }
+
+    // The witness must have as many elements as the statement
+    let fewer = non_members
+        .iter()
+        .copied()
+        .zip(non_mem_wits.iter().cloned())
+        .take(3)
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses_for(fewer),
+            nonce,
+            Default::default(),
+        ),
+        Err(ProofSystemError::BatchNonMembershipCountMismatch(1, 4, 3))
+    ));
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_batch_accumulator_membership() {
+    // Prove membership of 4 elements in a positive accumulator with a single statement. 2 of the elements are
+    // messages of a BBS+ signature.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    // Elements at index 0 and 2 of the batch are the messages at index 1 and 3 of the signature
+    let members = vec![msgs[1], Fr::rand(&mut rng), msgs[3], Fr::rand(&mut rng)];
+    for e in &members {
+        pos_accumulator = pos_accumulator
+            .add(*e, &pos_accum_keypair.secret_key, &mut pos_state)
+            .unwrap();
+    }
+    let mem_wits = members
+        .iter()
+        .map(|e| {
+            pos_accumulator
+                .get_membership_witness(e, &pos_accum_keypair.secret_key, &pos_state)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(BatchAccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params.clone(),
+        pos_accum_keypair.public_key.clone(),
+        mem_prk.clone(),
+        *pos_accumulator.value(),
+        members.len(),
+    ));
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (1, 2)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let witnesses_for = |elements: Vec<(Fr, _)>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(MembershipWit::new_batch_as_witness(elements));
+        witnesses
+    };
+
+    let witnesses = witnesses_for(
+        members
+            .iter()
+            .copied()
+            .zip(mem_wits.iter().cloned())
+            .collect(),
+    );
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    assert_eq!(
+        proof.statement_proof(1).unwrap().protocol_type(),
+        SubProtocolType::BatchAccumulatorMembership
+    );
+
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .unwrap();
+    }
+
+    // An element which is not a member can't be proven to be a member even with a valid witness of another element
+    let mut with_non_member = members
+        .iter()
+        .copied()
+        .zip(mem_wits.iter().cloned())
+        .collect::<Vec<_>>();
+    with_non_member[3].0 = Fr::rand(&mut rng);
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses_for(with_non_member),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .is_err());
+    }
+
+    // The witness must have as many elements as the statement
+    let fewer = members
+        .iter()
+        .copied()
+        .zip(mem_wits.iter().cloned())
+        .take(3)
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses_for(fewer),
+            nonce,
+            Default::default(),
+        ),
+        Err(ProofSystemError::BatchMembershipCountMismatch(1, 4, 3))
+    ));
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_committed_accumulator_membership() {
+    // Prove that a message of a BBS+ signature is a member of an accumulator whose value is only given to the
+    // verifier as a Pedersen commitment
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member_idx = 2;
+    pos_accumulator = pos_accumulator
+        .add(
+            msgs[member_idx],
+            &pos_accum_keypair.secret_key,
+            &mut pos_state,
+        )
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&msgs[member_idx], &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    // Commit to the accumulator value
+    let commitment_key = G1Projective::rand(&mut rng).into_affine();
+    let randomness = Fr::rand(&mut rng);
+    let commitment = (commitment_key * randomness + pos_accumulator.value()).into_affine();
+
+    let statements_for = |commitment: G1Affine| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            CommittedAccumulatorMembershipStmt::new_statement_from_params(
+                pos_accum_params.clone(),
+                pos_accum_keypair.public_key.clone(),
+                mem_prk.clone(),
+                commitment,
+                commitment_key,
+            ),
+        );
+        statements
+    };
+    let statements = statements_for(commitment);
+    test_serialization!(Statements<Bls12_381, G1Affine>, statements);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements.clone(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let witnesses_for = |element: Fr, randomness: Fr| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(CommittedMembershipWit::new_as_witness(
+            element,
+            mem_wit.clone(),
+            randomness,
+        ));
+        witnesses
+    };
+    let witnesses = witnesses_for(msgs[member_idx], randomness);
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    assert_eq!(
+        proof.statement_proof(1).unwrap().protocol_type(),
+        SubProtocolType::CommittedAccumulatorMembership
+    );
+
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .unwrap();
+    }
+
+    // The proof doesn't verify for a commitment to a different accumulator value
+    let other_commitment =
+        (commitment_key * Fr::rand(&mut rng) + pos_accumulator.value()).into_affine();
+    let other_proof_spec = ProofSpec::new(
+        statements_for(other_commitment),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &other_proof_spec, nonce.clone(), config)
+            .is_err());
+    }
+
+    // A prover not knowing the randomness of the commitment can't create a valid proof
+    let wrong_randomness = Fr::rand(&mut rng);
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses_for(msgs[member_idx], wrong_randomness),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    for config in [
+        VerifierConfig::default(),
+        VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            ..Default::default()
+        },
+    ] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), config)
+            .is_err());
+    }
+
+    // A message which is not a member can't be proven to be a member
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 0), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements_for(commitment), meta_statements, vec![], None);
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses_for(msgs[0], randomness),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .is_err());
+}
+
+#[test]
+fn accumulator_params_of_proof_spec() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (_, sig_params, sig_keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+    let (pos_accum_params, pos_accum_keypair, pos_accumulator, _) = setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, _) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+    let mem_prk = non_mem_prk.derive_membership_proving_key();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params.clone(),
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params.clone(),
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk.clone(),
+        *uni_accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let accum_params = proof_spec.accumulator_params().unwrap();
+    assert_eq!(accum_params.len(), 2);
+    assert_eq!(accum_params[0].0, 1);
+    assert_eq!(accum_params[0].1.params, &pos_accum_params);
+    assert_eq!(accum_params[0].1.public_key, &pos_accum_keypair.public_key);
+    assert_eq!(accum_params[0].1.params_ref, None);
+    assert_eq!(accum_params[1].0, 2);
+    assert_eq!(accum_params[1].1.params, &uni_accum_params);
+    assert_eq!(accum_params[1].1.public_key, &uni_accum_keypair.public_key);
+    assert_eq!(accum_params[1].1.public_key_ref, None);
+
+    // Params referred from setup params are returned along with their indices
+    let all_setup_params = vec![
+        SetupParams::<Bls12_381, G1Affine>::VbAccumulatorParams(uni_accum_params.clone()),
+        SetupParams::VbAccumulatorPublicKey(uni_accum_keypair.public_key.clone()),
+        SetupParams::VbAccumulatorNonMemProvingKey(non_mem_prk),
+    ];
+    let mut statements = Statements::new();
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params_ref(
+        0,
+        1,
+        2,
+        *uni_accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), all_setup_params, None);
+    proof_spec.validate().unwrap();
+
+    let accum_params = proof_spec.accumulator_params().unwrap();
+    assert_eq!(accum_params.len(), 1);
+    assert_eq!(accum_params[0].0, 0);
+    assert_eq!(accum_params[0].1.params, &uni_accum_params);
+    assert_eq!(accum_params[0].1.public_key, &uni_accum_keypair.public_key);
+    assert_eq!(accum_params[0].1.params_ref, Some(0));
+    assert_eq!(accum_params[0].1.public_key_ref, Some(1));
+}
+
+#[test]
+fn structure_of_bbs_plus_proof() {
+    // A proof with a missing response for a hidden message is rejected before verification
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let revealed_msgs = BTreeMap::from([(1, msgs[1]), (4, msgs[4])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed_msgs,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof.validate_structure(&proof_spec).unwrap();
+
+    let mut malformed = proof.clone();
+    match &mut malformed.statement_proofs[0] {
+        StatementProof::PoKBBSSignatureG1(p) => {
+            p.sc_resp_2.0.pop();
+        }
+        _ => panic!("expected a proof of knowledge of BBS+ signature"),
+    }
+    let err = malformed.validate_structure(&proof_spec).unwrap_err();
+    assert!(matches!(
+        err,
+        ProofSystemError::MalformedStatementProof { statement: 0, .. }
+    ));
+    assert_eq!(err.to_public(), PublicError::InvalidProof);
+    assert!(malformed
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+        .is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn batch_verification_of_proofs_in_parallel() {
+    // Serial and parallel batch verification should accept and reject the same batches of proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let proof_count = 8;
+    let msg_count = 5;
+    let member_idx = 2;
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let mut specs = vec![];
+    let mut wrong_key_specs = vec![];
+    let mut proofs = vec![];
+    let mut signers = vec![];
+    for _ in 0..proof_count {
+        let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+        accumulator = accumulator
+            .add(msgs[member_idx], &accum_keypair.secret_key, &mut state)
+            .unwrap();
+        signers.push((msgs, sig_params, sig_keypair, sig));
+    }
+
+    let spec_with_key = |sig_params: &SignatureParamsG1<Bls12_381>, public_key| {
+        let statements = Statements::<Bls12_381, G1Affine>::new_bbs_with_memberships(
+            sig_params.clone(),
+            public_key,
+            BTreeMap::new(),
+            vec![(
+                accum_params.clone(),
+                accum_keypair.public_key.clone(),
+                mem_prk.clone(),
+                *accumulator.value(),
+            )],
+        );
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, member_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        ProofSpec::new(statements, meta_statements, vec![], None)
+    };
+
+    for (i, (msgs, sig_params, sig_keypair, sig)) in signers.iter().enumerate() {
+        let proof_spec = spec_with_key(sig_params, sig_keypair.public_key.clone());
+        let mem_wit = accumulator
+            .get_membership_witness(&msgs[member_idx], &accum_keypair.secret_key, &state)
+            .unwrap();
+        let witnesses = Witnesses::new_bbs_with_memberships(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+            vec![(msgs[member_idx], mem_wit)],
+        );
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            Some(vec![i as u8]),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        // Public key of the next signer makes only the pairing check fail
+        let other_key = signers[(i + 1) % proof_count].2.public_key.clone();
+        wrong_key_specs.push(spec_with_key(sig_params, other_key));
+        specs.push(proof_spec);
+        proofs.push(proof);
+    }
+
+    let batch = |wrong_nonce: Option<usize>, wrong_key: Option<usize>| {
+        proofs
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let proof_spec = if wrong_key == Some(i) {
+                    &wrong_key_specs[i]
+                } else {
+                    &specs[i]
+                };
+                let nonce = if wrong_nonce == Some(i) {
+                    vec![i as u8 + 1]
+                } else {
+                    vec![i as u8]
+                };
+                (p.clone(), proof_spec, Some(nonce))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for (wrong_nonce, wrong_key, valid) in [
+        (None, None, true),
+        (Some(3), None, false),
+        (None, Some(5), false),
+        (Some(1), Some(6), false),
+    ] {
+        for lazy in [true, false] {
+            let start = Instant::now();
+            let serial = ProofG1::batch_verify::<StdRng, Blake2b512>(
+                &mut rng,
+                batch(wrong_nonce, wrong_key),
+                lazy,
+            );
+            let serial_time = start.elapsed();
+
+            let start = Instant::now();
+            let parallel = ProofG1::batch_verify_parallel::<StdRng, Blake2b512>(
+                &mut rng,
+                batch(wrong_nonce, wrong_key),
+                lazy,
+            );
+            let parallel_time = start.elapsed();
+
+            assert_eq!(serial.is_ok(), valid);
+            assert_eq!(parallel.is_ok(), valid);
+            assert_eq!(format!("{:?}", serial), format!("{:?}", parallel));
+            if wrong_nonce.is_none() && wrong_key.is_some() {
+                assert!(matches!(
+                    serial,
+                    Err(ProofSystemError::RandomizedPairingCheckFailed)
+                ));
+            }
+            println!(
+                "Batch verification of {} proofs with lazy={} takes {:?} serially and {:?} in parallel",
+                proof_count, lazy, serial_time, parallel_time
+            );
+        }
+    }
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_with_all_messages_revealed() {
+    // When all messages are revealed, the prover can choose to send the signature as it is instead of a proof of
+    // knowledge and the verifier has to choose to accept it
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let proof_spec = |revealed_msgs: BTreeMap<usize, Fr>| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            revealed_msgs,
+        ));
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let revealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+    let proof_spec_all_revealed = proof_spec(revealed_msgs.clone());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        BTreeMap::new(),
+    ));
+    let nonce = Some(b"test-nonce".to_vec());
+
+    // Without opting in, a proof of knowledge is created even though all messages are revealed
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_all_revealed,
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof.as_bbs_plus(0).is_ok());
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_all_revealed,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_all_revealed,
+        witnesses,
+        nonce.clone(),
+        ProverConfig {
+            send_fully_revealed_bbs_plus_signatures: true,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    assert_eq!(proof.as_bbs_plus_full_reveal(0).unwrap(), &sig);
+    assert!(proof.as_bbs_plus(0).is_err());
+
+    // The verifier rejects the signature unless it opts in
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_all_revealed,
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::FullRevealSignatureNotAccepted(0))
+    ));
+    assert!(matches!(
+        ProofG1::batch_verify::<StdRng, Blake2b512>(
+            &mut rng,
+            vec![(proof.clone(), &proof_spec_all_revealed, nonce.clone())],
+            false,
+        ),
+        Err(ProofSystemError::FullRevealSignatureNotAccepted(0))
+    ));
+
+    let accept_full_reveal = |use_lazy_randomized_pairing_checks| VerifierConfig {
+        use_lazy_randomized_pairing_checks,
+        accept_fully_revealed_bbs_plus_signatures: true,
+    };
+    for lazy in [None, Some(true)] {
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                &proof_spec_all_revealed,
+                nonce.clone(),
+                accept_full_reveal(lazy),
+            )
+            .unwrap();
+    }
+
+    // The signature isn't bound to the nonce so the proof verifies with any nonce
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_all_revealed,
+            Some(b"other-nonce".to_vec()),
+            accept_full_reveal(None),
+        )
+        .unwrap();
+
+    // Doesn't verify when a revealed message is different
+    let mut wrong_msgs = revealed_msgs.clone();
+    wrong_msgs.insert(2, Fr::rand(&mut rng));
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec(wrong_msgs),
+            nonce.clone(),
+            accept_full_reveal(None),
+        )
+        .is_err());
+
+    // Or when the verifier doesn't expect all messages to be revealed
+    let mut fewer_msgs = revealed_msgs.clone();
+    fewer_msgs.remove(&4);
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec(fewer_msgs.clone()),
+            nonce.clone(),
+            accept_full_reveal(None),
+        ),
+        Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(4, 5))
+    ));
+
+    // A proof of knowledge is still created when some message is hidden
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        BTreeMap::from([(4, msgs[4])]),
+    ));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec(fewer_msgs.clone()),
+        witnesses,
+        nonce.clone(),
+        ProverConfig {
+            send_fully_revealed_bbs_plus_signatures: true,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+    assert!(proof.as_bbs_plus(0).is_ok());
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec(fewer_msgs), nonce, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn proof_with_external_setup_params() {
+    // BBS+ params known to the verifier are left out of the serialized proof and its spec and taken from its cache
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 20;
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let setup_params = vec![
+        SetupParams::<Bls12_381, G1Affine>::BBSPlusSignatureParams(params),
+        SetupParams::BBSPlusPublicKey(keypair.public_key.clone()),
+    ];
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params_ref(
+        0,
+        1,
+        BTreeMap::from([(0, msgs[0])]),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), setup_params, None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().skip(1).collect(),
+    ));
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let params_hash = proof_spec.setup_params[0]
+        .content_hash::<Blake2b512>()
+        .unwrap();
+    assert_ne!(
+        params_hash,
+        proof_spec.setup_params[1]
+            .content_hash::<Blake2b512>()
+            .unwrap()
+    );
+    let known_hashes = BTreeSet::from([params_hash.clone()]);
+    let mut bytes = vec![];
+    proof
+        .serialize_with_external_setup::<Blake2b512, _>(&proof_spec, &known_hashes, &mut bytes)
+        .unwrap();
+    assert!(bytes.len() < proof_spec.compressed_size() + proof.compressed_size());
+
+    // Nothing is left out when no hash is known
+    let mut full_bytes = vec![];
+    proof
+        .serialize_with_external_setup::<Blake2b512, _>(
+            &proof_spec,
+            &BTreeSet::new(),
+            &mut full_bytes,
+        )
+        .unwrap();
+    assert!(full_bytes.len() > bytes.len());
+    let (full_proof, full_spec) =
+        ProofG1::deserialize_with_external_setup(&full_bytes[..], &ExternalSetupCache::new())
+            .unwrap();
+    assert_eq!(full_proof, proof);
+    assert_eq!(full_spec, proof_spec);
+
+    // The cache hashes the params once when it's built
+    let cache =
+        ExternalSetupCache::from_setup_params::<Blake2b512>([proof_spec.setup_params[0].clone()])
+            .unwrap();
+    assert!(cache.get(&params_hash).is_some());
+    let (reassembled_proof, reassembled_spec) =
+        ProofG1::deserialize_with_external_setup(&bytes[..], &cache).unwrap();
+    assert_eq!(reassembled_proof, proof);
+    assert_eq!(reassembled_spec, proof_spec);
+    reassembled_proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &reassembled_spec, nonce, Default::default())
+        .unwrap();
+
+    // Serialization starts with the format version and bytes with another version are rejected
+    assert_eq!(
+        u16::deserialize_compressed(&bytes[..]).unwrap(),
+        FORMAT_VERSION
+    );
+    let mut old_bytes = bytes.clone();
+    (FORMAT_VERSION - 1)
+        .serialize_compressed(&mut old_bytes[..2])
+        .unwrap();
+    assert!(matches!(
+        ProofG1::deserialize_with_external_setup(&old_bytes[..], &cache),
+        Err(ProofSystemError::UnsupportedProofVersion { found, supported })
+            if found == FORMAT_VERSION - 1 && supported == FORMAT_VERSION
+    ));
+
+    // Errors when the params aren't in the cache or the cache has different params
+    assert!(matches!(
+        ProofG1::deserialize_with_external_setup(&bytes[..], &ExternalSetupCache::new()),
+        Err(ProofSystemError::MissingExternalSetup(h)) if h == params_hash
+    ));
+    let (_, other_params, _, _) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let wrong_cache =
+        ExternalSetupCache::from_setup_params::<Blake2b512>([
+            SetupParams::<Bls12_381, G1Affine>::BBSPlusSignatureParams(other_params),
+        ])
+        .unwrap();
+    assert!(matches!(
+        ProofG1::deserialize_with_external_setup(&bytes[..], &wrong_cache),
+        Err(ProofSystemError::MissingExternalSetup(h)) if h == params_hash
+    ));
+}
+
+#[test]
+fn accumulator_witnesses_from_accumulator_manager() {
+    // Membership and non-membership witnesses created by the manager are used in proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 100;
+    let (params, keypair, accumulator, initial_elements, mut state) =
+        setup_universal_accum(&mut rng, max);
+    let members = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let accumulator = accumulator
+        .add_batch(
+            members.clone(),
+            &keypair.secret_key,
+            &initial_elements,
+            &mut state,
+        )
+        .unwrap();
+    let manager = AccumulatorManager::new(
+        accumulator.clone(),
+        params.clone(),
+        keypair.secret_key.clone(),
+        state,
+    );
+    // The secret key isn't part of the debug output
+    let debug = format!("{:?}", manager);
+    assert!(debug.contains("secret_key: \"<redacted>\""));
+    assert!(!debug.contains(&format!("{:?}", keypair.secret_key.0)));
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member = members[3];
+    let non_member = Fr::rand(&mut rng);
+    let mem_wit = manager.generate_membership_witness(&member).unwrap();
+    let non_mem_wit = manager
+        .generate_non_membership_witness(&non_member)
+        .unwrap();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        params.clone(),
+        keypair.public_key.clone(),
+        mem_prk,
+        *accumulator.value(),
+    ));
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        non_mem_prk,
+        *accumulator.value(),
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+
+    // A member has no non-membership witness and a non-member has no membership witness
+    assert!(matches!(
+        manager.generate_non_membership_witness(&member),
+        Err(ProofSystemError::VBAccumError(
+            VBAccumulatorError::ElementPresent
+        ))
+    ));
+    assert!(matches!(
+        manager.generate_membership_witness(&non_member),
+        Err(ProofSystemError::VBAccumError(
+            VBAccumulatorError::ElementAbsent
+        ))
+    ));
+}
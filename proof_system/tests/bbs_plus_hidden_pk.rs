@@ -0,0 +1,408 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, RngCore, SeedableRng},
+    UniformRand,
+};
+use bbs_plus::prelude::{KeypairG2, PublicKeyG2, SignatureG1, SignatureParamsG1};
+use blake2::Blake2b512;
+
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{MetaStatements, ProofSpec, SameIssuer, VerifierConfig, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bbs_plus_hidden_pk::PoKBBSSignatureG1HiddenPK as Stmt, Statements,
+    },
+    sub_protocols::bbs_plus_hidden_pk::RandomizedPublicKeyProtocol,
+    witness::PoKBBSSignatureG1HiddenPK as Wit,
+};
+
+use test_utils::{test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_with_hidden_public_key() {
+    // Prover proves knowledge of a signature from one of several issuers without revealing which one
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let keypairs = (0..3)
+        .map(|_| KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params))
+        .collect::<Vec<_>>();
+    let public_keys = keypairs
+        .iter()
+        .map(|kp| kp.public_key.clone())
+        .collect::<Vec<_>>();
+
+    let issuer_idx = 1;
+    let sig = SignatureG1::new(
+        &mut rng,
+        &msgs,
+        &keypairs[issuer_idx].secret_key,
+        &sig_params,
+    )
+    .unwrap();
+
+    let mut revealed_indices = BTreeSet::new();
+    revealed_indices.insert(0);
+    revealed_indices.insert(2);
+    let mut revealed_msgs = BTreeMap::new();
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate() {
+        if revealed_indices.contains(&i) {
+            revealed_msgs.insert(i, *m);
+        } else {
+            unrevealed_msgs.insert(i, *m);
+        }
+    }
+
+    let proof_spec = |public_keys: Vec<PublicKeyG2<Bls12_381>>| {
+        let mut statements = Statements::new();
+        statements.add(Stmt::new_statement_from_params(
+            sig_params.clone(),
+            public_keys,
+            revealed_msgs.clone(),
+        ));
+        let proof_spec =
+            ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let witnesses = |sig: &SignatureG1<Bls12_381>, public_key_index: usize| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Wit::new_as_witness(
+            sig.clone(),
+            unrevealed_msgs.clone(),
+            public_key_index,
+        ));
+        witnesses
+    };
+
+    let spec = proof_spec(public_keys.clone());
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, spec);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &spec,
+        witnesses(&sig, issuer_idx),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    // The randomized public key in the proof is none of the registered public keys
+    let randomized_pk = proof.as_bbs_plus_hidden_pk(0).unwrap().randomized_pk;
+    for pk in &public_keys {
+        assert_ne!(randomized_pk, pk.0);
+    }
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &spec, nonce.clone(), Default::default())
+        .unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &spec,
+            nonce.clone(),
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    // Verifier's registry doesn't contain the issuer's public key
+    let other_spec = proof_spec(vec![public_keys[0].clone(), public_keys[2].clone()]);
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &other_spec, nonce.clone(), Default::default())
+        .is_err());
+
+    // Signature by an issuer not in the registry can't be used
+    let unregistered = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params);
+    let other_sig =
+        SignatureG1::new(&mut rng, &msgs, &unregistered.secret_key, &sig_params).unwrap();
+    assert!(ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &spec,
+        witnesses(&other_sig, 0),
+        nonce.clone(),
+        Default::default(),
+    )
+    .and_then(|(proof, _)| {
+        proof.verify::<StdRng, Blake2b512>(&mut rng, &spec, nonce.clone(), Default::default())
+    })
+    .is_err());
+
+    // Index of the public key must be in the registry
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &spec,
+            witnesses(&sig, public_keys.len()),
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::InvalidHiddenPKIndex(0, 3))
+    ));
+}
+
+#[test]
+fn hidden_public_key_proof_independent_of_signer() {
+    // Proofs for signatures by each of the public keys have the same structure and size, and creating them uses the
+    // same amount of randomness
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let keypairs = (0..4)
+        .map(|_| KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params))
+        .collect::<Vec<_>>();
+    let public_keys = keypairs
+        .iter()
+        .map(|kp| kp.public_key.clone())
+        .collect::<Vec<_>>();
+
+    let mut revealed_msgs = BTreeMap::new();
+    revealed_msgs.insert(0, msgs[0]);
+    let unrevealed_msgs = (1..msg_count as usize)
+        .map(|i| (i, msgs[i]))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut statements = Statements::new();
+    statements.add(Stmt::new_statement_from_params(
+        sig_params.clone(),
+        public_keys.clone(),
+        revealed_msgs,
+    ));
+    let spec =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+    spec.validate().unwrap();
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let mut serialized_sizes = vec![];
+    for (i, keypair) in keypairs.iter().enumerate() {
+        let sig = SignatureG1::new(&mut rng, &msgs, &keypair.secret_key, &sig_params).unwrap();
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Wit::new_as_witness(sig, unrevealed_msgs.clone(), i));
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &spec,
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &spec, nonce.clone(), Default::default())
+            .unwrap();
+
+        // No challenge or response of the real Schnorr proof stands out
+        let pk_proof = &proof.as_bbs_plus_hidden_pk(0).unwrap().pk_proof;
+        assert_eq!(pk_proof.t.len(), public_keys.len());
+        assert_eq!(pk_proof.challenges.len(), public_keys.len());
+        assert_eq!(pk_proof.responses.len(), public_keys.len());
+        for (c, z) in pk_proof.challenges.iter().zip(pk_proof.responses.iter()) {
+            assert!(!c.is_zero());
+            assert!(!z.is_zero());
+        }
+        serialized_sizes.push(proof.compressed_size());
+    }
+    assert!(serialized_sizes.iter().all(|s| *s == serialized_sizes[0]));
+
+    // Same randomness is consumed whichever public key is the signer's
+    let randomizer = Fr::rand(&mut rng);
+    let next_random_values = (0..public_keys.len())
+        .map(|i| {
+            let mut rng = StdRng::seed_from_u64(1u64);
+            RandomizedPublicKeyProtocol::init(&mut rng, &public_keys, i, randomizer);
+            rng.next_u64()
+        })
+        .collect::<Vec<_>>();
+    assert!(next_random_values
+        .iter()
+        .all(|v| *v == next_random_values[0]));
+}
+
+#[test]
+fn hidden_public_key_credentials_from_same_issuer() {
+    // Prover proves that 2 signatures are by the same issuer without revealing which one
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 4;
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let keypairs = (0..3)
+        .map(|_| KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params))
+        .collect::<Vec<_>>();
+    let public_keys = keypairs
+        .iter()
+        .map(|kp| kp.public_key.clone())
+        .collect::<Vec<_>>();
+
+    let msgs = (0..2)
+        .map(|_| {
+            (0..msg_count)
+                .map(|_| Fr::rand(&mut rng))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let sign = |rng: &mut StdRng, msgs: &[Fr], issuer_idx: usize| {
+        SignatureG1::new(rng, msgs, &keypairs[issuer_idx].secret_key, &sig_params).unwrap()
+    };
+
+    let statements = |public_keys: [Vec<PublicKeyG2<Bls12_381>>; 2]| {
+        let mut statements = Statements::new();
+        for pks in public_keys {
+            statements.add(Stmt::new_statement_from_params(
+                sig_params.clone(),
+                pks,
+                BTreeMap::<usize, Fr>::new(),
+            ));
+        }
+        statements
+    };
+    let proof_spec = |meta_statements: MetaStatements| {
+        ProofSpec::<Bls12_381, G1Affine>::new(
+            statements([public_keys.clone(), public_keys.clone()]),
+            meta_statements,
+            vec![],
+            None,
+        )
+    };
+    let same_issuer = |indices: Vec<usize>| {
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_issuer_consistency(SameIssuer(indices.into_iter().collect()));
+        meta_statements
+    };
+    let witnesses = |sigs: [(&SignatureG1<Bls12_381>, usize); 2]| {
+        let mut witnesses = Witnesses::new();
+        for (i, (sig, public_key_index)) in sigs.into_iter().enumerate() {
+            witnesses.add(Wit::new_as_witness(
+                sig.clone(),
+                msgs[i].iter().cloned().enumerate().collect(),
+                public_key_index,
+            ));
+        }
+        witnesses
+    };
+
+    let spec = proof_spec(same_issuer(vec![0, 1]));
+    spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, spec);
+
+    let issuer_idx = 2;
+    let sig_1 = sign(&mut rng, &msgs[0], issuer_idx);
+    let sig_2 = sign(&mut rng, &msgs[1], issuer_idx);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &spec,
+        witnesses([(&sig_1, issuer_idx), (&sig_2, issuer_idx)]),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    let randomized_pk = proof.as_bbs_plus_hidden_pk(0).unwrap().randomized_pk;
+    assert_eq!(
+        randomized_pk,
+        proof.as_bbs_plus_hidden_pk(1).unwrap().randomized_pk
+    );
+    for pk in &public_keys {
+        assert_ne!(randomized_pk, pk.0);
+    }
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Signatures by different issuers can't be proven to be by the same issuer
+    let other_sig = sign(&mut rng, &msgs[1], 0);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &spec,
+            witnesses([(&sig_1, issuer_idx), (&other_sig, 0)]),
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::UnsatisfiedIssuerConsistency(0, 1))
+    ));
+
+    // A proof without the issuer consistency has unrelated randomized public keys so it doesn't satisfy the spec
+    // requiring it, even when the signatures are by the same issuer
+    let spec_without = proof_spec(MetaStatements::new());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &spec_without,
+        witnesses([(&sig_1, issuer_idx), (&sig_2, issuer_idx)]),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &spec_without, nonce.clone(), Default::default())
+        .unwrap();
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(&mut rng, &spec, nonce.clone(), Default::default()),
+        Err(ProofSystemError::InvalidIssuerConsistencyProof(0, 1))
+    ));
+
+    // Invalid issuer consistencies
+    assert!(matches!(
+        proof_spec(same_issuer(vec![0])).validate(),
+        Err(ProofSystemError::InvalidIssuerConsistency(0))
+    ));
+    assert!(matches!(
+        proof_spec(same_issuer(vec![0, 2])).validate(),
+        Err(ProofSystemError::InvalidIssuerConsistency(0))
+    ));
+    let mut meta_statements = same_issuer(vec![0, 1]);
+    meta_statements.add_issuer_consistency(SameIssuer([1, 0].into_iter().collect()));
+    assert!(matches!(
+        proof_spec(meta_statements).validate(),
+        Err(ProofSystemError::InvalidIssuerConsistency(1))
+    ));
+    let spec_different_pks = ProofSpec::<Bls12_381, G1Affine>::new(
+        statements([public_keys.clone(), public_keys[1..].to_vec()]),
+        same_issuer(vec![0, 1]),
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        spec_different_pks.validate(),
+        Err(ProofSystemError::InvalidIssuerConsistency(0))
+    ));
+    let mut statements = statements([public_keys.clone(), public_keys.clone()]);
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        public_keys[0].clone(),
+        BTreeMap::<usize, Fr>::new(),
+    ));
+    let spec_known_pk =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements, same_issuer(vec![0, 2]), vec![], None);
+    assert!(matches!(
+        spec_known_pk.validate(),
+        Err(ProofSystemError::InvalidIssuerConsistency(0))
+    ));
+}
@@ -0,0 +1,130 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams;
+
+use proof_system::{
+    error::ProofSystemError,
+    predicates::bitmask::{BitConstraint, BitmaskPredicate},
+    prelude::{MetaStatements, ProofSpec, Witness, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bound_check_bpp::BoundCheckBpp as BoundCheckStmt, Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_bits_of_signed_mask() {
+    // Prove that a signed permission bitmask has certain bits set without revealing the mask
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 4;
+    let mask_idx = 1;
+    let bit_count = 8;
+    // Bits 1, 3 and 5 are set
+    let mask = 0b0010_1010u64;
+
+    let mut msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs[mask_idx] = Fr::from(mask);
+    let (sig_params, keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let mut check = |constraint: BitConstraint| -> bool {
+        let predicate = BitmaskPredicate::<G1Affine>::new_with_label::<Blake2b512>(
+            (0, mask_idx),
+            bit_count,
+            constraint,
+            b"test",
+        )
+        .unwrap();
+
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let mut proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        let indices = predicate
+            .add_to_proof_spec(&mut proof_spec, |min, max| {
+                BoundCheckStmt::new_statement_from_params(min, max, bpp_setup_params.clone())
+            })
+            .unwrap();
+        assert_eq!(indices.decomposition, 1);
+        assert_eq!(indices.bit_checks.len(), predicate.decomposed_bits().len());
+        proof_spec.validate().unwrap();
+        test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        predicate.add_witnesses(&mut witnesses, mask, Witness::BoundCheckBpp);
+
+        let nonce = Some(b"test-nonce".to_vec());
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )
+        .and_then(|(proof, _)| {
+            proof.verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        })
+        .is_ok()
+    };
+
+    // Single required bit
+    assert!(check(BitConstraint::AllSet(BTreeSet::from([3]))));
+    // Multiple required bits
+    assert!(check(BitConstraint::AllSet(BTreeSet::from([3, 5]))));
+    assert!(check(BitConstraint::AllSet(BTreeSet::from([1, 3, 5]))));
+    // Bit 4 isn't set
+    assert!(!check(BitConstraint::AllSet(BTreeSet::from([4]))));
+    assert!(!check(BitConstraint::AllSet(BTreeSet::from([3, 4]))));
+
+    // At least one of the bits is set
+    assert!(check(BitConstraint::AnySet(BTreeSet::from([0, 3]))));
+    assert!(check(BitConstraint::AnySet(BTreeSet::from([1, 3, 5]))));
+    // None of the bits is set
+    assert!(!check(BitConstraint::AnySet(BTreeSet::from([0, 2, 7]))));
+
+    // Invalid predicates
+    let g = G1Affine::rand(&mut rng);
+    assert!(matches!(
+        BitmaskPredicate::new(
+            (0, mask_idx),
+            8,
+            BitConstraint::AllSet(BTreeSet::from([8])),
+            g
+        ),
+        Err(ProofSystemError::InvalidBitmaskPredicate)
+    ));
+    assert!(matches!(
+        BitmaskPredicate::new((0, mask_idx), 8, BitConstraint::AnySet(BTreeSet::new()), g),
+        Err(ProofSystemError::InvalidBitmaskPredicate)
+    ));
+    assert!(matches!(
+        BitmaskPredicate::new(
+            (0, mask_idx),
+            65,
+            BitConstraint::AllSet(BTreeSet::from([3])),
+            g
+        ),
+        Err(ProofSystemError::InvalidBitmaskPredicate)
+    ));
+}
@@ -18,7 +18,7 @@ use proof_system::{
     witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
 };
 
-use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+use test_utils::{bbs::*, test_proof_serialized_size, test_serialization, Fr, ProofG1};
 
 #[test]
 fn pok_of_bbs_plus_sig_and_bounded_message_using_bulletproofs_plus_plus() {
@@ -99,7 +99,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_bulletproofs_plus_plus() {
         let start = Instant::now();
         let proof = ProofG1::new::<StdRng, Blake2b512>(
             rng,
-            proof_spec_prover,
+            &proof_spec_prover,
             witnesses.clone(),
             None,
             Default::default(),
@@ -114,6 +114,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_bulletproofs_plus_plus() {
 
         if valid_proof {
             test_serialization!(ProofG1, proof);
+            test_proof_serialized_size!(proof);
         }
 
         let mut verifier_statements = Statements::new();
@@ -136,7 +137,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_bulletproofs_plus_plus() {
 
         let start = Instant::now();
         let res =
-            proof.verify::<StdRng, Blake2b512>(rng, proof_spec_verifier, None, Default::default());
+            proof.verify::<StdRng, Blake2b512>(rng, &proof_spec_verifier, None, Default::default());
         assert_eq!(res.is_ok(), valid_proof);
         println!(
             "Time taken to verify proof of Bulletproofs++ bound check of 1 message in signature over {} messages {:?}",
@@ -10,8 +10,8 @@ use std::time::Instant;
 
 use proof_system::{
     prelude::{
-        EqualWitnesses, MetaStatements, ProofSpec, ProverConfig, VerifierConfig, Witness,
-        WitnessRef, Witnesses,
+        EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError, ProverConfig, VerifierConfig,
+        Witness, WitnessRef, Witnesses,
     },
     prover::OldLegoGroth16Proof,
     setup_params::SetupParams,
@@ -22,15 +22,17 @@ use proof_system::{
             BoundCheckLegoGroth16Prover as BoundCheckProverStmt,
             BoundCheckLegoGroth16Verifier as BoundCheckVerifierStmt,
         },
-        Statements,
+        Statement, Statements,
+    },
+    sub_protocols::bound_check_legogroth16::{
+        generate_snark_srs_bound_check, BoundCheckLegoGrothProtocol,
     },
-    sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check,
     witness::{
         PoKBBSSignature23G1 as PoKSignatureBBS23G1Wit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
     },
 };
 
-use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+use test_utils::{bbs::*, test_proof_serialized_size, test_serialization, Fr, ProofG1};
 
 macro_rules! gen_tests {
     ($test1_name: ident, $test2_name: ident, $setup_fn_name: ident, $stmt: ident, $wit: ident) => {
@@ -100,7 +102,7 @@ macro_rules! gen_tests {
             let start = Instant::now();
             let (proof, comm_rand) = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_prover.clone(),
+                &proof_spec_prover,
                 witnesses.clone(),
                 None,
                 Default::default(),
@@ -113,6 +115,7 @@ macro_rules! gen_tests {
             );
 
             test_serialization!(ProofG1, proof);
+            test_proof_serialized_size!(proof);
 
             let mut verifier_statements = Statements::new();
             verifier_statements.add($stmt::new_statement_from_params(
@@ -140,7 +143,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     Default::default(),
                 )
@@ -154,10 +157,11 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -172,10 +176,13 @@ macro_rules! gen_tests {
             let config = ProverConfig::<Bls12_381> {
                 reuse_saver_proofs: None,
                 reuse_legogroth16_proofs: Some(m),
+                coalesce_pedersen_commitments: false,
+                send_fully_revealed_bbs_plus_signatures: false,
+                thread_pool: Default::default(),
             };
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_prover.clone(),
+                &proof_spec_prover,
                 witnesses.clone(),
                 None,
                 config,
@@ -191,7 +198,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     Default::default(),
                 )
@@ -199,10 +206,11 @@ macro_rules! gen_tests {
             proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -224,7 +232,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_prover,
+                &proof_spec_prover,
                 witnesses.clone(),
                 None,
                 Default::default(),
@@ -243,7 +251,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec_verifier.clone(),
+                    &proof_spec_verifier,
                     None,
                     Default::default()
                 )
@@ -251,10 +259,11 @@ macro_rules! gen_tests {
             assert!(proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec_verifier,
+                    &proof_spec_verifier,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -273,7 +282,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                proof_spec_prover,
+                &proof_spec_prover,
                 witnesses_wrong,
                 None,
                 Default::default(),
@@ -286,7 +295,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec_verifier.clone(),
+                    &proof_spec_verifier,
                     None,
                     Default::default()
                 )
@@ -294,10 +303,11 @@ macro_rules! gen_tests {
             assert!(proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    proof_spec_verifier,
+                    &proof_spec_verifier,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -408,7 +418,7 @@ macro_rules! gen_tests {
                 let start = Instant::now();
                 let (proof, comm_rand) = ProofG1::new::<StdRng, Blake2b512>(
                     &mut rng,
-                    prover_proof_spec.clone(),
+                    &prover_proof_spec,
                     witnesses.clone(),
                     None,
                     Default::default(),
@@ -484,7 +494,7 @@ macro_rules! gen_tests {
                     .clone()
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         Default::default(),
                     )
@@ -499,10 +509,11 @@ macro_rules! gen_tests {
                     .clone()
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         VerifierConfig {
                             use_lazy_randomized_pairing_checks: Some(false),
+                            ..Default::default()
                         },
                     )
                     .unwrap();
@@ -524,10 +535,13 @@ macro_rules! gen_tests {
                 let config = ProverConfig::<Bls12_381> {
                     reuse_saver_proofs: None,
                     reuse_legogroth16_proofs: Some(m),
+                    coalesce_pedersen_commitments: false,
+                    send_fully_revealed_bbs_plus_signatures: false,
+                    thread_pool: Default::default(),
                 };
                 let proof = ProofG1::new::<StdRng, Blake2b512>(
                     &mut rng,
-                    prover_proof_spec.clone(),
+                    &prover_proof_spec,
                     witnesses.clone(),
                     None,
                     config,
@@ -542,7 +556,7 @@ macro_rules! gen_tests {
                 proof
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         Default::default(),
                     )
@@ -630,7 +644,7 @@ fn pok_of_bbs_plus_sig_and_message_same_as_bound() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -660,6 +674,204 @@ fn pok_of_bbs_plus_sig_and_message_same_as_bound() {
     );
     proof_spec_verifier.validate().unwrap();
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn verify_using_proof_spec_for_verifier() {
+    // Verifier creates its proof spec from the prover's proof spec rather than building it independently
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let max = 200;
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    // Bounds of 2 messages are checked, one with the proving key given in the statement and the other with
+    // it given in the setup params
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements
+        .add(BoundCheckProverStmt::new_statement_from_params(min, max, snark_pk.clone()).unwrap());
+    prover_statements
+        .add(BoundCheckProverStmt::new_statement_from_params_ref(min, max, 0).unwrap());
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements,
+        meta_statements,
+        vec![SetupParams::LegoSnarkProvingKey(snark_pk.clone())],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[1]));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[3]));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let proof_spec_verifier = proof_spec_prover.for_verifier().unwrap();
+    proof_spec_verifier.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec_verifier);
+
+    // Prover-only statement and the proving key are gone
+    assert_eq!(
+        proof_spec_verifier.statements.0[0],
+        proof_spec_prover.statements.0[0]
+    );
+    assert!(matches!(
+        &proof_spec_verifier.statements.0[1],
+        Statement::BoundCheckLegoGroth16Verifier(s) if s.snark_verifying_key.as_ref() == Some(&snark_pk.vk)
+    ));
+    assert!(matches!(
+        &proof_spec_verifier.statements.0[2],
+        Statement::BoundCheckLegoGroth16Verifier(s) if s.snark_verifying_key_ref == Some(0)
+    ));
+    assert_eq!(
+        proof_spec_verifier.setup_params,
+        vec![SetupParams::LegoSnarkVerifyingKey(snark_pk.vk.clone())]
+    );
+    assert!(proof_spec_verifier.compressed_size() < proof_spec_prover.compressed_size() / 2);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier, None, Default::default())
+        .unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_verifier,
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn empty_and_single_element_range() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let msg_count = 5;
+    let msg_idx = 1;
+    let mut msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    msgs[msg_idx] = Fr::from(min);
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    // Empty range is rejected by the statements and the protocol
+    assert!(matches!(
+        BoundCheckProverStmt::new_statement_from_params::<G1Affine>(min, min, snark_pk.clone()),
+        Err(ProofSystemError::EmptyRange { min: 100, max: 100 })
+    ));
+    assert!(matches!(
+        BoundCheckVerifierStmt::new_statement_from_params::<G1Affine>(
+            min,
+            min,
+            snark_pk.vk.clone()
+        ),
+        Err(ProofSystemError::EmptyRange { min: 100, max: 100 })
+    ));
+    let mut protocol = BoundCheckLegoGrothProtocol::new_for_prover(0, min, min, &snark_pk);
+    assert!(matches!(
+        protocol.init(&mut rng, &[], msgs[msg_idx], None),
+        Err(ProofSystemError::EmptyRange { min: 100, max: 100 })
+    ));
+
+    // Range with a single element, the message itself, is valid
+    let max = min + 1;
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements
+        .add(BoundCheckProverStmt::new_statement_from_params(min, max, snark_pk.clone()).unwrap());
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[msg_idx]));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        BoundCheckVerifierStmt::new_statement_from_params(min, max, snark_pk.vk.clone()).unwrap(),
+    );
+    let proof_spec_verifier = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    proof_spec_verifier.validate().unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier, None, Default::default())
         .unwrap();
 }
@@ -1,15 +1,24 @@
+use ark_bls12_377::Bls12_377;
 use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::rand::{prelude::StdRng, SeedableRng};
+use ark_std::{
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
 use bbs_plus::prelude::{KeypairG2, SignatureG1, SignatureParamsG1};
 use blake2::Blake2b512;
 use std::collections::{BTreeMap, BTreeSet};
 
 use proof_system::prelude::{
-    BoundCheckSmcInnerProof, EqualWitnesses, MetaStatements, ProofSpec, StatementProof, Statements,
-    Witness, WitnessRef, Witnesses,
+    BoundCheckSmcInnerProof, EqualWitnesses, MetaStatement, MetaStatements, ProofSpec,
+    ProofSystemError, RevealedIndicesDiff, StatementProof, Statements, Witness, WitnessRef,
+    Witnesses,
+};
+use test_utils::{
+    bbs::bbs_plus_sig_setup_given_messages_generic, test_proof_serialized_size, test_serialization,
+    Fr, ProofG1, ProofG1Of,
 };
-use test_utils::{test_serialization, Fr, ProofG1};
 
 use proof_system::{
     prelude::bound_check_smc::SmcParamsAndCommitmentKey,
@@ -17,7 +26,7 @@ use proof_system::{
         bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
         bound_check_smc::BoundCheckSmc as BoundCheckStmt,
     },
-    sub_protocols::should_use_cls,
+    sub_protocols::{bound_check_smc::BoundCheckSmcProtocol, should_use_cls, SubProtocolType},
     witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
 };
 
@@ -96,7 +105,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
 
         let proof = ProofG1::new::<StdRng, Blake2b512>(
             rng,
-            proof_spec_prover,
+            &proof_spec_prover,
             witnesses.clone(),
             None,
             Default::default(),
@@ -106,6 +115,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
 
         if valid_proof {
             test_serialization!(ProofG1, proof);
+            test_proof_serialized_size!(proof);
         }
 
         if is_cls {
@@ -155,7 +165,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
         proof_spec_verifier.validate().unwrap();
 
         let res =
-            proof.verify::<StdRng, Blake2b512>(rng, proof_spec_verifier, None, Default::default());
+            proof.verify::<StdRng, Blake2b512>(rng, &proof_spec_verifier, None, Default::default());
         assert_eq!(res.is_ok(), valid_proof);
     }
 
@@ -251,3 +261,334 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
         is_cls,
     );
 }
+
+#[test]
+fn decompose_value_for_set_membership_check_range_proof() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let (smc_setup_params, _) =
+        SmcParamsAndCommitmentKey::<Bls12_381>::new::<_, Blake2b512>(&mut rng, b"test", 16);
+    let base = smc_setup_params.params.get_supported_base_for_range_proof() as u64;
+
+    // CCS protocol decomposes `value - min` in base `base`
+    let (min, max) = (100, 100 + (1 << 30));
+    assert!(!should_use_cls(min, max));
+    for value in [min, min + 1, min + 12345678, max - 1] {
+        let digits =
+            BoundCheckSmcProtocol::decompose_value(value, min, max, &smc_setup_params).unwrap();
+        assert!(digits.iter().all(|d| (*d as u64) < base));
+        let recomposed = digits
+            .iter()
+            .rev()
+            .fold(0u64, |acc, d| acc * base + *d as u64);
+        assert_eq!(recomposed, value - min);
+    }
+
+    // CLS protocol
+    let (min, max) = (18, 65);
+    assert!(should_use_cls(min, max));
+    for value in min..max {
+        let digits =
+            BoundCheckSmcProtocol::decompose_value(value, min, max, &smc_setup_params).unwrap();
+        assert!(!digits.is_empty());
+        assert!(digits.iter().all(|d| (*d as u64) < base));
+    }
+
+    assert!(BoundCheckSmcProtocol::decompose_value(min - 1, min, max, &smc_setup_params).is_err());
+    assert!(BoundCheckSmcProtocol::decompose_value(max, min, max, &smc_setup_params).is_err());
+    assert!(BoundCheckSmcProtocol::decompose_value(min, max, min, &smc_setup_params).is_err());
+}
+
+/// Prove that a signed message is within bounds using both CLS and CCS protocols over the given curve
+#[test]
+fn diff_of_proof_specs_with_added_bound_check() {
+    // The new policy reveals one more message and requires another message to be in a range
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let sig_keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params);
+    let msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let (smc_setup_params, _) =
+        SmcParamsAndCommitmentKey::new::<_, Blake2b512>(&mut rng, b"test", 2);
+
+    let mut old_statements = Statements::<Bls12_381, G1Affine>::new();
+    old_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::from([(0, msgs[0]), (1, msgs[1])]),
+    ));
+    let old_spec = ProofSpec::new(old_statements, MetaStatements::new(), vec![], None);
+
+    let mut new_statements = Statements::<Bls12_381, G1Affine>::new();
+    new_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::from([(0, msgs[0]), (1, msgs[1]), (4, msgs[4])]),
+    ));
+    new_statements
+        .add(BoundCheckStmt::new_statement_from_params(10, 100, smc_setup_params.clone()).unwrap());
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let new_spec = ProofSpec::new(new_statements, meta_statements.clone(), vec![], None);
+    new_spec.validate().unwrap();
+
+    assert!(old_spec.diff(&old_spec).is_empty());
+    assert!(new_spec.diff(&new_spec.clone()).is_empty());
+
+    let diff = old_spec.diff(&new_spec);
+    assert!(!diff.is_empty());
+    assert_eq!(
+        diff.added_statements,
+        vec![(1, SubProtocolType::BoundCheckSmc)]
+    );
+    assert!(diff.removed_statements.is_empty());
+    assert_eq!(diff.changed_statements, vec![0]);
+    assert_eq!(
+        diff.revealed_indices,
+        vec![RevealedIndicesDiff {
+            statement: 0,
+            added: BTreeSet::from([4]),
+            removed: BTreeSet::new(),
+        }]
+    );
+    assert_eq!(diff.added_meta_statements, meta_statements.0);
+    assert!(diff.removed_meta_statements.is_empty());
+    assert!(diff.added_setup_params.is_empty());
+    assert!(diff.removed_setup_params.is_empty());
+    assert!(diff.changed_setup_params.is_empty());
+
+    // Going back to the old policy reverses the diff
+    let diff = new_spec.diff(&old_spec);
+    assert!(diff.added_statements.is_empty());
+    assert_eq!(
+        diff.removed_statements,
+        vec![(1, SubProtocolType::BoundCheckSmc)]
+    );
+    assert_eq!(
+        diff.revealed_indices,
+        vec![RevealedIndicesDiff {
+            statement: 0,
+            added: BTreeSet::new(),
+            removed: BTreeSet::from([4]),
+        }]
+    );
+    assert!(matches!(
+        diff.removed_meta_statements.as_slice(),
+        [MetaStatement::WitnessEquality(_)]
+    ));
+}
+
+fn pok_of_bbs_plus_sig_and_bounded_message_over_curve<E: Pairing>() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let msg_idx = 1;
+
+    let mut msgs = (0..msg_count)
+        .map(|_| E::ScalarField::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs[msg_idx] = E::ScalarField::from(1000u64);
+    let (sig_params, sig_keypair, sig) =
+        bbs_plus_sig_setup_given_messages_generic::<E, _>(&mut rng, &msgs);
+
+    let (smc_setup_params, _) =
+        SmcParamsAndCommitmentKey::<E>::new::<_, Blake2b512>(&mut rng, b"test", 2);
+    smc_setup_params.verify().unwrap();
+
+    // Small range uses the CLS protocol and the large one uses CCS
+    for (min, max, is_cls) in [(500, 2000, true), (100, 100 + (1 << 30), false)] {
+        assert_eq!(should_use_cls(min, max), is_cls);
+        let mut statements = Statements::<E, E::G1Affine>::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            BoundCheckStmt::new_statement_from_params(min, max, smc_setup_params.clone()).unwrap(),
+        );
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+        test_serialization!(ProofSpec<E, E::G1Affine>, proof_spec);
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::BoundCheckSmc(msgs[msg_idx]));
+
+        let proof = ProofG1Of::<E>::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        test_serialization!(ProofG1Of<E>, proof);
+        match &proof.statement_proofs[1] {
+            StatementProof::BoundCheckSmc(p) => match &p.proof {
+                BoundCheckSmcInnerProof::CLS(_) => assert!(is_cls),
+                BoundCheckSmcInnerProof::CCS(_) => assert!(!is_cls),
+            },
+            _ => panic!("expected a set membership check based range proof"),
+        }
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+            .unwrap();
+    }
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_bounded_message_over_bls12_381() {
+    pok_of_bbs_plus_sig_and_bounded_message_over_curve::<Bls12_381>()
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_bounded_message_over_bls12_377() {
+    pok_of_bbs_plus_sig_and_bounded_message_over_curve::<Bls12_377>()
+}
+
+#[test]
+fn empty_and_single_element_range_for_set_membership_check_range_proof() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let msg_idx = 1;
+    let min = 1000;
+
+    let mut msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs[msg_idx] = Fr::from(min);
+    let (sig_params, sig_keypair, sig) =
+        bbs_plus_sig_setup_given_messages_generic::<Bls12_381, _>(&mut rng, &msgs);
+
+    let (smc_setup_params, _) =
+        SmcParamsAndCommitmentKey::<Bls12_381>::new::<_, Blake2b512>(&mut rng, b"test", 2);
+
+    // Empty range is rejected by both the statement and the protocol
+    assert!(matches!(
+        BoundCheckStmt::new_statement_from_params::<G1Affine>(min, min, smc_setup_params.clone()),
+        Err(ProofSystemError::EmptyRange {
+            min: 1000,
+            max: 1000
+        })
+    ));
+    let mut protocol = BoundCheckSmcProtocol::new(0, min, min, &smc_setup_params);
+    assert!(matches!(
+        protocol.init(&mut rng, &[], msgs[msg_idx], None),
+        Err(ProofSystemError::EmptyRange {
+            min: 1000,
+            max: 1000
+        })
+    ));
+
+    // Range with a single element, the message itself, is valid
+    let max = min + 1;
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(
+        BoundCheckStmt::new_statement_from_params(min, max, smc_setup_params.clone()).unwrap(),
+    );
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BoundCheckSmc(msgs[msg_idx]));
+
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn structure_of_set_membership_check_range_proof() {
+    // A proof with the CCS range proof where the bounds need the CLS range proof is rejected before verification
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let min = 1000;
+    let msg = Fr::from(min + 10);
+
+    let (smc_setup_params, _) =
+        SmcParamsAndCommitmentKey::<Bls12_381>::new::<_, Blake2b512>(&mut rng, b"test", 2);
+
+    let mut create = |max: u64| {
+        let mut statements = Statements::<Bls12_381, G1Affine>::new();
+        statements.add(
+            BoundCheckStmt::new_statement_from_params(min, max, smc_setup_params.clone()).unwrap(),
+        );
+        let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        proof_spec.validate().unwrap();
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Witness::BoundCheckSmc(msg));
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof.validate_structure(&proof_spec).unwrap();
+        (proof_spec, proof)
+    };
+
+    let cls_max = min + 100;
+    let ccs_max = min + (1 << 25);
+    assert!(should_use_cls(min, cls_max));
+    assert!(!should_use_cls(min, ccs_max));
+    let (cls_proof_spec, mut cls_proof) = create(cls_max);
+    let (_, ccs_proof) = create(ccs_max);
+
+    let ccs_inner = match &ccs_proof.statement_proofs[0] {
+        StatementProof::BoundCheckSmc(p) => p.proof.clone(),
+        _ => panic!("expected a set-membership check range proof"),
+    };
+    match &mut cls_proof.statement_proofs[0] {
+        StatementProof::BoundCheckSmc(p) => p.proof = ccs_inner,
+        _ => panic!("expected a set-membership check range proof"),
+    }
+    assert!(matches!(
+        cls_proof.validate_structure(&cls_proof_spec),
+        Err(ProofSystemError::MalformedStatementProof { statement: 0, .. })
+    ));
+    assert!(cls_proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &cls_proof_spec, None, Default::default())
+        .is_err());
+}
@@ -1,15 +1,18 @@
 use ark_bls12_381::{Bls12_381, G1Affine};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::rand::{prelude::StdRng, SeedableRng};
+use ark_std::{
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
 use bbs_plus::prelude::{KeypairG2, SignatureG1, SignatureParamsG1};
 use blake2::Blake2b512;
 use std::collections::{BTreeMap, BTreeSet};
 
 use proof_system::prelude::{
-    BoundCheckSmcWithKVInnerProof, EqualWitnesses, MetaStatements, ProofSpec, StatementProof,
+    BoundCheckSmcWithKVInnerProof, EqualWitnesses, MetaStatements, ProofSpec, ProofSystemError,
     Statements, Witness, WitnessRef, Witnesses,
 };
-use test_utils::{test_serialization, Fr, ProofG1};
+use test_utils::{test_proof_serialized_size, test_serialization, Fr, ProofG1};
 
 use proof_system::{
     prelude::{
@@ -109,7 +112,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
 
         let proof = ProofG1::new::<StdRng, Blake2b512>(
             rng,
-            proof_spec_prover,
+            &proof_spec_prover,
             witnesses.clone(),
             None,
             Default::default(),
@@ -119,35 +122,30 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
 
         if valid_proof {
             test_serialization!(ProofG1, proof);
+            test_proof_serialized_size!(proof);
         }
 
-        if is_cls {
-            match &proof.statement_proofs[1] {
-                StatementProof::BoundCheckSmcWithKV(p) => match &p.proof {
-                    BoundCheckSmcWithKVInnerProof::CCS(_) => {
-                        assert!(false, "expected CLS proof but found CCS")
-                    }
-                    BoundCheckSmcWithKVInnerProof::CLS(_) => assert!(true),
-                },
-                _ => assert!(
-                    false,
-                    "this shouldn't happen as this test is checking set membership based proof"
-                ),
+        match &proof.as_bound_check_smc_kv(1).unwrap().proof {
+            BoundCheckSmcWithKVInnerProof::CLS(_) => {
+                assert!(is_cls, "expected CCS proof but found CLS")
             }
-        } else {
-            match &proof.statement_proofs[1] {
-                StatementProof::BoundCheckSmcWithKV(p) => match &p.proof {
-                    BoundCheckSmcWithKVInnerProof::CLS(_) => {
-                        assert!(false, "expected CCS proof but found CLS")
-                    }
-                    BoundCheckSmcWithKVInnerProof::CCS(_) => assert!(true),
-                },
-                _ => assert!(
-                    false,
-                    "this shouldn't happen as this test is checking set membership based proof"
-                ),
+            BoundCheckSmcWithKVInnerProof::CCS(_) => {
+                assert!(!is_cls, "expected CLS proof but found CCS")
             }
         }
+        // Statement proof at index 0 is for the BBS+ signature
+        assert!(matches!(
+            proof.as_bound_check_smc_kv(0),
+            Err(ProofSystemError::UnexpectedStatementProof {
+                index: 0,
+                expected: "BoundCheckSmcWithKV",
+                found: "PoKBBSSignatureG1",
+            })
+        ));
+        assert!(matches!(
+            proof.as_bound_check_smc_kv(2),
+            Err(ProofSystemError::InvalidStatementProofIndex(2))
+        ));
 
         let mut verifier_statements = Statements::new();
         verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
@@ -172,8 +170,62 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
         );
         proof_spec_verifier.validate().unwrap();
 
+        let res = proof.clone().verify::<StdRng, Blake2b512>(
+            rng,
+            &proof_spec_verifier,
+            None,
+            Default::default(),
+        );
+        assert_eq!(res.is_ok(), valid_proof);
+
+        // Verifying with prepared params gives the same result
+        let prepared_params = smc_setup_params_with_sk.precompute();
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(
+            BoundCheckVerifierStmt::new_statement_from_prepared_params(min, max, prepared_params)
+                .unwrap(),
+        );
+        if valid_proof {
+            test_serialization!(Statements<Bls12_381, G1Affine>, verifier_statements);
+        }
+
+        // The prepared params are not serialized so the deserialized statements verify with the unprepared params
+        let mut verifier_statements_bytes = vec![];
+        verifier_statements
+            .serialize_compressed(&mut verifier_statements_bytes)
+            .unwrap();
+        let deserialized_verifier_statements =
+            Statements::<Bls12_381, G1Affine>::deserialize_compressed(
+                &verifier_statements_bytes[..],
+            )
+            .unwrap();
+        let deserialized_proof_spec_verifier = ProofSpec::new(
+            deserialized_verifier_statements,
+            meta_statements.clone(),
+            vec![],
+            None,
+        );
+        deserialized_proof_spec_verifier.validate().unwrap();
+
+        let proof_spec_verifier =
+            ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+        proof_spec_verifier.validate().unwrap();
+
+        let res = proof.clone().verify::<StdRng, Blake2b512>(
+            rng,
+            &deserialized_proof_spec_verifier,
+            None,
+            Default::default(),
+        );
+        assert_eq!(res.is_ok(), valid_proof);
+
         let res =
-            proof.verify::<StdRng, Blake2b512>(rng, proof_spec_verifier, None, Default::default());
+            proof.verify::<StdRng, Blake2b512>(rng, &proof_spec_verifier, None, Default::default());
         assert_eq!(res.is_ok(), valid_proof);
     }
 
@@ -273,3 +325,189 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
         is_cls,
     );
 }
+
+#[test]
+fn bound_check_with_keyed_verification_for_u32_range() {
+    // Prove that a small attribute like age satisfies bounds given as `u32` and check that the proof and the
+    // verifier's precomputed params are smaller than for a `u64` range
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let msg_count = 5;
+    let age_idx = 1;
+
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let sig_keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params);
+    let mut msgs = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs[age_idx] = Fr::from(30u64);
+    let sig = SignatureG1::<Bls12_381>::new(&mut rng, &msgs, &sig_keypair.secret_key, &sig_params)
+        .unwrap();
+
+    // With base 2 the window tables for u32 and u64 bounds have the same window size and thus the same size
+    let (smc_setup_params, sk) =
+        SmcParamsAndCommitmentKey::new::<_, Blake2b512>(&mut rng, b"test", 4);
+    let smc_setup_params_with_sk = SmcParamsAndCommitmentKeyAndSecretKey {
+        params_and_comm_key: smc_setup_params.clone(),
+        sk,
+    };
+    assert!(
+        smc_setup_params_with_sk
+            .precompute_for_u32_range()
+            .compressed_size()
+            < smc_setup_params_with_sk.precompute().compressed_size()
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, age_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let sig_stmt = || {
+        PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        )
+    };
+    let mut prove = |bound_check_stmt, bound_check_wit| {
+        let mut statements = Statements::new();
+        statements.add(sig_stmt());
+        statements.add(bound_check_stmt);
+        let proof_spec = ProofSpec::new(statements, meta_statements.clone(), vec![], None);
+        proof_spec.validate().unwrap();
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(bound_check_wit);
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .map(|(proof, _)| proof)
+    };
+
+    let proof = prove(
+        BoundCheckProverStmt::new_u32_range(0, 150, smc_setup_params.clone()).unwrap(),
+        Witness::BoundCheckSmcWithKVU32(msgs[age_idx]),
+    )
+    .unwrap();
+    test_serialization!(ProofG1, proof);
+    let proof_for_u64_range = prove(
+        BoundCheckProverStmt::new_statement_from_params(0, 1 << 40, smc_setup_params.clone())
+            .unwrap(),
+        Witness::BoundCheckSmcWithKV(msgs[age_idx]),
+    )
+    .unwrap();
+    assert!(proof.compressed_size() < proof_for_u64_range.compressed_size());
+
+    // A message not fitting in a u32 is rejected when the witness is for a u32 range
+    assert!(matches!(
+        prove(
+            BoundCheckProverStmt::new_u32_range(0, 150, smc_setup_params.clone()).unwrap(),
+            Witness::BoundCheckSmcWithKVU32(Fr::from(u32::MAX as u64 + 1)),
+        ),
+        Err(ProofSystemError::UnsupportedValue(_))
+    ));
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(sig_stmt());
+    verifier_statements.add(
+        BoundCheckVerifierStmt::new_u32_range(0, 150, smc_setup_params_with_sk.clone()).unwrap(),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, verifier_statements);
+    let proof_spec_verifier =
+        ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None);
+    proof_spec_verifier.validate().unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier, None, Default::default())
+        .unwrap();
+
+    // Doesn't verify for a different range
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(sig_stmt());
+    verifier_statements
+        .add(BoundCheckVerifierStmt::new_u32_range(50, 150, smc_setup_params_with_sk).unwrap());
+    let proof_spec_verifier = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier, None, Default::default())
+        .is_err());
+}
+
+#[test]
+fn bases_of_params_for_set_membership_check_with_keyed_verification() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let (min, max) = (10, 12);
+    let msg = Fr::from(11u64);
+
+    // Params with just enough bases, the width of the range is the base
+    let (smc_setup_params, sk) =
+        SmcParamsAndCommitmentKey::<Bls12_381>::new::<_, Blake2b512>(&mut rng, b"test", 2);
+    let smc_setup_params_with_sk = SmcParamsAndCommitmentKeyAndSecretKey {
+        params_and_comm_key: smc_setup_params.clone(),
+        sk,
+    };
+    let mut prover_statements = Statements::<Bls12_381, G1Affine>::new();
+    prover_statements
+        .add(BoundCheckProverStmt::new_statement_from_params(min, max, smc_setup_params).unwrap());
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::BoundCheckSmcWithKV(msg));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::<Bls12_381, G1Affine>::new();
+    verifier_statements.add(
+        BoundCheckVerifierStmt::new_statement_from_params(min, max, smc_setup_params_with_sk)
+            .unwrap(),
+    );
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    verifier_proof_spec.validate().unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
+        .unwrap();
+
+    // Params with a single member can't be used to decompose the range into digits
+    let (small_setup_params, small_sk) =
+        SmcParamsAndCommitmentKey::<Bls12_381>::new::<_, Blake2b512>(&mut rng, b"test", 1);
+    assert!(matches!(
+        BoundCheckProverStmt::new_statement_from_params::<G1Affine>(
+            min,
+            max,
+            small_setup_params.clone()
+        ),
+        Err(ProofSystemError::InsufficientSmcBases {
+            required: 2,
+            available: 1
+        })
+    ));
+    assert!(matches!(
+        BoundCheckVerifierStmt::new_statement_from_params::<G1Affine>(
+            min,
+            max,
+            SmcParamsAndCommitmentKeyAndSecretKey {
+                params_and_comm_key: small_setup_params,
+                sk: small_sk,
+            }
+        ),
+        Err(ProofSystemError::InsufficientSmcBases {
+            required: 2,
+            available: 1
+        })
+    ));
+}
@@ -0,0 +1,135 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use schnorr_pok::inequality::CommitmentKey;
+
+use proof_system::{
+    error::ProofSystemError,
+    predicates::committed_reveal::{CommitmentOpening, CommittedReveal},
+    prelude::{MetaStatements, ProofSpec, Witnesses},
+    statement::{bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, Statements},
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_with_committed_message() {
+    // Prover reveals a commitment to a signed message instead of the message and later opens it
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let committed_msg_idx = 3;
+    let (msgs, sig_params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let comm_key = CommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+
+    // Prover commits to the message and sends the commitment to the verifier
+    let (prover_reveal, opening) = CommittedReveal::commit(
+        &mut rng,
+        (0, committed_msg_idx),
+        comm_key.clone(),
+        msgs[committed_msg_idx],
+    );
+    let verifier_reveal = CommittedReveal::new(
+        (0, committed_msg_idx),
+        comm_key.clone(),
+        prover_reveal.commitment,
+    );
+
+    let proof_spec = |reveal: &CommittedReveal<G1Affine>| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let mut proof_spec =
+            ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+        assert_eq!(reveal.add_to_proof_spec(&mut proof_spec), 1);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let prover_proof_spec = proof_spec(&prover_reveal);
+    let verifier_proof_spec = proof_spec(&verifier_reveal);
+    assert_eq!(prover_proof_spec, verifier_proof_spec);
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, verifier_proof_spec);
+
+    let witnesses = |opening: &CommitmentOpening<Fr>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        prover_reveal.add_witness(&mut witnesses, opening);
+        witnesses
+    };
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &prover_proof_spec,
+        witnesses(&opening),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &verifier_proof_spec,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // A commitment to a different message can't be proven to be for the signed message
+    let other_msg = Fr::rand(&mut rng);
+    let (other_reveal, other_opening) = CommittedReveal::commit(
+        &mut rng,
+        (0, committed_msg_idx),
+        comm_key.clone(),
+        other_msg,
+    );
+    let other_proof_spec = proof_spec(&other_reveal);
+    assert!(ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &other_proof_spec,
+        witnesses(&other_opening),
+        nonce.clone(),
+        Default::default(),
+    )
+    .and_then(|(proof, _)| {
+        proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &other_proof_spec,
+            nonce.clone(),
+            Default::default(),
+        )
+    })
+    .is_err());
+
+    // Verifier opens the commitment once the prover shares the opening
+    assert_eq!(
+        verifier_reveal.open(&opening).unwrap(),
+        msgs[committed_msg_idx]
+    );
+    let wrong_opening = CommitmentOpening {
+        message: msgs[committed_msg_idx],
+        blinding: Fr::rand(&mut rng),
+    };
+    assert!(matches!(
+        verifier_reveal.open(&wrong_opening),
+        Err(ProofSystemError::InvalidCommitmentOpening)
+    ));
+    assert!(matches!(
+        verifier_reveal.open(&other_opening),
+        Err(ProofSystemError::InvalidCommitmentOpening)
+    ));
+}
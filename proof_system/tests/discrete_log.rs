@@ -0,0 +1,114 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use proof_system::{
+    prelude::{EqualWitnesses, MetaStatement, MetaStatements, Witness, WitnessRef, Witnesses},
+    proof_spec::ProofSpec,
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, ped_comm::PedersenCommitment,
+        Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_discrete_log_of_signed_message() {
+    // Prove that a signed message `x` is the discrete log of a public `Y = g * x` without revealing `x`
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let msg_idx = 3;
+    let (msgs, sig_params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let base = G1Projective::rand(&mut rng).into_affine();
+    let value = (base * msgs[msg_idx]).into_affine();
+
+    let proof_spec = |value: G1Affine| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(PedersenCommitment::new_statement_for_discrete_log(base, value));
+
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add(MetaStatement::WitnessEquality(EqualWitnesses(
+            vec![(0, msg_idx), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        )));
+        let proof_spec =
+            ProofSpec::<Bls12_381, G1Affine>::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let witnesses = |x| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::PedersenCommitment(vec![x]));
+        witnesses
+    };
+
+    let proof_spec_correct = proof_spec(value);
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec_correct);
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_correct,
+        witnesses(msgs[msg_idx]),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_correct,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Proof doesn't verify for a different value
+    let wrong_value = (base * msgs[msg_idx - 1]).into_affine();
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec(wrong_value),
+            nonce.clone(),
+            Default::default()
+        )
+        .is_err());
+
+    // Proof created for a value whose discrete log the prover doesn't know doesn't verify
+    let proof_spec_wrong = proof_spec(wrong_value);
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_wrong,
+        witnesses(msgs[msg_idx]),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_wrong, nonce, Default::default())
+        .is_err());
+}
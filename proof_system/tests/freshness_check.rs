@@ -0,0 +1,160 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams;
+
+use proof_system::{
+    predicates::freshness_check::FreshnessCheck,
+    prelude::{MetaStatements, ProofSpec, Witness, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bound_check_bpp::BoundCheckBpp as BoundCheckStmt, Statements,
+    },
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_with_fresh_issuance_time() {
+    // Prove that the issuance time signed in a credential is within the last 24 hours of the time the
+    // verifier considers current
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let now = 1_700_000_000u64;
+    let window = 24 * 60 * 60;
+    let msg_count = 4;
+    let issued_at_idx = 2;
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    fn check(
+        rng: &mut StdRng,
+        now: u64,
+        window: u64,
+        issued_at: u64,
+        msg_count: usize,
+        issued_at_idx: usize,
+        bpp_setup_params: SetupParams<G1Affine>,
+    ) -> bool {
+        let mut msgs = (0..msg_count).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs[issued_at_idx] = Fr::from(issued_at);
+        let (sig_params, keypair, sig) = bbs_plus_sig_setup_given_messages(rng, &msgs);
+
+        // Verifier builds the proof spec with its current time and shares it with the prover
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params,
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let mut proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+        let freshness = FreshnessCheck::new((0, issued_at_idx), now, window).unwrap();
+        assert_eq!(freshness.bounds(), (now - window, now + 1));
+        let bound_check_idx = freshness
+            .add_to_proof_spec(&mut proof_spec, |min, max| {
+                BoundCheckStmt::new_statement_from_params(min, max, bpp_setup_params)
+            })
+            .unwrap();
+        assert_eq!(bound_check_idx, 1);
+        proof_spec.validate().unwrap();
+        test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig,
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(Witness::BoundCheckBpp(msgs[issued_at_idx]));
+
+        let nonce = Some(b"test-nonce".to_vec());
+        ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            &proof_spec,
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )
+        .and_then(|(proof, _)| {
+            proof.verify::<StdRng, Blake2b512>(rng, &proof_spec, nonce, Default::default())
+        })
+        .is_ok()
+    }
+
+    // Issued an hour ago
+    assert!(check(
+        &mut rng,
+        now,
+        window,
+        now - 60 * 60,
+        msg_count,
+        issued_at_idx,
+        bpp_setup_params.clone()
+    ));
+    // Issued at the edges of the window
+    assert!(check(
+        &mut rng,
+        now,
+        window,
+        now - window,
+        msg_count,
+        issued_at_idx,
+        bpp_setup_params.clone()
+    ));
+    assert!(check(
+        &mut rng,
+        now,
+        window,
+        now,
+        msg_count,
+        issued_at_idx,
+        bpp_setup_params.clone()
+    ));
+
+    // Issued 2 days ago
+    assert!(!check(
+        &mut rng,
+        now,
+        window,
+        now - 2 * window,
+        msg_count,
+        issued_at_idx,
+        bpp_setup_params.clone()
+    ));
+    // Just outside the window
+    assert!(!check(
+        &mut rng,
+        now,
+        window,
+        now - window - 1,
+        msg_count,
+        issued_at_idx,
+        bpp_setup_params.clone()
+    ));
+
+    // Dated in the future
+    assert!(!check(
+        &mut rng,
+        now,
+        window,
+        now + 60,
+        msg_count,
+        issued_at_idx,
+        bpp_setup_params
+    ));
+
+    // Window longer than the time elapsed since 0
+    assert_eq!(
+        FreshnessCheck::new((0, issued_at_idx), 100, window)
+            .unwrap()
+            .bounds(),
+        (0, 101)
+    );
+    assert!(FreshnessCheck::new((0, issued_at_idx), u64::MAX, window).is_err());
+}
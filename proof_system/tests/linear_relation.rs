@@ -0,0 +1,219 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeSet,
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{EqualWitnesses, MetaStatement, MetaStatements, Witness, WitnessRef, Witnesses},
+    proof_spec::ProofSpec,
+    statement::{
+        linear_relation::SchnorrLinearRelation,
+        ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements,
+    },
+};
+
+use test_utils::{test_serialization, Fr, ProofG1};
+
+#[test]
+fn linear_relation_among_committed_values() {
+    // Prove that the values `x` and `y` in a Pedersen commitment satisfy `2x + 3y = 17` without revealing them
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let proof_spec_and_witnesses = |x: u64, y: u64, rng: &mut StdRng| {
+        let opening = vec![Fr::from(x), Fr::from(y), Fr::rand(rng)];
+        let commitment = G1Projective::msm_unchecked(&comm_key, &opening).into_affine();
+
+        let mut statements = Statements::new();
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            comm_key.clone(),
+            commitment,
+        ));
+        statements.add(
+            SchnorrLinearRelation::new_statement_from_params(
+                vec![vec![Fr::from(2u64), Fr::from(3u64)]],
+                vec![Fr::from(17u64)],
+            )
+            .unwrap(),
+        );
+
+        // The witnesses of the relation are the committed `x` and `y`
+        let mut meta_statements = MetaStatements::new();
+        for i in 0..2 {
+            meta_statements.add(MetaStatement::WitnessEquality(EqualWitnesses(
+                vec![(0, i), (1, i)]
+                    .into_iter()
+                    .collect::<BTreeSet<WitnessRef>>(),
+            )));
+        }
+
+        let proof_spec =
+            ProofSpec::<Bls12_381, G1Affine>::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Witness::PedersenCommitment(opening.clone()));
+        witnesses.add(Witness::SchnorrLinearRelation(opening[..2].to_vec()));
+        (proof_spec, witnesses)
+    };
+
+    // 2*4 + 3*3 = 17
+    let (proof_spec, witnesses) = proof_spec_and_witnesses(4, 3, &mut rng);
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Proof doesn't verify for a different target
+    let mut other_spec = proof_spec.clone();
+    other_spec.statements.0[1] = SchnorrLinearRelation::new_statement_from_params(
+        vec![vec![Fr::from(2u64), Fr::from(3u64)]],
+        vec![Fr::from(18u64)],
+    )
+    .unwrap();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &other_spec, nonce.clone(), Default::default())
+        .is_err());
+
+    // 2*5 + 3*3 != 17 so the prover can't create a proof
+    let (proof_spec, witnesses) = proof_spec_and_witnesses(5, 3, &mut rng);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses.clone(),
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::UnsatisfiedLinearRelation(1))
+    ));
+
+    // Witnesses satisfying the relation but different from the committed values don't verify
+    let mut other_witnesses = Witnesses::new();
+    other_witnesses.add(witnesses.0[0].clone());
+    other_witnesses.add(Witness::SchnorrLinearRelation(vec![
+        Fr::from(4u64),
+        Fr::from(3u64),
+    ]));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        other_witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .is_err());
+
+    // Equations must have the same number of coefficients and a target each
+    assert!(matches!(
+        SchnorrLinearRelation::<Bls12_381>::new_statement_from_params::<G1Affine>(
+            vec![vec![Fr::from(2u64), Fr::from(3u64)], vec![Fr::from(1u64)]],
+            vec![Fr::from(17u64), Fr::from(1u64)],
+        ),
+        Err(ProofSystemError::InvalidLinearRelation)
+    ));
+    assert!(matches!(
+        SchnorrLinearRelation::<Bls12_381>::new_statement_from_params::<G1Affine>(
+            vec![vec![Fr::from(2u64), Fr::from(3u64)]],
+            vec![],
+        ),
+        Err(ProofSystemError::InvalidLinearRelation)
+    ));
+}
+
+#[test]
+fn linear_relation_with_unbound_witnesses() {
+    // Witnesses of a linear relation not equal to witnesses of another statement are rejected since the prover
+    // could pick any values satisfying the relation
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let opening = vec![Fr::from(4u64), Fr::from(3u64), Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_unchecked(&comm_key, &opening).into_affine();
+    let relation = SchnorrLinearRelation::new_statement_from_params(
+        vec![vec![Fr::from(2u64), Fr::from(3u64)]],
+        vec![Fr::from(17u64)],
+    )
+    .unwrap();
+
+    // Linear relation alone
+    let mut statements = Statements::new();
+    statements.add(relation.clone());
+    let proof_spec =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+    assert!(matches!(
+        proof_spec.validate(),
+        Err(ProofSystemError::UnboundLinearRelationWitness(0, 0))
+    ));
+
+    // Only the first witness of the relation is equal to a committed value
+    let mut statements = Statements::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        comm_key.clone(),
+        commitment,
+    ));
+    statements.add(relation.clone());
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add(MetaStatement::WitnessEquality(EqualWitnesses(
+        vec![(0, 0), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    )));
+    let proof_spec = ProofSpec::<Bls12_381, G1Affine>::new(
+        statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        proof_spec.validate(),
+        Err(ProofSystemError::UnboundLinearRelationWitness(1, 1))
+    ));
+
+    // The second witness is equal only to a witness of another linear relation which doesn't bind it either
+    statements.add(relation);
+    meta_statements.add(MetaStatement::WitnessEquality(EqualWitnesses(
+        vec![(1, 1), (2, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    )));
+    meta_statements.add(MetaStatement::WitnessEquality(EqualWitnesses(
+        vec![(0, 0), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    )));
+    let proof_spec =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements, meta_statements, vec![], None);
+    assert!(matches!(
+        proof_spec.validate(),
+        Err(ProofSystemError::UnboundLinearRelationWitness(1, 1))
+    ));
+}
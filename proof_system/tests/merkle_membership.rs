@@ -0,0 +1,225 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use std::time::Instant;
+
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{EqualWitnesses, MetaStatements, ProofSpec, WitnessRef, Witnesses},
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        merkle_membership::{
+            MerkleMembershipProver as MerkleMembershipProverStmt,
+            MerkleMembershipVerifier as MerkleMembershipVerifierStmt,
+        },
+        Statements,
+    },
+    sub_protocols::merkle_membership::{
+        generate_snark_srs_merkle_membership, MerkleHashParams, MerkleTree,
+    },
+    witness::{MerkleMembership as MerkleMembershipWit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit},
+};
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_and_merkle_tree_membership() {
+    // Prove knowledge of BBS+ signature and that a specific message is a leaf of a Merkle tree with a public root
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    // The message at index `msg_idx` is the leaf at index `leaf_idx` of a tree with 16 leaves
+    let msg_idx = 2;
+    let leaf_idx = 5;
+    let depth = 4;
+    let mut leaves = (0..16).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    leaves[leaf_idx] = msgs[msg_idx];
+
+    let hash_params = MerkleHashParams::<Fr>::new::<Blake2b512>(b"test merkle tree");
+    let tree = MerkleTree::new(&hash_params, leaves.clone()).unwrap();
+    assert_eq!(tree.depth(), depth);
+    let root = *tree.root();
+    let path = tree.path(leaf_idx).unwrap();
+
+    // Verifier sets up LegoGroth16 public parameters for the Merkle membership circuit of this depth
+    let start = Instant::now();
+    let snark_pk =
+        generate_snark_srs_merkle_membership::<Bls12_381, _>(&hash_params, depth, &mut rng)
+            .unwrap();
+    println!(
+        "Time taken to generate SNARK params for Merkle tree of depth {} {:?}",
+        depth,
+        start.elapsed()
+    );
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        MerkleMembershipProverStmt::new_statement_from_params(
+            root,
+            depth as u8,
+            hash_params.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    test_serialization!(Statements<Bls12_381, G1Affine>, prover_statements);
+
+    let proof_spec_prover = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    proof_spec_prover.validate().unwrap();
+
+    let witnesses_for_leaf = |leaf: Fr, leaf_index: u64, siblings: Vec<Fr>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(MerkleMembershipWit::new_as_witness(
+            leaf, leaf_index, siblings,
+        ));
+        witnesses
+    };
+
+    let witnesses = witnesses_for_leaf(msgs[msg_idx], leaf_idx as u64, path.clone());
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let start = Instant::now();
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    println!(
+        "Time taken to create proof of Merkle tree membership of 1 message in signature over {} messages {:?}",
+        msg_count,
+        start.elapsed()
+    );
+
+    test_serialization!(ProofG1, proof);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        MerkleMembershipVerifierStmt::new_statement_from_params(root, snark_pk.vk.clone()).unwrap(),
+    );
+    test_serialization!(Statements<Bls12_381, G1Affine>, verifier_statements);
+
+    let verifier_proof_spec = ProofSpec::new(
+        verifier_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    verifier_proof_spec.validate().unwrap();
+    assert_eq!(
+        verifier_proof_spec,
+        proof_spec_prover.for_verifier().unwrap()
+    );
+
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
+        .unwrap();
+    println!(
+        "Time taken to verify proof of Merkle tree membership of 1 message in signature over {} messages {:?}",
+        msg_count,
+        start.elapsed()
+    );
+
+    // Verifying against a different root fails
+    let mut wrong_root_statements = Statements::new();
+    wrong_root_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    wrong_root_statements.add(
+        MerkleMembershipVerifierStmt::new_statement_from_params(
+            Fr::rand(&mut rng),
+            snark_pk.vk.clone(),
+        )
+        .unwrap(),
+    );
+    let wrong_root_proof_spec =
+        ProofSpec::new(wrong_root_statements, meta_statements.clone(), vec![], None);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &wrong_root_proof_spec, None, Default::default())
+        .is_err());
+
+    // A forged path doesn't lead to the root so the prover can't create a proof
+    let mut forged_path = path.clone();
+    forged_path[2] = Fr::rand(&mut rng);
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_prover,
+            witnesses_for_leaf(msgs[msg_idx], leaf_idx as u64, forged_path),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::InvalidMerkleMembershipWitness(1))
+    ));
+
+    // The correct siblings with a wrong leaf index don't lead to the root either
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_prover,
+            witnesses_for_leaf(msgs[msg_idx], leaf_idx as u64 + 1, path.clone()),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::InvalidMerkleMembershipWitness(1))
+    ));
+
+    // A valid path of another leaf gives a proof but it fails verification as the leaf is not the signed message
+    let other_idx = 6;
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_prover,
+        witnesses_for_leaf(
+            leaves[other_idx],
+            other_idx as u64,
+            tree.path(other_idx).unwrap(),
+        ),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
+        .is_err());
+}
@@ -0,0 +1,187 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+use vb_accumulator::prelude::{Accumulator, NonMembershipProvingKey};
+
+use proof_system::{
+    error::ProofSystemError,
+    predicates::non_revoked_credential::{NonRevokedCredential, NonRevokedCredentialStatements},
+    prelude::{MetaStatements, ProofSpec, Witnesses},
+    statement::Statements,
+};
+
+use test_utils::{accumulators::*, bbs::*, test_serialization, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_with_non_revoked_handle() {
+    // Prove knowledge of a signature whose revocation handle is not in the accumulator of revoked handles
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let handle_idx = 2;
+    let (msgs, sig_params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let (accum_params, accum_keypair, mut accumulator, initial_elements, mut state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let non_revoked = |accumulator_value: G1Affine| {
+        NonRevokedCredential::new(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            handle_idx,
+            accum_params.clone(),
+            accum_keypair.public_key.clone(),
+            non_mem_prk.clone(),
+            accumulator_value,
+        )
+    };
+    let revealed = BTreeMap::from([(0, msgs[0])]);
+    let unrevealed = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != 0)
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+    let proof_spec = |predicate: &NonRevokedCredential<Bls12_381>| {
+        let mut proof_spec = ProofSpec::<Bls12_381, G1Affine>::new(
+            Statements::new(),
+            MetaStatements::new(),
+            vec![],
+            None,
+        );
+        assert_eq!(
+            predicate
+                .add_to_proof_spec(&mut proof_spec, revealed.clone())
+                .unwrap(),
+            NonRevokedCredentialStatements {
+                signature: 0,
+                non_membership: 1,
+            }
+        );
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+
+    let non_mem_wit = accumulator
+        .get_non_membership_witness(
+            &msgs[handle_idx],
+            &accum_keypair.secret_key,
+            &state,
+            &accum_params,
+        )
+        .unwrap();
+    let predicate = non_revoked(*accumulator.value());
+    let proof_spec_before_revocation = proof_spec(&predicate);
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec_before_revocation);
+
+    let mut witnesses = Witnesses::new();
+    predicate
+        .add_witnesses(
+            &mut witnesses,
+            sig.clone(),
+            unrevealed.clone(),
+            non_mem_wit.clone(),
+        )
+        .unwrap();
+    assert_eq!(witnesses.len(), 2);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_before_revocation,
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, proof);
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_before_revocation,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Revoke the credential by adding its handle to the accumulator
+    accumulator = accumulator
+        .add(
+            msgs[handle_idx],
+            &accum_keypair.secret_key,
+            &initial_elements,
+            &mut state,
+        )
+        .unwrap();
+    assert!(accumulator
+        .get_non_membership_witness(
+            &msgs[handle_idx],
+            &accum_keypair.secret_key,
+            &state,
+            &accum_params,
+        )
+        .is_err());
+
+    // The old non-membership witness doesn't prove non-revocation against the new accumulator
+    let predicate = non_revoked(*accumulator.value());
+    let proof_spec_after_revocation = proof_spec(&predicate);
+    let mut witnesses = Witnesses::new();
+    predicate
+        .add_witnesses(&mut witnesses, sig.clone(), unrevealed.clone(), non_mem_wit)
+        .unwrap();
+    assert!(ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec_after_revocation,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .and_then(|(proof, _)| {
+        proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec_after_revocation,
+            nonce.clone(),
+            Default::default(),
+        )
+    })
+    .is_err());
+
+    // The handle can't be revealed
+    let mut proof_spec = ProofSpec::<Bls12_381, G1Affine>::new(
+        Statements::new(),
+        MetaStatements::new(),
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        predicate.add_to_proof_spec(
+            &mut proof_spec,
+            BTreeMap::from([(handle_idx, msgs[handle_idx])])
+        ),
+        Err(ProofSystemError::WitnessAlreadyBeingRevealed(0, 2))
+    ));
+
+    // The handle must be one of the unrevealed messages
+    let mut witnesses = Witnesses::new();
+    assert!(matches!(
+        predicate.add_witnesses(
+            &mut witnesses,
+            sig,
+            BTreeMap::from([(1, msgs[1])]),
+            accumulator
+                .get_non_membership_witness(
+                    &msgs[1],
+                    &accum_keypair.secret_key,
+                    &state,
+                    &accum_params,
+                )
+                .unwrap(),
+        ),
+        Err(ProofSystemError::MissingRevocationHandle(2))
+    ));
+}
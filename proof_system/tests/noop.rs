@@ -0,0 +1,111 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::BTreeSet,
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{EqualWitnesses, MetaStatements, Witness, WitnessRef, Witnesses},
+    proof_spec::ProofSpec,
+    statement::{noop::Noop, ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements},
+    statement_proof::{NoopProof, StatementProof},
+    witness::NoopWitness,
+};
+
+use test_utils::{test_serialization, Fr, ProofG1};
+
+#[test]
+fn proof_spec_template_with_noop_statement() {
+    // A proof spec template has a placeholder for a statement which is only known later
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars_1 = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment_1 = G1Projective::msm_unchecked(&bases, &scalars_1).into_affine();
+    // 1st committed value is the same in both commitments
+    let mut scalars_2 = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    scalars_2[0] = scalars_1[0];
+    let commitment_2 = G1Projective::msm_unchecked(&bases, &scalars_2).into_affine();
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        commitment_1,
+    ));
+    statements.add(Noop::new_statement());
+    let template = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    template.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, template);
+
+    // The placeholder has a trivial proof
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars_1.clone()));
+    witnesses.add(NoopWitness::new_as_witness());
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &template,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert_eq!(
+        proof.statement_proofs[1],
+        StatementProof::Noop(NoopProof {})
+    );
+    test_serialization!(ProofG1, proof);
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &template, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Only a placeholder can be replaced
+    let other_statement =
+        PedersenCommitmentStmt::new_statement_from_params(bases.clone(), commitment_2);
+    for s_idx in [0, 2] {
+        assert!(matches!(
+            template.clone().replace_noop(s_idx, other_statement.clone()),
+            Err(ProofSystemError::InvalidNoopReplacement(i)) if i == s_idx
+        ));
+    }
+
+    let mut proof_spec = template.clone();
+    proof_spec.replace_noop(1, other_statement).unwrap();
+    proof_spec
+        .meta_statements
+        .add_witness_equality(EqualWitnesses(
+            vec![(0, 0), (1, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    proof_spec.validate().unwrap();
+
+    // A proof for the template doesn't verify for the filled spec
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .is_err());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars_1));
+    witnesses.add(Witness::PedersenCommitment(scalars_2));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+}
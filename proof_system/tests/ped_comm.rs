@@ -9,13 +9,19 @@ use ark_std::{
 };
 use blake2::Blake2b512;
 use proof_system::{
+    error::{ProofSystemError, PublicError},
     prelude::{EqualWitnesses, MetaStatement, MetaStatements, Witness, WitnessRef, Witnesses},
+    proof::ProofStreamReader,
     proof_spec::ProofSpec,
+    prover::{ProofPhase, ProofProgress, ProverConfig},
     setup_params::SetupParams,
     statement::{ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements},
+    statement_proof::{CoalescedStatementProof, StatementProof},
 };
+use schnorr_pok::error::SchnorrError;
+use smc_range_proof::prelude::SmcRangeProofError;
 
-use test_utils::{test_serialization, Fr, ProofG1};
+use test_utils::{test_proof_serialized_size, test_serialization, Fr, ProofG1};
 
 #[test]
 fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
@@ -96,7 +102,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
     let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -105,9 +111,10 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
     .0;
 
     test_serialization!(ProofG1, proof);
+    test_proof_serialized_size!(proof);
 
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce.clone(), Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
 
     // Wrong commitment should fail to verify
@@ -132,7 +139,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_invalid.clone(),
+        &proof_spec_invalid,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -142,7 +149,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
     assert!(proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec_invalid,
+            &proof_spec_invalid,
             nonce.clone(),
             Default::default()
         )
@@ -166,7 +173,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_invalid.clone(),
+        &proof_spec_invalid,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -175,7 +182,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality() {
     .0;
 
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_invalid, nonce, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_invalid, nonce, Default::default())
         .is_err());
 }
 
@@ -276,7 +283,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality_with_commitment_key_reus
     let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -287,6 +294,727 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality_with_commitment_key_reus
     test_serialization!(ProofG1, proof);
 
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
         .unwrap();
 }
+
+#[test]
+fn pedersen_commitment_with_mismatched_commitment_key_size() {
+    // A proof created with a commitment key of one size should fail verification with a commitment key of a
+    // different size even when the commitment is the same for both keys
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let mut scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    // Last scalar is 0 so the commitment is the same whether or not the last base is part of the key
+    scalars[4] = Fr::from(0u64);
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &prover_proof_spec,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases[0..4].to_vec(),
+        commitment,
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    match proof.verify::<StdRng, Blake2b512>(
+        &mut rng,
+        &verifier_proof_spec,
+        nonce,
+        Default::default(),
+    ) {
+        Err(ProofSystemError::SchnorrError(SchnorrError::ExpectedSameSizeSequences(5, 4))) => (),
+        r => panic!(
+            "Expected error due to mismatched commitment key size but got {:?}",
+            r
+        ),
+    }
+}
+
+#[test]
+fn multiple_proofs_with_same_proof_spec() {
+    // The same `ProofSpec` is used by reference to create and verify several proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut statements = Statements::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    let proofs = (0..10u8)
+        .map(|i| {
+            let nonce = Some(vec![i; 10]);
+            let proof = ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                &proof_spec,
+                witnesses.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap()
+            .0;
+            (proof, nonce)
+        })
+        .collect::<Vec<_>>();
+
+    for (proof, nonce) in proofs {
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+            .unwrap();
+    }
+}
+
+#[test]
+fn empty_proof_spec_and_witnesses() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let empty_spec = ProofSpec::<Bls12_381, G1Affine>::new(
+        Statements::new(),
+        MetaStatements::new(),
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        empty_spec.validate(),
+        Err(ProofSystemError::EmptyProofSpec)
+    ));
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &empty_spec,
+            Witnesses::new(),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::EmptyProofSpec)
+    ));
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut statements = Statements::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    assert!(matches!(
+        ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            Witnesses::new(),
+            None,
+            Default::default(),
+        ),
+        Err(ProofSystemError::EmptyWitnesses)
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // A valid proof doesn't verify against an empty spec
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(&mut rng, &empty_spec, None, Default::default()),
+        Err(ProofSystemError::EmptyProofSpec)
+    ));
+}
+
+#[test]
+fn redundant_witness_equality_in_proof_spec() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    for _ in 0..3 {
+        let bases = (0..3)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let commitment = G1Projective::rand(&mut rng).into_affine();
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            bases, commitment,
+        ));
+    }
+
+    // 1st committed value is same in all commitments and the 3rd equality follows from the first 2
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 0), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(1, 0), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements.clone(), meta_statements.clone(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 0), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    // A redundant equality is valid, it only adds unnecessary work, and can be found to remove it
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+    assert_eq!(
+        proof_spec.meta_statements.find_redundant_equalities(),
+        vec![EqualWitnesses(
+            vec![(0, 0), (2, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        )]
+    );
+}
+
+#[test]
+fn public_errors() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut statements = Statements::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars.clone()));
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        Some(b"test-nonce".to_vec()),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    // Verifying with a different nonce fails with a detailed error which is reported as an invalid proof
+    let err = proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            Some(b"other-nonce".to_vec()),
+            Default::default(),
+        )
+        .unwrap_err();
+    assert_eq!(err.to_public(), PublicError::InvalidProof);
+
+    // Proof spec without statements
+    let err = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &ProofSpec::new(Statements::new(), MetaStatements::new(), vec![], None),
+        Witnesses::new(),
+        None,
+        Default::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_public(), PublicError::MalformedInput);
+
+    for (err, public) in [
+        (
+            ProofSystemError::SchnorrError(SchnorrError::InvalidResponse),
+            PublicError::InvalidProof,
+        ),
+        (
+            ProofSystemError::WitnessResponseNotEqual(1, 2),
+            PublicError::InvalidProof,
+        ),
+        (
+            ProofSystemError::SetMembershipBasedRangeProof(
+                SmcRangeProofError::InvalidRangeProofAtDigit(3),
+            ),
+            PublicError::InvalidProof,
+        ),
+        (
+            ProofSystemError::UnequalWitnessAndStatementCount(2, 3),
+            PublicError::MalformedInput,
+        ),
+        (
+            ProofSystemError::WitnessAlreadyBeingRevealed(0, 1),
+            PublicError::MalformedInput,
+        ),
+        (
+            ProofSystemError::InvalidSetupParamsIndex(4),
+            PublicError::MalformedInput,
+        ),
+        (
+            ProofSystemError::UnsupportedProofVersion {
+                found: 1,
+                supported: 3,
+            },
+            PublicError::UnsupportedFeature,
+        ),
+        (
+            ProofSystemError::UnsupportedValue("value".to_string()),
+            PublicError::UnsupportedFeature,
+        ),
+        (
+            ProofSystemError::TooManyCifertexts(10),
+            PublicError::TooLarge,
+        ),
+    ] {
+        assert_eq!(err.to_public(), public);
+    }
+}
+
+#[test]
+fn stream_statement_proofs_of_proof() {
+    // Read a serialized proof one statement proof at a time and verify it
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    let mut witnesses = Witnesses::new();
+    for i in 1..=5 {
+        let bases = (0..i)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..i).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            bases, commitment,
+        ));
+        witnesses.add(Witness::PedersenCommitment(scalars));
+    }
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let mut bytes = vec![];
+    proof.serialize_compressed(&mut bytes).unwrap();
+
+    let mut reader = ProofStreamReader::<Bls12_381, G1Affine, _>::new(bytes.as_slice()).unwrap();
+    assert_eq!(reader.remaining(), 5);
+    let mut statement_proofs = vec![];
+    while let Some(sp) = reader.next_statement_proof().unwrap() {
+        assert_eq!(sp, proof.statement_proofs[statement_proofs.len()]);
+        statement_proofs.push(sp);
+        assert_eq!(reader.remaining(), 5 - statement_proofs.len());
+    }
+    assert_eq!(statement_proofs.len(), 5);
+    assert!(reader.next_statement_proof().unwrap().is_none());
+
+    let streamed = reader.finish(statement_proofs).unwrap();
+    assert_eq!(streamed, proof);
+    streamed
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+
+    // Rest of the proof can't be read before all statement proofs are
+    let mut reader = ProofStreamReader::<Bls12_381, G1Affine, _>::new(bytes.as_slice()).unwrap();
+    let first = reader.next_statement_proof().unwrap().unwrap();
+    assert!(matches!(
+        reader.finish(vec![first]),
+        Err(ProofSystemError::UnreadStatementProofs(4))
+    ));
+
+    // Truncated proof fails to be read
+    let mut reader =
+        ProofStreamReader::<Bls12_381, G1Affine, _>::new(&bytes[..bytes.len() / 2]).unwrap();
+    let mut result = Ok(None);
+    for _ in 0..5 {
+        result = reader.next_statement_proof();
+        if result.is_err() {
+            break;
+        }
+    }
+    assert!(result.is_err());
+}
+
+#[test]
+fn coalesced_pedersen_commitments_with_same_bases() {
+    // Pedersen commitments with the same commitment key are proven using a single commitment to randomness
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..4)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let other_bases = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    let mut witnesses = Witnesses::new();
+    let mut add = |bases: &[G1Affine], scalars: Vec<Fr>| {
+        let commitment = G1Projective::msm_unchecked(bases, &scalars).into_affine();
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            bases.to_vec(),
+            commitment,
+        ));
+        witnesses.add(Witness::PedersenCommitment(scalars));
+    };
+    let random_scalars =
+        |n: usize, rng: &mut StdRng| (0..n).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    // Statements 0 and 1 can be coalesced. Statement 2 has the same bases but its first witness is proven equal to
+    // the first witness of statement 3
+    add(&bases, random_scalars(4, &mut rng));
+    add(&bases, random_scalars(4, &mut rng));
+    let shared = Fr::rand(&mut rng);
+    let mut scalars = random_scalars(4, &mut rng);
+    scalars[0] = shared;
+    add(&bases, scalars);
+    add(&other_bases, vec![shared, Fr::rand(&mut rng)]);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(2, 0), (3, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+    assert_eq!(
+        proof_spec.coalescable_pedersen_commitments().unwrap(),
+        vec![vec![0, 1]]
+    );
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let coalesced_proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        ProverConfig {
+            coalesce_pedersen_commitments: true,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+    test_serialization!(ProofG1, coalesced_proof);
+
+    assert_eq!(
+        coalesced_proof
+            .as_coalesced_pedersen_commitment(0)
+            .unwrap()
+            .coalesced,
+        vec![1]
+    );
+    assert_eq!(
+        coalesced_proof.statement_proofs[1],
+        StatementProof::Coalesced(CoalescedStatementProof { into: 0 })
+    );
+    assert!(coalesced_proof.as_pedersen_commitment(2).is_ok());
+    assert!(coalesced_proof.as_pedersen_commitment(3).is_ok());
+    assert!(coalesced_proof.compressed_size() < proof.compressed_size());
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+    coalesced_proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .unwrap();
+
+    // Doesn't verify if the commitment of a coalesced statement is different
+    let mut other_spec = proof_spec.clone();
+    other_spec.statements.0[1] = PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        G1Projective::rand(&mut rng).into_affine(),
+    );
+    assert!(coalesced_proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, &other_spec, nonce.clone(), Default::default())
+        .is_err());
+
+    // A statement can't be left out of the coalesced proof
+    let mut tampered = coalesced_proof.clone();
+    match &mut tampered.statement_proofs[0] {
+        StatementProof::CoalescedPedersenCommitment(p) => p.coalesced = vec![],
+        _ => panic!("Expected a coalesced Pedersen commitment proof"),
+    }
+    assert!(matches!(
+        tampered.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            nonce.clone(),
+            Default::default()
+        ),
+        Err(ProofSystemError::InvalidCoalescedProof(0))
+    ));
+
+    // A statement with a witness equality can't be coalesced
+    let mut tampered = coalesced_proof;
+    tampered.statement_proofs[2] = StatementProof::Coalesced(CoalescedStatementProof { into: 0 });
+    match &mut tampered.statement_proofs[0] {
+        StatementProof::CoalescedPedersenCommitment(p) => p.coalesced = vec![1, 2],
+        _ => panic!("Expected a coalesced Pedersen commitment proof"),
+    }
+    assert!(matches!(
+        tampered.verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default()),
+        Err(ProofSystemError::InvalidCoalescedProof(0))
+    ));
+}
+
+#[test]
+fn constant_time_equality_of_proofs() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let create_proof = |rng: &mut StdRng, nonce: &[u8]| {
+        ProofG1::new::<StdRng, Blake2b512>(
+            rng,
+            &proof_spec,
+            witnesses.clone(),
+            Some(nonce.to_vec()),
+            Default::default(),
+        )
+        .unwrap()
+        .0
+    };
+    let proof = create_proof(&mut rng, b"test nonce");
+    assert!(bool::from(proof.ct_eq(&proof.clone())));
+
+    // Proofs differing in randomness, nonce or number of statement proofs aren't equal
+    let other_proof = create_proof(&mut rng, b"test nonce");
+    assert!(!bool::from(proof.ct_eq(&other_proof)));
+    let mut other_proof = proof.clone();
+    other_proof.nonce = Some(b"test nonce 1".to_vec());
+    assert!(!bool::from(proof.ct_eq(&other_proof)));
+    let mut other_proof = proof.clone();
+    other_proof.statement_proofs.pop();
+    assert!(!bool::from(proof.ct_eq(&other_proof)));
+}
+
+#[test]
+fn progress_of_proof_creation() {
+    // Progress is reported for each statement in order, first for commitments and then for responses
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let count = 4;
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    let mut witnesses = Witnesses::new();
+    for i in 1..=count {
+        let bases = (0..i)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let scalars = (0..i).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            bases, commitment,
+        ));
+        witnesses.add(Witness::PedersenCommitment(scalars));
+    }
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let nonce = Some(b"test nonce".to_vec());
+    let mut events = vec![];
+    let proof = ProofG1::new_with_progress::<StdRng, Blake2b512>(
+        &mut StdRng::seed_from_u64(1u64),
+        &proof_spec,
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+        &mut |p| events.push(p),
+    )
+    .unwrap()
+    .0;
+
+    let expected = [ProofPhase::Commit, ProofPhase::Response]
+        .into_iter()
+        .flat_map(|phase| {
+            (0..count).map(move |statement_index| ProofProgress {
+                statement_index,
+                total: count,
+                phase,
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(events, expected);
+
+    // Reporting progress doesn't change the proof
+    let proof_without_progress = ProofG1::new::<StdRng, Blake2b512>(
+        &mut StdRng::seed_from_u64(1u64),
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert_eq!(proof, proof_without_progress);
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
+        .unwrap();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn proof_creation_in_dedicated_thread_pool() {
+    // Proof created in a dedicated thread pool rather than the global one
+    use ark_serialize::Compress;
+    use std::sync::Arc;
+
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    let mut witnesses = Witnesses::new();
+    let shared = Fr::rand(&mut rng);
+    for _ in 0..3 {
+        let bases = (0..10)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let mut scalars = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        scalars[0] = shared;
+        let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            bases, commitment,
+        ));
+        witnesses.add(Witness::PedersenCommitment(scalars));
+    }
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 0), (1, 0), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(pool.current_num_threads(), 2);
+    let config = ProverConfig::default().with_thread_pool(pool.clone());
+    // A config with a thread pool can't be serialized
+    let mut bytes = vec![];
+    assert!(config
+        .serialize_with_mode(&mut bytes, Compress::Yes)
+        .is_err());
+
+    let nonce = Some(b"test nonce".to_vec());
+    for _ in 0..3 {
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses.clone(),
+            nonce.clone(),
+            config.clone(),
+        )
+        .unwrap()
+        .0;
+        test_serialization!(ProofG1, proof);
+        proof
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+            .unwrap();
+    }
+
+    // The pool is still usable after creating proofs in it
+    assert_eq!(pool.install(rayon::current_num_threads), 2);
+}
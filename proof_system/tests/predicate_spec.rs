@@ -0,0 +1,203 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams as BppSetupParams;
+use vb_accumulator::prelude::{Accumulator, MembershipProvingKey, NonMembershipProvingKey};
+
+use proof_system::{
+    error::ProofSystemError,
+    predicates::predicate_spec::{
+        AccumulatorInfo, BoundCheckParams, CredentialSchema, Predicate, PredicateSpec, WitnessSlot,
+    },
+    prelude::bound_check_smc::SmcParamsAndCommitmentKey,
+    statement::Statement,
+};
+use test_utils::{accumulators::*, bbs::*, Fr, ProofG1};
+
+#[test]
+fn compile_predicates_and_prove() {
+    // Verifier asks for proof that age is at least 18, the country is one of the allowed ones and the
+    // credential is not revoked
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let schema = CredentialSchema::new(
+        ["name", "age", "country", "id"]
+            .into_iter()
+            .map(|a| a.to_string())
+            .collect(),
+    );
+    let attributes = vec![
+        Fr::rand(&mut rng),
+        Fr::from(30u64),
+        Fr::from(49u64),
+        Fr::rand(&mut rng),
+    ];
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &attributes);
+
+    // Accumulator of allowed countries
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    for country in [33u64, 44, 49] {
+        pos_accumulator = pos_accumulator
+            .add(
+                Fr::from(country),
+                &pos_accum_keypair.secret_key,
+                &mut pos_state,
+            )
+            .unwrap();
+    }
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&attributes[2], &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    // Accumulator of revoked credential ids
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, 100);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &attributes[3],
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let (smc_params, _) = SmcParamsAndCommitmentKey::new::<_, Blake2b512>(&mut rng, b"test", 2);
+    let bpp_params =
+        BppSetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let mut predicate_spec = PredicateSpec::<Bls12_381> {
+        schema,
+        sig_params,
+        public_key: sig_keypair.public_key.clone(),
+        predicates: vec![
+            Predicate::InRange {
+                attribute: "age".to_string(),
+                min: 18,
+                max: 150,
+            },
+            Predicate::InAccumulator {
+                attribute: "country".to_string(),
+                accumulator: AccumulatorInfo {
+                    params: pos_accum_params,
+                    public_key: pos_accum_keypair.public_key.clone(),
+                    accumulator_value: *pos_accumulator.value(),
+                },
+                proving_key: mem_prk,
+            },
+            Predicate::NotInAccumulator {
+                attribute: "id".to_string(),
+                accumulator: AccumulatorInfo {
+                    params: uni_accum_params,
+                    public_key: uni_accum_keypair.public_key.clone(),
+                    accumulator_value: *uni_accumulator.value(),
+                },
+                proving_key: non_mem_prk,
+            },
+        ],
+        bound_check_params: BoundCheckParams {
+            bpp: Some(bpp_params),
+            smc: Some(smc_params),
+        },
+        context: Some(b"test-context".to_vec()),
+    };
+
+    // Prover and verifier compile the same predicates
+    let (proof_spec, witness_template) = predicate_spec.compile().unwrap();
+    proof_spec.validate().unwrap();
+    assert_eq!(proof_spec.statements.len(), 4);
+    // Range is small so set-membership check based range proof is used
+    assert!(matches!(
+        proof_spec.statements.0[1],
+        Statement::BoundCheckSmc(_)
+    ));
+    assert!(matches!(
+        proof_spec.statements.0[2],
+        Statement::AccumulatorMembership(_)
+    ));
+    assert!(matches!(
+        proof_spec.statements.0[3],
+        Statement::AccumulatorNonMembership(_)
+    ));
+    assert_eq!(
+        witness_template.slots,
+        vec![
+            WitnessSlot::Signature,
+            WitnessSlot::BoundCheckSmc(1),
+            WitnessSlot::AccumulatorMembership(2),
+            WitnessSlot::AccumulatorNonMembership(3)
+        ]
+    );
+    assert_eq!(
+        proof_spec.witness_refs().collect::<Vec<_>>(),
+        vec![(0, 1), (0, 2), (0, 3), (1, 0), (2, 0), (3, 0)]
+    );
+
+    let witnesses = witness_template
+        .witnesses(
+            sig.clone(),
+            attributes.clone(),
+            vec![mem_wit.clone()],
+            vec![non_mem_wit.clone()],
+        )
+        .unwrap();
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let (verifier_proof_spec, _) = predicate_spec.compile().unwrap();
+    assert_eq!(verifier_proof_spec, proof_spec);
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, nonce, Default::default())
+        .unwrap();
+
+    // Missing accumulator witness
+    assert!(witness_template
+        .witnesses(sig.clone(), attributes.clone(), vec![], vec![non_mem_wit])
+        .is_err());
+
+    // Large range is proved using Bulletproofs++
+    predicate_spec.predicates[0] = Predicate::InRange {
+        attribute: "age".to_string(),
+        min: 18,
+        max: u64::MAX,
+    };
+    let (proof_spec, witness_template) = predicate_spec.compile().unwrap();
+    assert!(matches!(
+        proof_spec.statements.0[1],
+        Statement::BoundCheckBpp(_)
+    ));
+    assert_eq!(witness_template.slots[1], WitnessSlot::BoundCheckBpp(1));
+
+    // Without any range proof params, range predicates can't be compiled
+    let bound_check_params = predicate_spec.bound_check_params.clone();
+    predicate_spec.bound_check_params = BoundCheckParams::default();
+    assert!(matches!(
+        predicate_spec.compile(),
+        Err(ProofSystemError::BoundCheckParamsNotProvided)
+    ));
+    predicate_spec.bound_check_params = bound_check_params;
+
+    // Predicate over an attribute not in the schema
+    predicate_spec.predicates.push(Predicate::InRange {
+        attribute: "height".to_string(),
+        min: 100,
+        max: 250,
+    });
+    assert!(matches!(
+        predicate_spec.compile(),
+        Err(ProofSystemError::UnknownAttribute(a)) if a == "height"
+    ));
+}
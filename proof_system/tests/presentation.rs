@@ -0,0 +1,227 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{
+        EqualWitnesses, MetaStatements, PresentationToken, ProofSpec, VersionedSerialization,
+        Witness, WitnessRef, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        bound_check_legogroth16::BoundCheckLegoGroth16Prover as BoundCheckProverStmt, Statement,
+        Statements,
+    },
+    sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check,
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+
+#[test]
+fn presentation_token_verifies_from_serialized_bytes() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let max = 200;
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    // Reveal 2 messages and prove bounds on another
+    let revealed_indices = BTreeSet::from([0, 3]);
+    let msg_idx = 1;
+    let mut revealed_msgs = BTreeMap::new();
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate() {
+        if revealed_indices.contains(&i) {
+            revealed_msgs.insert(i, *m);
+        } else {
+            unrevealed_msgs.insert(i, *m);
+        }
+    }
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed_msgs.clone(),
+    ));
+    statements.add(BoundCheckProverStmt::new_statement_from_params(min, max, snark_pk).unwrap());
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let context = Some(b"test-context".to_vec());
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], context);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[msg_idx]));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let token = PresentationToken::new(proof, &proof_spec).unwrap();
+    // Token has the verifier's statement and not the prover's
+    assert!(matches!(
+        token.proof_spec.statements.0[1],
+        Statement::BoundCheckLegoGroth16Verifier(_)
+    ));
+    assert_eq!(token.nonce(), &nonce);
+    assert_eq!(
+        token.revealed_messages(),
+        BTreeMap::from([(0, &revealed_msgs)])
+    );
+    token
+        .verify::<StdRng, Blake2b512>(&mut rng, Default::default())
+        .unwrap();
+
+    test_serialization!(PresentationToken<Bls12_381, G1Affine>, token);
+
+    let mut bytes = vec![];
+    token.serialize_versioned(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), token.versioned_serialized_size());
+
+    // Verifier later deserializes the token and verifies it without any other data
+    let mut rng = StdRng::seed_from_u64(1u64);
+    let token =
+        PresentationToken::<Bls12_381, G1Affine>::deserialize_versioned(&bytes[..]).unwrap();
+    assert_eq!(token.nonce(), &nonce);
+    assert_eq!(
+        token.revealed_messages(),
+        BTreeMap::from([(0, &revealed_msgs)])
+    );
+    token
+        .verify::<StdRng, Blake2b512>(&mut rng, Default::default())
+        .unwrap();
+
+    // Changing the nonce makes the token fail verification
+    let mut tampered = token.clone();
+    tampered.proof.nonce = Some(b"other-nonce".to_vec());
+    assert!(tampered
+        .verify::<StdRng, Blake2b512>(&mut rng, Default::default())
+        .is_err());
+
+    // Changing a revealed message makes the token fail verification
+    let mut tampered = token;
+    match &mut tampered.proof_spec.statements.0[0] {
+        Statement::PoKBBSSignatureG1(s) => {
+            s.revealed_messages.insert(0, Fr::from(1u64));
+        }
+        _ => panic!("expected a BBS+ signature statement"),
+    }
+    assert!(tampered
+        .verify::<StdRng, Blake2b512>(&mut rng, Default::default())
+        .is_err());
+}
+
+#[test]
+fn presentation_token_with_mismatched_revealed_message() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let revealed_msgs = BTreeMap::from([(0, msgs[0]), (3, msgs[3])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed_msgs,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let token = PresentationToken::new(proof, &proof_spec).unwrap();
+
+    // Verifier expecting the same revealed messages
+    token
+        .verify_with_spec::<StdRng, Blake2b512>(&mut rng, &proof_spec, Default::default())
+        .unwrap();
+
+    let with_revealed = |update: &dyn Fn(&mut BTreeMap<usize, Fr>)| {
+        let mut spec = proof_spec.clone();
+        match &mut spec.statements.0[0] {
+            Statement::PoKBBSSignatureG1(s) => update(&mut s.revealed_messages),
+            _ => panic!("expected a BBS+ signature statement"),
+        }
+        spec
+    };
+
+    // Verifier declares a different value for a revealed message
+    let verifier_spec = with_revealed(&|m| {
+        m.insert(3, Fr::from(1u64));
+    });
+    assert!(matches!(
+        token.verify_with_spec::<StdRng, Blake2b512>(&mut rng, &verifier_spec, Default::default()),
+        Err(ProofSystemError::RevealedMessageMismatch {
+            statement: 0,
+            index: 3
+        })
+    ));
+
+    // Verifier expects a message to be revealed which isn't
+    let verifier_spec = with_revealed(&|m| {
+        m.insert(2, msgs[2]);
+    });
+    assert!(matches!(
+        token.check_revealed_messages(&verifier_spec),
+        Err(ProofSystemError::RevealedMessageMismatch {
+            statement: 0,
+            index: 2
+        })
+    ));
+
+    // Verifier doesn't expect a revealed message
+    let verifier_spec = with_revealed(&|m| {
+        m.remove(&0);
+    });
+    assert!(matches!(
+        token.check_revealed_messages(&verifier_spec),
+        Err(ProofSystemError::RevealedMessageMismatch {
+            statement: 0,
+            index: 0
+        })
+    ));
+}
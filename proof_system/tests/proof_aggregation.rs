@@ -144,7 +144,7 @@ fn pok_of_bbs_plus_sigs_and_verifiable_encryption_with_saver_aggregation() {
 
     let (proof, _) = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        prover_proof_spec,
+        &prover_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -199,10 +199,11 @@ fn pok_of_bbs_plus_sigs_and_verifiable_encryption_with_saver_aggregation() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec.clone(),
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -216,10 +217,11 @@ fn pok_of_bbs_plus_sigs_and_verifiable_encryption_with_saver_aggregation() {
     updated_proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec,
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -346,7 +348,7 @@ fn pok_of_bbs_plus_sigs_and_bound_check_with_aggregation() {
 
     let (proof, _) = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        prover_proof_spec,
+        &prover_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -398,10 +400,11 @@ fn pok_of_bbs_plus_sigs_and_bound_check_with_aggregation() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec.clone(),
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -415,10 +418,11 @@ fn pok_of_bbs_plus_sigs_and_bound_check_with_aggregation() {
     updated_proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec,
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -40,7 +40,7 @@ use proof_system::{
     },
     witness::{Membership as MembershipWit, NonMembership as NonMembershipWit, PoKPSSignature},
 };
-use test_utils::{accumulators::*, test_serialization, Fr, ProofG1};
+use test_utils::{accumulators::*, test_proof_serialized_size, test_serialization, Fr, ProofG1};
 
 #[test]
 fn pok_of_3_ps_sig_and_message_equality() {
@@ -191,7 +191,7 @@ fn pok_of_3_ps_sig_and_message_equality() {
     let nonce = Some(b"some nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses,
         nonce.clone(),
         Default::default(),
@@ -202,16 +202,17 @@ fn pok_of_3_ps_sig_and_message_equality() {
     // Proof with no nonce shouldn't verify
     assert!(proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
         .is_err());
     assert!(proof
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec.clone(),
+            &proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -221,7 +222,7 @@ fn pok_of_3_ps_sig_and_message_equality() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec.clone(),
+            &proof_spec,
             Some(b"random...".to_vec()),
             Default::default()
         )
@@ -230,26 +231,23 @@ fn pok_of_3_ps_sig_and_message_equality() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec.clone(),
+            &proof_spec,
             Some(b"random...".to_vec()),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
 
     test_serialization!(ProofG1, proof);
+    test_proof_serialized_size!(proof);
 
     // Verifier verifies the proof
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
     println!(
         "Time to verify proof with 3 PS signatures: {:?}",
@@ -260,10 +258,11 @@ fn pok_of_3_ps_sig_and_message_equality() {
     proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec,
+            &proof_spec,
             nonce,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -358,7 +357,7 @@ fn pok_of_ps_sig_and_accumulator() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -371,12 +370,7 @@ fn pok_of_ps_sig_and_accumulator() {
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
     println!(
         "Time to verify proof with a PS signature and positive accumulator membership: {:?}",
@@ -387,10 +381,11 @@ fn pok_of_ps_sig_and_accumulator() {
     proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec.clone(),
+            &proof_spec,
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -411,7 +406,7 @@ fn pok_of_ps_sig_and_accumulator() {
     );
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_incorrect.clone(),
+        &proof_spec_incorrect,
         witnesses,
         nonce.clone(),
         Default::default(),
@@ -423,7 +418,7 @@ fn pok_of_ps_sig_and_accumulator() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec_incorrect.clone(),
+            &proof_spec_incorrect,
             nonce.clone(),
             Default::default()
         )
@@ -431,10 +426,11 @@ fn pok_of_ps_sig_and_accumulator() {
     assert!(proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec_incorrect,
+            &proof_spec_incorrect,
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -462,7 +458,7 @@ fn pok_of_ps_sig_and_accumulator() {
     proof_spec.validate().unwrap();
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses_incorrect,
         nonce.clone(),
         Default::default(),
@@ -471,20 +467,16 @@ fn pok_of_ps_sig_and_accumulator() {
     .0;
     assert!(proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default()
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .is_err());
     assert!(proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec,
+            &proof_spec,
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -556,7 +548,7 @@ fn pok_of_ps_sig_and_accumulator() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -569,12 +561,7 @@ fn pok_of_ps_sig_and_accumulator() {
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
     println!(
         "Time to verify proof with a PS signature and universal accumulator membership: {:?}",
@@ -586,10 +573,11 @@ fn pok_of_ps_sig_and_accumulator() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec.clone(),
+            &proof_spec,
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -654,7 +642,7 @@ fn pok_of_ps_sig_and_accumulator() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -667,12 +655,7 @@ fn pok_of_ps_sig_and_accumulator() {
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
     println!(
         "Time to verify proof with a PS signature and universal accumulator non-membership: {:?}",
@@ -683,10 +666,11 @@ fn pok_of_ps_sig_and_accumulator() {
     proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec.clone(),
+            &proof_spec,
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -781,7 +765,7 @@ fn pok_of_ps_sig_and_accumulator() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -794,12 +778,7 @@ fn pok_of_ps_sig_and_accumulator() {
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            proof_spec.clone(),
-            nonce.clone(),
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
     println!("Time to verify proof with a PS signature and 3 accumulator membership and non-membership checks: {:?}", start.elapsed());
 
@@ -807,10 +786,11 @@ fn pok_of_ps_sig_and_accumulator() {
     proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec,
+            &proof_spec,
             nonce,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -881,7 +861,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_ps_sig() {
     let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -892,7 +872,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_ps_sig() {
     test_serialization!(ProofG1, proof);
 
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce.clone(), Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
         .unwrap();
 
     // Wrong message equality should fail to verify
@@ -913,7 +893,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_ps_sig() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_invalid.clone(),
+        &proof_spec_invalid,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -922,7 +902,7 @@ fn pok_of_knowledge_in_pedersen_commitment_and_ps_sig() {
     .0;
 
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_invalid, nonce, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_invalid, nonce, Default::default())
         .is_err());
 }
 
@@ -1006,7 +986,7 @@ fn requesting_partially_blind_ps_sig() {
     let nonce = Some(b"test nonce".to_vec());
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses.clone(),
         nonce.clone(),
         Default::default(),
@@ -1017,7 +997,7 @@ fn requesting_partially_blind_ps_sig() {
     test_serialization!(ProofG1, proof);
 
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce, Default::default())
         .unwrap();
 
     // Now requester picks the messages he is revealing to the signer and prepares `uncommitted_messages`
@@ -1111,7 +1091,7 @@ fn proof_spec_modification() {
 
     assert!(ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        invalid_proof_spec,
+        &invalid_proof_spec,
         witnesses.clone(),
         None,
         Default::default()
@@ -1127,7 +1107,7 @@ fn proof_spec_modification() {
     let invalid_proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
     assert!(ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        invalid_proof_spec,
+        &invalid_proof_spec,
         witnesses.clone(),
         None,
         Default::default()
@@ -1143,7 +1123,7 @@ fn proof_spec_modification() {
     let invalid_proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
     assert!(ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        invalid_proof_spec,
+        &invalid_proof_spec,
         witnesses.clone(),
         None,
         Default::default()
@@ -1170,7 +1150,7 @@ fn proof_spec_modification() {
     // Proof created using modified proof spec wont be a valid
     let invalid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        modified_proof_spec.clone(),
+        &modified_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1181,16 +1161,16 @@ fn proof_spec_modification() {
     // Above proof is valid if verified using the modified proof spec but not with the original proof spec
     invalid_proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, modified_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &modified_proof_spec, None, Default::default())
         .unwrap();
     assert!(invalid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec.clone(), None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .is_err());
 
     // Proof created using original proof spec will be valid
     let valid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        orig_proof_spec.clone(),
+        &orig_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1198,7 +1178,7 @@ fn proof_spec_modification() {
     .unwrap()
     .0;
     valid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .unwrap();
 
     // Verifier creates proof spec with 2 statements, prover modifies it to remove a statement
@@ -1230,7 +1210,7 @@ fn proof_spec_modification() {
     // Proof created using modified proof spec wont be a valid
     let invalid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        modified_proof_spec.clone(),
+        &modified_proof_spec,
         only_1_witness.clone(),
         None,
         Default::default(),
@@ -1241,16 +1221,16 @@ fn proof_spec_modification() {
     // Above proof is valid if verified using the modified proof spec but not with the original proof spec
     invalid_proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, modified_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &modified_proof_spec, None, Default::default())
         .unwrap();
     assert!(invalid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec.clone(), None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .is_err());
 
     // Proof created using original proof spec will be valid
     let valid_proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        orig_proof_spec.clone(),
+        &orig_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1258,7 +1238,7 @@ fn proof_spec_modification() {
     .unwrap()
     .0;
     valid_proof
-        .verify::<StdRng, Blake2b512>(&mut rng, orig_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &orig_proof_spec, None, Default::default())
         .unwrap();
 }
 
@@ -1354,7 +1334,7 @@ fn verifier_local_linkability() {
 
     let proof_1 = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_1.clone(),
+        &proof_spec_1,
         witnesses_1.clone(),
         None,
         Default::default(),
@@ -1363,7 +1343,7 @@ fn verifier_local_linkability() {
     .0;
 
     proof_1
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_1, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_1, None, Default::default())
         .unwrap();
 
     // Prover proves to verifier 2
@@ -1403,7 +1383,7 @@ fn verifier_local_linkability() {
 
     let proof_2 = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_2.clone(),
+        &proof_spec_2,
         witnesses_2.clone(),
         None,
         Default::default(),
@@ -1412,7 +1392,7 @@ fn verifier_local_linkability() {
     .0;
 
     proof_2
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_2, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_2, None, Default::default())
         .unwrap();
 
     // Prover again proves to verifier 1, this time something different like revealing a message but still uses his registration
@@ -1463,7 +1443,7 @@ fn verifier_local_linkability() {
 
     let proof_3 = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_3.clone(),
+        &proof_spec_3,
         witnesses_3.clone(),
         None,
         Default::default(),
@@ -1472,7 +1452,7 @@ fn verifier_local_linkability() {
     .0;
 
     proof_3
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_3, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_3, None, Default::default())
         .unwrap();
 }
 
@@ -1570,7 +1550,7 @@ fn pok_of_ps_sig_with_reusing_setup_params() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec.clone(),
+        &proof_spec,
         witnesses,
         None,
         Default::default(),
@@ -1581,7 +1561,7 @@ fn pok_of_ps_sig_with_reusing_setup_params() {
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, None, Default::default())
         .unwrap();
     println!(
         "Time to verify proof with 4 PS signatures: {:?}",
@@ -1592,10 +1572,11 @@ fn pok_of_ps_sig_with_reusing_setup_params() {
     proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            proof_spec,
+            &proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -140,7 +140,7 @@ fn pok_of_bbs_plus_sigs_and_sum_of_certain_attributes_less_than_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -177,7 +177,7 @@ fn pok_of_bbs_plus_sigs_and_sum_of_certain_attributes_less_than_check() {
     );
     verifier_proof_spec.validate().unwrap();
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
     println!(
         "Verifying proof for bounded sum takes {:?}",
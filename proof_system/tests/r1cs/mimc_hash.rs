@@ -124,7 +124,7 @@ fn pok_of_bbs_plus_sig_and_knowledge_of_hash_preimage() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -155,7 +155,7 @@ fn pok_of_bbs_plus_sig_and_knowledge_of_hash_preimage() {
     verifier_proof_spec.validate().unwrap();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
     println!(
         "Verifying proof for MiMC circuit takes {:?}",
@@ -180,6 +180,6 @@ fn pok_of_bbs_plus_sig_and_knowledge_of_hash_preimage() {
     );
     verifier_proof_spec_1.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec_1, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec_1, None, Default::default())
         .is_err());
 }
@@ -141,7 +141,7 @@ fn pok_of_bbs_plus_sig_and_attribute_less_than_check_with_private_and_public_val
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -185,6 +185,6 @@ fn pok_of_bbs_plus_sig_and_attribute_less_than_check_with_private_and_public_val
     );
     verifier_proof_spec.validate().unwrap();
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 }
@@ -127,7 +127,7 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -184,10 +184,11 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec.clone(),
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: None,
+                ..Default::default()
             },
         )
         .unwrap();
@@ -196,10 +197,11 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec.clone(),
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -207,10 +209,11 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
     updated_proof
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec,
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
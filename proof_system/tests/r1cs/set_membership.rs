@@ -87,7 +87,7 @@ fn pok_of_bbs_plus_sig_and_set_membership() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover.clone(),
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -117,7 +117,7 @@ fn pok_of_bbs_plus_sig_and_set_membership() {
     );
     verifier_proof_spec.validate().unwrap();
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 
     // -------------------------------------------------------------------------------------- //
@@ -137,7 +137,7 @@ fn pok_of_bbs_plus_sig_and_set_membership() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -166,6 +166,6 @@ fn pok_of_bbs_plus_sig_and_set_membership() {
     );
     verifier_proof_spec.validate().unwrap();
     proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 }
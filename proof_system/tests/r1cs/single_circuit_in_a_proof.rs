@@ -27,7 +27,7 @@ use proof_system::{
 use std::collections::{BTreeMap, BTreeSet};
 
 use crate::r1cs::get_r1cs_and_wasm_bytes;
-use test_utils::{bbs::*, test_serialization, Fr, ProofG1, G1};
+use test_utils::{bbs::*, test_proof_serialized_size, test_serialization, Fr, ProofG1, G1};
 
 #[test]
 fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
@@ -97,7 +97,7 @@ fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover.clone(),
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -106,6 +106,7 @@ fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
     .0;
 
     test_serialization!(ProofG1, proof);
+    test_proof_serialized_size!(proof);
 
     let mut verifier_statements = Statements::new();
     verifier_statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
@@ -137,12 +138,7 @@ fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
 
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            verifier_proof_spec.clone(),
-            None,
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 
     // Proof with wrong public input fails
@@ -167,7 +163,7 @@ fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
     );
     verifier_proof_spec_1.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec_1, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec_1, None, Default::default())
         .is_err());
 
     // Proof with wrong meta statement fails. Here the relation being proven in Circom is correct but
@@ -189,7 +185,7 @@ fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover_1,
+        &proof_spec_prover_1,
         witnesses.clone(),
         None,
         Default::default(),
@@ -205,7 +201,7 @@ fn pok_of_bbs_plus_sig_and_attributes_not_equals_check() {
     );
     proof_spec_verifier_2.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier_2, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier_2, None, Default::default())
         .is_err());
 }
 
@@ -301,7 +297,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
 
         let proof = ProofG1::new::<StdRng, Blake2b512>(
             rng,
-            proof_spec_prover.clone(),
+            &proof_spec_prover,
             witnesses.clone(),
             None,
             Default::default(),
@@ -336,12 +332,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
 
         proof
             .clone()
-            .verify::<StdRng, Blake2b512>(
-                rng,
-                verifier_proof_spec.clone(),
-                None,
-                Default::default(),
-            )
+            .verify::<StdRng, Blake2b512>(rng, &verifier_proof_spec, None, Default::default())
             .unwrap();
 
         // Proof with wrong public input fails
@@ -363,7 +354,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
         );
         verifier_proof_spec_1.validate().unwrap();
         assert!(proof
-            .verify::<StdRng, Blake2b512>(rng, verifier_proof_spec_1, None, Default::default())
+            .verify::<StdRng, Blake2b512>(rng, &verifier_proof_spec_1, None, Default::default())
             .is_err());
 
         // -----------------------------------------------------------------------------
@@ -401,7 +392,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
 
         let proof_1 = ProofG1::new::<StdRng, Blake2b512>(
             rng,
-            proof_spec_prover_1,
+            &proof_spec_prover_1,
             witnesses_1.clone(),
             None,
             Default::default(),
@@ -429,7 +420,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
         verifier_proof_spec_2.validate().unwrap();
         proof_1
             .clone()
-            .verify::<StdRng, Blake2b512>(rng, verifier_proof_spec_2, None, Default::default())
+            .verify::<StdRng, Blake2b512>(rng, &verifier_proof_spec_2, None, Default::default())
             .unwrap();
 
         // Proof with wrong public input fails
@@ -450,7 +441,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
         );
         verifier_proof_spec_3.validate().unwrap();
         assert!(proof_1
-            .verify::<StdRng, Blake2b512>(rng, verifier_proof_spec_3, None, Default::default())
+            .verify::<StdRng, Blake2b512>(rng, &verifier_proof_spec_3, None, Default::default())
             .is_err());
     }
 
@@ -553,7 +544,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover,
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -594,7 +585,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
     verifier_proof_spec.validate().unwrap();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 
     // Proof with wrong public input fails
@@ -622,7 +613,7 @@ fn pok_of_bbs_plus_sig_and_attributes_less_than_check() {
     );
     verifier_proof_spec_1.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec_1, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec_1, None, Default::default())
         .is_err());
 }
 
@@ -713,7 +704,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover.clone(),
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -749,12 +740,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
 
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            verifier_proof_spec.clone(),
-            None,
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 
     // Proof with wrong public input fails
@@ -776,7 +762,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
     );
     verifier_proof_spec_1.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec_1, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec_1, None, Default::default())
         .is_err());
 
     // Proof with wrong meta statement fails. Here the relation being proven in Circom is correct but
@@ -803,7 +789,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover_1,
+        &proof_spec_prover_1,
         witnesses.clone(),
         None,
         Default::default(),
@@ -819,7 +805,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
     );
     proof_spec_verifier_2.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier_2, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier_2, None, Default::default())
         .is_err());
 
     // ---------------- Case 2 ----------------------------------------------
@@ -866,7 +852,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover.clone(),
+        &proof_spec_prover,
         witnesses.clone(),
         None,
         Default::default(),
@@ -902,12 +888,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
 
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            verifier_proof_spec.clone(),
-            None,
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 
     // Proof with wrong public input fails
@@ -928,7 +909,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
     );
     verifier_proof_spec_1.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec_1, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec_1, None, Default::default())
         .is_err());
 
     // Proof with wrong meta statement fails. Here the relation being proven in Circom is correct but
@@ -950,7 +931,7 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
 
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        proof_spec_prover_1,
+        &proof_spec_prover_1,
         witnesses.clone(),
         None,
         Default::default(),
@@ -966,6 +947,6 @@ fn pok_of_bbs_plus_sig_and_multiplication_check() {
     );
     proof_spec_verifier_2.validate().unwrap();
     assert!(proof
-        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier_2, None, Default::default())
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec_verifier_2, None, Default::default())
         .is_err());
 }
@@ -35,7 +35,7 @@ use saver::{
 };
 use std::time::Instant;
 
-use test_utils::{bbs::*, test_serialization, Fr, ProofG1};
+use test_utils::{bbs::*, test_proof_serialized_size, test_serialization, Fr, ProofG1};
 
 pub fn decrypt_and_verify(
     proof: &ProofG1,
@@ -145,7 +145,7 @@ macro_rules! gen_tests {
             let start = Instant::now();
             let (proof, comm_rand) = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                prover_proof_spec.clone(),
+                &prover_proof_spec,
                 witnesses.clone(),
                 None,
                 Default::default(),
@@ -158,6 +158,7 @@ macro_rules! gen_tests {
             );
 
             test_serialization!(ProofG1, proof);
+            test_proof_serialized_size!(proof);
 
             let mut verifier_statements = Statements::new();
             verifier_statements.add($stmt::new_statement_from_params(
@@ -193,7 +194,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     Default::default(),
                 )
@@ -208,10 +209,11 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -226,10 +228,11 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(true),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -266,10 +269,13 @@ macro_rules! gen_tests {
             let config = ProverConfig::<Bls12_381> {
                 reuse_saver_proofs: Some(m),
                 reuse_legogroth16_proofs: None,
+                coalesce_pedersen_commitments: false,
+                send_fully_revealed_bbs_plus_signatures: false,
+                thread_pool: Default::default(),
             };
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                prover_proof_spec.clone(),
+                &prover_proof_spec,
                 witnesses.clone(),
                 None,
                 config,
@@ -285,7 +291,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     Default::default(),
                 )
@@ -318,7 +324,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                prover_proof_spec,
+                &prover_proof_spec,
                 witnesses.clone(),
                 None,
                 Default::default(),
@@ -334,7 +340,7 @@ macro_rules! gen_tests {
             );
             verifier_proof_spec.validate().unwrap();
             assert!(proof
-                .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+                .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
                 .is_err());
 
             // Verifiably encrypt a message which was not signed
@@ -351,7 +357,7 @@ macro_rules! gen_tests {
 
             let proof = ProofG1::new::<StdRng, Blake2b512>(
                 &mut rng,
-                prover_proof_spec,
+                &prover_proof_spec,
                 witnesses_wrong,
                 None,
                 Default::default(),
@@ -366,7 +372,7 @@ macro_rules! gen_tests {
                 .clone()
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec.clone(),
+                    &verifier_proof_spec,
                     None,
                     Default::default()
                 )
@@ -374,10 +380,11 @@ macro_rules! gen_tests {
             assert!(proof
                 .verify::<StdRng, Blake2b512>(
                     &mut rng,
-                    verifier_proof_spec,
+                    &verifier_proof_spec,
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -475,7 +482,7 @@ macro_rules! gen_tests {
                 let start = Instant::now();
                 let (proof, comm_rand) = ProofG1::new::<StdRng, Blake2b512>(
                     &mut rng,
-                    prover_proof_spec.clone(),
+                    &prover_proof_spec,
                     witnesses.clone(),
                     None,
                     Default::default(),
@@ -541,7 +548,7 @@ macro_rules! gen_tests {
                     .clone()
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         Default::default(),
                     )
@@ -558,10 +565,11 @@ macro_rules! gen_tests {
                     .clone()
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         VerifierConfig {
                             use_lazy_randomized_pairing_checks: Some(false),
+                            ..Default::default()
                         },
                     )
                     .unwrap();
@@ -577,10 +585,11 @@ macro_rules! gen_tests {
                     .clone()
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         VerifierConfig {
                             use_lazy_randomized_pairing_checks: Some(true),
+                            ..Default::default()
                         },
                     )
                     .unwrap();
@@ -623,10 +632,13 @@ macro_rules! gen_tests {
                 let config = ProverConfig::<Bls12_381> {
                     reuse_saver_proofs: Some(m),
                     reuse_legogroth16_proofs: None,
+                    coalesce_pedersen_commitments: false,
+                    send_fully_revealed_bbs_plus_signatures: false,
+                    thread_pool: Default::default(),
                 };
                 let proof = ProofG1::new::<StdRng, Blake2b512>(
                     &mut rng,
-                    prover_proof_spec.clone(),
+                    &prover_proof_spec,
                     witnesses.clone(),
                     None,
                     config,
@@ -642,7 +654,7 @@ macro_rules! gen_tests {
                 proof
                     .verify::<StdRng, Blake2b512>(
                         &mut rng,
-                        verifier_proof_spec.clone(),
+                        &verifier_proof_spec,
                         None,
                         Default::default(),
                     )
@@ -880,7 +892,7 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
         let start = Instant::now();
         let (proof, comm_rand) = ProofG1::new::<StdRng, Blake2b512>(
             &mut rng,
-            prover_proof_spec.clone(),
+            &prover_proof_spec,
             witnesses.clone(),
             None,
             Default::default(),
@@ -1017,12 +1029,7 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
         let start = Instant::now();
         proof
             .clone()
-            .verify::<StdRng, Blake2b512>(
-                &mut rng,
-                verifier_proof_spec.clone(),
-                None,
-                Default::default(),
-            )
+            .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
             .unwrap();
         println!(
             "Time taken to verify proof of verifiable encryption of 4 messages in signature: {:?}",
@@ -1034,10 +1041,11 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
             .clone()
             .verify::<StdRng, Blake2b512>(
                 &mut rng,
-                verifier_proof_spec.clone(),
+                &verifier_proof_spec,
                 None,
                 VerifierConfig {
                     use_lazy_randomized_pairing_checks: Some(false),
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1051,10 +1059,11 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
             .clone()
             .verify::<StdRng, Blake2b512>(
                 &mut rng,
-                verifier_proof_spec.clone(),
+                &verifier_proof_spec,
                 None,
                 VerifierConfig {
                     use_lazy_randomized_pairing_checks: Some(false),
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1116,10 +1125,13 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
         let config = ProverConfig::<Bls12_381> {
             reuse_saver_proofs: Some(m),
             reuse_legogroth16_proofs: None,
+            coalesce_pedersen_commitments: false,
+            send_fully_revealed_bbs_plus_signatures: false,
+            thread_pool: Default::default(),
         };
         let proof = ProofG1::new::<StdRng, Blake2b512>(
             &mut rng,
-            prover_proof_spec.clone(),
+            &prover_proof_spec,
             witnesses.clone(),
             None,
             config,
@@ -1132,12 +1144,7 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
         );
 
         proof
-            .verify::<StdRng, Blake2b512>(
-                &mut rng,
-                verifier_proof_spec.clone(),
-                None,
-                Default::default(),
-            )
+            .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
             .unwrap();
     }
 
@@ -1287,7 +1294,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
     let start = Instant::now();
     let (proof, comm_rand) = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        prover_proof_spec.clone(),
+        &prover_proof_spec,
         witnesses.clone(),
         None,
         Default::default(),
@@ -1339,12 +1346,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
     let start = Instant::now();
     proof
         .clone()
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            verifier_proof_spec.clone(),
-            None,
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
     println!(
         "Time taken to verify proof of 2 bound checks and 1 verifiable encryption: {:?}",
@@ -1356,10 +1358,11 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
         .clone()
         .verify::<StdRng, Blake2b512>(
             &mut rng,
-            verifier_proof_spec.clone(),
+            &verifier_proof_spec,
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -1400,11 +1403,12 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
     let config = ProverConfig::<Bls12_381> {
         reuse_saver_proofs: Some(g),
         reuse_legogroth16_proofs: Some(l),
+        ..Default::default()
     };
     let start = Instant::now();
     let proof = ProofG1::new::<StdRng, Blake2b512>(
         &mut rng,
-        prover_proof_spec.clone(),
+        &prover_proof_spec,
         witnesses.clone(),
         None,
         config,
@@ -1417,11 +1421,6 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
         start.elapsed()
     );
     proof
-        .verify::<StdRng, Blake2b512>(
-            &mut rng,
-            verifier_proof_spec.clone(),
-            None,
-            Default::default(),
-        )
+        .verify::<StdRng, Blake2b512>(&mut rng, &verifier_proof_spec, None, Default::default())
         .unwrap();
 }
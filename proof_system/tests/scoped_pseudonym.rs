@@ -0,0 +1,135 @@
+use ark_bls12_381::{Bls12_381, G1Affine};
+use ark_std::{
+    collections::BTreeMap,
+    rand::{prelude::StdRng, SeedableRng},
+};
+use blake2::Blake2b512;
+
+use proof_system::{
+    predicates::scoped_pseudonym::ScopedPseudonym,
+    prelude::{MetaStatements, ProofSpec, Witnesses},
+    statement::{bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt, Statements},
+    witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
+};
+
+use test_utils::{bbs::*, ProofG1};
+
+#[test]
+fn pok_of_bbs_plus_sig_with_scoped_pseudonym() {
+    // Prover presents a pseudonym of a signed message for a scope. Presentations for the same scope have the same
+    // pseudonym while presentations for different scopes have different ones.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let secret_msg_idx = 2;
+    let (msgs, sig_params, keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let proof_spec = |pseudonym: &ScopedPseudonym<G1Affine>| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        let mut proof_spec =
+            ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+        assert_eq!(pseudonym.add_to_proof_spec(&mut proof_spec), 1);
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+    let witnesses = |pseudonym: &ScopedPseudonym<G1Affine>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        pseudonym.add_witness(&mut witnesses, msgs[secret_msg_idx]);
+        witnesses
+    };
+
+    let mut present = |scope: &[u8]| {
+        let prover_pseudonym = ScopedPseudonym::<G1Affine>::generate::<Blake2b512>(
+            (0, secret_msg_idx),
+            scope.to_vec(),
+            msgs[secret_msg_idx],
+        );
+        let nonce = Some(b"test-nonce".to_vec());
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec(&prover_pseudonym),
+            witnesses(&prover_pseudonym),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+
+        // Verifier only receives the pseudonym and creates the base from the scope itself
+        let verifier_pseudonym = ScopedPseudonym::<G1Affine>::new::<Blake2b512>(
+            (0, secret_msg_idx),
+            scope.to_vec(),
+            prover_pseudonym.pseudonym,
+        );
+        assert_eq!(verifier_pseudonym, prover_pseudonym);
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                &proof_spec(&verifier_pseudonym),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+        (verifier_pseudonym.pseudonym, proof, nonce)
+    };
+
+    let (pseudonym_1, _, _) = present(b"example.com");
+    let (pseudonym_2, proof_2, nonce_2) = present(b"example.com");
+    let (pseudonym_3, _, _) = present(b"example.org");
+
+    // Same scope gives the same pseudonym but a different scope gives a different one
+    assert_eq!(pseudonym_1, pseudonym_2);
+    assert_ne!(pseudonym_1, pseudonym_3);
+
+    // Pseudonym for one scope can't be presented for another scope
+    let wrong_scope = ScopedPseudonym::<G1Affine>::new::<Blake2b512>(
+        (0, secret_msg_idx),
+        b"example.org".to_vec(),
+        pseudonym_2,
+    );
+    assert!(proof_2
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec(&wrong_scope),
+            nonce_2,
+            Default::default()
+        )
+        .is_err());
+
+    // Pseudonym of a different message is different and can't be proven for the signed message
+    let other_msg_pseudonym = ScopedPseudonym::<G1Affine>::generate::<Blake2b512>(
+        (0, secret_msg_idx),
+        b"example.com".to_vec(),
+        msgs[secret_msg_idx + 1],
+    );
+    assert_ne!(other_msg_pseudonym.pseudonym, pseudonym_1);
+    let spec = proof_spec(&other_msg_pseudonym);
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    other_msg_pseudonym.add_witness(&mut witnesses, msgs[secret_msg_idx + 1]);
+    let nonce = Some(b"test-nonce".to_vec());
+    assert!(ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .and_then(|(proof, _)| {
+        proof.verify::<StdRng, Blake2b512>(&mut rng, &spec, nonce, Default::default())
+    })
+    .is_err());
+}
@@ -0,0 +1,282 @@
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective};
+use ark_ec::CurveGroup;
+use ark_ff::One;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    rand::{prelude::StdRng, SeedableRng},
+    UniformRand,
+};
+use bbs_plus::prelude::{KeypairG2, SignatureG1, SignatureParamsG1};
+use blake2::Blake2b512;
+
+use proof_system::{
+    error::ProofSystemError,
+    prelude::{
+        EqualWitnesses, MetaStatements, ProofSpec, StatementDisjunction, VerifierConfig, Witnesses,
+    },
+    statement::{
+        bbs_plus::PoKBBSSignatureG1 as PoKSignatureBBSG1Stmt,
+        ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements,
+    },
+    statement_proof::StatementProof,
+    witness::{NoopWitness, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit},
+};
+
+use test_utils::{test_serialization, Fr, ProofG1};
+
+#[test]
+fn disjunction_of_bbs_plus_sigs() {
+    // Prover proves knowledge of a signature by one of 2 issuers, each with its own statement, while holding a
+    // signature by only one of them
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, msg_count);
+    let keypairs = (0..2)
+        .map(|_| KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params))
+        .collect::<Vec<_>>();
+
+    // Both credentials have the same 1st message which is revealed
+    let revealed = Fr::rand(&mut rng);
+    let mut revealed_msgs = BTreeMap::new();
+    revealed_msgs.insert(0, revealed);
+    let credential = |rng: &mut StdRng, keypair: &KeypairG2<Bls12_381>| {
+        let mut msgs = (0..msg_count).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        msgs[0] = revealed;
+        let sig = SignatureG1::new(rng, &msgs, &keypair.secret_key, &sig_params).unwrap();
+        let unrevealed_msgs = msgs
+            .into_iter()
+            .enumerate()
+            .skip(1)
+            .collect::<BTreeMap<_, _>>();
+        (sig, unrevealed_msgs)
+    };
+    let credentials = keypairs
+        .iter()
+        .map(|kp| credential(&mut rng, kp))
+        .collect::<Vec<_>>();
+
+    let mut statements = Statements::new();
+    for kp in &keypairs {
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            kp.public_key.clone(),
+            revealed_msgs.clone(),
+        ));
+    }
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_statement_disjunction(StatementDisjunction(
+        vec![0, 1].into_iter().collect::<BTreeSet<usize>>(),
+    ));
+    let proof_spec =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements.clone(), meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+    test_serialization!(ProofSpec<Bls12_381, G1Affine>, proof_spec);
+
+    // Witness for the statement of the held credential and a `Noop` witness for the other
+    let witnesses = |held: usize| {
+        let mut witnesses = Witnesses::new();
+        for (i, (sig, unrevealed_msgs)) in credentials.iter().enumerate() {
+            if i == held {
+                witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+                    sig.clone(),
+                    unrevealed_msgs.clone(),
+                ));
+            } else {
+                witnesses.add(NoopWitness::new_as_witness());
+            }
+        }
+        witnesses
+    };
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let mut proofs = vec![];
+    for held in 0..2 {
+        let proof = ProofG1::new::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            witnesses(held),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        test_serialization!(ProofG1, proof);
+
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+            .unwrap();
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                &proof_spec,
+                nonce.clone(),
+                VerifierConfig {
+                    use_lazy_randomized_pairing_checks: Some(true),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        proofs.push(proof);
+    }
+
+    // The proofs have the same structure whichever credential is held
+    for i in 0..2 {
+        for proof in &proofs {
+            assert!(matches!(
+                proof.statement_proofs[i],
+                StatementProof::PoKBBSSignatureG1Disjunct(_)
+            ));
+        }
+        assert_eq!(
+            proofs[0].statement_proofs[i].compressed_len(),
+            proofs[1].statement_proofs[i].compressed_len()
+        );
+    }
+    assert_eq!(proofs[0].compressed_len(), proofs[1].compressed_len());
+
+    // The challenges of the statements' proofs must add up to the challenge of the proof
+    let mut tampered = proofs[0].clone();
+    match &mut tampered.statement_proofs[1] {
+        StatementProof::PoKBBSSignatureG1Disjunct(p) => p.pk_proof.challenges[0] += Fr::one(),
+        _ => unreachable!(),
+    }
+    assert!(matches!(
+        tampered.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &proof_spec,
+            nonce.clone(),
+            Default::default()
+        ),
+        Err(ProofSystemError::InvalidStatementDisjunctionProof(0))
+    ));
+
+    // A disjunction's proof doesn't verify for the statements without the disjunction
+    let without_disjunction =
+        ProofSpec::<Bls12_381, G1Affine>::new(statements, MetaStatements::new(), vec![], None);
+    assert!(proofs[0]
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            &without_disjunction,
+            nonce.clone(),
+            Default::default()
+        )
+        .is_err());
+
+    // A signature by the other issuer doesn't satisfy the statement
+    let mut witnesses = Witnesses::new();
+    let (sig, unrevealed_msgs) = credentials[1].clone();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    witnesses.add(NoopWitness::new_as_witness());
+    let proof = ProofG1::new::<StdRng, Blake2b512>(
+        &mut rng,
+        &proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, &proof_spec, nonce.clone(), Default::default())
+        .is_err());
+
+    // Exactly one statement of the disjunction should have a witness
+    let mut no_witness = Witnesses::new();
+    no_witness.add(NoopWitness::new_as_witness());
+    no_witness.add(NoopWitness::new_as_witness());
+    let mut both_witnesses = Witnesses::new();
+    for (sig, unrevealed_msgs) in credentials {
+        both_witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    }
+    for witnesses in [no_witness, both_witnesses] {
+        assert!(matches!(
+            ProofG1::new::<StdRng, Blake2b512>(
+                &mut rng,
+                &proof_spec,
+                witnesses,
+                nonce.clone(),
+                Default::default(),
+            ),
+            Err(ProofSystemError::UnsatisfiedStatementDisjunction(0))
+        ));
+    }
+}
+
+#[test]
+fn invalid_statement_disjunctions() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, 3);
+    let keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &sig_params);
+    let bases = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+
+    // 3 signature statements and a Pedersen commitment
+    let mut statements = Statements::<Bls12_381, G1Affine>::new();
+    for _ in 0..3 {
+        statements.add(PoKSignatureBBSG1Stmt::new_statement_from_params(
+            sig_params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+    }
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        G1Projective::rand(&mut rng).into_affine(),
+    ));
+
+    let disjunction =
+        |s: Vec<usize>| StatementDisjunction(s.into_iter().collect::<BTreeSet<usize>>());
+    let check = |meta_statements: MetaStatements, invalid: usize| {
+        let proof_spec = ProofSpec::new(statements.clone(), meta_statements, vec![], None);
+        assert!(matches!(
+            proof_spec.validate(),
+            Err(ProofSystemError::InvalidStatementDisjunction(i)) if i == invalid
+        ));
+    };
+
+    // Fewer than 2 statements
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_statement_disjunction(disjunction(vec![0]));
+    check(meta_statements, 0);
+
+    // Statement which isn't a signature or doesn't exist
+    for s_idx in [3, 4] {
+        let mut meta_statements = MetaStatements::new();
+        meta_statements.add_statement_disjunction(disjunction(vec![0, s_idx]));
+        check(meta_statements, 0);
+    }
+
+    // Statement in 2 disjunctions
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_statement_disjunction(disjunction(vec![0, 1]));
+    meta_statements.add_statement_disjunction(disjunction(vec![1, 2]));
+    check(meta_statements, 1);
+
+    // Statement with a witness in a witness equality
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(2, 0), (3, 0)]
+            .into_iter()
+            .collect::<BTreeSet<(usize, usize)>>(),
+    ));
+    meta_statements.add_statement_disjunction(disjunction(vec![1, 2]));
+    check(meta_statements, 1);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_statement_disjunction(disjunction(vec![0, 1]));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(2, 0), (3, 0)]
+            .into_iter()
+            .collect::<BTreeSet<(usize, usize)>>(),
+    ));
+    ProofSpec::new(statements, meta_statements, vec![], None)
+        .validate()
+        .unwrap();
+}
@@ -45,7 +45,10 @@ use ark_std::{cfg_iter, fmt::Debug, io::Write, ops::Add, vec::Vec};
 use digest::Digest;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use dock_crypto_utils::hashing_utils::field_elem_from_try_and_incr;
+use dock_crypto_utils::{
+    hashing_utils::field_elem_from_try_and_incr,
+    msm::{ArkMSM, MultiScalarMul},
+};
 
 use dock_crypto_utils::serde_utils::*;
 use serde::{Deserialize, Serialize};
@@ -94,7 +97,16 @@ where
     /// Create commitment as `bases[0] * blindings[0] + bases[1] * blindings[1] + ... + bases[i] * blindings[i]`
     /// for step-1 of the protocol. Extra `bases` or `blindings` are ignored.
     pub fn new(bases: &[G], blindings: Vec<G::ScalarField>) -> Self {
-        let t = G::Group::msm_unchecked(bases, &blindings).into_affine();
+        Self::new_with_msm(bases, blindings, &ArkMSM)
+    }
+
+    /// Same as [`Self::new`] but uses the given backend for the multi-scalar multiplication
+    pub fn new_with_msm(
+        bases: &[G],
+        blindings: Vec<G::ScalarField>,
+        msm: &dyn MultiScalarMul<G>,
+    ) -> Self {
+        let t = msm.msm(bases, &blindings).into_affine();
         Self { blindings, t }
     }
 
@@ -391,6 +403,45 @@ mod tests {
         test_schnorr_in_group!(G2, G2Affine);
     }
 
+    #[test]
+    fn schnorr_commitment_with_custom_msm() {
+        use ark_std::Zero;
+
+        // An MSM backend which multiplies each base separately
+        struct SlowMSM;
+
+        impl<G: AffineRepr> MultiScalarMul<G> for SlowMSM {
+            fn msm(&self, bases: &[G], scalars: &[G::ScalarField]) -> G::Group {
+                bases
+                    .iter()
+                    .zip(scalars.iter())
+                    .fold(G::Group::zero(), |acc, (b, s)| {
+                        acc + b.mul_bigint(s.into_bigint())
+                    })
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let count = 10;
+        let bases = (0..count)
+            .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let witnesses = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let y = <Bls12_381 as Pairing>::G1::msm_unchecked(&bases, &witnesses).into_affine();
+        let blindings = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        let comm = SchnorrCommitment::new(&bases, blindings.clone());
+        let comm_with_custom_msm = SchnorrCommitment::new_with_msm(&bases, blindings, &SlowMSM);
+        assert_eq!(comm, comm_with_custom_msm);
+
+        let challenge = Fr::rand(&mut rng);
+        let resp = comm_with_custom_msm
+            .response(&witnesses, &challenge)
+            .unwrap();
+        resp.is_valid(&bases, &y, &comm_with_custom_msm.t, &challenge)
+            .unwrap();
+    }
+
     #[test]
     fn schnorr_single() {
         let mut rng = StdRng::seed_from_u64(0u64);
@@ -261,14 +261,14 @@ impl<E: Pairing> CCSArbitraryRangeProof<E> {
                 E::G2Prepared::from(yc_sigma_min[i]),
             );
             if lhs_min[i] != rhs {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
             let rhs = E::pairing(
                 E::G1Prepared::from(self.V_max[i]),
                 E::G2Prepared::from(yc_sigma_max[i]),
             );
             if lhs_max[i] != rhs {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
         }
         Ok(())
@@ -350,12 +350,15 @@ impl<E: Pairing> CCSArbitraryRangeProof<E> {
         }
         params.validate_base(self.base)?;
         let l = find_l_greater_than(max, self.base) as usize;
-        assert_eq!(self.V_min.len(), l);
-        assert_eq!(self.V_max.len(), l);
-        assert_eq!(self.a_min.len(), l);
-        assert_eq!(self.a_max.len(), l);
-        assert_eq!(self.z_sigma_min.len(), l);
-        assert_eq!(self.z_sigma_max.len(), l);
+        if self.V_min.len() != l
+            || self.V_max.len() != l
+            || self.a_min.len() != l
+            || self.a_max.len() != l
+            || self.z_sigma_min.len() != l
+            || self.z_sigma_max.len() != l
+        {
+            return Err(SmcRangeProofError::InvalidRangeProof);
+        }
         Ok(())
     }
 
@@ -551,6 +554,21 @@ mod tests {
                         .unwrap();
                     verifying_time += start.elapsed();
 
+                    // Proof with a tampered digit fails with the index of that digit
+                    let mut tampered_proof = proof.clone();
+                    tampered_proof.V_max[1] = tampered_proof.V_max[0];
+                    assert!(matches!(
+                        tampered_proof.verify(
+                            &commitment,
+                            &challenge_verifier,
+                            min,
+                            max,
+                            &comm_key,
+                            params.clone(),
+                        ),
+                        Err(SmcRangeProofError::InvalidRangeProofAtDigit(1))
+                    ));
+
                     let start = Instant::now();
                     let mut pairing_checker =
                         RandomizedPairingChecker::new_using_rng(&mut rng, true);
@@ -12,7 +12,7 @@ use ark_std::{cfg_into_iter, format, io::Write, rand::RngCore, vec::Vec, Uniform
 use dock_crypto_utils::misc::n_rand;
 
 use crate::ccs_range_proof::util::{check_commitment_for_arbitrary_range, find_l_greater_than};
-use dock_crypto_utils::msm::multiply_field_elems_with_same_group_elem;
+use dock_crypto_utils::msm::{multiply_field_elems_with_same_group_elem, WindowTable};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -226,6 +226,28 @@ impl<E: Pairing> CCSArbitraryRangeWithKVProof<E> {
         comm_key: &MemberCommitmentKey<E::G1Affine>,
         params: &SetMembershipCheckParams<E>,
         secret_key: &SecretKey<E::ScalarField>,
+    ) -> Result<(), SmcRangeProofError> {
+        let g1_table = WindowTable::new(
+            self.z_v_min.len() + self.z_v_max.len(),
+            params.bb_sig_params.g1.into_group(),
+        );
+        self.verify_given_g1_table(
+            commitment, challenge, min, max, comm_key, params, secret_key, &g1_table,
+        )
+    }
+
+    /// Same as `Self::verify` but uses the given window table of `params.bb_sig_params.g1` rather than creating
+    /// it. Useful when the verifier verifies many proofs with the same params.
+    pub fn verify_given_g1_table(
+        &self,
+        commitment: &E::G1Affine,
+        challenge: &E::ScalarField,
+        min: u64,
+        max: u64,
+        comm_key: &MemberCommitmentKey<E::G1Affine>,
+        params: &SetMembershipCheckParams<E>,
+        secret_key: &SecretKey<E::ScalarField>,
+        g1_table: &WindowTable<E::G1>,
     ) -> Result<(), SmcRangeProofError> {
         self.non_crypto_validate(min, max, &params)?;
         check_commitment_for_arbitrary_range::<E>(
@@ -241,22 +263,21 @@ impl<E: Pairing> CCSArbitraryRangeWithKVProof<E> {
             comm_key,
         )?;
 
-        let g1 = params.bb_sig_params.g1.into_group();
-        let g1v_min = multiply_field_elems_with_same_group_elem(g1.clone(), &self.z_v_min);
-        let g1v_max = multiply_field_elems_with_same_group_elem(g1, &self.z_v_max);
+        let g1v_min = g1_table.multiply_many(&self.z_v_min);
+        let g1v_max = g1_table.multiply_many(&self.z_v_max);
 
         for i in 0..self.V_min.len() {
             if self.a_min[i]
                 != (self.V_min[i] * (secret_key.0 * challenge - self.z_sigma_min[i]) + g1v_min[i])
                     .into_affine()
             {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
             if self.a_max[i]
                 != (self.V_max[i] * (secret_key.0 * challenge - self.z_sigma_max[i]) + g1v_max[i])
                     .into_affine()
             {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
         }
         Ok(())
@@ -296,12 +317,15 @@ impl<E: Pairing> CCSArbitraryRangeWithKVProof<E> {
         }
         params.validate_base(self.base)?;
         let l = find_l_greater_than(max, self.base) as usize;
-        assert_eq!(self.V_min.len(), l);
-        assert_eq!(self.V_max.len(), l);
-        assert_eq!(self.a_min.len(), l);
-        assert_eq!(self.a_max.len(), l);
-        assert_eq!(self.z_sigma_min.len(), l);
-        assert_eq!(self.z_sigma_max.len(), l);
+        if self.V_min.len() != l
+            || self.V_max.len() != l
+            || self.a_min.len() != l
+            || self.a_max.len() != l
+            || self.z_sigma_min.len() != l
+            || self.z_sigma_max.len() != l
+        {
+            return Err(SmcRangeProofError::InvalidRangeProof);
+        }
         Ok(())
     }
 }
@@ -175,7 +175,7 @@ impl<E: Pairing> CCSPerfectRangeWithKVProof<E> {
         for i in 0..self.V.len() {
             // Check a[i] == V[i] * (challenge * secret_key - z_sigma[i]) + g1 * z_v[i]
             if self.a[i] != (self.V[i] * (sk_c - self.z_sigma[i]) + g1v[i]).into_affine() {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
         }
         Ok(())
@@ -200,10 +200,10 @@ impl<E: Pairing> CCSPerfectRangeWithKVProof<E> {
     ) -> Result<(), SmcRangeProofError> {
         params.validate_base(self.base)?;
         let l = find_l(max, self.base) as usize;
-        assert_eq!(self.V.len(), l);
-        assert_eq!(self.a.len(), l);
-        assert_eq!(self.z_v.len(), l);
-        assert_eq!(self.z_sigma.len(), l);
+        if self.V.len() != l || self.a.len() != l || self.z_v.len() != l || self.z_sigma.len() != l
+        {
+            return Err(SmcRangeProofError::InvalidRangeProof);
+        }
         Ok(())
     }
 }
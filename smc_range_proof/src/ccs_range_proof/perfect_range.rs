@@ -184,7 +184,7 @@ impl<E: Pairing> CCSPerfectRangeProof<E> {
                 E::G2Prepared::from(yc_sigma[i]),
             );
             if lhs[i] != rhs {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
         }
         Ok(())
@@ -239,10 +239,10 @@ impl<E: Pairing> CCSPerfectRangeProof<E> {
     ) -> Result<(), SmcRangeProofError> {
         params.validate_base(self.base)?;
         let l = find_l(max, self.base) as usize;
-        assert_eq!(self.V.len(), l);
-        assert_eq!(self.a.len(), l);
-        assert_eq!(self.z_v.len(), l);
-        assert_eq!(self.z_sigma.len(), l);
+        if self.V.len() != l || self.a.len() != l || self.z_v.len() != l || self.z_sigma.len() != l
+        {
+            return Err(SmcRangeProofError::InvalidRangeProof);
+        }
         Ok(())
     }
 
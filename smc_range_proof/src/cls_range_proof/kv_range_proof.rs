@@ -12,7 +12,10 @@ use crate::{
 use dock_crypto_utils::misc::n_rand;
 
 use crate::prelude::SecretKey;
-use dock_crypto_utils::{ff::inner_product, msm::multiply_field_elems_with_same_group_elem};
+use dock_crypto_utils::{
+    ff::inner_product,
+    msm::{multiply_field_elems_with_same_group_elem, WindowTable},
+};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -206,6 +209,25 @@ impl<E: Pairing> CLSRangeProofWithKV<E> {
         comm_key: &MemberCommitmentKey<E::G1Affine>,
         params: &SetMembershipCheckParams<E>,
         secret_key: &SecretKey<E::ScalarField>,
+    ) -> Result<(), SmcRangeProofError> {
+        let g1_table = WindowTable::new(self.z_v.len(), params.bb_sig_params.g1.into_group());
+        self.verify_given_g1_table(
+            commitment, challenge, min, max, comm_key, params, secret_key, &g1_table,
+        )
+    }
+
+    /// Same as `Self::verify` but uses the given window table of `params.bb_sig_params.g1` rather than creating
+    /// it. Useful when the verifier verifies many proofs with the same params.
+    pub fn verify_given_g1_table(
+        &self,
+        commitment: &E::G1Affine,
+        challenge: &E::ScalarField,
+        min: u64,
+        max: u64,
+        comm_key: &MemberCommitmentKey<E::G1Affine>,
+        params: &SetMembershipCheckParams<E>,
+        secret_key: &SecretKey<E::ScalarField>,
+        g1_table: &WindowTable<E::G1>,
     ) -> Result<(), SmcRangeProofError> {
         self.non_crypto_validate(min, max, &params)?;
 
@@ -221,16 +243,13 @@ impl<E: Pairing> CLSRangeProofWithKV<E> {
             comm_key,
         )?;
 
-        let g1v = multiply_field_elems_with_same_group_elem(
-            params.bb_sig_params.g1.into_group(),
-            &self.z_v,
-        );
+        let g1v = g1_table.multiply_many(&self.z_v);
 
         let sk_c = secret_key.0 * challenge;
 
         for i in 0..self.V.len() {
             if self.a[i] != (self.V[i] * (sk_c - self.z_sigma[i]) + g1v[i]).into_affine() {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
         }
         Ok(())
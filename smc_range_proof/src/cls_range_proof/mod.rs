@@ -6,3 +6,4 @@ pub mod range_proof;
 
 pub use kv_range_proof::{CLSRangeProofWithKV, CLSRangeProofWithKVProtocol};
 pub use range_proof::{CLSRangeProof, CLSRangeProofProtocol};
+pub use util::decompose_value;
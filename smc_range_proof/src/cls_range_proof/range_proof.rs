@@ -239,7 +239,7 @@ impl<E: Pairing> CLSRangeProof<E> {
                 E::G2Prepared::from(yc_sigma[i]),
             );
             if lhs[i] != rhs {
-                return Err(SmcRangeProofError::InvalidRangeProof);
+                return Err(SmcRangeProofError::InvalidRangeProofAtDigit(i));
             }
         }
         Ok(())
@@ -334,7 +334,7 @@ mod tests {
     use crate::{
         ccs_set_membership::setup::SetMembershipCheckParams,
         cls_range_proof::util::{
-            find_number_of_digits, find_sumset_boundaries, solve_linear_equations,
+            decompose_value, find_number_of_digits, find_sumset_boundaries, solve_linear_equations,
         },
     };
     use ark_bls12_381::{Bls12_381, Fr, G1Affine};
@@ -390,6 +390,23 @@ mod tests {
         println!("Time for {} runs: {:?}", runs, start.elapsed());
     }
 
+    #[test]
+    fn decompose_value_check() {
+        for base in [2, 3, 4, 16] {
+            let (min, max) = (100, 1000);
+            for value in [min, 555, max - 1] {
+                let digits = decompose_value(value, min, max, base).unwrap();
+                assert!(digits.iter().all(|d| *d < base));
+            }
+            assert!(decompose_value(min - 1, min, max, base).is_none());
+            assert!(decompose_value(max, min, max, base).is_none());
+            assert!(decompose_value(min, max, min, base).is_none());
+        }
+        // Range too large to be scaled by `base - 1`
+        assert!(decompose_value(5, 0, u64::MAX, 4).is_none());
+        assert!(decompose_value(5, 0, 10, 1).is_none());
+    }
+
     #[test]
     fn cls_range_proof() {
         let mut rng = StdRng::seed_from_u64(0u64);
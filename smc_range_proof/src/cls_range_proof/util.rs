@@ -108,6 +108,28 @@ pub fn find_sumset_boundaries(max: u64, base: u16, num: u16) -> Vec<u64> {
     }
 }
 
+/// Digits the range proof decomposes `value` into when proving `min <= value < max`. Each digit is `< base`.
+/// Returns `None` if `value` is not within the bounds or the range is too large to be decomposed in this base.
+pub fn decompose_value(value: u64, min: u64, max: u64, base: u16) -> Option<Vec<u16>> {
+    if base < 2 || value < min || value >= max {
+        return None;
+    }
+    // The range is scaled by `base - 1` if it's not a multiple of it and the number of digits is found by
+    // multiplying with the base, neither should overflow
+    let b_1 = (base - 1) as u64;
+    if (max - min) > u64::MAX / b_1 / base as u64 {
+        return None;
+    }
+    let (range, randomness_multiple) = get_range_and_randomness_multiple(base, min, max);
+    let mut value = value - min;
+    if randomness_multiple != 1 {
+        value = value * b_1;
+    }
+    let l = find_number_of_digits(range, base);
+    let G = find_sumset_boundaries(range, base, l);
+    solve_linear_equations(value, &G, base)
+}
+
 pub fn solve_linear_equations(y: u64, coefficients: &[u64], u: u16) -> Option<Vec<u16>> {
     let n = coefficients.len();
     let mut solutions = vec![0; n];
@@ -9,6 +9,8 @@ pub enum SmcRangeProofError {
     CannotFindElementInSet,
     InvalidSetMembershipProof,
     InvalidRangeProof,
+    /// The check for the digit at this index in the base decomposition of the value failed
+    InvalidRangeProofAtDigit(usize),
     UnsupportedBase(u16, u16),
     InvalidRange(u64, u16),
     IncorrectBounds(String),
@@ -37,7 +37,8 @@ pub mod prelude {
             SetMembershipCheckParams, SetMembershipCheckParamsWithPairing,
         },
         cls_range_proof::{
-            CLSRangeProof, CLSRangeProofProtocol, CLSRangeProofWithKV, CLSRangeProofWithKVProtocol,
+            decompose_value, CLSRangeProof, CLSRangeProofProtocol, CLSRangeProofWithKV,
+            CLSRangeProofWithKVProtocol,
         },
         common::MemberCommitmentKey,
         error::SmcRangeProofError,
@@ -1,5 +1,6 @@
-use crate::Fr;
+use crate::{Fr, FrOf};
 use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
 use ark_std::rand::rngs::StdRng;
 use std::{collections::HashSet, hash::Hash};
 use vb_accumulator::{
@@ -19,12 +20,7 @@ pub fn setup_positive_accum(
     PositiveAccumulator<Bls12_381>,
     InMemoryState<Fr>,
 ) {
-    let params = SetupParams::<Bls12_381>::generate_using_rng(rng);
-    let keypair = Keypair::<Bls12_381>::generate_using_rng(rng, &params);
-
-    let accumulator = PositiveAccumulator::initialize(&params);
-    let state = InMemoryState::new();
-    (params, keypair, accumulator, state)
+    setup_positive_accum_generic::<Bls12_381>(rng)
 }
 
 pub fn setup_universal_accum(
@@ -37,8 +33,39 @@ pub fn setup_universal_accum(
     InMemoryInitialElements<Fr>,
     InMemoryState<Fr>,
 ) {
-    let params = SetupParams::<Bls12_381>::generate_using_rng(rng);
-    let keypair = Keypair::<Bls12_381>::generate_using_rng(rng, &params);
+    setup_universal_accum_generic::<Bls12_381>(rng, max)
+}
+
+/// Same as `setup_positive_accum` but for any pairing engine
+pub fn setup_positive_accum_generic<E: Pairing>(
+    rng: &mut StdRng,
+) -> (
+    SetupParams<E>,
+    Keypair<E>,
+    PositiveAccumulator<E>,
+    InMemoryState<FrOf<E>>,
+) {
+    let params = SetupParams::<E>::generate_using_rng(rng);
+    let keypair = Keypair::<E>::generate_using_rng(rng, &params);
+
+    let accumulator = PositiveAccumulator::initialize(&params);
+    let state = InMemoryState::new();
+    (params, keypair, accumulator, state)
+}
+
+/// Same as `setup_universal_accum` but for any pairing engine
+pub fn setup_universal_accum_generic<E: Pairing>(
+    rng: &mut StdRng,
+    max: u64,
+) -> (
+    SetupParams<E>,
+    Keypair<E>,
+    UniversalAccumulator<E>,
+    InMemoryInitialElements<FrOf<E>>,
+    InMemoryState<FrOf<E>>,
+) {
+    let params = SetupParams::<E>::generate_using_rng(rng);
+    let keypair = Keypair::<E>::generate_using_rng(rng, &params);
 
     let mut initial_elements = InMemoryInitialElements::new();
     let accumulator = UniversalAccumulator::initialize_with_all_random(
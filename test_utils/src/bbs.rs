@@ -1,10 +1,11 @@
 use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
 use ark_std::{rand::RngCore, UniformRand};
 use bbs_plus::prelude::{
     KeypairG2, Signature23G1, SignatureG1, SignatureParams23G1, SignatureParamsG1,
 };
 
-use crate::Fr;
+use crate::{Fr, FrOf};
 
 pub fn bbs_plus_sig_setup<R: RngCore>(
     rng: &mut R,
@@ -28,9 +29,32 @@ pub fn bbs_plus_sig_setup_given_messages<R: RngCore>(
     KeypairG2<Bls12_381>,
     SignatureG1<Bls12_381>,
 ) {
-    let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(rng, messages.len() as u32);
-    let keypair = KeypairG2::<Bls12_381>::generate_using_rng(rng, &params);
-    let sig = SignatureG1::<Bls12_381>::new(rng, messages, &keypair.secret_key, &params).unwrap();
+    bbs_plus_sig_setup_given_messages_generic::<Bls12_381, R>(rng, messages)
+}
+
+/// Same as `bbs_plus_sig_setup` but for any pairing engine
+pub fn bbs_plus_sig_setup_generic<E: Pairing, R: RngCore>(
+    rng: &mut R,
+    message_count: u32,
+) -> (
+    Vec<FrOf<E>>,
+    SignatureParamsG1<E>,
+    KeypairG2<E>,
+    SignatureG1<E>,
+) {
+    let messages: Vec<FrOf<E>> = (0..message_count).map(|_| <FrOf<E>>::rand(rng)).collect();
+    let (params, keypair, sig) = bbs_plus_sig_setup_given_messages_generic::<E, R>(rng, &messages);
+    (messages, params, keypair, sig)
+}
+
+/// Same as `bbs_plus_sig_setup_given_messages` but for any pairing engine
+pub fn bbs_plus_sig_setup_given_messages_generic<E: Pairing, R: RngCore>(
+    rng: &mut R,
+    messages: &[FrOf<E>],
+) -> (SignatureParamsG1<E>, KeypairG2<E>, SignatureG1<E>) {
+    let params = SignatureParamsG1::<E>::generate_using_rng(rng, messages.len() as u32);
+    let keypair = KeypairG2::<E>::generate_using_rng(rng, &params);
+    let sig = SignatureG1::<E>::new(rng, messages, &keypair.secret_key, &params).unwrap();
     sig.verify(messages, keypair.public_key.clone(), params.clone())
         .unwrap();
     (params, keypair, sig)
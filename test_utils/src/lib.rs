@@ -2,11 +2,18 @@ use ark_bls12_381::{Bls12_381, G1Affine};
 use ark_ec::pairing::Pairing;
 use proof_system::proof::Proof;
 
-pub type Fr = <Bls12_381 as Pairing>::ScalarField;
-pub type G1 = <Bls12_381 as Pairing>::G1Affine;
-pub type G2 = <Bls12_381 as Pairing>::G2Affine;
+pub type Fr = FrOf<Bls12_381>;
+pub type G1 = G1Of<Bls12_381>;
+pub type G2 = G2Of<Bls12_381>;
 pub type ProofG1 = Proof<Bls12_381, G1Affine>;
 
+/// Same as `Fr`, `G1`, `G2` and `ProofG1` but for any pairing engine, to run tests over curves other than
+/// BLS12-381
+pub type FrOf<E> = <E as Pairing>::ScalarField;
+pub type G1Of<E> = <E as Pairing>::G1Affine;
+pub type G2Of<E> = <E as Pairing>::G2Affine;
+pub type ProofG1Of<E> = Proof<E, <E as Pairing>::G1Affine>;
+
 pub mod accumulators;
 pub mod bbs;
 #[macro_use]
@@ -47,3 +47,19 @@ macro_rules! test_serialization {
         assert_eq!($obj, deser);
     };
 }
+
+/// Check that `Proof::compressed_len` and `StatementProof::compressed_len` give the size of the
+/// serialized proof and statement proofs
+#[macro_export]
+macro_rules! test_proof_serialized_size {
+    ($proof: expr) => {
+        let mut serz = vec![];
+        CanonicalSerialize::serialize_compressed(&$proof, &mut serz).unwrap();
+        assert_eq!($proof.compressed_len(), serz.len());
+        for sp in $proof.statement_proofs() {
+            let mut serz = vec![];
+            CanonicalSerialize::serialize_compressed(sp, &mut serz).unwrap();
+            assert_eq!(sp.compressed_len(), serz.len());
+        }
+    };
+}
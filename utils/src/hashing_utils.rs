@@ -3,6 +3,7 @@
 use crate::concat_slices;
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
+use ark_std::io::{self, Write};
 use digest::Digest;
 
 /// Hash bytes to a point on the curve. Returns as Projective coordinates. This is vulnerable to timing attack and is only used when input
@@ -38,3 +39,72 @@ pub fn field_elem_from_try_and_incr<F: PrimeField, D: Digest>(bytes: &[u8]) -> F
     }
     f.unwrap()
 }
+
+/// Same as `field_elem_from_try_and_incr` for the bytes that `hasher` has been updated with. As the bytes aren't
+/// needed again, they don't have to be in memory at once.
+pub fn field_elem_from_try_and_incr_given_digest<F: PrimeField, D: Digest + Clone>(hasher: D) -> F {
+    let mut hash = hasher.clone().finalize();
+    let mut f = F::from_random_bytes(&hash);
+    let mut j = 1u64;
+    while f.is_none() {
+        let mut h = hasher.clone();
+        h.update(b"-attempt-");
+        h.update(j.to_le_bytes());
+        hash = h.finalize();
+        f = F::from_random_bytes(&hash);
+        j += 1;
+    }
+    f.unwrap()
+}
+
+/// Adapter to write bytes directly to a hash function, like when serializing objects with `CanonicalSerialize`,
+/// instead of collecting them in a buffer and hashing the buffer.
+#[derive(Clone, Debug, Default)]
+pub struct DigestWriter<D: Digest>(pub D);
+
+impl<D: Digest> DigestWriter<D> {
+    pub fn new() -> Self {
+        Self(D::new())
+    }
+
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.update(data)
+    }
+
+    pub fn into_inner(self) -> D {
+        self.0
+    }
+}
+
+impl<D: Digest> Write for DigestWriter<D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+    use blake2::Blake2b512;
+
+    #[test]
+    fn field_elem_from_digest() {
+        // Around 1 in 10 of the inputs need more than one attempt
+        for i in 0..100u32 {
+            let bytes = concat_slices!(b"test-", i.to_le_bytes());
+            let mut writer = DigestWriter::<Blake2b512>::new();
+            writer.write_all(b"test-").unwrap();
+            writer.update(i.to_le_bytes());
+            assert_eq!(
+                field_elem_from_try_and_incr_given_digest::<Fr, _>(writer.into_inner()),
+                field_elem_from_try_and_incr::<Fr, Blake2b512>(&bytes)
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::{collections::VecDeque, vec::Vec};
 use itertools::{EitherOrBoth, Itertools};
 
 use super::try_iter::{try_pair_with_slice, try_validate, IndexIsOutOfBounds, SeqValidator};
@@ -18,6 +19,98 @@ where
         })
 }
 
+/// Same as `pluck_missed` but the indices don't need to be sorted or unique. The indices are collected into a
+/// vector which is sorted and deduplicated before plucking so this allocates and takes `O(k * log(k))` time for
+/// `k` indices. Prefer `pluck_missed` when the indices are known to be sorted.
+/// **This function implies that the items iterator is in the order of its indices.**
+pub fn pluck_missed_unsorted<Indices, Iter>(
+    indices: Indices,
+    iter: Iter,
+) -> impl Iterator<Item = Iter::Item>
+where
+    Indices: IntoIterator<Item = usize>,
+    Iter: IntoIterator,
+{
+    let mut indices = indices.into_iter().collect::<Vec<_>>();
+    indices.sort_unstable();
+    indices.dedup();
+    pluck_missed(indices, iter)
+}
+
+/// Same as `pluck_missed` but also returns the number of plucked items. The items are plucked eagerly to count
+/// them so the returned iterator is over the already plucked items.
+/// **This function implies that both iterators are sorted.**
+pub fn pluck_missed_count<Indices, Iter>(
+    indices: Indices,
+    iter: Iter,
+) -> (impl Iterator<Item = Iter::Item>, usize)
+where
+    Indices: IntoIterator<Item = usize>,
+    Iter: IntoIterator,
+{
+    let plucked = pluck_missed(indices, iter).collect::<Vec<_>>();
+    let count = plucked.len();
+    (plucked.into_iter(), count)
+}
+
+/// Yields windows of `window_size` consecutive items from the supplied iterator where each window starts `step`
+/// items after the previous one. Windows overlap when `step < window_size` and items are skipped when
+/// `step > window_size`. The last window is shorter than `window_size` when the iterator runs out of items, and
+/// is only yielded if it has items not yielded in an earlier window.
+/// Panics if `window_size` or `step` is 0.
+pub fn windowed_iter<I>(
+    iter: I,
+    window_size: usize,
+    step: usize,
+) -> impl Iterator<Item = Vec<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    assert!(window_size > 0, "window size must be non-zero");
+    assert!(step > 0, "step must be non-zero");
+
+    let mut iter = iter.into_iter().fuse();
+    let mut window = VecDeque::with_capacity(window_size);
+    // Items of the current window which weren't part of the previous one
+    let mut new_items = 0;
+    // Items to skip before filling the next window, when `step > window_size`
+    let mut to_skip = 0;
+    let mut done = false;
+
+    core::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        for _ in 0..to_skip {
+            iter.next()?;
+        }
+        to_skip = 0;
+        while window.len() < window_size {
+            match iter.next() {
+                Some(item) => {
+                    window.push_back(item);
+                    new_items += 1;
+                }
+                None => {
+                    done = true;
+                    break;
+                }
+            }
+        }
+        if new_items == 0 {
+            return None;
+        }
+        new_items = 0;
+
+        let items = window.iter().cloned().collect();
+        window.drain(..step.min(window.len()));
+        to_skip = step.saturating_sub(window_size);
+
+        Some(items)
+    })
+}
+
 /// Maps supplied iterator and attempts to pair each item with an item from the slice which has provided index.
 /// Returns `Err` containing an invalid index in case slice length is exceeded.
 pub fn pair_with_slice<'iter, 'pairs, I, Item, P>(
@@ -106,7 +199,7 @@ where
 mod tests {
     use crate::{
         misc::{seq_inc_by_n_from, seq_pairs_satisfy},
-        try_iter::{InvalidPair, InvalidPairOrSingle},
+        try_iter::{AlwaysValid, CheckLeft, CheckRight, InvalidPair, InvalidPairOrSingle},
     };
 
     use super::*;
@@ -168,6 +261,26 @@ mod tests {
         assert_eq!(invalid, Some(InvalidPairOrSingle::Pair(InvalidPair(3, 6))));
     }
 
+    #[test]
+    fn always_valid_is_noop() {
+        let mut invalid = None;
+        assert_eq!(
+            take_while_satisfy([5, 3, 3, 1], AlwaysValid, &mut invalid).collect::<Vec<_>>(),
+            vec![5, 3, 3, 1]
+        );
+        assert_eq!(invalid, None);
+
+        let pairs = [(2, 'b'), (1, 'a'), (3, 'c')];
+        assert!(validate(pairs, CheckLeft::<AlwaysValid>::default()).all(|res| res.is_ok()));
+        assert!(validate(pairs, CheckRight::<AlwaysValid>::default()).all(|res| res.is_ok()));
+        assert_eq!(
+            validate(pairs, CheckLeft(AlwaysValid))
+                .map(Result::unwrap)
+                .collect::<Vec<_>>(),
+            pairs
+        );
+    }
+
     #[test]
     fn check_pluck_missed() {
         assert_eq!(
@@ -179,4 +292,103 @@ mod tests {
             [0, 1, 2, 4, 6, 7, 8, 9]
         );
     }
+    #[test]
+    fn check_pluck_missed_unsorted() {
+        assert_eq!(
+            pluck_missed_unsorted([3, 1], [0, 1, 2, 3, 4]).collect::<Vec<_>>(),
+            vec![0, 2, 4]
+        );
+        assert_eq!(
+            pluck_missed_unsorted([5, 3, 5, 9, 3, 0], 0..10).collect::<Vec<_>>(),
+            [1, 2, 4, 6, 7, 8]
+        );
+        assert_eq!(
+            pluck_missed_unsorted([2, 2, 2], ['a', 'b', 'c']).collect::<Vec<_>>(),
+            ['a', 'b']
+        );
+        // Indices past the end of the items are ignored
+        assert_eq!(
+            pluck_missed_unsorted([7, 1], [0, 1, 2]).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(
+            pluck_missed_unsorted([], [0, 1, 2]).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        // Same result as the sorted version for sorted indices
+        for indices in [vec![], vec![0], vec![1, 3], vec![3, 5], vec![0, 4, 9]] {
+            assert_eq!(
+                pluck_missed_unsorted(indices.clone(), 0..10).collect::<Vec<_>>(),
+                pluck_missed(indices, 0..10).collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(
+            pluck_missed([3, 5], 0..10).collect::<Vec<_>>(),
+            [0, 1, 2, 4, 6, 7, 8, 9]
+        );
+    }
+
+    #[test]
+    fn check_pluck_missed_count() {
+        let (plucked, count) = pluck_missed_count([1, 3], [0, 1, 2]);
+        assert_eq!(count, 2);
+        assert_eq!(plucked.collect::<Vec<_>>(), vec![0, 2]);
+
+        let (plucked, count) = pluck_missed_count([3, 5], 0..10);
+        assert_eq!(count, 8);
+        assert_eq!(plucked.collect::<Vec<_>>(), [0, 1, 2, 4, 6, 7, 8, 9]);
+
+        // Nothing is plucked when all indices are missed or there are no items
+        let (mut plucked, count) = pluck_missed_count(0..3, [0, 1, 2]);
+        assert_eq!(count, 0);
+        assert!(plucked.next().is_none());
+        let (_, count) = pluck_missed_count([0, 1], core::iter::empty::<usize>());
+        assert_eq!(count, 0);
+
+        // Indices past the end of the items don't affect the count
+        let (_, count) = pluck_missed_count([1, 7, 9], [0, 1, 2]);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn check_windowed_iter() {
+        // Non-overlapping windows
+        assert_eq!(
+            windowed_iter(0..6, 2, 2).collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![2, 3], vec![4, 5]]
+        );
+        assert_eq!(
+            windowed_iter(0..7, 3, 3).collect::<Vec<_>>(),
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+        );
+
+        // Overlapping windows
+        assert_eq!(
+            windowed_iter(0..5, 3, 1).collect::<Vec<_>>(),
+            vec![vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]
+        );
+        assert_eq!(
+            windowed_iter(0..6, 3, 2).collect::<Vec<_>>(),
+            vec![vec![0, 1, 2], vec![2, 3, 4], vec![4, 5]]
+        );
+
+        // Items between windows are skipped when the step is larger than the window
+        assert_eq!(
+            windowed_iter(0..8, 2, 3).collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![3, 4], vec![6, 7]]
+        );
+
+        // Fewer items than a single window
+        assert_eq!(
+            windowed_iter([1, 2], 5, 1).collect::<Vec<_>>(),
+            vec![vec![1, 2]]
+        );
+
+        // Empty iterator
+        assert!(windowed_iter(core::iter::empty::<u32>(), 3, 3)
+            .next()
+            .is_none());
+        assert!(windowed_iter(Vec::<u32>::new(), 3, 1).next().is_none());
+    }
 }
@@ -1,4 +1,4 @@
-use ark_ec::{scalar_mul::fixed_base::FixedBase, CurveGroup};
+use ark_ec::{scalar_mul::fixed_base::FixedBase, AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{fmt::Debug, vec::Vec};
@@ -60,6 +60,24 @@ pub fn multiply_field_elems_with_same_group_elem<G: CurveGroup>(
     table.multiply_many(elements)
 }
 
+/// Backend for multi-scalar multiplication. Implement this to use a different MSM implementation, like one
+/// running on a GPU, for computing the commitments.
+pub trait MultiScalarMul<G: AffineRepr> {
+    /// Compute `bases[0] * scalars[0] + bases[1] * scalars[1] + ... + bases[i] * scalars[i]`. Extra `bases`
+    /// or `scalars` are ignored.
+    fn msm(&self, bases: &[G], scalars: &[G::ScalarField]) -> G::Group;
+}
+
+/// The default MSM backend which uses arkworks' `VariableBaseMSM`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArkMSM;
+
+impl<G: AffineRepr> MultiScalarMul<G> for ArkMSM {
+    fn msm(&self, bases: &[G], scalars: &[G::ScalarField]) -> G::Group {
+        G::Group::msm_unchecked(bases, scalars)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -181,6 +181,16 @@ where
         self.current_random *= self.random;
     }
 
+    /// Absorb the pairing equations added to `other` so that a single call to `Self::verify` checks
+    /// the equations of both checkers. Both checkers should have been created with independently
+    /// sampled random values, eg. when equations are added from different threads.
+    pub fn combine(&mut self, mut other: Self) {
+        self.left.0.mul_assign(other.left.0);
+        self.right += other.right;
+        self.pending.0.append(&mut other.pending.0);
+        self.pending.1.append(&mut other.pending.1);
+    }
+
     /// Verify that all added pairing equations are satisfied.
     pub fn verify(&self) -> bool {
         assert_eq!(self.pending.0.len(), self.pending.1.len());
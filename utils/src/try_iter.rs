@@ -81,6 +81,19 @@ where
     }
 }
 
+/// Implements `SeqValidator` which considers every item valid. Useful as a no-op validator, e.g. to check
+/// only one member of a pair with `CheckLeft`/`CheckRight`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlwaysValid;
+
+impl<I> SeqValidator<I> for AlwaysValid {
+    type Failure = core::convert::Infallible;
+
+    fn validate(&mut self, _: &I) -> Option<Self::Failure> {
+        None
+    }
+}
+
 macro_rules! impl_validator {
     (@ $self: ident $item: ident) => { None };
     (@ $self: ident $item: ident $main: ident = $main_idx: tt $($ty: ident = $idx: tt)*) => {
@@ -32,6 +32,7 @@ pub mod utils;
 pub mod batch_utils;
 pub mod error;
 pub mod persistence;
+pub mod planner;
 pub mod positive;
 pub mod proofs;
 pub mod setup;
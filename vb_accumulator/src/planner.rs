@@ -0,0 +1,249 @@
+//! Helps choosing the parameters of a universal accumulator used for revocation and estimating the sizes of what
+//! the accumulator manager publishes and what the holders store and present.
+//! # Examples
+//!
+//! ```
+//! use ark_bls12_381::Bls12_381;
+//! use ark_std::rand::{rngs::StdRng, SeedableRng};
+//! use vb_accumulator::{
+//!     persistence::InitialElementsStore,
+//!     planner::AccumulatorPlanner,
+//!     setup::{Keypair, SetupParams},
+//! };
+//! # use std::collections::HashSet;
+//! # type Fr = ark_bls12_381::Fr;
+//! # struct InitialElements(HashSet<Fr>);
+//! # impl InitialElementsStore<Fr> for InitialElements {
+//! #     fn add(&mut self, element: Fr) {
+//! #         self.0.insert(element);
+//! #     }
+//! #     fn has(&self, element: &Fr) -> bool {
+//! #         self.0.contains(element)
+//! #     }
+//! # }
+//!
+//! // Up to 100 members and non-membership witnesses are updated after every 10 additions or removals
+//! let planner = AccumulatorPlanner::new(100, 10);
+//! let plan = planner.plan::<Bls12_381>();
+//! // Size of `Omega` published after each batch of updates
+//! let omega_size = plan.omega_size;
+//!
+//! let mut rng = StdRng::seed_from_u64(0u64);
+//! let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+//! let keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+//! // `initial_elements` should be a persistent db implementing the trait `InitialElementsStore`
+//! let mut initial_elements = InitialElements(HashSet::new());
+//!
+//! // Create the accumulator with the planned size
+//! let accumulator = planner.initialize_universal_accumulator(
+//!     &mut rng,
+//!     &params,
+//!     &keypair.secret_key,
+//!     vec![],
+//!     &mut initial_elements,
+//! );
+//! ```
+
+use crate::{
+    persistence::InitialElementsStore,
+    setup::{SecretKey, SetupParams},
+    universal::UniversalAccumulator,
+};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr,
+};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{rand::RngCore, vec::Vec, Zero};
+
+/// Number of bytes taken by the length of a vector in its serialization
+const VEC_LENGTH_SIZE: usize = 8;
+
+/// Plans a universal accumulator for a target number of members where non-membership witnesses are updated using
+/// `Omega` published after each batch of `witness_update_batch_size` additions and removals.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccumulatorPlanner {
+    pub max_members: u64,
+    pub witness_update_batch_size: usize,
+}
+
+/// Recommended params and estimated sizes, in bytes of the compressed serialization, for a planned accumulator
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccumulatorPlan {
+    /// Max size the accumulator should be initialized with
+    pub max_size: u64,
+    /// Number of random initial elements the manager has to store, in addition to the constants passed during
+    /// initialization
+    pub initial_elements_count: u64,
+    pub membership_witness_size: usize,
+    pub non_membership_witness_size: usize,
+    /// Size of `Omega` published after a batch of `witness_update_batch_size` updates
+    pub omega_size: usize,
+    pub membership_proof_size: usize,
+    pub non_membership_proof_size: usize,
+}
+
+impl AccumulatorPlanner {
+    pub fn new(max_members: u64, witness_update_batch_size: usize) -> Self {
+        Self {
+            max_members,
+            witness_update_batch_size,
+        }
+    }
+
+    /// Recommended params and size estimates over the pairing `E`
+    pub fn plan<E: Pairing>(&self) -> AccumulatorPlan {
+        let g1 = E::G1Affine::zero().compressed_size();
+        let gt = PairingOutput::<E>::zero().compressed_size();
+        let fr = E::ScalarField::zero().compressed_size();
+        AccumulatorPlan {
+            max_size: self.max_members,
+            // As in `UniversalAccumulator::initialize`
+            initial_elements_count: self.max_members + 1,
+            membership_witness_size: g1,
+            non_membership_witness_size: fr + g1,
+            // `Omega` has a group element for each coefficient of the polynomial of the batch's updates
+            omega_size: VEC_LENGTH_SIZE + self.witness_update_batch_size * g1,
+            // Randomized witness (3 group elements), Schnorr commitments (a target group element and 4 group
+            // elements) and 5 responses
+            membership_proof_size: 7 * g1 + gt + 5 * fr,
+            // Same as membership with 2 more group elements in the randomized witness, 2 more in the commitments
+            // and 3 more responses
+            non_membership_proof_size: 11 * g1 + gt + 8 * fr,
+        }
+    }
+
+    /// Initialize a universal accumulator with the planned max size. See `UniversalAccumulator::initialize`
+    pub fn initialize_universal_accumulator<E: Pairing, R: RngCore>(
+        &self,
+        rng: &mut R,
+        setup_params: &SetupParams<E>,
+        sk: &SecretKey<E::ScalarField>,
+        xs: Vec<E::ScalarField>,
+        initial_elements_store: &mut dyn InitialElementsStore<E::ScalarField>,
+    ) -> UniversalAccumulator<E> {
+        UniversalAccumulator::initialize(
+            rng,
+            setup_params,
+            self.plan::<E>().max_size,
+            sk,
+            xs,
+            initial_elements_store,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        batch_utils::Omega,
+        persistence::test::*,
+        positive::Accumulator,
+        proofs::{
+            MembershipProofProtocol, MembershipProvingKey, NonMembershipProofProtocol,
+            NonMembershipProvingKey,
+        },
+        setup::Keypair,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+    fn assert_close(estimate: usize, actual: usize) {
+        // Estimates should be within 5% of the actual sizes
+        assert!(
+            estimate.abs_diff(actual) * 20 <= actual,
+            "estimate {} is not close to actual size {}",
+            estimate,
+            actual
+        );
+    }
+
+    #[test]
+    fn planned_sizes() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let max_members = 20;
+        let batch_size = 4;
+        let planner = AccumulatorPlanner::new(max_members, batch_size);
+        let plan = planner.plan::<Bls12_381>();
+
+        let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+        let keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let mut initial_elements = InMemoryInitialElements::new();
+        let mut accumulator = planner.initialize_universal_accumulator(
+            &mut rng,
+            &params,
+            &keypair.secret_key,
+            vec![],
+            &mut initial_elements,
+        );
+        let mut state = InMemoryState::new();
+        assert_eq!(accumulator.max_size(), max_members);
+        assert_eq!(
+            initial_elements.db.len() as u64,
+            plan.initial_elements_count
+        );
+
+        let members = (0..batch_size)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let old_value = *accumulator.value();
+        accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &keypair.secret_key,
+                &initial_elements,
+                &mut state,
+            )
+            .unwrap();
+        let omega = Omega::new(&members, &[], &old_value, &keypair.secret_key);
+        assert_close(plan.omega_size, omega.compressed_size());
+
+        let mem_wit = accumulator
+            .get_membership_witness(&members[0], &keypair.secret_key, &state)
+            .unwrap();
+        assert_close(plan.membership_witness_size, mem_wit.compressed_size());
+        let non_member = Fr::rand(&mut rng);
+        let non_mem_wit = accumulator
+            .get_non_membership_witness(&non_member, &keypair.secret_key, &state, &params)
+            .unwrap();
+        assert_close(
+            plan.non_membership_witness_size,
+            non_mem_wit.compressed_size(),
+        );
+
+        let challenge = Fr::rand(&mut rng);
+        let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+        let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+        let mem_proof = MembershipProofProtocol::init(
+            &mut rng,
+            &members[0],
+            None,
+            &mem_wit,
+            &keypair.public_key,
+            &params,
+            &mem_prk,
+        )
+        .gen_proof(&challenge);
+        assert_close(plan.membership_proof_size, mem_proof.compressed_size());
+        let non_mem_proof = NonMembershipProofProtocol::init(
+            &mut rng,
+            &non_member,
+            None,
+            &non_mem_wit,
+            &keypair.public_key,
+            &params,
+            &non_mem_prk,
+        )
+        .gen_proof(&challenge);
+        assert_close(
+            plan.non_membership_proof_size,
+            non_mem_proof.compressed_size(),
+        );
+    }
+}
@@ -118,6 +118,9 @@ use dock_crypto_utils::{
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
+/// Label prefixed to the accumulator params when generating the default membership proving key
+pub const DEFAULT_MEMBERSHIP_PROVING_KEY_LABEL: &[u8] = b"VB-ACCUM-DEFAULT-MEMBERSHIP-PROVING-KEY";
+
 /// The public parameters (in addition to public key, accumulator setup params) used during the proof
 /// of membership and non-membership are called `ProvingKey`. These are mutually agreed upon by the
 /// prover and verifier and can be different between different provers and verifiers but using the
@@ -198,6 +201,20 @@ where
     pub fn new<D: Digest>(label: &[u8]) -> Self {
         Self(ProvingKey::generate_proving_key_using_hash::<D>(label))
     }
+
+    /// The default proving key for the accumulator params, generated by hashing the params with `D`. Provers and
+    /// verifiers that haven't agreed on a proving key can use this but they must use the same `D`. Generating it
+    /// involves hashing to the curve so it should be created once and reused for all proofs with the same params.
+    pub fn default_for<D: Digest, E: Pairing<G1Affine = G>>(
+        params: &SetupParams<E>,
+    ) -> Result<Self, VBAccumulatorError> {
+        let mut params_bytes = Vec::new();
+        params.serialize_compressed(&mut params_bytes)?;
+        Ok(Self::new::<D>(&concat_slices!(
+            DEFAULT_MEMBERSHIP_PROVING_KEY_LABEL,
+            params_bytes
+        )))
+    }
 }
 
 impl<G> NonMembershipProvingKey<G>
@@ -637,6 +637,36 @@ where
         Self { C, d }
     }
 
+    /// Update a non-membership witness after several elements are added to the accumulator one after the
+    /// other. `old_accumulators[i]` is the accumulator before `additions[i]` was added. Unlike the batch
+    /// updates, this doesn't need the secret key or `Omega`, only the published accumulator values.
+    ///
+    /// The accumulator value before each addition is needed, not only the values before and after all of them.
+    /// Adding `y` to the accumulator `V` changes the witness `(C, d)` of `x` to `((y - x) * C + V, (y - x) * d)`,
+    /// so every addition uses the accumulator just before it. For additions `y_1, y_2, ..., y_n` to `V`, these are
+    /// `V * (y_1 + alpha) * ... * (y_{i-1} + alpha)` which can't be computed from `V` and the final accumulator
+    /// without the secret key `alpha`. A batch of additions published with only the old and new accumulator
+    /// values needs `Omega` from the manager, see `Self::update_using_public_info_after_batch_updates`.
+    pub fn update_after_additions(
+        &self,
+        non_member: &G::ScalarField,
+        additions: &[G::ScalarField],
+        old_accumulators: &[G],
+    ) -> Result<Self, VBAccumulatorError> {
+        if additions.len() != old_accumulators.len() {
+            return Err(VBAccumulatorError::NeedSameNoOfElementsAndWitnesses);
+        }
+        let mut witness = self.clone();
+        for (addition, old_accumulator) in additions.iter().zip(old_accumulators) {
+            // The witness would become invalid as the element is now a member
+            if addition == non_member {
+                return Err(VBAccumulatorError::ElementPresent);
+            }
+            witness = witness.update_after_addition(non_member, addition, old_accumulator);
+        }
+        Ok(witness)
+    }
+
     /// Update a non-membership witness after an element is removed from the accumulator. Needs the
     /// accumulator after the removal was done.
     pub fn update_after_removal(